@@ -0,0 +1,123 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+/// Bulk loading of HNSW and IVF indexes, sequential insert vs insert_batch
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hnsw::core::{HNSWConfig, HNSWIndex};
+use vector_db::ivf::core::{Centroid, IVFConfig, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+const DIMENSIONS: usize = 128;
+const BATCH_SIZE: usize = 10_000;
+
+/// Generate deterministic vectors
+fn create_vectors(count: usize, dimensions: usize, seed: usize) -> Vec<(VectorId, Vec<f32>)> {
+    (0..count)
+        .map(|i| {
+            let vector = (0..dimensions)
+                .map(|d| (((i + seed) * 31 + d * 17) % 97) as f32 / 97.0 - 0.5)
+                .collect();
+            (VectorId::from_u64(i as u64), vector)
+        })
+        .collect()
+}
+
+fn hnsw_index() -> HNSWIndex {
+    HNSWIndex::new(HNSWConfig {
+        seed: Some(42),
+        ..HNSWConfig::default()
+    })
+}
+
+fn ivf_config() -> IVFConfig {
+    IVFConfig {
+        n_clusters: 100,
+        n_probe: 8,
+        train_size: 4_000,
+        max_iterations: 10,
+        seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    }
+}
+
+/// Train once and hand out empty indexes sharing the same centroids
+fn ivf_centroids(items: &[(VectorId, Vec<f32>)]) -> Vec<Centroid> {
+    let mut index = IVFIndex::new(ivf_config());
+    let training: Vec<Vec<f32>> = items[..4_000].iter().map(|(_, v)| v.clone()).collect();
+    index.train(&training).unwrap();
+    index.get_centroids().to_vec()
+}
+
+fn ivf_index(centroids: &[Centroid]) -> IVFIndex {
+    let mut index = IVFIndex::new(ivf_config());
+    index.set_trained(centroids.to_vec(), DIMENSIONS);
+    index
+}
+
+fn bench_hnsw_batch_insert(c: &mut Criterion) {
+    let items = create_vectors(BATCH_SIZE, DIMENSIONS, 1);
+
+    let mut group = c.benchmark_group("hnsw_batch_insert");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("sequential", BATCH_SIZE), |bench| {
+        bench.iter_batched(
+            || (hnsw_index(), items.clone()),
+            |(mut index, items)| {
+                for (id, vector) in items {
+                    index.insert(id, vector).unwrap();
+                }
+                index
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function(BenchmarkId::new("insert_batch", BATCH_SIZE), |bench| {
+        bench.iter_batched(
+            || (hnsw_index(), items.clone()),
+            |(mut index, items)| {
+                index.insert_batch(items).unwrap();
+                index
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_ivf_batch_insert(c: &mut Criterion) {
+    let items = create_vectors(BATCH_SIZE, DIMENSIONS, 1);
+    let centroids = ivf_centroids(&items);
+
+    let mut group = c.benchmark_group("ivf_batch_insert");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("sequential", BATCH_SIZE), |bench| {
+        bench.iter_batched(
+            || (ivf_index(&centroids), items.clone()),
+            |(mut index, items)| {
+                for (id, vector) in items {
+                    index.insert(id, vector).unwrap();
+                }
+                index
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function(BenchmarkId::new("insert_batch", BATCH_SIZE), |bench| {
+        bench.iter_batched(
+            || (ivf_index(&centroids), items.clone()),
+            |(mut index, items)| {
+                index.insert_batch(items).unwrap();
+                index
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_hnsw_batch_insert, bench_ivf_batch_insert);
+criterion_main!(benches);
@@ -0,0 +1,75 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+/// HNSW search with and without the per-search distance memo
+/// Reports distance computations per query alongside search time
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use vector_db::core::types::VectorId;
+use vector_db::hnsw::core::{HNSWConfig, HNSWIndex};
+
+const DIMENSIONS: usize = 384; // Standard embedding dimension
+const INDEX_SIZE: usize = 5_000;
+const EF: usize = 100;
+
+/// Generate deterministic normalized vectors
+fn create_vectors(count: usize, dimensions: usize, seed: usize) -> Vec<Vec<f32>> {
+    (0..count)
+        .map(|i| {
+            let v: Vec<f32> = (0..dimensions)
+                .map(|d| (((i + seed) * 31 + d * 17) % 97) as f32 / 97.0 - 0.5)
+                .collect();
+            let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            v.into_iter().map(|x| x / norm).collect()
+        })
+        .collect()
+}
+
+fn build_index(vectors: &[Vec<f32>], cache_distances: bool) -> HNSWIndex {
+    let mut index = HNSWIndex::new(HNSWConfig {
+        seed: Some(42),
+        cache_distances,
+        ..HNSWConfig::default()
+    });
+    for (i, vector) in vectors.iter().enumerate() {
+        index.insert(VectorId::from_u64(i as u64), vector.clone()).unwrap();
+    }
+    index
+}
+
+fn bench_distance_cache(c: &mut Criterion) {
+    let vectors = create_vectors(INDEX_SIZE, DIMENSIONS, 1);
+    let queries = create_vectors(50, DIMENSIONS, 7_919);
+
+    let mut group = c.benchmark_group("hnsw_distance_cache");
+    group.sample_size(20);
+    for cache_distances in [false, true] {
+        let index = build_index(&vectors, cache_distances);
+
+        let computations: usize = queries
+            .iter()
+            .map(|q| index.search_with_stats(q, 10, EF).unwrap().1.distance_computations)
+            .sum();
+        println!(
+            "cache_distances={}: {:.1} distance computations per query",
+            cache_distances,
+            computations as f64 / queries.len() as f64
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("search", if cache_distances { "cached" } else { "uncached" }),
+            &index,
+            |bench, index| {
+                bench.iter(|| {
+                    for query in &queries {
+                        black_box(index.search(black_box(query), 10, EF).unwrap());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_distance_cache);
+criterion_main!(benches);
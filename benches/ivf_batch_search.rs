@@ -0,0 +1,102 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+/// IVF batch search of similar queries, shared vs independent cluster probing
+/// Reports cluster fetches and distance computations alongside search time
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use tokio::runtime::Runtime;
+
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::types::VectorId;
+use vector_db::ivf::core::{BatchSearchOptions, IVFConfig, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+const DIMENSIONS: usize = 128;
+const INDEX_SIZE: usize = 10_000;
+const BATCH_SIZE: usize = 64;
+const N_PROBE: usize = 8;
+
+/// Generate deterministic vectors
+fn create_vectors(count: usize, dimensions: usize, seed: usize) -> Vec<Vec<f32>> {
+    (0..count)
+        .map(|i| {
+            (0..dimensions)
+                .map(|d| (((i + seed) * 31 + d * 17) % 97) as f32 / 97.0 - 0.5)
+                .collect()
+        })
+        .collect()
+}
+
+/// A recommendation-style fan-out: small perturbations of a few seed
+/// vectors, with each perturbation requested twice
+fn similar_queries(seeds: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    (0..BATCH_SIZE)
+        .map(|i| {
+            let seed = &seeds[i % seeds.len()];
+            let jitter = (i / (2 * seeds.len())) as f32 * 0.001;
+            seed.iter().map(|x| x + jitter).collect()
+        })
+        .collect()
+}
+
+fn build_index(vectors: &[Vec<f32>]) -> IVFIndex {
+    let mut index = IVFIndex::new(IVFConfig {
+        n_clusters: 100,
+        n_probe: N_PROBE,
+        train_size: 4_000,
+        max_iterations: 10,
+        seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    });
+    index.train(&vectors[..4_000]).unwrap();
+    for (i, vector) in vectors.iter().enumerate() {
+        index.insert(VectorId::from_u64(i as u64), vector.clone()).unwrap();
+    }
+    index
+}
+
+fn bench_batch_search(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let vectors = create_vectors(INDEX_SIZE, DIMENSIONS, 1);
+    let index = build_index(&vectors);
+    let queries = similar_queries(&vectors[..4]);
+
+    let mut group = c.benchmark_group("ivf_batch_search");
+    group.sample_size(20);
+    for share_clusters in [false, true] {
+        let options = BatchSearchOptions {
+            n_probe: N_PROBE,
+            share_clusters,
+        };
+
+        let (_, stats) = rt
+            .block_on(index.search_batch_with(&queries, 10, options))
+            .unwrap();
+        println!(
+            "share_clusters={}: {} cluster fetches, {} distance computations for {} queries",
+            share_clusters,
+            stats.cluster_fetches,
+            stats.distance_computations,
+            queries.len()
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("search_batch", if share_clusters { "shared" } else { "independent" }),
+            &options,
+            |bench, options| {
+                bench.iter(|| {
+                    black_box(
+                        rt.block_on(index.search_batch_with(black_box(&queries), 10, *options))
+                            .unwrap(),
+                    )
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch_search);
+criterion_main!(benches);
@@ -1,5 +1,91 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
-// Placeholder for vector operation benchmarks
-fn main() {}
+/// Vector operation benchmarks
+/// Compares the f32 distance path against the int8 quantized fast path
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use vector_db::core::types::VectorId;
+use vector_db::core::vector_ops::{
+    distance_kernels, euclidean_distance_i8, euclidean_distance_scalar, euclidean_distance_simd,
+    quantize_i8,
+};
+use vector_db::core::{FlatVectorStore, VectorPrecision};
+
+const DIMENSIONS: usize = 384; // Standard embedding dimension
+const STORE_SIZE: usize = 10_000;
+
+/// Generate deterministic normalized vectors
+fn create_vectors(count: usize, dimensions: usize, seed: usize) -> Vec<Vec<f32>> {
+    (0..count)
+        .map(|i| {
+            let v: Vec<f32> = (0..dimensions)
+                .map(|d| (((i + seed) * 31 + d * 17) % 97) as f32 / 97.0 - 0.5)
+                .collect();
+            let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            v.into_iter().map(|x| x / norm).collect()
+        })
+        .collect()
+}
+
+fn bench_pairwise_distance(c: &mut Criterion) {
+    let vectors = create_vectors(2, DIMENSIONS, 0);
+    let (a, b) = (&vectors[0], &vectors[1]);
+    let (qa, qb) = (quantize_i8(a), quantize_i8(b));
+
+    let mut group = c.benchmark_group("euclidean_distance");
+    group.bench_function("f32", |bench| {
+        bench.iter(|| euclidean_distance_scalar(black_box(a), black_box(b)))
+    });
+    group.bench_function("int8", |bench| {
+        bench.iter(|| euclidean_distance_i8(black_box(&qa), black_box(&qb)))
+    });
+    group.finish();
+}
+
+fn bench_flat_search(c: &mut Criterion) {
+    let vectors = create_vectors(STORE_SIZE, DIMENSIONS, 1);
+    let query = create_vectors(1, DIMENSIONS, 99).remove(0);
+
+    let mut group = c.benchmark_group("flat_search");
+    group.sample_size(20);
+    for precision in [VectorPrecision::F32, VectorPrecision::Int8] {
+        let mut store = FlatVectorStore::new(precision);
+        for (i, vector) in vectors.iter().enumerate() {
+            store.insert(VectorId::from_u64(i as u64), vector).unwrap();
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new(format!("{:?}", precision), STORE_SIZE),
+            &store,
+            |bench, store| bench.iter(|| store.search(black_box(&query), 10).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+/// Scalar reference against the runtime-dispatched kernel; the benchmark id
+/// names the level that was selected on this machine
+fn bench_dispatched_distance(c: &mut Criterion) {
+    let vectors = create_vectors(2, DIMENSIONS, 0);
+    let (a, b) = (&vectors[0], &vectors[1]);
+    let level = distance_kernels().level;
+    println!("Distance kernels dispatched to {}", level.as_str());
+
+    let mut group = c.benchmark_group("dispatched_distance");
+    group.bench_function("scalar", |bench| {
+        bench.iter(|| euclidean_distance_scalar(black_box(a), black_box(b)))
+    });
+    group.bench_function(format!("dispatch_{}", level.as_str()), |bench| {
+        bench.iter(|| euclidean_distance_simd(black_box(a), black_box(b)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_pairwise_distance,
+    bench_flat_search,
+    bench_dispatched_distance
+);
+criterion_main!(benches);
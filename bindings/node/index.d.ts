@@ -112,6 +112,18 @@ export declare class VectorDbSession {
     cid: string,
     options?: LoadOptions | undefined | null,
   ): Promise<void>;
+  /**
+   * Train the index on `training_data` before any vectors are added,
+   * fixing the session's vector dimension. Without it, `addVectors`
+   * trains on the first vectors it is given.
+   *
+   * # Errors
+   * Returns error if:
+   * - The index is already initialized
+   * - The training data is empty or its vectors differ in dimension
+   * - Session has been destroyed
+   */
+  initialize(trainingData: Array<Array<number>>): Promise<void>;
   /** Search for similar vectors */
   search(
     queryVector: Array<number>,
@@ -120,6 +132,16 @@ export declare class VectorDbSession {
   ): Promise<Array<SearchResult>>;
   /** Add vectors to the index */
   addVectors(vectors: Array<VectorInput>): Promise<void>;
+  /**
+   * Add a single vector, with optional metadata
+   *
+   * Same as `addVectors` with one entry; metadata defaults to `{}`.
+   */
+  insert(
+    id: string,
+    vector: Array<number>,
+    metadata?: any | undefined | null,
+  ): Promise<void>;
   /**
    * Delete a vector from the index by ID
    *
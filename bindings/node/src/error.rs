@@ -73,6 +73,14 @@ impl From<anyhow::Error> for VectorDBError {
 // Convert from HybridError
 impl From<vector_db::hybrid::HybridError> for VectorDBError {
     fn from(err: vector_db::hybrid::HybridError) -> Self {
-        VectorDBError::index_error(err.to_string())
+        use vector_db::hybrid::HybridError;
+        match err {
+            HybridError::DimensionMismatch { .. }
+            | HybridError::TrainedDimensionMismatch { .. }
+            | HybridError::DuplicateVector(_)
+            | HybridError::InvalidQuery(_) => VectorDBError::invalid_input(err.to_string()),
+            HybridError::InvalidConfig(_) => VectorDBError::invalid_config(err.to_string()),
+            _ => VectorDBError::index_error(err.to_string()),
+        }
     }
 }
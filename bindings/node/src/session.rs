@@ -75,6 +75,8 @@ impl VectorDBSession {
             connection_timeout: Some(30000), // 30 seconds
             retry_attempts: Some(3),
             encrypt_at_rest: config.encrypt_at_rest, // Use from config (defaults to true if None)
+            encryption_key: None,
+            compress: None,
         };
 
         let storage = EnhancedS5Storage::new(s5_config)
@@ -198,6 +200,48 @@ impl VectorDBSession {
         Ok(())
     }
 
+    /// Train the index on `training_data` before any vectors are added,
+    /// fixing the session's vector dimension. Without it, `addVectors`
+    /// trains on the first vectors it is given.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - The index is already initialized
+    /// - The training data is empty or its vectors differ in dimension
+    /// - Session has been destroyed
+    #[napi]
+    pub async unsafe fn initialize(&mut self, training_data: Vec<Vec<f64>>) -> Result<()> {
+        let state = self.state.as_mut()
+            .ok_or_else(|| VectorDBError::session_error("Session already destroyed"))?;
+
+        let training_data: Vec<Vec<f32>> = training_data
+            .into_iter()
+            .map(utils::js_array_to_vec_f32)
+            .collect();
+        let dimension = match training_data.first() {
+            Some(first) => first.len(),
+            None => return Err(VectorDBError::invalid_input("Training data must not be empty").into()),
+        };
+        if let Some(expected_dim) = state.vector_dimension {
+            if dimension != expected_dim {
+                return Err(VectorDBError::invalid_input(
+                    format!("Training data dimension mismatch: expected {}, got {}", expected_dim, dimension)
+                ).into());
+            }
+        }
+
+        let mut index_guard = state.index.write().await;
+        if index_guard.is_initialized() {
+            return Err(VectorDBError::index_error("Index is already initialized").into());
+        }
+        index_guard.initialize(training_data)
+            .await
+            .map_err(VectorDBError::from)?;
+        state.vector_dimension = Some(dimension);
+
+        Ok(())
+    }
+
     /// Search for similar vectors
     #[napi]
     pub async fn search(
@@ -234,7 +278,11 @@ impl VectorDBSession {
         let filter = if let Some(filter_json) = options.as_ref().and_then(|o| o.filter.as_ref()) {
             use vector_db::core::metadata_filter::MetadataFilter;
 
-            match MetadataFilter::from_json(filter_json) {
+            let parsed = match state.schema.read().await.as_ref() {
+                Some(schema) => MetadataFilter::from_json_with_schema(filter_json, schema),
+                None => MetadataFilter::from_json(filter_json),
+            };
+            match parsed {
                 Ok(f) => Some(f),
                 Err(e) => {
                     return Err(VectorDBError::invalid_input(
@@ -431,6 +479,24 @@ impl VectorDBSession {
         Ok(())
     }
 
+    /// Add a single vector, with optional metadata
+    ///
+    /// Same as `addVectors` with one entry; metadata defaults to `{}`.
+    #[napi]
+    pub async unsafe fn insert(
+        &mut self,
+        id: String,
+        vector: Vec<f64>,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<()> {
+        self.add_vectors(vec![VectorInput {
+            id,
+            vector,
+            metadata: metadata.unwrap_or_else(|| serde_json::json!({})),
+        }])
+        .await
+    }
+
     /// Delete a vector from the index by ID
     ///
     /// Performs soft deletion - vector is marked as deleted but not physically removed
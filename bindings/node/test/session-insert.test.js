@@ -0,0 +1,90 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+const { describe, test, before, after } = require('node:test');
+const assert = require('node:assert');
+const { VectorDbSession } = require('../index.js');
+const { startS5Service } = require('./helpers/s5-service.cjs');
+
+let s5Service = null;
+
+const DIM = 16;
+
+function config(sessionId) {
+  return {
+    s5Portal: 'http://127.0.0.1:5534',
+    userSeedPhrase: 'test-seed-phrase-for-insert-tests-12345678901234567890',
+    sessionId,
+    encryptAtRest: false,
+  };
+}
+
+function vector(seed) {
+  return Array(DIM).fill(0).map((_, i) => Math.sin(seed + i * 0.3));
+}
+
+before(async () => {
+  s5Service = await startS5Service({ port: 5534, mode: 'mock' });
+});
+
+after(async () => {
+  if (s5Service) {
+    await s5Service.close();
+  }
+});
+
+describe('initialize / insert / search / deleteVector', () => {
+  test('inserts and searches a small dataset', async () => {
+    const session = await VectorDbSession.create(config('test-insert-small-dataset'));
+    await session.initialize(Array.from({ length: 20 }, (_, i) => vector(i * 7)));
+
+    for (let i = 0; i < 8; i++) {
+      await session.insert(`doc-${i}`, vector(i), { category: i % 2 === 0 ? 'even' : 'odd', rank: i });
+    }
+    await session.insert('no-metadata', vector(100));
+
+    const results = await session.search(vector(3), 3);
+    assert.strictEqual(results.length, 3);
+    assert.strictEqual(results[0].id, 'doc-3');
+    assert.deepStrictEqual(results[0].metadata, { category: 'odd', rank: 3 });
+    assert.ok(results[0].score > 0.99, `score ${results[0].score}`);
+    for (let i = 1; i < results.length; i++) {
+      assert.ok(results[i - 1].score >= results[i].score, 'results are sorted by score');
+    }
+
+    const filtered = await session.search(vector(3), 3, { filter: { category: 'even' } });
+    assert.ok(filtered.length > 0);
+    assert.ok(filtered.every((r) => r.metadata.category === 'even'));
+
+    const unfiltered = await session.search(vector(100), 1);
+    assert.strictEqual(unfiltered[0].id, 'no-metadata');
+    assert.deepStrictEqual(unfiltered[0].metadata, {});
+
+    await session.deleteVector('doc-3');
+    const afterDelete = await session.search(vector(3), 3);
+    assert.ok(afterDelete.every((r) => r.id !== 'doc-3'));
+
+    await session.destroy();
+  });
+
+  test('rejects mismatched dimensions and repeated initialization', async () => {
+    const session = await VectorDbSession.create(config('test-insert-validation'));
+    await assert.rejects(session.initialize([]), /must not be empty/);
+
+    await session.initialize(Array.from({ length: 20 }, (_, i) => vector(i)));
+    await assert.rejects(
+      session.initialize([vector(1)]),
+      /already initialized/
+    );
+    await assert.rejects(
+      session.insert('short', [0.1, 0.2, 0.3]),
+      /dimension mismatch/
+    );
+    await assert.rejects(
+      session.search([0.1, 0.2, 0.3], 5),
+      /dimension mismatch/
+    );
+
+    await session.destroy();
+  });
+});
@@ -253,6 +253,30 @@ impl InMemoryIndex {
         bincode::deserialize(data)
             .map_err(|e| JsValue::from_str(&format!("Deserialization failed: {}", e)))
     }
+
+    /// CBOR encoding of the whole index, metadata included, for saving to
+    /// IndexedDB or localStorage; restore it with `from_bytes`
+    #[wasm_bindgen]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_cbor::to_vec(self).expect("index contents are always representable in CBOR")
+    }
+
+    #[wasm_bindgen]
+    pub fn from_bytes(data: &[u8]) -> Result<InMemoryIndex, JsValue> {
+        let index: InMemoryIndex = serde_cbor::from_slice(data)
+            .map_err(|e| JsValue::from_str(&format!("Deserialization failed: {}", e)))?;
+
+        if let Some(entry) = index.vectors.iter().find(|v| v.vector.len() != index.dimension) {
+            return Err(JsValue::from_str(&format!(
+                "Vector '{}' has dimension {}, expected {}",
+                entry.id,
+                entry.vector.len(),
+                index.dimension
+            )));
+        }
+
+        Ok(index)
+    }
 }
 
 #[wasm_bindgen]
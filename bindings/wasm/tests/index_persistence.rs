@@ -0,0 +1,80 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Round trips of `InMemoryIndex` through `to_bytes`/`from_bytes`.
+//! Run with `wasm-pack test --node`.
+
+use serde::Serialize;
+use serde_wasm_bindgen::from_value;
+use vector_db_wasm::{InMemoryIndex, SearchFilter, SearchResult};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[derive(Serialize)]
+struct Metadata {
+    genre: &'static str,
+    year: u32,
+}
+
+fn build_index() -> InMemoryIndex {
+    let mut index = InMemoryIndex::new(3);
+    let genres = ["ai", "music", "ai", "sports"];
+    for (i, genre) in genres.iter().enumerate() {
+        let vector = vec![i as f32, 1.0, 0.5 * i as f32];
+        let metadata = serde_wasm_bindgen::to_value(&Metadata {
+            genre,
+            year: 2020 + i as u32,
+        })
+        .unwrap();
+        index
+            .add_vector_with_metadata(&format!("video_{}", i), vector, metadata)
+            .unwrap();
+    }
+    index.add_vector("no_metadata", vec![0.2, 0.4, 0.6]).unwrap();
+    index
+}
+
+fn summarize(results: &[SearchResult]) -> Vec<(String, f32, serde_json::Value)> {
+    results
+        .iter()
+        .map(|r| {
+            let metadata: serde_json::Value = from_value(r.metadata()).unwrap();
+            (r.id(), r.distance(), metadata)
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn search_results_survive_round_trip() {
+    let index = build_index();
+    let restored = InMemoryIndex::from_bytes(&index.to_bytes()).unwrap();
+    let query = vec![1.0, 1.0, 1.0];
+
+    assert_eq!(restored.size(), index.size());
+    assert_eq!(
+        summarize(&restored.search(query.clone(), 5).unwrap()),
+        summarize(&index.search(query, 5).unwrap())
+    );
+}
+
+#[wasm_bindgen_test]
+fn filtered_search_survives_round_trip() {
+    let index = build_index();
+    let restored = InMemoryIndex::from_bytes(&index.to_bytes()).unwrap();
+    let mut filter = SearchFilter::new();
+    filter.add_string_filter("genre", "ai");
+    filter.add_number_filter("year", "gte", 2021.0);
+    let query = vec![1.0, 1.0, 1.0];
+
+    let before = summarize(&index.search_with_filter(query.clone(), 5, &filter).unwrap());
+    let after = summarize(&restored.search_with_filter(query, 5, &filter).unwrap());
+    assert_eq!(before.len(), 1);
+    assert_eq!(after, before);
+}
+
+#[wasm_bindgen_test]
+fn invalid_bytes_are_rejected() {
+    let result = InMemoryIndex::from_bytes(b"not an index");
+    assert!(result.is_err());
+    assert!(result.err().map(|e: JsValue| e.as_string().is_some()).unwrap());
+}
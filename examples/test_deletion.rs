@@ -2,8 +2,10 @@
 // Run with: cargo run --example test_deletion
 
 use chrono::Utc;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::types::VectorId;
 use vector_db::hybrid::core::{HybridConfig, HybridIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 #[tokio::main]
 async fn main() {
@@ -19,9 +21,13 @@ async fn main() {
             train_size: 100,
             max_iterations: 10,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         },
         migration_batch_size: 100,
         auto_migrate: false,
+        migration_interval: std::time::Duration::from_secs(60),
     };
 
     let mut index = HybridIndex::new(config);
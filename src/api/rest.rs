@@ -1,23 +1,29 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+use crate::core::metadata_filter::MetadataFilter;
+use crate::core::schema::MetadataSchema;
 use crate::core::types::*;
-use crate::hybrid::{HybridConfig, HybridIndex, TimestampedVector};
+use crate::hnsw::operations::{GraphExport, GraphExportOptions};
+use crate::hybrid::maintenance::{BackupConfig, BackupManager};
+use crate::hybrid::{
+    HybridConfig, HybridIndex, InsertOutcome, LockContentionStats, OnDuplicate, SearchDefaults,
+};
 use crate::storage::{S5StorageFactory, EnhancedS5Storage, Storage};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::{IntoResponse, Response, Sse},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use std::env;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, RwLock, Semaphore};
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::limit::RequestBodyLimitLayer;
 use tracing::{info, error};
@@ -29,6 +35,37 @@ pub struct ApiConfig {
     pub max_request_size: usize,
     pub timeout: Duration,
     pub cors_origins: Vec<String>,
+    /// Where search results look up vector metadata
+    #[serde(default)]
+    pub metadata_lookup: MetadataLookupOrder,
+    /// Bound on concurrently executing searches; `None` leaves them unbounded
+    #[serde(default)]
+    pub search_limit: Option<SearchLimitConfig>,
+    /// Largest accepted metadata per vector, measured as serialized JSON
+    #[serde(default = "default_max_metadata_bytes")]
+    pub max_metadata_bytes: usize,
+    /// Reuse serialized search responses for identical requests; `None`
+    /// serializes every response
+    #[serde(default)]
+    pub response_cache: Option<ResponseCacheConfig>,
+    /// What searches return before the index has been initialized
+    #[serde(default)]
+    pub uninitialized_search: UninitializedSearch,
+    /// Update events buffered for `/stream/updates` subscribers; a client
+    /// falling further behind receives a `lagged` event
+    #[serde(default = "default_update_buffer")]
+    pub update_buffer: usize,
+    /// Schema inserted metadata must match; `None` accepts any metadata
+    #[serde(default)]
+    pub metadata_schema: Option<MetadataSchema>,
+}
+
+fn default_max_metadata_bytes() -> usize {
+    1024 * 1024 // 1MB
+}
+
+fn default_update_buffer() -> usize {
+    1024
 }
 
 impl Default for ApiConfig {
@@ -39,6 +76,240 @@ impl Default for ApiConfig {
             max_request_size: 10 * 1024 * 1024, // 10MB
             timeout: Duration::from_secs(30),
             cors_origins: vec!["http://localhost:3000".to_string()],
+            metadata_lookup: MetadataLookupOrder::default(),
+            search_limit: None,
+            max_metadata_bytes: default_max_metadata_bytes(),
+            response_cache: None,
+            uninitialized_search: UninitializedSearch::default(),
+            update_buffer: default_update_buffer(),
+            metadata_schema: None,
+        }
+    }
+}
+
+/// Backpressure settings for the search endpoint
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchLimitConfig {
+    /// Maximum number of searches running at once; zero leaves searches
+    /// unlimited
+    pub max_concurrent: usize,
+    /// How long a search waits for a free slot before being rejected;
+    /// zero rejects immediately
+    pub queue_timeout: Duration,
+    /// Seconds advertised in the `Retry-After` header of rejected searches
+    pub retry_after_secs: u64,
+}
+
+impl Default for SearchLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 64,
+            queue_timeout: Duration::from_secs(1),
+            retry_after_secs: 1,
+        }
+    }
+}
+
+/// Semaphore guarding the search path
+#[derive(Clone, Debug)]
+pub struct SearchLimiter {
+    permits: Arc<Semaphore>,
+    queue_timeout: Duration,
+    retry_after_secs: u64,
+}
+
+impl SearchLimiter {
+    pub fn new(config: &SearchLimitConfig) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(match config.max_concurrent {
+                0 => Semaphore::MAX_PERMITS,
+                max_concurrent => max_concurrent,
+            })),
+            queue_timeout: config.queue_timeout,
+            retry_after_secs: config.retry_after_secs,
+        }
+    }
+
+    /// Wait up to the queue timeout for a slot; `None` means the search
+    /// should be rejected
+    pub async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        if self.queue_timeout.is_zero() {
+            return self.permits.clone().try_acquire_owned().ok();
+        }
+        tokio::time::timeout(self.queue_timeout, self.permits.clone().acquire_owned())
+            .await
+            .ok()
+            .and_then(|permit| permit.ok())
+    }
+
+    /// Number of searches that can start without waiting
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after_secs
+    }
+}
+
+/// Settings for the serialized search response cache
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// How long a cached response is served
+    pub ttl: Duration,
+    /// Responses kept at once; the oldest is dropped to make room
+    pub max_entries: usize,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(1),
+            max_entries: 1024,
+        }
+    }
+}
+
+/// Serialized `SearchResponse` bodies keyed by a hash of the full request,
+/// cleared whenever the index changes
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    entries: Arc<std::sync::Mutex<HashMap<[u8; 32], CachedResponse>>>,
+    /// Bumped on every invalidation so searches that raced a mutation
+    /// don't store stale bodies
+    generation: Arc<std::sync::atomic::AtomicU64>,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    body: axum::body::Bytes,
+    stored_at: std::time::Instant,
+}
+
+impl ResponseCache {
+    pub fn new(config: &ResponseCacheConfig) -> Self {
+        Self {
+            entries: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            generation: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            ttl: config.ttl,
+            max_entries: config.max_entries,
+        }
+    }
+
+    /// Cache key covering the query vector, `k`, filter and options
+    pub fn key(request: &SearchRequest) -> [u8; 32] {
+        let encoded = serde_json::to_vec(request).unwrap_or_default();
+        *blake3::hash(&encoded).as_bytes()
+    }
+
+    /// Current generation, to pass back to `put`
+    pub fn generation(&self) -> u64 {
+        self.generation.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    pub fn get(&self, key: &[u8; 32]) -> Option<axum::body::Bytes> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => Some(entry.body.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store a body computed while the cache was at `generation`; dropped
+    /// if the cache has been invalidated since
+    pub fn put(&self, key: [u8; 32], body: axum::body::Bytes, generation: u64) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if self.generation() != generation {
+            return;
+        }
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.stored_at.elapsed() < self.ttl);
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.stored_at)
+                    .map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            key,
+            CachedResponse {
+                body,
+                stored_at: std::time::Instant::now(),
+            },
+        );
+    }
+
+    pub fn invalidate(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        self.generation.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        entries.clear();
+    }
+}
+
+/// Order in which the in-memory map and storage are consulted for metadata
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataLookupOrder {
+    /// Use the in-memory copy, falling back to storage when it is missing
+    #[default]
+    MemoryFirst,
+    /// Use the stored copy, falling back to memory when storage has no entry
+    StorageFirst,
+    /// Never touch storage
+    MemoryOnly,
+}
+
+impl std::str::FromStr for MetadataLookupOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "memory_first" => Ok(Self::MemoryFirst),
+            "storage_first" => Ok(Self::StorageFirst),
+            "memory_only" => Ok(Self::MemoryOnly),
+            other => Err(format!("Unknown metadata lookup order: {}", other)),
+        }
+    }
+}
+
+/// Seconds advertised in the `Retry-After` header of searches rejected
+/// because the index isn't initialized yet
+const NOT_READY_RETRY_AFTER_SECS: u64 = 5;
+
+/// How the search endpoint answers while the index is uninitialized, which
+/// with auto-initialization means until the first vector is inserted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UninitializedSearch {
+    /// `503 Service Unavailable`, so a misconfigured server isn't mistaken
+    /// for one where nothing matched
+    #[default]
+    NotReady,
+    /// `200 OK` with no results
+    Empty,
+}
+
+impl std::str::FromStr for UninitializedSearch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "not_ready" => Ok(Self::NotReady),
+            "empty" => Ok(Self::Empty),
+            other => Err(format!("Unknown uninitialized search policy: {}", other)),
         }
     }
 }
@@ -47,8 +318,38 @@ impl Default for ApiConfig {
 pub struct AppState {
     pub hybrid_index: Arc<HybridIndex>,
     pub storage: Arc<EnhancedS5Storage>,
-    pub vector_map: Arc<RwLock<HashMap<String, TimestampedVector>>>,
+    /// Metadata of stored vectors, keyed by client id
+    pub metadata_map: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+    /// Bumped after every change to stored vectors or their metadata, so
+    /// cached filter matches are reused only while the metadata is unchanged
+    pub metadata_generation: Arc<std::sync::atomic::AtomicU64>,
+    /// Maps index ids back to the client ids they were derived from
+    pub id_map: Arc<RwLock<HashMap<VectorId, String>>>,
     pub storage_config: StorageConfigInfo,
+    pub config: ApiConfig,
+    pub search_limiter: Option<SearchLimiter>,
+    pub response_cache: Option<ResponseCache>,
+    /// Vector changes published to `/stream/updates` subscribers
+    pub updates: broadcast::Sender<UpdateEvent>,
+}
+
+/// Kind of change carried by an [`UpdateEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// A vector change streamed by `GET /stream/updates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateEvent {
+    pub id: String,
+    pub operation: UpdateOperation,
+    pub timestamp: String,
+    /// Sub-index holding the vector, `recent` or `historical`
+    pub index: String,
 }
 
 #[derive(Clone, Debug)]
@@ -64,6 +365,9 @@ pub struct InsertVectorRequest {
     pub vector: Vec<f32>,
     #[serde(default)]
     pub metadata: serde_json::Value,
+    /// What to do if the id is already stored; `error` when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_duplicate: Option<OnDuplicate>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +380,9 @@ pub struct InsertVectorResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchInsertRequest {
     pub vectors: Vec<InsertVectorRequest>,
+    /// Duplicate handling for items that don't set their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_duplicate: Option<OnDuplicate>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -91,10 +398,84 @@ pub struct BatchError {
     pub error: String,
 }
 
+/// Request for `PUT /vectors/batch`, which inserts new ids and replaces
+/// existing ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchUpsertRequest {
+    pub vectors: Vec<InsertVectorRequest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpsertStatus {
+    Inserted,
+    Updated,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchUpsertItem {
+    pub id: String,
+    pub status: UpsertStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchUpsertResponse {
+    pub inserted: usize,
+    pub updated: usize,
+    pub failed: usize,
+    /// One entry per requested vector, in request order
+    pub results: Vec<BatchUpsertItem>,
+}
+
+/// Query options for `POST /vectors/batch`
+#[derive(Debug, Default, Deserialize)]
+pub struct BatchInsertQuery {
+    /// Respond with NDJSON lines of per-item results as they are processed
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// One line of a streamed batch insert response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchStreamEvent {
+    Item {
+        id: String,
+        success: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+        /// Line of the request body the item came from, for
+        /// `POST /vectors/stream`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        line: Option<usize>,
+    },
+    Summary {
+        successful: usize,
+        failed: usize,
+    },
+}
+
+impl BatchStreamEvent {
+    fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchRequest {
     pub vector: Vec<f32>,
-    pub k: usize,
+    /// Number of results; the index's default `k` when omitted
+    #[serde(default)]
+    pub k: Option<usize>,
+    /// Results to skip before the `k` returned, for paging; deep pages
+    /// may drift since the search is approximate
+    #[serde(default)]
+    pub offset: usize,
     #[serde(default)]
     pub filter: Option<serde_json::Value>,
     #[serde(default)]
@@ -110,6 +491,95 @@ pub struct SearchOptions {
     pub timeout_ms: Option<u64>,
     pub include_metadata: Option<bool>,
     pub score_threshold: Option<f32>,
+    /// Dotted metadata paths to return instead of the full metadata;
+    /// implies `include_metadata`
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+    /// Order results at (nearly) equal distances by a metadata field
+    #[serde(default)]
+    pub sort_by: Option<SortBy>,
+    /// Scan every vector for the true top-k instead of approximating;
+    /// rejected when the index is too large for a full scan
+    #[serde(default)]
+    pub exact: Option<bool>,
+    /// Include each result's vector; omitted when not set
+    #[serde(default)]
+    pub vector_format: Option<VectorFormat>,
+}
+
+/// How much of a stored vector a response carries
+///
+/// In JSON bodies this is `"full"`, `"none"` or `{"truncated": n}`; query
+/// strings use `full`, `none` or `truncated:n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorFormat {
+    #[default]
+    Full,
+    /// Omit the vector field
+    None,
+    /// Only the first `n` components
+    Truncated(usize),
+}
+
+impl VectorFormat {
+    /// The part of `vector` to return, or `None` to omit it
+    pub fn apply(&self, vector: &[f32]) -> Option<Vec<f32>> {
+        match self {
+            VectorFormat::Full => Some(vector.to_vec()),
+            VectorFormat::None => None,
+            VectorFormat::Truncated(n) => Some(vector[..(*n).min(vector.len())].to_vec()),
+        }
+    }
+}
+
+impl std::str::FromStr for VectorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(VectorFormat::Full),
+            "none" => Ok(VectorFormat::None),
+            _ => s
+                .strip_prefix("truncated:")
+                .and_then(|n| n.parse().ok())
+                .map(VectorFormat::Truncated)
+                .ok_or_else(|| format!("Invalid vector_format '{}'", s)),
+        }
+    }
+}
+
+/// Query options for `GET /vectors/{id}`
+#[derive(Debug, Default, Deserialize)]
+pub struct GetVectorQuery {
+    /// `full` (default), `none` or `truncated:n`
+    #[serde(default)]
+    pub vector_format: Option<String>,
+}
+
+/// Secondary sort applied after the distance ranking
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SortBy {
+    /// Dotted metadata path of the sort key
+    pub field: String,
+    #[serde(default)]
+    pub direction: SortDirection,
+    /// Results whose distances differ from the first of a run by at most
+    /// this much are ordered by `field` instead of distance
+    #[serde(default = "default_sort_tie_tolerance")]
+    pub tie_tolerance: f32,
+}
+
+fn default_sort_tie_tolerance() -> f32 {
+    1e-4
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -118,6 +588,17 @@ pub struct SearchResponse {
     pub search_time_ms: f64,
     pub indices_searched: u32,
     pub partial_results: bool,
+    /// Parameters the search ran with, after applying the index's defaults
+    #[serde(default)]
+    pub parameters: Option<SearchParameters>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchParameters {
+    pub k: usize,
+    pub hnsw_ef: usize,
+    pub ivf_n_probe: usize,
+    pub exact: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -127,6 +608,8 @@ pub struct SearchResult {
     pub score: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector: Option<Vec<f32>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -165,6 +648,9 @@ pub struct StatisticsResponse {
     pub recent_vectors: usize,
     pub historical_vectors: usize,
     pub memory_usage: MemoryUsage,
+    /// Write-lock wait times, only with the `lock-metrics` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_contention: Option<LockContentionStats>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -180,12 +666,46 @@ pub struct MigrationResponse {
     pub duration_ms: f64,
 }
 
+/// Optional JSON body of `POST /admin/migrate`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MigrationRequest {
+    /// Migrate vectors older than this many seconds instead of the index's
+    /// `recent_threshold`
+    #[serde(default)]
+    pub threshold_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MigrationQuery {
+    /// Respond with SSE `progress` events per batch and a final `complete` event
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// Data of an SSE `progress` event sent during a streamed migration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationProgress {
+    pub migrated: usize,
+    pub total: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RebalanceResponse {
     pub clusters_modified: usize,
     pub vectors_moved: usize,
 }
 
+/// Query parameters for `GET /admin/hnsw-graph`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HnswGraphQuery {
+    /// Comma separated layer numbers, e.g. `0,1`
+    pub layers: Option<String>,
+    pub max_nodes: Option<usize>,
+}
+
+/// Node budget for graph exports when the request doesn't give one
+const DEFAULT_GRAPH_EXPORT_NODES: usize = 1000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackupRequest {
     pub backup_path: String,
@@ -205,6 +725,9 @@ pub struct ErrorResponse {
     pub error: String,
     #[serde(skip)]
     pub status_code: StatusCode,
+    /// Seconds sent back in a `Retry-After` header
+    #[serde(skip)]
+    pub retry_after: Option<u64>,
 }
 
 impl ErrorResponse {
@@ -212,6 +735,7 @@ impl ErrorResponse {
         Self {
             error,
             status_code: StatusCode::INTERNAL_SERVER_ERROR,
+            retry_after: None,
         }
     }
 
@@ -219,13 +743,38 @@ impl ErrorResponse {
         Self {
             error,
             status_code: StatusCode::BAD_REQUEST,
+            retry_after: None,
+        }
+    }
+
+    pub fn conflict(error: String) -> Self {
+        Self {
+            error,
+            status_code: StatusCode::CONFLICT,
+            retry_after: None,
+        }
+    }
+
+    pub fn service_unavailable(error: String, retry_after: u64) -> Self {
+        Self {
+            error,
+            status_code: StatusCode::SERVICE_UNAVAILABLE,
+            retry_after: Some(retry_after),
         }
     }
 }
 
 impl IntoResponse for ErrorResponse {
     fn into_response(self) -> Response {
-        (self.status_code, Json(self)).into_response()
+        match self.retry_after {
+            Some(secs) => (
+                self.status_code,
+                [(header::RETRY_AFTER, secs.to_string())],
+                Json(self),
+            )
+                .into_response(),
+            None => (self.status_code, Json(self)).into_response(),
+        }
     }
 }
 
@@ -260,6 +809,8 @@ pub async fn create_app(config: ApiConfig) -> Result<Router, anyhow::Error> {
                     connection_timeout: Some(5000),
                     retry_attempts: Some(3),
                     encrypt_at_rest: None,
+                    encryption_key: env::var("S5_ENCRYPTION_KEY").ok(),
+                    compress: env::var("S5_COMPRESS").ok().and_then(|v| v.parse().ok()),
                 };
                 let info = StorageConfigInfo {
                     mode: "mock".to_string(),
@@ -280,6 +831,8 @@ pub async fn create_app(config: ApiConfig) -> Result<Router, anyhow::Error> {
             connection_timeout: Some(5000),
             retry_attempts: Some(3),
             encrypt_at_rest: None,
+            encryption_key: env::var("S5_ENCRYPTION_KEY").ok(),
+            compress: env::var("S5_COMPRESS").ok().and_then(|v| v.parse().ok()),
         };
         let info = StorageConfigInfo {
             mode: "mock".to_string(),
@@ -287,34 +840,56 @@ pub async fn create_app(config: ApiConfig) -> Result<Router, anyhow::Error> {
         };
         (Arc::new(EnhancedS5Storage::new(mock_config).map_err(|e| anyhow::anyhow!("Storage error: {}", e))?), info)
     };
-    
-    // Initialize HybridIndex with default config
-    let hybrid_config = HybridConfig::default();
-    let mut hybrid_index = HybridIndex::new(hybrid_config);
-    
-    // Initialize with minimal training data
-    // Get dimension from environment or use a small default for testing
-    let dimension = std::env::var("VECTOR_DIMENSION")
-        .ok()
-        .and_then(|v| v.parse::<usize>().ok())
-        .unwrap_or(3); // Use 3 for testing, matching test vectors
-    
-    let training_vectors = vec![
-        vec![1.0; dimension],
-        vec![0.5; dimension],
-        vec![0.0; dimension],
-    ];
-    hybrid_index.initialize(training_vectors).await
-        .map_err(|e| anyhow::anyhow!("Failed to initialize hybrid index: {}", e))?;
-    
+
+    create_app_with_storage(config, storage, storage_config_info).await
+}
+
+/// Build the router around an already configured storage backend
+pub async fn create_app_with_storage(
+    config: ApiConfig,
+    storage: Arc<EnhancedS5Storage>,
+    storage_config_info: StorageConfigInfo,
+) -> Result<Router, anyhow::Error> {
+    let state = create_state(config, storage, storage_config_info).await?;
+    Ok(create_router(state))
+}
+
+/// Initialize the index and shared state served by the router
+pub async fn create_state(
+    config: ApiConfig,
+    storage: Arc<EnhancedS5Storage>,
+    storage_config_info: StorageConfigInfo,
+) -> Result<AppState, anyhow::Error> {
+    // The first inserted vector fixes the dimension and the historical
+    // index trains once enough real vectors have arrived
+    let hybrid_config = HybridConfig {
+        auto_initialize: true,
+        ..HybridConfig::default()
+    };
+    let hybrid_index = HybridIndex::new(hybrid_config);
+
     let hybrid_index = Arc::new(hybrid_index);
 
-    let state = AppState { 
+    let search_limiter = config.search_limit.as_ref().map(SearchLimiter::new);
+    let response_cache = config.response_cache.as_ref().map(ResponseCache::new);
+    let (updates, _) = broadcast::channel(config.update_buffer.max(1));
+    Ok(AppState {
         hybrid_index,
         storage,
-        vector_map: Arc::new(RwLock::new(HashMap::new())),
+        metadata_map: Arc::new(RwLock::new(HashMap::new())),
+        metadata_generation: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        id_map: Arc::new(RwLock::new(HashMap::new())),
         storage_config: storage_config_info,
-    };
+        config,
+        search_limiter,
+        response_cache,
+        updates,
+    })
+}
+
+/// Mount the API routes and middleware on top of `state`
+pub fn create_router(state: AppState) -> Router {
+    let max_request_size = state.config.max_request_size;
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
@@ -327,29 +902,33 @@ pub async fn create_app(config: ApiConfig) -> Result<Router, anyhow::Error> {
         .route("/health", get(health_handler))
         // Vector operations
         .route("/vectors", post(insert_vector))
-        .route("/vectors/batch", post(batch_insert))
+        .route("/vectors/batch", post(batch_insert).put(batch_upsert))
         .route("/vectors/:id", get(get_vector))
         .route("/vectors/:id", delete(delete_vector))
         // Search
         .route("/search", post(search))
+        .route("/search-defaults", get(get_search_defaults))
+        .route("/search-defaults", put(set_search_defaults))
         // Admin
         .route("/admin/statistics", get(get_statistics))
+        .route("/admin/hnsw-graph", get(export_hnsw_graph))
         .route("/admin/migrate", post(trigger_migration))
         .route("/admin/rebalance", post(rebalance))
         .route("/admin/backup", post(backup))
         // Streaming
         .route("/stream/updates", get(sse_updates))
-        .route("/ws", get(websocket_handler));
-    
+        .route("/ws", get(websocket_handler))
+        .route_layer(RequestBodyLimitLayer::new(max_request_size))
+        // Streamed inserts read their body a line at a time, so only each
+        // line is held to the size limit
+        .route("/vectors/stream", post(stream_insert));
+
     // Mount API v1 under /api/v1 prefix
-    let app = Router::new()
+    Router::new()
         .nest("/api/v1", api_v1)
         // Middleware
         .layer(cors)
-        .layer(RequestBodyLimitLayer::new(config.max_request_size))
-        .with_state(state);
-
-    Ok(app)
+        .with_state(state)
 }
 
 // Handler implementations
@@ -397,47 +976,88 @@ async fn insert_vector(
     if let Err(e) = validate_vector(&request.vector) {
         return Err(ErrorResponse::bad_request(e));
     }
+    validate_metadata(&request.metadata, state.config.max_metadata_bytes)
+        .map_err(ErrorResponse::bad_request)?;
+    validate_metadata_schema(&request.metadata, state.config.metadata_schema.as_ref())
+        .map_err(ErrorResponse::bad_request)?;
 
     let timestamp = chrono::Utc::now();
-    
-    // Create timestamped vector
     let vector_id = VectorId::from_string(&request.id);
-    let timestamped_vector = TimestampedVector::new(
-        vector_id.clone(),
-        request.vector.clone(),
-        timestamp,
-    );
-    
-    // Add to hybrid index using insert_with_timestamp
-    state.hybrid_index
-        .insert_with_timestamp(vector_id.clone(), request.vector.clone(), timestamp)
-        .await
-        .map_err(|e| ErrorResponse::new(format!("Failed to add vector to index: {}", e)))?;
-    
-    // Store in vector map for retrieval
-    state.vector_map.write().await.insert(
-        request.id.clone(),
-        timestamped_vector.clone(),
-    );
-    
-    // Persist to storage
     let storage_key = format!("vectors/{}", request.id);
     let vector_data = Vector {
-        id: vector_id,
+        id: vector_id.clone(),
         embedding: Embedding::new(request.vector.clone())
             .map_err(|e| ErrorResponse::new(format!("Invalid embedding: {}", e)))?,
-        metadata: Some(request.metadata),
+        metadata: Some(request.metadata.clone()),
     };
+
+    // Claim the id in storage first, so of two concurrent inserts of the
+    // same id only one gets through
+    let on_duplicate = request.on_duplicate.unwrap_or_default();
+    let claimed = on_duplicate == OnDuplicate::Error;
+    if claimed
+        && !state.storage
+            .put_if_absent(&storage_key, &vector_data)
+            .await
+            .map_err(|e| ErrorResponse::new(format!("Failed to persist vector: {}", e)))?
+    {
+        return Err(ErrorResponse::conflict(format!("Vector {} already exists", request.id)));
+    }
     
-    state.storage
-        .put(&storage_key, &vector_data)
-        .await
-        .map_err(|e| ErrorResponse::new(format!("Failed to persist vector: {}", e)))?;
+    // Add to hybrid index
+    let inserted = state.hybrid_index
+        .insert_with_policy(vector_id.clone(), request.vector.clone(), timestamp, on_duplicate)
+        .await;
+    if inserted.is_err() && claimed {
+        let _ = state.storage.delete(&storage_key).await;
+    }
+    let outcome = inserted.map_err(|e| match e {
+        crate::hybrid::HybridError::DimensionMismatch { .. } => {
+            ErrorResponse::bad_request(e.to_string())
+        }
+        crate::hybrid::HybridError::DuplicateVector(_) => {
+            ErrorResponse::conflict(format!("Vector {} already exists", request.id))
+        }
+        e => ErrorResponse::new(format!("Failed to add vector to index: {}", e)),
+    })?;
+
+    if outcome == InsertOutcome::Skipped {
+        let stored_timestamp = state.hybrid_index.timestamps.read().await
+            .get(&vector_id)
+            .copied()
+            .unwrap_or(timestamp);
+        return Ok((
+            StatusCode::OK,
+            Json(InsertVectorResponse {
+                id: request.id,
+                index: "recent".to_string(),
+                timestamp: stored_timestamp.to_rfc3339(),
+            }),
+        ));
+    }
+    
+    state.metadata_map.write().await.insert(request.id.clone(), request.metadata.clone());
+    invalidate_caches(&state).await;
+    state.id_map.write().await.insert(vector_id, request.id.clone());
+    
+    // Persist to storage, unless already written when claiming the id
+    if !claimed {
+        state.storage
+            .put(&storage_key, &vector_data)
+            .await
+            .map_err(|e| ErrorResponse::new(format!("Failed to persist vector: {}", e)))?;
+    }
     
     info!("Stored vector {} with {} dimensions", request.id, request.vector.len());
+    publish_update(&state, &request.id, outcome_operation(outcome), timestamp);
     
+    let status = if outcome == InsertOutcome::Updated {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
     Ok((
-        StatusCode::CREATED,
+        status,
         Json(InsertVectorResponse {
             id: request.id,
             index: "recent".to_string(),
@@ -446,79 +1066,29 @@ async fn insert_vector(
     ))
 }
 
+/// Items buffered ahead of a slow streaming client before processing pauses
+const BATCH_STREAM_BUFFER: usize = 16;
+
 async fn batch_insert(
     State(state): State<AppState>,
+    Query(query): Query<BatchInsertQuery>,
     Json(request): Json<BatchInsertRequest>,
-) -> Result<Json<BatchInsertResponse>, ErrorResponse> {
+) -> Result<Response, ErrorResponse> {
+    if query.stream {
+        return Ok(stream_batch_insert(state, request.vectors, request.on_duplicate));
+    }
+
     let mut successful = 0;
     let mut failed = 0;
     let mut errors = Vec::new();
     
     for vector_req in request.vectors {
-        // Validate vector
-        if let Err(e) = validate_vector(&vector_req.vector) {
-            failed += 1;
-            errors.push(BatchError {
-                id: vector_req.id.clone(),
-                error: e,
-            });
-            continue;
-        }
-        
-        let timestamp = chrono::Utc::now();
-        let vector_id = VectorId::from_string(&vector_req.id);
-        let timestamped_vector = TimestampedVector::new(
-            vector_id.clone(),
-            vector_req.vector.clone(),
-            timestamp,
-        );
-        
-        // Try to insert into index
-        match state.hybrid_index
-            .insert_with_timestamp(vector_id.clone(), vector_req.vector.clone(), timestamp)
-            .await {
-            Ok(_) => {
-                // Store in vector map
-                state.vector_map.write().await.insert(
-                    vector_req.id.clone(),
-                    timestamped_vector,
-                );
-                
-                // Persist to storage
-                let storage_key = format!("vectors/{}", vector_req.id);
-                let vector_data = Vector {
-                    id: vector_id,
-                    embedding: match Embedding::new(vector_req.vector.clone()) {
-                        Ok(e) => e,
-                        Err(e) => {
-                            failed += 1;
-                            errors.push(BatchError {
-                                id: vector_req.id.clone(),
-                                error: format!("Invalid embedding: {}", e),
-                            });
-                            continue;
-                        }
-                    },
-                    metadata: Some(vector_req.metadata),
-                };
-                
-                match state.storage.put(&storage_key, &vector_data).await {
-                    Ok(_) => successful += 1,
-                    Err(e) => {
-                        failed += 1;
-                        errors.push(BatchError {
-                            id: vector_req.id.clone(),
-                            error: format!("Storage error: {}", e),
-                        });
-                    }
-                }
-            },
-            Err(e) => {
+        let id = vector_req.id.clone();
+        match insert_batch_item(&state, vector_req, request.on_duplicate).await {
+            Ok(_) => successful += 1,
+            Err(error) => {
                 failed += 1;
-                errors.push(BatchError {
-                    id: vector_req.id.clone(),
-                    error: format!("Index error: {}", e),
-                });
+                errors.push(BatchError { id, error });
             }
         }
     }
@@ -527,26 +1097,301 @@ async fn batch_insert(
         successful,
         failed,
         errors,
-    }))
+    })
+    .into_response())
+}
+
+/// Process a batch item by item, writing one NDJSON line per item and a
+/// final summary line.
+///
+/// Lines go through a bounded channel, so processing pauses while the
+/// client isn't reading and stops once it disconnects.
+fn stream_batch_insert(
+    state: AppState,
+    vectors: Vec<InsertVectorRequest>,
+    on_duplicate: Option<OnDuplicate>,
+) -> Response {
+    let (tx, rx) = tokio::sync::mpsc::channel::<String>(BATCH_STREAM_BUFFER);
+
+    tokio::spawn(async move {
+        let mut successful = 0;
+        let mut failed = 0;
+
+        for vector_req in vectors {
+            let id = vector_req.id.clone();
+            let event = match insert_batch_item(&state, vector_req, on_duplicate).await {
+                Ok(_) => {
+                    successful += 1;
+                    BatchStreamEvent::Item { id, success: true, error: None, line: None }
+                }
+                Err(error) => {
+                    failed += 1;
+                    BatchStreamEvent::Item { id, success: false, error: Some(error), line: None }
+                }
+            };
+            if tx.send(event.to_line()).await.is_err() {
+                info!("Client disconnected, stopping streamed batch insert");
+                return;
+            }
+        }
+
+        let _ = tx
+            .send(BatchStreamEvent::Summary { successful, failed }.to_line())
+            .await;
+    });
+
+    let body = axum::body::Body::from_stream(
+        tokio_stream::wrappers::ReceiverStream::new(rx).map(Ok::<_, std::convert::Infallible>),
+    );
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], body).into_response()
+}
+
+/// Lines of a streamed insert being inserted at once
+const STREAM_INSERT_CONCURRENCY: usize = 8;
+
+/// Insert vectors from an NDJSON body of `InsertVectorRequest` lines as
+/// they arrive.
+///
+/// Up to `STREAM_INSERT_CONCURRENCY` lines are inserted at a time, and the
+/// body is only read as fast as inserts finish and the client reads the
+/// response. Each non-blank line gets an `item` line tagged with its line
+/// number, in completion order, and a `summary` line ends the response.
+/// Lines that fail to parse or insert are reported and skipped.
+async fn stream_insert(State(state): State<AppState>, body: axum::body::Body) -> Response {
+    let (tx, rx) = tokio::sync::mpsc::channel::<String>(BATCH_STREAM_BUFFER);
+    let max_line = state.config.max_request_size;
+
+    tokio::spawn(async move {
+        let mut successful = 0;
+        let mut failed = 0;
+
+        let mut events = std::pin::pin!(ndjson_lines(body, max_line)
+            .map(|(line, text)| {
+                let state = state.clone();
+                async move { insert_stream_line(&state, line, text).await }
+            })
+            .buffer_unordered(STREAM_INSERT_CONCURRENCY));
+
+        while let Some(event) = events.next().await {
+            match &event {
+                BatchStreamEvent::Item { success: true, .. } => successful += 1,
+                _ => failed += 1,
+            }
+            if tx.send(event.to_line()).await.is_err() {
+                info!("Client disconnected, stopping streamed insert");
+                return;
+            }
+        }
+
+        let _ = tx
+            .send(BatchStreamEvent::Summary { successful, failed }.to_line())
+            .await;
+    });
+
+    let body = axum::body::Body::from_stream(
+        tokio_stream::wrappers::ReceiverStream::new(rx).map(Ok::<_, std::convert::Infallible>),
+    );
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], body).into_response()
+}
+
+/// Parse and insert one line of a streamed insert
+async fn insert_stream_line(
+    state: &AppState,
+    line: usize,
+    text: Result<String, String>,
+) -> BatchStreamEvent {
+    let request = text.and_then(|text| {
+        serde_json::from_str::<InsertVectorRequest>(&text).map_err(|e| format!("Invalid JSON: {}", e))
+    });
+    let (id, result) = match request {
+        Ok(vector_req) => (
+            vector_req.id.clone(),
+            insert_batch_item(state, vector_req, None).await.map(|_| ()),
+        ),
+        Err(error) => (String::new(), Err(error)),
+    };
+    BatchStreamEvent::Item {
+        id,
+        success: result.is_ok(),
+        error: result.err(),
+        line: Some(line),
+    }
+}
+
+/// Split a request body into numbered, trimmed lines, skipping blank ones.
+///
+/// A final line without a trailing newline still counts. A line longer
+/// than `max_line` bytes or a failed body read is reported as an error and
+/// ends the stream.
+fn ndjson_lines(
+    body: axum::body::Body,
+    max_line: usize,
+) -> impl Stream<Item = (usize, Result<String, String>)> {
+    let state = (body.into_data_stream(), Vec::<u8>::new(), 0usize, false);
+    futures::stream::unfold(state, move |(mut chunks, mut buffer, mut line, mut done)| async move {
+        loop {
+            let end = buffer.iter().position(|&b| b == b'\n');
+            if end.is_some() || (done && !buffer.is_empty()) {
+                let raw: Vec<u8> = match end {
+                    Some(end) => buffer.drain(..=end).collect(),
+                    None => std::mem::take(&mut buffer),
+                };
+                line += 1;
+                let text = match String::from_utf8(raw) {
+                    Ok(text) if text.trim().is_empty() => continue,
+                    Ok(text) => Ok(text.trim().to_string()),
+                    Err(_) => Err("Line is not valid UTF-8".to_string()),
+                };
+                return Some(((line, text), (chunks, buffer, line, done)));
+            }
+            if done {
+                return None;
+            }
+            if buffer.len() > max_line {
+                let error = format!("Line exceeds the {} byte limit", max_line);
+                return Some(((line + 1, Err(error)), (chunks, Vec::new(), line + 1, true)));
+            }
+
+            match chunks.next().await {
+                Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                Some(Err(e)) => {
+                    let error = format!("Failed to read request body: {}", e);
+                    return Some(((line + 1, Err(error)), (chunks, Vec::new(), line + 1, true)));
+                }
+                None => done = true,
+            }
+        }
+    })
+}
+
+/// Insert, record and persist one vector of a batch; `on_duplicate` is the
+/// batch-wide default the item may override
+async fn insert_batch_item(
+    state: &AppState,
+    vector_req: InsertVectorRequest,
+    on_duplicate: Option<OnDuplicate>,
+) -> Result<InsertOutcome, String> {
+    validate_vector(&vector_req.vector)?;
+    validate_metadata(&vector_req.metadata, state.config.max_metadata_bytes)?;
+    validate_metadata_schema(&vector_req.metadata, state.config.metadata_schema.as_ref())?;
+
+    let timestamp = chrono::Utc::now();
+    let vector_id = VectorId::from_string(&vector_req.id);
+
+    let storage_key = format!("vectors/{}", vector_req.id);
+    let vector_data = Vector {
+        id: vector_id.clone(),
+        embedding: Embedding::new(vector_req.vector.clone())
+            .map_err(|e| format!("Invalid embedding: {}", e))?,
+        metadata: Some(vector_req.metadata.clone()),
+    };
+
+    // Claim the id in storage first, as `insert_vector` does
+    let on_duplicate = vector_req.on_duplicate.or(on_duplicate).unwrap_or_default();
+    let claimed = on_duplicate == OnDuplicate::Error;
+    if claimed
+        && !state.storage
+            .put_if_absent(&storage_key, &vector_data)
+            .await
+            .map_err(|e| format!("Storage error: {}", e))?
+    {
+        return Err(format!("Vector {} already exists", vector_req.id));
+    }
+
+    let inserted = state.hybrid_index
+        .insert_with_policy(vector_id.clone(), vector_req.vector.clone(), timestamp, on_duplicate)
+        .await;
+    if inserted.is_err() && claimed {
+        let _ = state.storage.delete(&storage_key).await;
+    }
+    let outcome = inserted.map_err(|e| format!("Index error: {}", e))?;
+    if outcome == InsertOutcome::Skipped {
+        return Ok(outcome);
+    }
+
+    state.metadata_map.write().await.insert(vector_req.id.clone(), vector_req.metadata.clone());
+    invalidate_caches(state).await;
+    state.id_map.write().await.insert(vector_id, vector_req.id.clone());
+
+    // Persist to storage, unless already written when claiming the id
+    if !claimed {
+        state.storage
+            .put(&storage_key, &vector_data)
+            .await
+            .map_err(|e| format!("Storage error: {}", e))?;
+    }
+    publish_update(state, &vector_req.id, outcome_operation(outcome), timestamp);
+    Ok(outcome)
+}
+
+async fn batch_upsert(
+    State(state): State<AppState>,
+    Json(request): Json<BatchUpsertRequest>,
+) -> Json<BatchUpsertResponse> {
+    let mut response = BatchUpsertResponse {
+        inserted: 0,
+        updated: 0,
+        failed: 0,
+        results: Vec::with_capacity(request.vectors.len()),
+    };
+
+    for mut vector_req in request.vectors {
+        let id = vector_req.id.clone();
+        vector_req.on_duplicate = Some(OnDuplicate::Update);
+        let (status, error) = match insert_batch_item(&state, vector_req, None).await {
+            Ok(InsertOutcome::Updated) => {
+                response.updated += 1;
+                (UpsertStatus::Updated, None)
+            }
+            Ok(_) => {
+                response.inserted += 1;
+                (UpsertStatus::Inserted, None)
+            }
+            Err(error) => {
+                response.failed += 1;
+                (UpsertStatus::Failed, Some(error))
+            }
+        };
+        response.results.push(BatchUpsertItem { id, status, error });
+    }
+
+    Json(response)
 }
 
 async fn get_vector(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Query(query): Query<GetVectorQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    // First check in-memory map
-    if let Some(vector) = state.vector_map.read().await.get(&id) {
-        let response = serde_json::json!({
+    let vector_format: VectorFormat = match &query.vector_format {
+        Some(format) => format.parse().map_err(|_| StatusCode::BAD_REQUEST)?,
+        None => VectorFormat::Full,
+    };
+
+    // First check the index
+    let vector_id = VectorId::from_string(&id);
+    if let Some(vector) = state.hybrid_index.get_vector(&vector_id).await {
+        let timestamp = state.hybrid_index.timestamps.read().await
+            .get(&vector_id)
+            .copied()
+            .unwrap_or_else(chrono::Utc::now);
+        let metadata = state.metadata_map.read().await.get(&id).cloned();
+        let mut response = serde_json::json!({
             "id": id,
-            "vector": vector.vector(),
-            "metadata": {},
-            "index": if vector.is_recent(Duration::from_secs(7 * 24 * 3600)) { 
-                "recent" 
-            } else { 
-                "historical" 
+            "vector": vector_format.apply(&vector),
+            "metadata": metadata.unwrap_or(serde_json::json!({})),
+            "index": if state.hybrid_index.is_in_recent(&vector_id) {
+                "recent"
+            } else {
+                "historical"
             },
-            "timestamp": chrono::DateTime::<chrono::Utc>::from(vector.timestamp()).to_rfc3339(),
+            "timestamp": timestamp.to_rfc3339(),
         });
+        if vector_format == VectorFormat::None {
+            if let Some(fields) = response.as_object_mut() {
+                fields.remove("vector");
+            }
+        }
         return Ok(Json(response));
     }
     
@@ -554,12 +1399,17 @@ async fn get_vector(
     let storage_key = format!("vectors/{}", id);
     match state.storage.get::<Vector>(&storage_key).await {
         Ok(vector) => {
-            let response = serde_json::json!({
+            let mut response = serde_json::json!({
                 "id": id,
-                "vector": vector.embedding.as_slice(),
+                "vector": vector_format.apply(vector.embedding.as_slice()),
                 "metadata": vector.metadata.unwrap_or(serde_json::json!({})),
                 "timestamp": chrono::Utc::now().to_rfc3339(),
             });
+            if vector_format == VectorFormat::None {
+                if let Some(fields) = response.as_object_mut() {
+                    fields.remove("vector");
+                }
+            }
             Ok(Json(response))
         },
         Err(e) => {
@@ -573,20 +1423,26 @@ async fn delete_vector(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, ErrorResponse> {
-    // Remove from in-memory map
-    let existed = state.vector_map.write().await.remove(&id).is_some();
+    // Remove from the index and in-memory maps
+    let vector_id = VectorId::from_string(&id);
+    let existed = state.hybrid_index.delete(vector_id.clone()).await.is_ok();
+    state.metadata_map.write().await.remove(&id);
+    invalidate_caches(&state).await;
+    state.id_map.write().await.remove(&vector_id);
     
     // Delete from storage
     let storage_key = format!("vectors/{}", id);
     match state.storage.delete(&storage_key).await {
         Ok(_) => {
             info!("Deleted vector {}", id);
+            publish_update(&state, &id, UpdateOperation::Delete, chrono::Utc::now());
             Ok(StatusCode::NO_CONTENT)
         },
         Err(e) => {
             if existed {
                 // Was in memory but failed to delete from storage
                 error!("Failed to delete vector {} from storage: {}", id, e);
+                publish_update(&state, &id, UpdateOperation::Delete, chrono::Utc::now());
                 Ok(StatusCode::NO_CONTENT) // Still report success since it's removed from memory
             } else {
                 // Not found anywhere
@@ -596,18 +1452,119 @@ async fn delete_vector(
     }
 }
 
+/// Announce a vector change to `/stream/updates` subscribers, if any
+fn publish_update(
+    state: &AppState,
+    id: &str,
+    operation: UpdateOperation,
+    timestamp: chrono::DateTime<chrono::Utc>,
+) {
+    let index = if state.hybrid_index.is_in_historical(&VectorId::from_string(id)) {
+        "historical"
+    } else {
+        "recent"
+    };
+    // Sending only fails when nobody is subscribed
+    let _ = state.updates.send(UpdateEvent {
+        id: id.to_string(),
+        operation,
+        timestamp: timestamp.to_rfc3339(),
+        index: index.to_string(),
+    });
+}
+
+fn outcome_operation(outcome: InsertOutcome) -> UpdateOperation {
+    if outcome == InsertOutcome::Updated {
+        UpdateOperation::Update
+    } else {
+        UpdateOperation::Insert
+    }
+}
+
+/// Drop cached filter results and serialized responses after a mutation
+async fn invalidate_caches(state: &AppState) {
+    state
+        .metadata_generation
+        .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    state.hybrid_index.invalidate_filter_cache().await;
+    if let Some(cache) = &state.response_cache {
+        cache.invalidate();
+    }
+}
+
 async fn search(
     State(state): State<AppState>,
     Json(request): Json<SearchRequest>,
-) -> Result<Json<SearchResponse>, ErrorResponse> {
+) -> Result<Response, ErrorResponse> {
+    let Some(cache) = state.response_cache.clone() else {
+        return Ok(Json(run_search(&state, request).await?).into_response());
+    };
+
+    let key = ResponseCache::key(&request);
+    if let Some(body) = cache.get(&key) {
+        return Ok(cached_json(body, "hit"));
+    }
+    let generation = cache.generation();
+    let response = run_search(&state, request).await?;
+    let body = serde_json::to_vec(&response)
+        .map_err(|e| ErrorResponse::new(format!("Failed to serialize response: {}", e)))?;
+    let body = axum::body::Bytes::from(body);
+    cache.put(key, body.clone(), generation);
+    Ok(cached_json(body, "miss"))
+}
+
+/// Pre-serialized JSON body with an `X-Cache` header saying whether it came
+/// from the response cache
+fn cached_json(body: axum::body::Bytes, status: &'static str) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (header::HeaderName::from_static("x-cache"), status),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+async fn run_search(
+    state: &AppState,
+    request: SearchRequest,
+) -> Result<SearchResponse, ErrorResponse> {
     // Validate query vector
     if let Err(e) = validate_vector(&request.vector) {
         return Err(ErrorResponse::bad_request(e));
     }
+    let filter = request
+        .filter
+        .as_ref()
+        .map(MetadataFilter::from_json)
+        .transpose()
+        .map_err(|e| ErrorResponse::bad_request(e.to_string()))?;
+
+    if !state.hybrid_index.is_initialized()
+        && state.config.uninitialized_search == UninitializedSearch::NotReady
+    {
+        return Err(ErrorResponse::service_unavailable(
+            "Index not ready: it has not been initialized yet".to_string(),
+            NOT_READY_RETRY_AFTER_SECS,
+        ));
+    }
     
+    // Hold a slot for the rest of the request when searches are bounded
+    let _permit = match &state.search_limiter {
+        Some(limiter) => Some(limiter.acquire().await.ok_or_else(|| {
+            ErrorResponse::service_unavailable(
+                "Too many concurrent searches".to_string(),
+                limiter.retry_after_secs(),
+            )
+        })?),
+        None => None,
+    };
+
     let start_time = std::time::Instant::now();
     
-    // Configure search
+    // Configure search, falling back to the index's defaults
+    let defaults = state.hybrid_index.default_search_config().await;
     let search_config = crate::hybrid::HybridSearchConfig {
         search_recent: request.options.as_ref()
             .and_then(|o| o.search_recent)
@@ -618,48 +1575,115 @@ async fn search(
         recent_k: 0,
         historical_k: 0,
         recent_threshold_override: None,
-        k: request.k,
+        k: request.k.unwrap_or(defaults.k),
+        offset: request.offset,
         hnsw_ef: request.options.as_ref()
             .and_then(|o| o.hnsw_ef)
-            .unwrap_or(50),
+            .unwrap_or(defaults.hnsw_ef),
         ivf_n_probe: request.options.as_ref()
             .and_then(|o| o.ivf_n_probe)
-            .unwrap_or(10),
+            .unwrap_or(defaults.ivf_n_probe),
+        rerank_exact: false,
+        nan_distances: crate::hybrid::NanDistancePolicy::RankLast,
+        skip_empty_indices: true,
+        exact: request.options.as_ref()
+            .and_then(|o| o.exact)
+            .unwrap_or(false),
+        adaptive_ef: None,
     };
     
-    // Perform search - HybridIndex search method takes vector and k
-    let search_results = state.hybrid_index
-        .search(&request.vector, request.k)
-        .await
-        .map_err(|e| ErrorResponse::new(format!("Search failed: {}", e)))?;
+    let search_results = match &filter {
+        Some(filter) => {
+            // Read before the map so a concurrent change can't be cached
+            // under the generation it replaces
+            let generation = state
+                .metadata_generation
+                .load(std::sync::atomic::Ordering::SeqCst);
+            let metadata_map = filter_metadata_map(state).await;
+            state.hybrid_index
+                .search_with_filter_config(
+                    &request.vector,
+                    search_config.clone(),
+                    Some(filter),
+                    &metadata_map,
+                    Some(generation),
+                )
+                .await
+        }
+        None => state.hybrid_index
+            .search_with_config(&request.vector, search_config.clone())
+            .await,
+    }
+    .map_err(|e| match e {
+            crate::hybrid::HybridError::ExactSearchTooLarge { .. }
+            | crate::hybrid::HybridError::InvalidQuery(_) => {
+                ErrorResponse::bad_request(e.to_string())
+            }
+            e => ErrorResponse::new(format!("Search failed: {}", e)),
+        })?;
     
+    let fields = request.options.as_ref().and_then(|o| o.fields.as_ref());
+    let include_metadata = request.options.as_ref()
+        .and_then(|o| o.include_metadata)
+        .unwrap_or(false)
+        || fields.is_some();
+    let sort_by = request.options.as_ref().and_then(|o| o.sort_by.as_ref());
+    let vector_format = request.options.as_ref()
+        .and_then(|o| o.vector_format)
+        .unwrap_or(VectorFormat::None);
+
+    // Resolve index ids back to the ids clients inserted with
+    let ids: Vec<String> = {
+        let id_map = state.id_map.read().await;
+        search_results
+            .iter()
+            .map(|r| id_map.get(&r.vector_id).cloned().unwrap_or_else(|| r.vector_id.to_string()))
+            .collect()
+    };
+
     // Convert results
     let mut results = Vec::new();
-    for result in search_results {
-        // Get metadata from storage or in-memory map
-        let metadata = if let Some(vector) = state.vector_map.read().await.get(&result.vector_id.to_string()) {
-            serde_json::json!({})
+    let mut sort_keys = Vec::new();
+    for (result, id) in search_results.into_iter().zip(ids) {
+        let full_metadata = if include_metadata || sort_by.is_some() {
+            Some(lookup_metadata(state, &id).await)
         } else {
-            let storage_key = format!("vectors/{}", result.vector_id.to_string());
-            match state.storage.get::<Vector>(&storage_key).await {
-                Ok(vector) => vector.metadata.unwrap_or(serde_json::json!({})),
-                Err(_) => serde_json::json!({}),
-            }
+            None
+        };
+        if let Some(sort_by) = sort_by {
+            sort_keys.push(
+                full_metadata
+                    .as_ref()
+                    .and_then(|metadata| crate::core::metadata_filter::get_field(metadata, &sort_by.field))
+                    .filter(|value| !value.is_null())
+                    .cloned(),
+            );
+        }
+        let metadata = if include_metadata {
+            full_metadata.map(|metadata| match fields {
+                Some(fields) => crate::core::metadata_filter::project_fields(&metadata, fields),
+                None => metadata,
+            })
+        } else {
+            None
+        };
+        let vector = match vector_format {
+            VectorFormat::None => None,
+            format => lookup_vector(state, &id).await.and_then(|v| format.apply(&v)),
         };
         
         results.push(SearchResult {
-            id: result.vector_id.to_string(),
+            id,
             distance: result.distance,
             score: 1.0 / (1.0 + result.distance), // Convert distance to similarity score
-            metadata: if request.options.as_ref()
-                .and_then(|o| o.include_metadata)
-                .unwrap_or(false) { 
-                Some(metadata) 
-            } else { 
-                None 
-            },
+            metadata,
+            vector,
         });
     }
+
+    if let Some(sort_by) = sort_by {
+        results = sort_ties_by_metadata(results, sort_keys, sort_by);
+    }
     
     // Apply score threshold if specified
     if let Some(threshold) = request.options.as_ref().and_then(|o| o.score_threshold) {
@@ -668,38 +1692,153 @@ async fn search(
     
     let elapsed = start_time.elapsed();
     
-    Ok(Json(SearchResponse {
+    Ok(SearchResponse {
         results,
         search_time_ms: elapsed.as_secs_f64() * 1000.0,
         indices_searched: if search_config.search_recent && search_config.search_historical { 2 } else { 1 },
         partial_results: false,
-    }))
+        parameters: Some(SearchParameters {
+            k: search_config.k,
+            hnsw_ef: search_config.hnsw_ef,
+            ivf_n_probe: search_config.ivf_n_probe,
+            exact: search_config.exact,
+        }),
+    })
+}
+
+async fn get_search_defaults(State(state): State<AppState>) -> Json<SearchDefaults> {
+    Json(state.hybrid_index.search_defaults().await)
+}
+
+async fn set_search_defaults(
+    State(state): State<AppState>,
+    Json(defaults): Json<SearchDefaults>,
+) -> Result<Json<SearchDefaults>, ErrorResponse> {
+    state
+        .hybrid_index
+        .set_search_defaults(defaults)
+        .await
+        .map_err(|e| ErrorResponse::bad_request(e.to_string()))?;
+    invalidate_caches(&state).await;
+    Ok(Json(state.hybrid_index.search_defaults().await))
 }
 
 async fn get_statistics(
     State(state): State<AppState>,
 ) -> Result<Json<StatisticsResponse>, ErrorResponse> {
-    // TODO: Implement statistics
+    let stats = state.hybrid_index.get_statistics().await;
     Ok(Json(StatisticsResponse {
-        total_vectors: 0,
-        recent_vectors: 0,
-        historical_vectors: 0,
+        total_vectors: stats.total_vectors,
+        recent_vectors: stats.recent_vectors,
+        historical_vectors: stats.historical_vectors,
         memory_usage: MemoryUsage {
-            total_bytes: 0,
-            hnsw_bytes: 0,
-            ivf_bytes: 0,
+            total_bytes: stats.recent_index_memory + stats.historical_index_memory,
+            hnsw_bytes: stats.recent_index_memory,
+            ivf_bytes: stats.historical_index_memory,
         },
+        lock_contention: state.hybrid_index.lock_contention(),
     }))
 }
 
+async fn export_hnsw_graph(
+    State(state): State<AppState>,
+    Query(query): Query<HnswGraphQuery>,
+) -> Result<Json<GraphExport>, ErrorResponse> {
+    let layers = match query.layers.as_deref() {
+        Some(layers) => Some(
+            layers
+                .split(',')
+                .map(|l| l.trim().parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ErrorResponse::bad_request(format!("Invalid layers: {}", layers)))?,
+        ),
+        None => None,
+    };
+    let options = GraphExportOptions {
+        layers,
+        max_nodes: Some(query.max_nodes.unwrap_or(DEFAULT_GRAPH_EXPORT_NODES)),
+    };
+
+    let recent = state.hybrid_index.get_recent_index().await;
+    Ok(Json(recent.export_graph_with(&options)))
+}
+
 async fn trigger_migration(
     State(state): State<AppState>,
-) -> Result<Json<MigrationResponse>, ErrorResponse> {
-    // TODO: Implement migration
+    Query(query): Query<MigrationQuery>,
+    body: axum::body::Bytes,
+) -> Result<Response, ErrorResponse> {
+    // The body is optional; without one the configured threshold applies
+    let request: MigrationRequest = if body.is_empty() {
+        MigrationRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| ErrorResponse::bad_request(format!("Invalid migration request: {}", e)))?
+    };
+    let threshold = request
+        .threshold_secs
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(state.hybrid_index.config().recent_threshold);
+
+    if query.stream {
+        return Ok(stream_migration(state, threshold));
+    }
+
+    let start_time = std::time::Instant::now();
+    let vectors_migrated = state
+        .hybrid_index
+        .migrate_with_threshold(threshold)
+        .await
+        .map_err(|e| ErrorResponse::new(format!("Migration failed: {}", e)))?;
+    invalidate_caches(&state).await;
+
     Ok(Json(MigrationResponse {
-        vectors_migrated: 0,
-        duration_ms: 0.0,
-    }))
+        vectors_migrated,
+        duration_ms: start_time.elapsed().as_secs_f64() * 1000.0,
+    })
+    .into_response())
+}
+
+/// Run a migration in the background, sending a `progress` event after each
+/// batch and a final `complete` (or `error`) event
+fn stream_migration(state: AppState, threshold: std::time::Duration) -> Response {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<axum::response::sse::Event>();
+
+    tokio::spawn(async move {
+        let start_time = std::time::Instant::now();
+        let progress_tx = tx.clone();
+        let result = state
+            .hybrid_index
+            .migrate_with_progress(threshold, move |migrated, total| {
+                let event = axum::response::sse::Event::default()
+                    .event("progress")
+                    .json_data(MigrationProgress { migrated, total });
+                if let Ok(event) = event {
+                    let _ = progress_tx.send(event);
+                }
+            })
+            .await;
+        invalidate_caches(&state).await;
+
+        let event = match result {
+            Ok(vectors_migrated) => axum::response::sse::Event::default()
+                .event("complete")
+                .json_data(MigrationResponse {
+                    vectors_migrated,
+                    duration_ms: start_time.elapsed().as_secs_f64() * 1000.0,
+                }),
+            Err(e) => axum::response::sse::Event::default()
+                .event("error")
+                .json_data(serde_json::json!({ "error": e.to_string() })),
+        };
+        if let Ok(event) = event {
+            let _ = tx.send(event);
+        }
+    });
+
+    let stream = tokio_stream::wrappers::UnboundedReceiverStream::new(rx)
+        .map(Ok::<_, std::convert::Infallible>);
+    Sse::new(stream).into_response()
 }
 
 async fn rebalance(
@@ -712,24 +1851,54 @@ async fn rebalance(
     }))
 }
 
+/// Save the index to `backup_path` in the server's storage, see
+/// `BackupManager::create_backup`
 async fn backup(
     State(state): State<AppState>,
     Json(request): Json<BackupRequest>,
 ) -> Result<Json<BackupResponse>, ErrorResponse> {
-    // TODO: Implement backup
+    if request.backup_path.trim_matches('/').is_empty() {
+        return Err(ErrorResponse::bad_request("backup_path must not be empty".to_string()));
+    }
+
+    let config = BackupConfig {
+        compress: request.compress,
+        ..BackupConfig::default()
+    };
+    let result = BackupManager::new(state.storage.clone())
+        .create_backup(&state.hybrid_index, &request.backup_path, config)
+        .await
+        .map_err(|e| ErrorResponse::new(format!("Backup failed: {}", e)))?;
+
     Ok(Json(BackupResponse {
-        backup_size: 0,
-        vectors_backed_up: 0,
-        compression_ratio: 1.0,
+        backup_size: result.backup_size as u64,
+        vectors_backed_up: result.vectors_backed_up,
+        compression_ratio: result.compression_ratio as f64,
     }))
 }
 
+/// Forward vector changes as `update` events. A client that falls behind
+/// the broadcast buffer gets a `lagged` event with the number of skipped
+/// updates and carries on from the oldest one still buffered.
 async fn sse_updates(
     State(state): State<AppState>,
 ) -> Sse<impl Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>> {
-    // TODO: Implement SSE updates
-    let stream = futures::stream::empty();
-    Sse::new(stream)
+    let rx = state.updates.subscribe();
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        let event = match rx.recv().await {
+            Ok(update) => axum::response::sse::Event::default()
+                .event("update")
+                .json_data(update),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                axum::response::sse::Event::default()
+                    .event("lagged")
+                    .json_data(serde_json::json!({ "skipped": skipped }))
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
+        };
+        Some((Ok(event.unwrap_or_default()), rx))
+    });
+    Sse::new(stream).keep_alive(axum::response::sse::KeepAlive::default())
 }
 
 async fn websocket_handler() -> impl IntoResponse {
@@ -737,6 +1906,126 @@ async fn websocket_handler() -> impl IntoResponse {
     StatusCode::SWITCHING_PROTOCOLS
 }
 
+// Metadata lookup helpers
+
+/// Metadata of every stored vector, keyed by index id string as
+/// `HybridIndex::search_with_filter` expects. Read from the in-memory map,
+/// which every insert writes alongside `id_map`, so filtering never goes
+/// to storage.
+async fn filter_metadata_map(state: &AppState) -> HashMap<String, serde_json::Value> {
+    let id_map = state.id_map.read().await;
+    let memory = state.metadata_map.read().await;
+    id_map
+        .iter()
+        .map(|(vector_id, id)| {
+            let metadata = memory.get(id).cloned().unwrap_or(serde_json::json!({}));
+            (vector_id.to_string(), metadata)
+        })
+        .collect()
+}
+
+async fn lookup_metadata(state: &AppState, id: &str) -> serde_json::Value {
+    let found = match state.config.metadata_lookup {
+        MetadataLookupOrder::MemoryFirst => match memory_metadata(state, id).await {
+            Some(metadata) => Some(metadata),
+            None => storage_metadata(state, id).await,
+        },
+        MetadataLookupOrder::StorageFirst => match storage_metadata(state, id).await {
+            Some(metadata) => Some(metadata),
+            None => memory_metadata(state, id).await,
+        },
+        MetadataLookupOrder::MemoryOnly => memory_metadata(state, id).await,
+    };
+    found.unwrap_or(serde_json::json!({}))
+}
+
+async fn lookup_vector(state: &AppState, id: &str) -> Option<Vec<f32>> {
+    if let Some(vector) = state.hybrid_index.get_vector(&VectorId::from_string(id)).await {
+        return Some(vector);
+    }
+    let storage_key = format!("vectors/{}", id);
+    state
+        .storage
+        .get::<Vector>(&storage_key)
+        .await
+        .ok()
+        .map(|vector| vector.embedding.as_slice().to_vec())
+}
+
+async fn memory_metadata(state: &AppState, id: &str) -> Option<serde_json::Value> {
+    state.metadata_map.read().await.get(id).cloned()
+}
+
+async fn storage_metadata(state: &AppState, id: &str) -> Option<serde_json::Value> {
+    let storage_key = format!("vectors/{}", id);
+    match state.storage.get::<Vector>(&storage_key).await {
+        Ok(vector) => vector.metadata,
+        Err(_) => None,
+    }
+}
+
+// Secondary sort helpers
+
+/// Stable-sort each run of results whose distances lie within the tie
+/// tolerance of the run's first result by its metadata sort key. Results
+/// without the field go last in either direction.
+fn sort_ties_by_metadata(
+    results: Vec<SearchResult>,
+    sort_keys: Vec<Option<serde_json::Value>>,
+    sort_by: &SortBy,
+) -> Vec<SearchResult> {
+    let mut keyed: Vec<(SearchResult, Option<serde_json::Value>)> =
+        results.into_iter().zip(sort_keys).collect();
+
+    let mut start = 0;
+    while start < keyed.len() {
+        let anchor = keyed[start].0.distance;
+        let mut end = start + 1;
+        while end < keyed.len() && (keyed[end].0.distance - anchor).abs() <= sort_by.tie_tolerance {
+            end += 1;
+        }
+        keyed[start..end].sort_by(|a, b| match (&a.1, &b.1) {
+            (Some(a), Some(b)) => match sort_by.direction {
+                SortDirection::Asc => compare_sort_keys(a, b),
+                SortDirection::Desc => compare_sort_keys(b, a),
+            },
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        start = end;
+    }
+
+    keyed.into_iter().map(|(result, _)| result).collect()
+}
+
+/// Numbers compare numerically and strings lexicographically (so ISO 8601
+/// timestamps sort chronologically); mixed types order by type
+fn compare_sort_keys(a: &serde_json::Value, b: &serde_json::Value) -> std::cmp::Ordering {
+    use serde_json::Value;
+
+    fn type_rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .unwrap_or(f64::NAN)
+            .total_cmp(&b.as_f64().unwrap_or(f64::NAN)),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => type_rank(a).cmp(&type_rank(b)),
+    }
+}
+
 // Validation helpers
 pub fn validate_vector(vector: &[f32]) -> Result<(), String> {
     if vector.is_empty() {
@@ -744,3 +2033,34 @@ pub fn validate_vector(vector: &[f32]) -> Result<(), String> {
     }
     Ok(())
 }
+
+/// Reject metadata whose serialized JSON is larger than `max_bytes`
+pub fn validate_metadata(metadata: &serde_json::Value, max_bytes: usize) -> Result<(), String> {
+    let size = serde_json::to_vec(metadata)
+        .map_err(|e| format!("Invalid metadata: {}", e))?
+        .len();
+    if size > max_bytes {
+        return Err(format!(
+            "Metadata is {} bytes, exceeding the limit of {} bytes",
+            size, max_bytes
+        ));
+    }
+    Ok(())
+}
+
+/// Reject metadata that doesn't match `schema`, if one is set. Missing
+/// metadata counts as an empty object, so required fields still apply;
+/// number fields accept integers and floats alike.
+pub fn validate_metadata_schema(
+    metadata: &serde_json::Value,
+    schema: Option<&MetadataSchema>,
+) -> Result<(), String> {
+    let Some(schema) = schema else {
+        return Ok(());
+    };
+    let result = match metadata {
+        serde_json::Value::Null => schema.validate(&serde_json::json!({})),
+        metadata => schema.validate(metadata),
+    };
+    result.map_err(|e| format!("Metadata does not match schema: {}", e))
+}
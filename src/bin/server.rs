@@ -5,7 +5,7 @@ use anyhow::Result;
 use std::net::SocketAddr;
 use tokio::signal;
 use tracing::info;
-use vector_db::api::rest::{create_app, ApiConfig};
+use vector_db::api::rest::{create_app, ApiConfig, ResponseCacheConfig, SearchLimitConfig};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -62,6 +62,48 @@ fn load_config() -> ApiConfig {
             .ok()
             .map(|origins| origins.split(',').map(|s| s.trim().to_string()).collect())
             .unwrap_or_else(|| vec!["http://localhost:3000".to_string()]),
+        metadata_lookup: std::env::var("VECTOR_DB_METADATA_LOOKUP")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default(),
+        search_limit: std::env::var("VECTOR_DB_MAX_CONCURRENT_SEARCHES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(|max_concurrent| SearchLimitConfig {
+                max_concurrent,
+                queue_timeout: std::time::Duration::from_millis(
+                    std::env::var("VECTOR_DB_SEARCH_QUEUE_TIMEOUT_MS")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(1000),
+                ),
+                ..Default::default()
+            }),
+        max_metadata_bytes: std::env::var("VECTOR_DB_MAX_METADATA_BYTES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1024 * 1024), // 1MB default
+        response_cache: std::env::var("VECTOR_DB_RESPONSE_CACHE_TTL_MS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(|ttl_ms| ResponseCacheConfig {
+                ttl: std::time::Duration::from_millis(ttl_ms),
+                ..Default::default()
+            }),
+        uninitialized_search: std::env::var("VECTOR_DB_UNINITIALIZED_SEARCH")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default(),
+        update_buffer: std::env::var("VECTOR_DB_UPDATE_BUFFER")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1024),
+        metadata_schema: std::env::var("VECTOR_DB_METADATA_SCHEMA").ok().map(|path| {
+            let json = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("Failed to read metadata schema {}: {}", path, e));
+            serde_json::from_str(&json)
+                .unwrap_or_else(|e| panic!("Invalid metadata schema {}: {}", path, e))
+        }),
     }
 }
 
@@ -126,6 +126,8 @@ pub struct SearchBuilder {
     #[doc(hidden)]
     pub k: usize,
     #[doc(hidden)]
+    pub offset: usize,
+    #[doc(hidden)]
     pub filter: Option<serde_json::Value>,
     #[doc(hidden)]
     pub timeout: Option<Duration>,
@@ -141,6 +143,12 @@ impl SearchBuilder {
         self
     }
 
+    /// Skip the first `offset` results, to fetch later pages
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
     pub fn filter(mut self, key: &str, value: &str) -> Self {
         let filter = serde_json::json!({ key: value });
         self.filter = Some(filter);
@@ -180,7 +188,8 @@ impl SearchBuilder {
 
         let request = SearchRequest {
             vector: self.vector,
-            k: self.k,
+            k: Some(self.k),
+            offset: self.offset,
             filter: self.filter,
             options: Some(options),
         };
@@ -304,6 +313,7 @@ impl VectorDbClient {
             metadata: vector
                 .metadata
                 .unwrap_or(serde_json::Value::Object(serde_json::Map::new())),
+            on_duplicate: None,
         };
 
         let response = self
@@ -420,8 +430,10 @@ impl VectorDbClient {
                     metadata: v
                         .metadata
                         .unwrap_or(serde_json::Value::Object(serde_json::Map::new())),
+                    on_duplicate: None,
                 })
                 .collect(),
+            on_duplicate: None,
         };
 
         let response = self
@@ -455,6 +467,7 @@ impl VectorDbClient {
             client: Arc::new(self.clone()),
             vector,
             k: 10,
+            offset: 0,
             filter: None,
             timeout: None,
             indices: None,
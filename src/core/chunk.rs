@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 /// Chunk types for chunked vector storage with lazy loading
-use crate::core::types::VectorId;
+use crate::core::types::{DistanceMetric, VectorId};
 use crate::core::schema::MetadataSchema;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
@@ -24,10 +25,44 @@ pub enum ChunkError {
 
     #[error("Invalid chunk range: start={start}, end={end}")]
     InvalidRange { start: usize, end: usize },
+
+    #[error("Checksum mismatch for chunk {chunk_id}: expected {expected}, found {actual}")]
+    ChecksumMismatch {
+        chunk_id: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// Checksum of serialized chunk bytes, as recorded in `ChunkMetadata::checksum`
+pub fn chunk_checksum(data: &[u8]) -> String {
+    blake3::hash(data).to_hex().to_string()
+}
+
+/// Check serialized chunk bytes against the checksum recorded at save time
+pub fn verify_chunk_checksum(chunk_id: &str, data: &[u8], expected: &str) -> Result<(), ChunkError> {
+    let actual = chunk_checksum(data);
+    if actual != expected {
+        return Err(ChunkError::ChecksumMismatch {
+            chunk_id: chunk_id.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
 }
 
 /// Current manifest version
-pub const MANIFEST_VERSION: u32 = 3;
+///
+/// - v3: soft-deleted vectors and the metadata schema
+/// - v4: save generations, per-chunk timestamp files, chunk content hashes
+///   and checksums, the IVF metric, deleted vectors recorded by full id
+///   with their deletion times, and chunks, the HNSW graph and metadata
+///   rewritten by a later save stored under generation keys
+pub const MANIFEST_VERSION: u32 = 4;
+
+/// Oldest manifest version `Manifest::migrate` can upgrade
+pub const MIN_MANIFEST_VERSION: u32 = 1;
 
 // ============================================================================
 // VectorChunk - Storage unit for vectors
@@ -40,6 +75,14 @@ pub struct VectorChunk {
     pub start_idx: usize,
     pub end_idx: usize,
     pub vectors: HashMap<VectorId, Vec<f32>>,
+    /// Opaque per-vector payloads, stored beside the vectors rather than in
+    /// filterable metadata
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub payloads: HashMap<VectorId, Vec<u8>>,
+    /// Product-quantized codes of vectors the IVF index stores compressed,
+    /// kept instead of their full vectors
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub codes: HashMap<VectorId, Vec<u8>>,
 }
 
 impl VectorChunk {
@@ -50,6 +93,8 @@ impl VectorChunk {
             start_idx,
             end_idx,
             vectors: HashMap::new(),
+            payloads: HashMap::new(),
+            codes: HashMap::new(),
         }
     }
 
@@ -63,6 +108,26 @@ impl VectorChunk {
         self.vectors.get(id)
     }
 
+    /// Add a product-quantized vector to the chunk
+    pub fn add_codes(&mut self, id: VectorId, codes: Vec<u8>) {
+        self.codes.insert(id, codes);
+    }
+
+    /// Ids of every vector in the chunk, full or product-quantized
+    pub fn ids(&self) -> impl Iterator<Item = &VectorId> {
+        self.vectors.keys().chain(self.codes.keys())
+    }
+
+    /// Attach a payload to a vector in this chunk
+    pub fn set_payload(&mut self, id: VectorId, payload: Vec<u8>) {
+        self.payloads.insert(id, payload);
+    }
+
+    /// Get a vector's payload from the chunk
+    pub fn get_payload(&self, id: &VectorId) -> Option<&Vec<u8>> {
+        self.payloads.get(id)
+    }
+
     /// Check if this chunk overlaps with another
     pub fn overlaps_with(&self, other: &VectorChunk) -> bool {
         // Check if ranges overlap
@@ -85,14 +150,52 @@ impl VectorChunk {
         serde_cbor::from_slice(data).map_err(|e| ChunkError::Deserialization(e.to_string()))
     }
 
+    /// blake3 hex digest of the chunk's vectors.
+    ///
+    /// Hashes entries in id order rather than the CBOR bytes, whose map
+    /// order isn't stable, so equal contents always give equal hashes.
+    pub fn content_hash(&self) -> String {
+        let mut entries: Vec<(&VectorId, &Vec<f32>)> = self.vectors.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hasher = blake3::Hasher::new();
+        for (id, vector) in entries {
+            hasher.update(&id.as_bytes());
+            hasher.update(&(vector.len() as u64).to_le_bytes());
+            for value in vector {
+                hasher.update(&value.to_le_bytes());
+            }
+        }
+
+        // Payloads only contribute when present, so hashes of chunks without
+        // payloads are unchanged
+        let mut payloads: Vec<(&VectorId, &Vec<u8>)> = self.payloads.iter().collect();
+        payloads.sort_by(|a, b| a.0.cmp(b.0));
+        for (id, payload) in payloads {
+            hasher.update(&id.as_bytes());
+            hasher.update(&(payload.len() as u64).to_le_bytes());
+            hasher.update(payload);
+        }
+
+        // Likewise codes, present only for product-quantized vectors
+        let mut codes: Vec<(&VectorId, &Vec<u8>)> = self.codes.iter().collect();
+        codes.sort_by(|a, b| a.0.cmp(b.0));
+        for (id, code) in codes {
+            hasher.update(&id.as_bytes());
+            hasher.update(&(code.len() as u64).to_le_bytes());
+            hasher.update(code);
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
     /// Get the number of vectors in this chunk
     pub fn len(&self) -> usize {
-        self.vectors.len()
+        self.vectors.len() + self.codes.len()
     }
 
     /// Check if the chunk is empty
     pub fn is_empty(&self) -> bool {
-        self.vectors.is_empty()
+        self.vectors.is_empty() && self.codes.is_empty()
     }
 }
 
@@ -108,6 +211,16 @@ pub struct ChunkMetadata {
     pub vector_count: usize,
     pub byte_size: usize,
     pub vector_id_range: (VectorId, VectorId), // (start, end)
+    /// `VectorChunk::content_hash` of the chunk, used to detect changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// `chunk_checksum` of the stored bytes, verified when the chunk is loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Generation of the save that stored the chunk, if it was stored under
+    /// a versioned key (see `chunk_file_name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<u64>,
 }
 
 impl ChunkMetadata {
@@ -125,9 +238,17 @@ impl ChunkMetadata {
             vector_count,
             byte_size,
             vector_id_range: (start_id, end_id),
+            content_hash: None,
+            checksum: None,
+            generation: None,
         }
     }
 
+    /// Storage path of the chunk under `base_path`
+    pub fn storage_path(&self, base_path: &str) -> String {
+        format!("{}/chunks/{}", base_path, chunk_file_name(&self.chunk_id, self.generation))
+    }
+
     /// Set the S5 CID after upload
     pub fn set_cid(&mut self, cid: String) {
         self.cid = Some(cid);
@@ -144,6 +265,93 @@ impl ChunkMetadata {
     }
 }
 
+// ============================================================================
+// TimestampChunkMetadata - Timestamps stored alongside a vector chunk
+// ============================================================================
+
+/// Metadata for the timestamps of one vector chunk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimestampChunkMetadata {
+    /// Id of the vector chunk these timestamps belong to
+    pub chunk_id: String,
+    pub entry_count: usize,
+    /// blake3 hex digest of the serialized chunk, used to skip unchanged writes
+    pub checksum: String,
+    /// Generation of the save that stored the chunk, if it was stored under
+    /// a versioned key (see `chunk_file_name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<u64>,
+}
+
+impl TimestampChunkMetadata {
+    /// Storage path of the timestamp chunk under `base_path`
+    pub fn storage_path(&self, base_path: &str) -> String {
+        format!("{}/timestamps/{}", base_path, chunk_file_name(&self.chunk_id, self.generation))
+    }
+}
+
+/// File name of a chunk, or of the HNSW graph or metadata, stored by save
+/// `generation`
+///
+/// The first save at a path stores files under their plain name. Later saves
+/// write changed files under a name carrying their generation, so the files
+/// the previous manifest references stay intact until the new manifest is
+/// stored.
+pub fn chunk_file_name(chunk_id: &str, generation: Option<u64>) -> String {
+    match generation {
+        Some(generation) => format!("{}.g{}.cbor", chunk_id, generation),
+        None => format!("{}.cbor", chunk_id),
+    }
+}
+
+// ============================================================================
+// DeletedVector - A soft-deleted vector recorded in the manifest
+// ============================================================================
+
+/// A soft-deleted vector and when it was deleted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "DeletedVectorRepr")]
+pub struct DeletedVector {
+    /// Id as `VectorId::hash_hex`; saves before v4 used the truncated
+    /// display form
+    pub id: String,
+    /// `None` for saves from before v4, which recorded only the id
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl DeletedVector {
+    pub fn new(id: &VectorId, deleted_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            id: id.hash_hex(),
+            deleted_at,
+        }
+    }
+}
+
+/// Saves before v4 list deleted vectors as bare id strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeletedVectorRepr {
+    Id(String),
+    Entry {
+        id: String,
+        #[serde(default)]
+        deleted_at: Option<DateTime<Utc>>,
+    },
+}
+
+impl From<DeletedVectorRepr> for DeletedVector {
+    fn from(repr: DeletedVectorRepr) -> Self {
+        match repr {
+            DeletedVectorRepr::Id(id) => Self {
+                id,
+                deleted_at: None,
+            },
+            DeletedVectorRepr::Entry { id, deleted_at } => Self { id, deleted_at },
+        }
+    }
+}
+
 // ============================================================================
 // HNSW Manifest - Graph structure without vectors
 // ============================================================================
@@ -201,6 +409,10 @@ impl HNSWManifest {
 pub struct IVFManifest {
     pub centroids: Vec<Vec<f32>>, // Keep centroids in memory
     pub cluster_assignments: HashMap<usize, Vec<String>>, // cluster_id -> [chunk_ids]
+    /// Metric the centroids were trained with; manifests written before it
+    /// was recorded are `Euclidean`
+    #[serde(default)]
+    pub metric: DistanceMetric,
 }
 
 impl IVFManifest {
@@ -209,6 +421,7 @@ impl IVFManifest {
         Self {
             centroids,
             cluster_assignments: HashMap::new(),
+            metric: DistanceMetric::default(),
         }
     }
 
@@ -242,15 +455,35 @@ pub struct Manifest {
     pub hnsw_structure: Option<HNSWManifest>,
     pub ivf_structure: Option<IVFManifest>,
 
-    /// List of soft-deleted vector IDs (v3+)
+    /// List of soft-deleted vectors (v3+), with their deletion times (v4+)
     /// These vectors are marked as deleted but not physically removed
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub deleted_vectors: Option<Vec<String>>,
+    pub deleted_vectors: Option<Vec<DeletedVector>>,
 
     /// Optional metadata schema for validation (v3+)
     /// If present, all metadata operations will be validated against this schema
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<MetadataSchema>,
+
+    /// Per-chunk timestamp files (v3+)
+    /// When absent, timestamps are stored in a single `timestamps.cbor`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_chunks: Option<Vec<TimestampChunkMetadata>>,
+
+    /// Incremented by every save or append at the same path, so readers
+    /// can tell whether a cached manifest is stale
+    #[serde(default)]
+    pub generation: u64,
+
+    /// Generation of the save that stored the HNSW graph, if it was stored
+    /// under a versioned key (see `chunk_file_name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hnsw_nodes_generation: Option<u64>,
+
+    /// Generation of the save or append that stored the index metadata, if
+    /// it was stored under a versioned key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_generation: Option<u64>,
 }
 
 impl Manifest {
@@ -265,6 +498,10 @@ impl Manifest {
             ivf_structure: None,
             deleted_vectors: None,
             schema: None,
+            timestamp_chunks: None,
+            generation: 0,
+            hnsw_nodes_generation: None,
+            metadata_generation: None,
         }
     }
 
@@ -304,6 +541,48 @@ impl Manifest {
         Ok(manifest)
     }
 
+    /// Upgrade a manifest written as `from_version` to `MANIFEST_VERSION`,
+    /// one version at a time.
+    ///
+    /// Content hashes and checksums need the chunk data, so chunks upgraded
+    /// from before v4 are left without them; `HybridPersister` fills them
+    /// in when it loads the chunks.
+    pub fn migrate(&mut self, from_version: u32) -> Result<(), ChunkError> {
+        if !(MIN_MANIFEST_VERSION..=MANIFEST_VERSION).contains(&from_version) {
+            return Err(ChunkError::InvalidVersion {
+                expected: MANIFEST_VERSION,
+                found: from_version,
+            });
+        }
+
+        for version in from_version..MANIFEST_VERSION {
+            match version {
+                // v2 kept the v1 layout
+                1 => {}
+                // v3 added soft deletes and the metadata schema; older saves had neither
+                2 => {
+                    self.deleted_vectors = None;
+                    self.schema = None;
+                }
+                3 => self.migrate_v3(),
+                _ => unreachable!("manifest version {} has no upgrade step", version),
+            }
+        }
+        self.version = MANIFEST_VERSION;
+        Ok(())
+    }
+
+    /// v3 to v4. The other v4 fields are optional, so a v3 manifest already
+    /// parsed as generation 0 with a single timestamps file, no chunk
+    /// hashes, deleted vectors without deletion times and chunks under their
+    /// plain storage keys; centroids were always trained Euclidean before
+    /// the metric was recorded, whatever `DistanceMetric::default` is now.
+    fn migrate_v3(&mut self) {
+        if let Some(ivf) = &mut self.ivf_structure {
+            ivf.metric = DistanceMetric::Euclidean;
+        }
+    }
+
     /// Validate the manifest (check for overlaps, etc.)
     pub fn validate(&self) -> Result<(), ChunkError> {
         // Check for chunk overlaps in vector ID ranges
@@ -339,6 +618,28 @@ impl Manifest {
     pub fn get_chunk_ids(&self) -> Vec<String> {
         self.chunks.iter().map(|c| c.chunk_id.clone()).collect()
     }
+
+    /// Storage path of the saved HNSW graph under `base_path`
+    pub fn hnsw_nodes_path(&self, base_path: &str) -> String {
+        format!("{}/{}", base_path, chunk_file_name("hnsw_nodes", self.hnsw_nodes_generation))
+    }
+
+    /// Storage path of the saved index metadata under `base_path`
+    pub fn metadata_path(&self, base_path: &str) -> String {
+        format!("{}/{}", base_path, chunk_file_name("metadata", self.metadata_generation))
+    }
+
+    /// Recorded checksums keyed by chunk path under `base_path`, for
+    /// `ChunkLoader::with_checksums`. Chunks saved without one are left out.
+    pub fn chunk_checksums(&self, base_path: &str) -> HashMap<String, String> {
+        self.chunks
+            .iter()
+            .filter_map(|c| {
+                let checksum = c.checksum.clone()?;
+                Some((c.storage_path(base_path), checksum))
+            })
+            .collect()
+    }
 }
 
 // ============================================================================
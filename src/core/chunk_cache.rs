@@ -128,6 +128,20 @@ impl ChunkCache {
         cache.contains(key)
     }
 
+    /// Remove a single chunk from the cache
+    ///
+    /// Used when a chunk is rewritten in storage so stale data is not served.
+    ///
+    /// # Arguments
+    /// * `key` - The chunk identifier
+    ///
+    /// # Returns
+    /// The removed chunk, if it was cached
+    pub fn remove(&self, key: &str) -> Option<VectorChunk> {
+        let mut cache = self.cache.write().unwrap();
+        cache.pop(key)
+    }
+
     /// Remove all chunks from the cache
     ///
     /// This also resets the capacity to its original value but does NOT reset metrics.
@@ -0,0 +1,169 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Flat (brute-force) vector storage with configurable precision.
+//!
+//! Int8 precision keeps one byte per dimension plus a per-vector scale and
+//! computes distances in integer arithmetic, trading a little recall on
+//! normalized embeddings for roughly 4x less memory and faster scans on
+//! constrained targets (edge, WASM).
+
+use crate::core::types::{SearchResult, VectorId};
+use crate::core::vector_ops::{
+    euclidean_distance, euclidean_distance_i8, quantize_i8, Int8QuantizedVector,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum FlatStoreError {
+    #[error("Dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    #[error("Vector already exists: {0:?}")]
+    DuplicateVector(VectorId),
+}
+
+/// How vectors are kept in a `FlatVectorStore`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorPrecision {
+    #[default]
+    F32,
+    Int8,
+}
+
+#[derive(Debug, Clone)]
+enum StoredVectors {
+    F32(HashMap<VectorId, Vec<f32>>),
+    Int8(HashMap<VectorId, Int8QuantizedVector>),
+}
+
+#[derive(Debug, Clone)]
+pub struct FlatVectorStore {
+    vectors: StoredVectors,
+    dimension: Option<usize>,
+}
+
+impl FlatVectorStore {
+    pub fn new(precision: VectorPrecision) -> Self {
+        let vectors = match precision {
+            VectorPrecision::F32 => StoredVectors::F32(HashMap::new()),
+            VectorPrecision::Int8 => StoredVectors::Int8(HashMap::new()),
+        };
+        Self {
+            vectors,
+            dimension: None,
+        }
+    }
+
+    pub fn precision(&self) -> VectorPrecision {
+        match self.vectors {
+            StoredVectors::F32(_) => VectorPrecision::F32,
+            StoredVectors::Int8(_) => VectorPrecision::Int8,
+        }
+    }
+
+    pub fn dimension(&self) -> Option<usize> {
+        self.dimension
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.vectors {
+            StoredVectors::F32(map) => map.len(),
+            StoredVectors::Int8(map) => map.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn insert(&mut self, id: VectorId, vector: &[f32]) -> Result<(), FlatStoreError> {
+        if let Some(dim) = self.dimension {
+            if vector.len() != dim {
+                return Err(FlatStoreError::DimensionMismatch {
+                    expected: dim,
+                    actual: vector.len(),
+                });
+            }
+        }
+        if self.contains(&id) {
+            return Err(FlatStoreError::DuplicateVector(id));
+        }
+
+        match &mut self.vectors {
+            StoredVectors::F32(map) => {
+                map.insert(id, vector.to_vec());
+            }
+            StoredVectors::Int8(map) => {
+                map.insert(id, quantize_i8(vector));
+            }
+        }
+        self.dimension = Some(vector.len());
+        Ok(())
+    }
+
+    pub fn contains(&self, id: &VectorId) -> bool {
+        match &self.vectors {
+            StoredVectors::F32(map) => map.contains_key(id),
+            StoredVectors::Int8(map) => map.contains_key(id),
+        }
+    }
+
+    pub fn remove(&mut self, id: &VectorId) -> bool {
+        match &mut self.vectors {
+            StoredVectors::F32(map) => map.remove(id).is_some(),
+            StoredVectors::Int8(map) => map.remove(id).is_some(),
+        }
+    }
+
+    /// The stored vector, dequantized for int8 precision
+    pub fn get(&self, id: &VectorId) -> Option<Vec<f32>> {
+        match &self.vectors {
+            StoredVectors::F32(map) => map.get(id).cloned(),
+            StoredVectors::Int8(map) => map.get(id).map(|v| v.dequantize()),
+        }
+    }
+
+    /// Exhaustive k-nearest-neighbour search by euclidean distance
+    pub fn search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>, FlatStoreError> {
+        if let Some(dim) = self.dimension {
+            if query.len() != dim {
+                return Err(FlatStoreError::DimensionMismatch {
+                    expected: dim,
+                    actual: query.len(),
+                });
+            }
+        }
+
+        let mut results: Vec<SearchResult> = match &self.vectors {
+            StoredVectors::F32(map) => map
+                .iter()
+                .map(|(id, v)| SearchResult::new(id.clone(), euclidean_distance(query, v), None))
+                .collect(),
+            StoredVectors::Int8(map) => {
+                let query = quantize_i8(query);
+                map.iter()
+                    .map(|(id, v)| SearchResult::new(id.clone(), euclidean_distance_i8(&query, v), None))
+                    .collect()
+            }
+        };
+
+        results.sort_by(SearchResult::cmp_distance);
+        results.truncate(k);
+        Ok(results)
+    }
+
+    /// Approximate bytes held by the stored vectors (ids excluded)
+    pub fn memory_bytes(&self) -> usize {
+        match &self.vectors {
+            StoredVectors::F32(map) => map
+                .values()
+                .map(|v| v.len() * std::mem::size_of::<f32>())
+                .sum(),
+            StoredVectors::Int8(map) => map.values().map(|v| v.memory_bytes()).sum(),
+        }
+    }
+}
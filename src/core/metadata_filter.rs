@@ -6,8 +6,12 @@
 //! Provides a MongoDB-style query language for filtering vectors based on metadata.
 //! Supports equality, range, set membership, and boolean combinators.
 
+use crate::core::schema::{get_value_type_name, FieldType, MetadataSchema};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Errors that can occur during filter parsing or evaluation
@@ -19,8 +23,12 @@ pub enum FilterError {
     #[error("Unsupported operator: {0}")]
     UnsupportedOperator(String),
 
-    #[error("Type mismatch: expected {expected}, got {actual}")]
-    TypeMismatch { expected: String, actual: String },
+    #[error("Type mismatch for field '{field}': expected {expected}, got {actual}")]
+    TypeMismatch {
+        field: String,
+        expected: String,
+        actual: String,
+    },
 }
 
 /// Metadata filter for querying vectors
@@ -36,12 +44,36 @@ pub enum MetadataFilter {
         value: JsonValue,
     },
 
+    /// Not equal: `{ "field": { "$ne": "value" } }`. Also matches when the
+    /// field is absent, and for array fields when no element equals the value.
+    NotEquals {
+        field: String,
+        value: JsonValue,
+    },
+
     /// Set membership: `{ "field": { "$in": ["val1", "val2"] } }`
     In {
         field: String,
         values: Vec<JsonValue>,
     },
 
+    /// Set exclusion: `{ "field": { "$nin": ["val1", "val2"] } }`. Also
+    /// matches when the field is absent, and for array fields when no element
+    /// is in the set.
+    NotIn {
+        field: String,
+        values: Vec<JsonValue>,
+    },
+
+    /// Text pattern: `{ "title": { "$regex": "tutorial" } }`, with
+    /// `"$options": "i"` for a case-insensitive match. Only string fields
+    /// match.
+    Matches {
+        field: String,
+        #[serde(flatten)]
+        pattern: TextPattern,
+    },
+
     /// Range query: `{ "age": { "$gte": 18, "$lte": 65 } }` or `{ "score": { "$gt": 40, "$lt": 100 } }`
     Range {
         field: String,
@@ -56,6 +88,82 @@ pub enum MetadataFilter {
 
     /// At least one sub-filter must match: `{ "$or": [filter1, filter2] }`
     Or(Vec<MetadataFilter>),
+
+    /// The sub-filter must not match: `{ "$not": filter }`
+    ///
+    /// A struct variant, since a newtype variant holding the enum itself
+    /// recurses without bound in the internally tagged serializer.
+    Not { filter: Box<MetadataFilter> },
+}
+
+/// A `$regex` pattern, compiled once when the filter is parsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "TextPatternSpec", try_from = "TextPatternSpec")]
+pub struct TextPattern {
+    source: String,
+    case_insensitive: bool,
+    regex: Regex,
+}
+
+/// Serialized form of a [`TextPattern`]
+#[derive(Serialize, Deserialize)]
+struct TextPatternSpec {
+    pattern: String,
+    #[serde(default)]
+    case_insensitive: bool,
+}
+
+impl TextPattern {
+    /// Compile `pattern`, failing with `InvalidSyntax` if it isn't a valid regex
+    pub fn new(pattern: &str, case_insensitive: bool) -> Result<Self, FilterError> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| {
+                FilterError::InvalidSyntax(format!("Invalid $regex pattern '{}': {}", pattern, e))
+            })?;
+        Ok(Self {
+            source: pattern.to_string(),
+            case_insensitive,
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Whether the pattern matches anywhere in `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+impl PartialEq for TextPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.case_insensitive == other.case_insensitive
+    }
+}
+
+impl From<TextPattern> for TextPatternSpec {
+    fn from(pattern: TextPattern) -> Self {
+        Self {
+            pattern: pattern.source,
+            case_insensitive: pattern.case_insensitive,
+        }
+    }
+}
+
+impl TryFrom<TextPatternSpec> for TextPattern {
+    type Error = FilterError;
+
+    fn try_from(spec: TextPatternSpec) -> Result<Self, Self::Error> {
+        Self::new(&spec.pattern, spec.case_insensitive)
+    }
 }
 
 impl MetadataFilter {
@@ -95,9 +203,15 @@ impl MetadataFilter {
                     return Self::parse_or(or_filters);
                 }
 
+                if let Some(not_filter) = map.get("$not") {
+                    return Ok(MetadataFilter::Not {
+                        filter: Box::new(Self::from_json(not_filter)?),
+                    });
+                }
+
                 // Check for unsupported top-level operators
                 for key in map.keys() {
-                    if key.starts_with('$') && key != "$and" && key != "$or" {
+                    if key.starts_with('$') && key != "$and" && key != "$or" && key != "$not" {
                         return Err(FilterError::UnsupportedOperator(key.clone()));
                     }
                 }
@@ -121,6 +235,87 @@ impl MetadataFilter {
         }
     }
 
+    /// Parse a filter from JSON and check it against a metadata schema
+    ///
+    /// Range operators must target `Number` fields, and equality and `$in`
+    /// values must have the field's type (for array fields, equality may also
+    /// name a single element). Fields the schema doesn't describe are not
+    /// checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use vector_db::core::metadata_filter::{FilterError, MetadataFilter};
+    /// use vector_db::core::schema::{FieldType, MetadataSchema};
+    ///
+    /// let mut schema = MetadataSchema::new();
+    /// schema.add_field("title", FieldType::String, false);
+    ///
+    /// let result = MetadataFilter::from_json_with_schema(
+    ///     &json!({"title": {"$gte": 10}}),
+    ///     &schema,
+    /// );
+    /// assert!(matches!(result, Err(FilterError::TypeMismatch { .. })));
+    /// ```
+    pub fn from_json_with_schema(
+        value: &JsonValue,
+        schema: &MetadataSchema,
+    ) -> Result<Self, FilterError> {
+        let filter = Self::from_json(value)?;
+        filter.check_schema(schema)?;
+        Ok(filter)
+    }
+
+    /// Check field types against a schema, see `from_json_with_schema`
+    pub fn check_schema(&self, schema: &MetadataSchema) -> Result<(), FilterError> {
+        match self {
+            MetadataFilter::Equals { field, value } | MetadataFilter::NotEquals { field, value } => {
+                let Some(field_type) = schema.field_type(field) else {
+                    return Ok(());
+                };
+                let element_matches = match field_type {
+                    FieldType::Array(element_type) => {
+                        element_type.validate_value(field, value).is_ok()
+                    }
+                    _ => false,
+                };
+                if element_matches {
+                    return Ok(());
+                }
+                check_value_type(field, field_type, value)
+            }
+            MetadataFilter::In { field, values } | MetadataFilter::NotIn { field, values } => {
+                let Some(field_type) = schema.field_type(field) else {
+                    return Ok(());
+                };
+                values
+                    .iter()
+                    .try_for_each(|value| check_value_type(field, field_type, value))
+            }
+            MetadataFilter::Matches { field, .. } => match schema.field_type(field) {
+                Some(FieldType::String) | Some(FieldType::Any) | None => Ok(()),
+                Some(field_type) => Err(FilterError::TypeMismatch {
+                    field: field.clone(),
+                    expected: "String for a $regex filter".to_string(),
+                    actual: field_type.type_name(),
+                }),
+            },
+            MetadataFilter::Range { field, .. } => match schema.field_type(field) {
+                Some(FieldType::Number) | Some(FieldType::Any) | None => Ok(()),
+                Some(field_type) => Err(FilterError::TypeMismatch {
+                    field: field.clone(),
+                    expected: "Number for a range filter".to_string(),
+                    actual: field_type.type_name(),
+                }),
+            },
+            MetadataFilter::And(filters) | MetadataFilter::Or(filters) => {
+                filters.iter().try_for_each(|f| f.check_schema(schema))
+            }
+            MetadataFilter::Not { filter } => filter.check_schema(schema),
+        }
+    }
+
     /// Parse an AND combinator
     fn parse_and(value: &JsonValue) -> Result<Self, FilterError> {
         match value {
@@ -162,6 +357,50 @@ impl MetadataFilter {
                     return Self::parse_in(field, in_values);
                 }
 
+                // Check for $nin operator
+                if let Some(nin_values) = ops.get("$nin") {
+                    return match nin_values {
+                        JsonValue::Array(values) => Ok(MetadataFilter::NotIn {
+                            field: field.to_string(),
+                            values: values.clone(),
+                        }),
+                        _ => Err(FilterError::InvalidSyntax(
+                            "$nin value must be an array".to_string(),
+                        )),
+                    };
+                }
+
+                // Check for $ne operator
+                if let Some(ne_value) = ops.get("$ne") {
+                    return Ok(MetadataFilter::NotEquals {
+                        field: field.to_string(),
+                        value: ne_value.clone(),
+                    });
+                }
+
+                // Check for $regex operator, with optional $options
+                if let Some(pattern) = ops.get("$regex") {
+                    let pattern = pattern.as_str().ok_or_else(|| {
+                        FilterError::InvalidSyntax("$regex value must be a string".to_string())
+                    })?;
+                    let case_insensitive = match ops.get("$options") {
+                        None => false,
+                        Some(JsonValue::String(options)) if options.is_empty() || options == "i" => {
+                            options == "i"
+                        }
+                        Some(options) => {
+                            return Err(FilterError::InvalidSyntax(format!(
+                                "Unsupported $options for $regex: {} (only \"i\" is supported)",
+                                options
+                            )));
+                        }
+                    };
+                    return Ok(MetadataFilter::Matches {
+                        field: field.to_string(),
+                        pattern: TextPattern::new(pattern, case_insensitive)?,
+                    });
+                }
+
                 // Check for range operators ($gte, $gt, $lte, $lt)
                 let min_gte = ops.get("$gte").and_then(|v| v.as_f64());
                 let min_gt = ops.get("$gt").and_then(|v| v.as_f64());
@@ -206,6 +445,9 @@ impl MetadataFilter {
                 for key in ops.keys() {
                     if key.starts_with('$')
                         && key != "$in"
+                        && key != "$ne"
+                        && key != "$nin"
+                        && key != "$regex"
                         && key != "$gte"
                         && key != "$gt"
                         && key != "$lte"
@@ -282,6 +524,12 @@ impl MetadataFilter {
                 }
             }
 
+            MetadataFilter::NotEquals { field, value } => match get_field(metadata, field) {
+                Some(JsonValue::Array(arr)) => !arr.contains(value),
+                Some(field_value) => field_value != value,
+                None => true,
+            },
+
             MetadataFilter::In { field, values } => {
                 if let Some(field_value) = get_field(metadata, field) {
                     values.contains(field_value)
@@ -290,17 +538,27 @@ impl MetadataFilter {
                 }
             }
 
+            MetadataFilter::NotIn { field, values } => match get_field(metadata, field) {
+                Some(JsonValue::Array(arr)) => !arr.iter().any(|element| values.contains(element)),
+                Some(field_value) => !values.contains(field_value),
+                None => true,
+            },
+
+            MetadataFilter::Matches { field, pattern } => get_field(metadata, field)
+                .and_then(|value| value.as_str())
+                .is_some_and(|text| pattern.is_match(text)),
+
             MetadataFilter::Range { field, min, max, min_inclusive, max_inclusive } => {
                 if let Some(field_value) = get_field(metadata, field) {
                     if let Some(num) = field_value.as_f64() {
-                        let min_ok = min.map_or(true, |m| {
+                        let min_ok = min.is_none_or(|m| {
                             if *min_inclusive {
                                 num >= m  // $gte
                             } else {
                                 num > m   // $gt
                             }
                         });
-                        let max_ok = max.map_or(true, |m| {
+                        let max_ok = max.is_none_or(|m| {
                             if *max_inclusive {
                                 num <= m  // $lte
                             } else {
@@ -331,10 +589,23 @@ impl MetadataFilter {
                 }
                 filters.iter().any(|f| f.matches(metadata))
             }
+
+            MetadataFilter::Not { filter } => !filter.matches(metadata),
         }
     }
 }
 
+/// Fail with `TypeMismatch` unless `value` has the type of `field`
+fn check_value_type(field: &str, field_type: &FieldType, value: &JsonValue) -> Result<(), FilterError> {
+    field_type
+        .validate_value(field, value)
+        .map_err(|_| FilterError::TypeMismatch {
+            field: field.to_string(),
+            expected: field_type.type_name(),
+            actual: get_value_type_name(value),
+        })
+}
+
 /// Get a field value from metadata using dot notation
 ///
 /// Supports nested field access: "user.id" → metadata["user"]["id"]
@@ -372,6 +643,153 @@ pub fn get_field<'a>(metadata: &'a JsonValue, path: &str) -> Option<&'a JsonValu
     Some(current)
 }
 
+/// Project metadata down to the given dotted paths
+///
+/// Each path found with `get_field` is copied into the result at the same
+/// nested position; missing paths are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use vector_db::core::metadata_filter::project_fields;
+///
+/// let metadata = json!({
+///     "title": "Intro",
+///     "user": { "id": "123", "name": "Alice" }
+/// });
+///
+/// let projected = project_fields(&metadata, &["user.id".to_string()]);
+/// assert_eq!(projected, json!({ "user": { "id": "123" } }));
+/// ```
+pub fn project_fields(metadata: &JsonValue, paths: &[String]) -> JsonValue {
+    let mut projected = JsonValue::Object(serde_json::Map::new());
+
+    for path in paths {
+        let Some(value) = get_field(metadata, path) else {
+            continue;
+        };
+
+        let parts: Vec<&str> = path.split('.').collect();
+        let mut current = &mut projected;
+        for part in &parts[..parts.len() - 1] {
+            current = current
+                .as_object_mut()
+                .expect("projection only creates objects")
+                .entry(part.to_string())
+                .or_insert_with(|| JsonValue::Object(serde_json::Map::new()));
+        }
+        if let Some(map) = current.as_object_mut() {
+            map.insert(parts[parts.len() - 1].to_string(), value.clone());
+        }
+    }
+
+    projected
+}
+
+/// Counters for a `FilterCache`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilterCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Times the cache was cleared because vectors or metadata changed
+    pub invalidations: u64,
+    pub entries: usize,
+}
+
+/// Ids matching each recently used filter, so repeated faceted queries
+/// intersect candidates with a posting set instead of re-evaluating the
+/// filter.
+///
+/// Entries are keyed by the filter and tagged with the generation of the
+/// metadata they were built from. Callers bump the generation whenever the
+/// metadata changes, which drops every entry of older generations; owners
+/// also call `invalidate` on every insert or delete. Holds at most
+/// `capacity` filters, evicting the oldest first; a capacity of 0 disables
+/// caching.
+#[derive(Debug, Default)]
+pub struct FilterCache {
+    capacity: usize,
+    /// Metadata generation the cached entries were built from
+    generation: u64,
+    entries: HashMap<String, Arc<HashSet<String>>>,
+    order: VecDeque<String>,
+    stats: FilterCacheStats,
+}
+
+impl FilterCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Ids in `metadata_map` matching `filter`, built on a miss.
+    ///
+    /// `generation` identifies the contents of `metadata_map`. A newer
+    /// generation replaces every cached entry; sets built for an older one
+    /// are returned without being cached.
+    pub fn get_or_build(
+        &mut self,
+        filter: &MetadataFilter,
+        metadata_map: &HashMap<String, JsonValue>,
+        generation: u64,
+    ) -> Arc<HashSet<String>> {
+        if generation > self.generation {
+            self.invalidate();
+            self.generation = generation;
+        }
+        let key = serde_json::to_string(filter).unwrap_or_else(|_| format!("{:?}", filter));
+        if generation == self.generation {
+            if let Some(ids) = self.entries.get(&key) {
+                self.stats.hits += 1;
+                return ids.clone();
+            }
+        }
+
+        self.stats.misses += 1;
+        let ids: Arc<HashSet<String>> = Arc::new(
+            metadata_map
+                .iter()
+                .filter(|(_, metadata)| filter.matches(metadata))
+                .map(|(id, _)| id.clone())
+                .collect(),
+        );
+
+        if self.is_enabled() && generation == self.generation {
+            if self.entries.len() >= self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+            self.entries.insert(key.clone(), ids.clone());
+            self.order.push_back(key);
+        }
+        ids
+    }
+
+    /// Drop every cached posting set
+    pub fn invalidate(&mut self) {
+        if !self.entries.is_empty() {
+            self.entries.clear();
+            self.order.clear();
+            self.stats.invalidations += 1;
+        }
+    }
+
+    pub fn stats(&self) -> FilterCacheStats {
+        FilterCacheStats {
+            entries: self.entries.len(),
+            ..self.stats
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,6 +853,42 @@ mod tests {
         assert!(!filter.matches(&metadata_archived));
     }
 
+    #[test]
+    fn test_not_equals_filter() {
+        let filter = MetadataFilter::from_json(&json!({"category": {"$ne": "sports"}})).unwrap();
+        assert_eq!(
+            filter,
+            MetadataFilter::NotEquals {
+                field: "category".to_string(),
+                value: json!("sports"),
+            }
+        );
+
+        assert!(filter.matches(&json!({"category": "technology"})));
+        assert!(!filter.matches(&json!({"category": "sports"})));
+        // An absent field is not equal to anything
+        assert!(filter.matches(&json!({"title": "Game Results"})));
+        // Values of another type never compare equal
+        assert!(filter.matches(&json!({"category": 5})));
+        assert!(MetadataFilter::from_json(&json!({"priority": {"$ne": 1}}))
+            .unwrap()
+            .matches(&json!({"priority": "1"})));
+    }
+
+    #[test]
+    fn test_not_equals_filter_array_and_nested_fields() {
+        let filter = MetadataFilter::from_json(&json!({"tags": {"$ne": "ai"}})).unwrap();
+        assert!(!filter.matches(&json!({"tags": ["ai", "rust"]})));
+        assert!(filter.matches(&json!({"tags": ["rust", "tutorial"]})));
+        assert!(filter.matches(&json!({"tags": []})));
+
+        let filter = MetadataFilter::from_json(&json!({"user.role": {"$ne": "admin"}})).unwrap();
+        assert!(!filter.matches(&json!({"user": {"role": "admin"}})));
+        assert!(filter.matches(&json!({"user": {"role": "viewer"}})));
+        assert!(filter.matches(&json!({"user": {"name": "Alice"}})));
+        assert!(filter.matches(&json!({"user": "admin"})));
+    }
+
     #[test]
     fn test_range_filter() {
         let filter = MetadataFilter::Range {
@@ -503,6 +957,112 @@ mod tests {
         assert!(!filter.matches(&metadata_no_match));
     }
 
+    #[test]
+    fn test_not_in_filter() {
+        let filter = MetadataFilter::from_json(&json!({
+            "status": {"$nin": ["archived", "deleted"]}
+        }))
+        .unwrap();
+        assert_eq!(
+            filter,
+            MetadataFilter::NotIn {
+                field: "status".to_string(),
+                values: vec![json!("archived"), json!("deleted")],
+            }
+        );
+
+        assert!(filter.matches(&json!({"status": "active"})));
+        assert!(!filter.matches(&json!({"status": "archived"})));
+        assert!(filter.matches(&json!({"title": "No status"})));
+
+        let tags = MetadataFilter::from_json(&json!({"tags": {"$nin": ["spam", "nsfw"]}})).unwrap();
+        assert!(tags.matches(&json!({"tags": ["ai", "rust"]})));
+        assert!(!tags.matches(&json!({"tags": ["ai", "spam"]})));
+
+        assert!(matches!(
+            MetadataFilter::from_json(&json!({"status": {"$nin": "archived"}})),
+            Err(FilterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_regex_filter() {
+        let filter = MetadataFilter::from_json(&json!({"title": {"$regex": "tutorial"}})).unwrap();
+        assert!(filter.matches(&json!({"title": "Rust tutorial, part 1"})));
+        assert!(!filter.matches(&json!({"title": "Rust Tutorial, part 2"})));
+        assert!(!filter.matches(&json!({"description": "A tutorial"})));
+        // Non-string fields never match, even if their JSON text would
+        assert!(!MetadataFilter::from_json(&json!({"year": {"$regex": "^20"}}))
+            .unwrap()
+            .matches(&json!({"year": 2024})));
+
+        let filter = MetadataFilter::from_json(&json!({
+            "title": {"$regex": "^rust tutorial", "$options": "i"}
+        }))
+        .unwrap();
+        assert!(filter.matches(&json!({"title": "RUST Tutorial, part 2"})));
+        assert!(!filter.matches(&json!({"title": "Advanced Rust tutorial"})));
+    }
+
+    #[test]
+    fn test_regex_filter_invalid() {
+        assert!(matches!(
+            MetadataFilter::from_json(&json!({"title": {"$regex": "tutorial("}})),
+            Err(FilterError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            MetadataFilter::from_json(&json!({"title": {"$regex": 5}})),
+            Err(FilterError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            MetadataFilter::from_json(&json!({"title": {"$regex": "a", "$options": "x"}})),
+            Err(FilterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_regex_filter_serde_round_trip() {
+        let filter = MetadataFilter::from_json(&json!({
+            "title": {"$regex": "tutorial", "$options": "i"}
+        }))
+        .unwrap();
+
+        let serialized = serde_json::to_value(&filter).unwrap();
+        assert_eq!(
+            serialized,
+            json!({"type": "matches", "field": "title", "pattern": "tutorial", "case_insensitive": true})
+        );
+        let restored: MetadataFilter = serde_json::from_value(serialized).unwrap();
+        assert_eq!(restored, filter);
+        assert!(restored.matches(&json!({"title": "TUTORIAL"})));
+
+        let invalid = json!({"type": "matches", "field": "title", "pattern": "("});
+        assert!(serde_json::from_value::<MetadataFilter>(invalid).is_err());
+    }
+
+    #[test]
+    fn test_not_combinator() {
+        let filter = MetadataFilter::from_json(&json!({
+            "$not": {"views": {"$gte": 1000}}
+        }))
+        .unwrap();
+        assert!(filter.matches(&json!({"views": 10})));
+        assert!(!filter.matches(&json!({"views": 5000})));
+        // The inner range fails on a missing field, so its negation matches
+        assert!(filter.matches(&json!({"title": "No views"})));
+
+        let filter = MetadataFilter::from_json(&json!({
+            "$or": [
+                {"status": "urgent"},
+                {"$not": {"$and": [{"category": "sports"}, {"published": true}]}}
+            ]
+        }))
+        .unwrap();
+        assert!(filter.matches(&json!({"status": "urgent", "category": "sports", "published": true})));
+        assert!(filter.matches(&json!({"status": "normal", "category": "technology", "published": true})));
+        assert!(!filter.matches(&json!({"status": "normal", "category": "sports", "published": true})));
+    }
+
     #[test]
     fn test_nested_field_access() {
         let filter = MetadataFilter::Equals {
@@ -613,4 +1173,35 @@ mod tests {
         );
         assert_eq!(get_field(&metadata, "user.missing"), None);
     }
+
+    #[test]
+    fn test_project_fields() {
+        let metadata = json!({
+            "title": "Intro",
+            "user": {
+                "id": "123",
+                "profile": {
+                    "email": "test@example.com",
+                    "bio": "long text"
+                }
+            }
+        });
+
+        let paths = vec![
+            "title".to_string(),
+            "user.profile.email".to_string(),
+            "user.missing".to_string(),
+        ];
+        assert_eq!(
+            project_fields(&metadata, &paths),
+            json!({
+                "title": "Intro",
+                "user": { "profile": { "email": "test@example.com" } }
+            })
+        );
+
+        // A parent path wins over a child path requested alongside it
+        let paths = vec!["user.id".to_string(), "user".to_string()];
+        assert_eq!(project_fields(&metadata, &paths)["user"], metadata["user"]);
+    }
 }
@@ -3,17 +3,23 @@
 
 pub mod chunk;
 pub mod chunk_cache;
+pub mod flat_store;
 pub mod metadata_filter;
 pub mod schema;
 pub mod storage;
 pub mod types;
+pub mod vector_cache;
 pub mod vector_ops;
 
-pub use types::{Vector, VectorId, Embedding, VideoMetadata};
+pub use types::{DistanceMetric, Vector, VectorId, Embedding, VideoMetadata};
 pub use chunk::{
     VectorChunk, ChunkMetadata, Manifest, HNSWManifest, IVFManifest,
     LayerMetadata, ChunkError, MANIFEST_VERSION,
 };
 pub use chunk_cache::{ChunkCache, CacheMetrics};
-pub use metadata_filter::{MetadataFilter, FilterError, get_field};
+pub use flat_store::{FlatStoreError, FlatVectorStore, VectorPrecision};
+pub use metadata_filter::{
+    MetadataFilter, FilterCache, FilterCacheStats, FilterError, get_field, project_fields,
+};
 pub use schema::{MetadataSchema, FieldType, SchemaError};
+pub use vector_cache::{VectorCache, DEFAULT_VECTOR_CACHE_CAPACITY};
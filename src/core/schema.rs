@@ -49,6 +49,9 @@ pub enum FieldType {
 
     /// Object type with nested fields
     Object(HashMap<String, FieldType>),
+
+    /// Any value; inferred where samples disagree on a field's type
+    Any,
 }
 
 impl FieldType {
@@ -60,6 +63,7 @@ impl FieldType {
             FieldType::Boolean => "Boolean".to_string(),
             FieldType::Array(inner) => format!("Array<{}>", inner.type_name()),
             FieldType::Object(_) => "Object".to_string(),
+            FieldType::Any => "Any".to_string(),
         }
     }
 
@@ -71,6 +75,7 @@ impl FieldType {
         }
 
         match self {
+            FieldType::Any => {}
             FieldType::String => {
                 if !value.is_string() {
                     return Err(SchemaError::InvalidType {
@@ -168,6 +173,34 @@ impl MetadataSchema {
         }
     }
 
+    /// Infer a schema from sample metadata documents
+    ///
+    /// Each top-level field gets the type seen across the samples, with
+    /// nested objects and array elements inferred the same way. Fields
+    /// whose samples disagree are typed `Any`, and fields present in every
+    /// sample are marked required. Null values and empty arrays say nothing
+    /// about a type, and samples that aren't objects are ignored.
+    pub fn infer_from(samples: &[Value]) -> MetadataSchema {
+        let objects: Vec<_> = samples.iter().filter_map(Value::as_object).collect();
+
+        let mut inferred: HashMap<String, InferredType> = HashMap::new();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for obj in &objects {
+            for (key, value) in obj.iter() {
+                *seen.entry(key.clone()).or_default() += 1;
+                let field = inferred.remove(key).unwrap_or(InferredType::Unknown);
+                inferred.insert(key.clone(), field.merge(InferredType::of(value)));
+            }
+        }
+
+        let mut schema = MetadataSchema::new();
+        for (name, field) in inferred {
+            let required = seen.get(&name) == Some(&objects.len());
+            schema.add_field(name, field.into_field_type(), required);
+        }
+        schema
+    }
+
     /// Add a field to the schema
     pub fn add_field(&mut self, name: impl Into<String>, field_type: FieldType, required: bool) {
         let name = name.into();
@@ -205,6 +238,21 @@ impl MetadataSchema {
 
         Ok(())
     }
+
+    /// Type of the field at a dotted path, descending into object fields
+    ///
+    /// Returns `None` when the schema doesn't describe the path.
+    pub fn field_type(&self, path: &str) -> Option<&FieldType> {
+        let mut parts = path.split('.');
+        let mut current = self.fields.get(parts.next()?)?;
+        for part in parts {
+            match current {
+                FieldType::Object(fields) => current = fields.get(part)?,
+                _ => return None,
+            }
+        }
+        Some(current)
+    }
 }
 
 impl Default for MetadataSchema {
@@ -213,8 +261,82 @@ impl Default for MetadataSchema {
     }
 }
 
+/// Field type being accumulated by `MetadataSchema::infer_from`
+#[derive(Debug, Clone)]
+enum InferredType {
+    /// Only nulls or empty arrays seen so far
+    Unknown,
+    /// Samples disagree
+    Mixed,
+    String,
+    Number,
+    Boolean,
+    Array(Box<InferredType>),
+    Object(HashMap<String, InferredType>),
+}
+
+impl InferredType {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => InferredType::Unknown,
+            Value::Bool(_) => InferredType::Boolean,
+            Value::Number(_) => InferredType::Number,
+            Value::String(_) => InferredType::String,
+            Value::Array(items) => InferredType::Array(Box::new(
+                items
+                    .iter()
+                    .fold(InferredType::Unknown, |acc, item| acc.merge(InferredType::of(item))),
+            )),
+            Value::Object(obj) => InferredType::Object(
+                obj.iter()
+                    .map(|(key, value)| (key.clone(), InferredType::of(value)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn merge(self, other: InferredType) -> Self {
+        match (self, other) {
+            (InferredType::Unknown, other) | (other, InferredType::Unknown) => other,
+            (InferredType::String, InferredType::String) => InferredType::String,
+            (InferredType::Number, InferredType::Number) => InferredType::Number,
+            (InferredType::Boolean, InferredType::Boolean) => InferredType::Boolean,
+            (InferredType::Array(a), InferredType::Array(b)) => {
+                InferredType::Array(Box::new(a.merge(*b)))
+            }
+            (InferredType::Object(mut a), InferredType::Object(b)) => {
+                for (key, field) in b {
+                    let merged = match a.remove(&key) {
+                        Some(existing) => existing.merge(field),
+                        None => field,
+                    };
+                    a.insert(key, merged);
+                }
+                InferredType::Object(a)
+            }
+            _ => InferredType::Mixed,
+        }
+    }
+
+    fn into_field_type(self) -> FieldType {
+        match self {
+            InferredType::Unknown | InferredType::Mixed => FieldType::Any,
+            InferredType::String => FieldType::String,
+            InferredType::Number => FieldType::Number,
+            InferredType::Boolean => FieldType::Boolean,
+            InferredType::Array(element) => FieldType::Array(Box::new(element.into_field_type())),
+            InferredType::Object(fields) => FieldType::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, field)| (key, field.into_field_type()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
 /// Get type name of a JSON value for error messages
-fn get_value_type_name(value: &Value) -> String {
+pub(crate) fn get_value_type_name(value: &Value) -> String {
     match value {
         Value::Null => "Null".to_string(),
         Value::Bool(_) => "Boolean".to_string(),
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 use async_trait::async_trait;
+use futures::stream::{FuturesUnordered, StreamExt};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -21,12 +22,92 @@ pub enum StorageError {
     CircuitBreakerOpen,
 }
 
+/// Maximum concurrent requests issued by the default `S5Storage::get_many`
+pub const GET_MANY_CONCURRENCY: usize = 8;
+
 #[async_trait]
 pub trait S5Storage: Send + Sync {
     async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError>;
     async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError>;
     async fn delete(&self, path: &str) -> Result<(), StorageError>;
     async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError>;
+
+    /// Fetch several paths at once, returning them in input order
+    ///
+    /// Missing paths come back as `None`; the first storage error fails the
+    /// whole call. The default issues up to `GET_MANY_CONCURRENCY` `get`s
+    /// concurrently.
+    async fn get_many(
+        &self,
+        paths: &[String],
+    ) -> Result<Vec<(String, Option<Vec<u8>>)>, StorageError> {
+        let mut results: Vec<Option<Option<Vec<u8>>>> = vec![None; paths.len()];
+        let mut pending = paths.iter().enumerate();
+        let mut in_flight = FuturesUnordered::new();
+
+        loop {
+            while in_flight.len() < GET_MANY_CONCURRENCY {
+                match pending.next() {
+                    Some((i, path)) => in_flight.push(async move { (i, self.get(path).await) }),
+                    None => break,
+                }
+            }
+            match in_flight.next().await {
+                Some((i, data)) => results[i] = Some(data?),
+                None => break,
+            }
+        }
+
+        Ok(paths
+            .iter()
+            .cloned()
+            .zip(results.into_iter().map(Option::flatten))
+            .collect())
+    }
+
+    /// Write `data` only if nothing is stored at `path`, returning whether
+    /// it was written
+    ///
+    /// The default checks and writes in two steps, so concurrent writers
+    /// can race; backends that can do better override it.
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, StorageError> {
+        if self.get(path).await?.is_some() {
+            return Ok(false);
+        }
+        self.put(path, data).await?;
+        Ok(true)
+    }
+}
+
+/// Shared storage, e.g. an `Arc<dyn S5Storage>` handed to several owners
+#[async_trait]
+impl<T: S5Storage + ?Sized> S5Storage for Arc<T> {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        (**self).get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        (**self).put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        (**self).delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        (**self).list(prefix).await
+    }
+
+    async fn get_many(
+        &self,
+        paths: &[String],
+    ) -> Result<Vec<(String, Option<Vec<u8>>)>, StorageError> {
+        (**self).get_many(paths).await
+    }
+
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, StorageError> {
+        (**self).put_if_absent(path, data).await
+    }
 }
 
 // Cache entry with timestamp
@@ -680,4 +761,14 @@ impl S5Storage for MockS5Storage {
             .cloned()
             .collect())
     }
+
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, StorageError> {
+        // Check and insert under one write lock
+        let mut storage = self.data.write().await;
+        if storage.contains_key(path) {
+            return Ok(false);
+        }
+        storage.insert(path.to_string(), data);
+        Ok(true)
+    }
 }
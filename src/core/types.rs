@@ -1,36 +1,72 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+use crate::core::vector_ops::{cosine_similarity, dot_product, euclidean_distance};
 use blake3;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
-pub struct VectorId([u8; 32]);
+/// Identifier of a stored vector.
+///
+/// Ids are either a 32-byte blake3 hash (from strings or random) or a plain
+/// `u64` for integer-keyed datasets, which skips hashing entirely. Hash ids
+/// serialize as before (a 32 element byte sequence); integer ids serialize as
+/// a bare integer, so existing indices keep loading unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VectorId(IdRepr);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum IdRepr {
+    Hash([u8; 32]),
+    Int(u64),
+}
 
 impl VectorId {
     pub fn new() -> Self {
         let uuid = uuid::Uuid::new_v4();
         let hash = blake3::hash(uuid.as_bytes());
-        VectorId(hash.into())
+        VectorId(IdRepr::Hash(hash.into()))
     }
 
     pub fn from_string(s: &str) -> Self {
         let hash = blake3::hash(s.as_bytes());
-        VectorId(hash.into())
+        VectorId(IdRepr::Hash(hash.into()))
+    }
+
+    pub fn from_u64(id: u64) -> Self {
+        VectorId(IdRepr::Int(id))
     }
 
-    pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
+    /// The integer value, if this id was created with `from_u64`
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.0 {
+            IdRepr::Int(id) => Some(id),
+            IdRepr::Hash(_) => None,
+        }
+    }
+
+    /// 32-byte form of the id; integer ids are big-endian, left-padded with zeros
+    pub fn as_bytes(&self) -> [u8; 32] {
+        match self.0 {
+            IdRepr::Hash(bytes) => bytes,
+            IdRepr::Int(id) => {
+                let mut bytes = [0u8; 32];
+                bytes[24..].copy_from_slice(&id.to_be_bytes());
+                bytes
+            }
+        }
     }
 
     pub fn hash_hex(&self) -> String {
-        hex::encode(&self.0)
+        hex::encode(self.as_bytes())
     }
 
     pub fn to_string(&self) -> String {
-        format!("vec_{}", &self.hash_hex()[..8])
+        match self.0 {
+            IdRepr::Hash(_) => format!("vec_{}", &self.hash_hex()[..8]),
+            IdRepr::Int(id) => id.to_string(),
+        }
     }
 
     pub fn to_cbor(&self) -> Result<Vec<u8>, serde_cbor::Error> {
@@ -42,6 +78,61 @@ impl VectorId {
     }
 }
 
+impl From<u64> for VectorId {
+    fn from(id: u64) -> Self {
+        VectorId::from_u64(id)
+    }
+}
+
+impl Serialize for VectorId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            IdRepr::Hash(bytes) => bytes.serialize(serializer),
+            IdRepr::Int(id) => serializer.serialize_u64(*id),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VectorId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VectorIdVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for VectorIdVisitor {
+            type Value = VectorId;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an unsigned integer or 32 bytes")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, id: u64) -> Result<VectorId, E> {
+                Ok(VectorId::from_u64(id))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> Result<VectorId, E> {
+                let bytes: [u8; 32] = bytes
+                    .try_into()
+                    .map_err(|_| E::invalid_length(bytes.len(), &self))?;
+                Ok(VectorId(IdRepr::Hash(bytes)))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<VectorId, A::Error> {
+                let mut bytes = [0u8; 32];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                if seq.next_element::<u8>()?.is_some() {
+                    return Err(serde::de::Error::invalid_length(33, &self));
+                }
+                Ok(VectorId(IdRepr::Hash(bytes)))
+            }
+        }
+
+        deserializer.deserialize_any(VectorIdVisitor)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Embedding {
     data: Vec<f32>,
@@ -85,21 +176,7 @@ impl Embedding {
             );
         }
 
-        let dot_product: f32 = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(a, b)| a * b)
-            .sum();
-
-        let mag_a = self.magnitude();
-        let mag_b = other.magnitude();
-
-        if mag_a == 0.0 || mag_b == 0.0 {
-            return 0.0;
-        }
-
-        dot_product / (mag_a * mag_b)
+        cosine_similarity(&self.data, &other.data)
     }
 
     pub fn euclidean_distance(&self, other: &Self) -> f32 {
@@ -111,12 +188,7 @@ impl Embedding {
             );
         }
 
-        self.data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(a, b)| (a - b) * (a - b))
-            .sum::<f32>()
-            .sqrt()
+        euclidean_distance(&self.data, &other.data)
     }
     
     // For backward compatibility, provide a method that doesn't return Result
@@ -187,6 +259,69 @@ impl Default for VideoMetadata {
     }
 }
 
+/// Distance function an index ranks vectors by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceMetric {
+    #[default]
+    Euclidean,
+    Cosine,
+    DotProduct,
+}
+
+impl DistanceMetric {
+    /// Distance from `a` to `b`, where smaller always means more similar:
+    /// the L2 distance, `1 - cosine similarity`, or the negated inner
+    /// product, so every metric ranks in ascending order. NaN if the
+    /// lengths differ.
+    pub fn distance(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            DistanceMetric::Euclidean => euclidean_distance(a, b),
+            DistanceMetric::Cosine => 1.0 - cosine_similarity(a, b),
+            DistanceMetric::DotProduct => -dot_product(a, b),
+        }
+    }
+
+    /// Similarity of `a` to `b`, where larger means more alike:
+    /// `1 / (1 + L2 distance)`, the cosine similarity, or the inner product
+    pub fn similarity(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            DistanceMetric::Euclidean => 1.0 / (1.0 + euclidean_distance(a, b)),
+            DistanceMetric::Cosine => cosine_similarity(a, b),
+            DistanceMetric::DotProduct => dot_product(a, b),
+        }
+    }
+
+    /// Per-dimension terms of the distance from `a` to `b`: the squared
+    /// difference, which sums to the squared L2 distance; half the squared
+    /// difference of the normalized vectors, which sums to the cosine
+    /// distance; or the negated product, which sums to the dot-product
+    /// distance. The slices must have equal lengths.
+    pub fn components(self, a: &[f32], b: &[f32]) -> Vec<f32> {
+        match self {
+            DistanceMetric::Euclidean => a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).collect(),
+            DistanceMetric::Cosine => {
+                let norm_a = dot_product(a, a).sqrt();
+                let norm_b = dot_product(b, b).sqrt();
+                a.iter()
+                    .zip(b)
+                    .map(|(x, y)| {
+                        let diff = x / norm_a - y / norm_b;
+                        diff * diff / 2.0
+                    })
+                    .collect()
+            }
+            DistanceMetric::DotProduct => a.iter().zip(b).map(|(x, y)| -(x * y)).collect(),
+        }
+    }
+
+    /// Whether `vector` can be ranked under this metric. A zero vector has
+    /// no direction, so its cosine similarity to anything is undefined.
+    pub fn accepts(self, vector: &[f32]) -> bool {
+        self != DistanceMetric::Cosine || vector.iter().any(|x| *x != 0.0)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchResult {
     pub vector_id: VectorId,
@@ -221,6 +356,18 @@ impl SearchResult {
         deduped.sort();
         deduped
     }
+
+    /// Total order by distance that ranks NaN distances last, as the worst
+    /// possible match
+    pub fn cmp_distance(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        match (self.distance.is_nan(), other.distance.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.distance.total_cmp(&other.distance),
+        }
+    }
 }
 
 impl PartialOrd for SearchResult {
@@ -231,9 +378,7 @@ impl PartialOrd for SearchResult {
 
 impl Ord for SearchResult {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.distance
-            .partial_cmp(&other.distance)
-            .unwrap_or(std::cmp::Ordering::Equal)
+        self.cmp_distance(other)
     }
 }
 
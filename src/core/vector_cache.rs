@@ -0,0 +1,121 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Bounded LRU cache for lazily loaded vectors
+//!
+//! Indices built over chunked storage only keep a chunk reference per
+//! vector and decode vectors on demand. This cache holds the decoded
+//! vectors between uses, dropping the least recently used ones once it
+//! reaches capacity; an evicted vector is fetched from its chunk again the
+//! next time it is needed.
+
+use crate::core::chunk_cache::CacheMetrics;
+use crate::core::types::VectorId;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, RwLock};
+
+/// Vectors cached by default in each index
+pub const DEFAULT_VECTOR_CACHE_CAPACITY: usize = 100_000;
+
+type SharedLru = Arc<RwLock<LruCache<VectorId, Vec<f32>>>>;
+
+/// Thread-safe LRU cache of decoded vectors; clones share the same cache
+#[derive(Clone)]
+pub struct VectorCache {
+    /// `None` when caching is disabled
+    cache: Option<SharedLru>,
+    metrics: Arc<RwLock<CacheMetrics>>,
+    capacity: usize,
+}
+
+impl VectorCache {
+    /// Cache holding at most `capacity` vectors; a capacity of 0 disables
+    /// caching, so every lookup misses and every vector is fetched from its
+    /// chunk
+    pub fn new(capacity: usize) -> Self {
+        let cache = NonZeroUsize::new(capacity)
+            .map(|capacity| Arc::new(RwLock::new(LruCache::new(capacity))));
+
+        Self {
+            cache,
+            metrics: Arc::new(RwLock::new(CacheMetrics::new())),
+            capacity,
+        }
+    }
+
+    /// Cached vector for `id`, marking it recently used and counting a hit
+    /// or miss
+    pub fn get(&self, id: &VectorId) -> Option<Vec<f32>> {
+        let vector = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.write().unwrap().get(id).cloned());
+        let mut metrics = self.metrics.write().unwrap();
+        match vector {
+            Some(_) => metrics.hits += 1,
+            None => metrics.misses += 1,
+        }
+        vector
+    }
+
+    /// Cache `vector`, evicting the least recently used vector if full
+    pub fn put(&self, id: VectorId, vector: Vec<f32>) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        let mut cache = cache.write().unwrap();
+        let replacing = cache.contains(&id);
+        if cache.push(id, vector).is_some() && !replacing {
+            self.metrics.write().unwrap().evictions += 1;
+        }
+    }
+
+    /// Whether `id` is cached, without touching the LRU order or metrics
+    pub fn contains(&self, id: &VectorId) -> bool {
+        self.cache
+            .as_ref()
+            .is_some_and(|cache| cache.read().unwrap().contains(id))
+    }
+
+    pub fn remove(&self, id: &VectorId) -> Option<Vec<f32>> {
+        self.cache
+            .as_ref()
+            .and_then(|cache| cache.write().unwrap().pop(id))
+    }
+
+    pub fn clear(&self) {
+        if let Some(cache) = &self.cache {
+            cache.write().unwrap().clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache
+            .as_ref()
+            .map_or(0, |cache| cache.read().unwrap().len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Snapshot of the hit, miss and eviction counters
+    pub fn metrics(&self) -> CacheMetrics {
+        self.metrics.read().unwrap().clone()
+    }
+}
+
+impl std::fmt::Debug for VectorCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
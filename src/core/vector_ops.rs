@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 use crate::core::types::{Embedding, SearchResult, VectorId};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
@@ -57,42 +58,259 @@ pub fn euclidean_distance_scalar(a: &[f32], b: &[f32]) -> f32 {
 }
 
 // SIMD implementations
+//
+// Kernels are picked at runtime from the CPU features actually present, so a
+// portable build still uses AVX2 or SSE where available and never executes
+// instructions the CPU lacks.
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
+use std::sync::OnceLock;
+
+/// Environment variable that caps the SIMD level, e.g. `scalar` to disable
+/// SIMD entirely. Levels the CPU doesn't support are never selected.
+pub const SIMD_LEVEL_ENV: &str = "VECTOR_DB_SIMD";
+
+/// Instruction set used by the distance kernels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimdLevel {
+    Scalar,
+    Sse,
+    Avx2,
+}
 
-#[cfg(target_arch = "x86_64")]
-pub fn dot_product_simd(a: &[f32], b: &[f32]) -> f32 {
-    unsafe {
-        let mut sum = _mm256_setzero_ps();
-        let chunks = a.len() / 8;
-
-        for i in 0..chunks {
-            let a_vec = _mm256_loadu_ps(a.as_ptr().add(i * 8));
-            let b_vec = _mm256_loadu_ps(b.as_ptr().add(i * 8));
-            let prod = _mm256_mul_ps(a_vec, b_vec);
-            sum = _mm256_add_ps(sum, prod);
+impl SimdLevel {
+    /// Best level supported by the running CPU
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return SimdLevel::Avx2;
+            }
+            if is_x86_feature_detected!("sse") {
+                return SimdLevel::Sse;
+            }
+        }
+        SimdLevel::Scalar
+    }
+
+    /// Parse a `SIMD_LEVEL_ENV` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "scalar" | "none" => Some(SimdLevel::Scalar),
+            "sse" => Some(SimdLevel::Sse),
+            "avx2" => Some(SimdLevel::Avx2),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimdLevel::Scalar => "scalar",
+            SimdLevel::Sse => "sse",
+            SimdLevel::Avx2 => "avx2",
         }
+    }
+}
+
+type DistanceFn = fn(&[f32], &[f32]) -> f32;
 
-        // Sum the 8 floats in the AVX register
-        let mut result = [0.0f32; 8];
-        _mm256_storeu_ps(result.as_mut_ptr(), sum);
-        let mut scalar_sum = result.iter().sum::<f32>();
+/// Distance functions for one SIMD level
+#[derive(Debug, Clone, Copy)]
+pub struct DistanceKernels {
+    pub level: SimdLevel,
+    pub dot_product: DistanceFn,
+    pub euclidean_distance: DistanceFn,
+}
 
-        // Handle remaining elements
-        for i in (chunks * 8)..a.len() {
-            scalar_sum += a[i] * b[i];
+impl DistanceKernels {
+    /// Kernels for `level`, or `None` if the running CPU doesn't support it
+    pub fn for_level(level: SimdLevel) -> Option<Self> {
+        if level > SimdLevel::detect() {
+            return None;
         }
+        Some(match level {
+            SimdLevel::Scalar => Self {
+                level,
+                dot_product: dot_product_scalar,
+                euclidean_distance: euclidean_distance_scalar,
+            },
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Sse => Self {
+                level,
+                dot_product: dot_product_sse,
+                euclidean_distance: euclidean_distance_sse,
+            },
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx2 => Self {
+                level,
+                dot_product: dot_product_avx2,
+                euclidean_distance: euclidean_distance_avx2,
+            },
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => unreachable!("detect() only reports scalar on this architecture"),
+        })
+    }
+}
+
+static KERNELS: OnceLock<DistanceKernels> = OnceLock::new();
+
+/// Kernels used by the `*_simd` functions, selected on first use from the
+/// detected CPU features and `SIMD_LEVEL_ENV`
+pub fn distance_kernels() -> &'static DistanceKernels {
+    KERNELS.get_or_init(|| {
+        let detected = SimdLevel::detect();
+        let level = std::env::var(SIMD_LEVEL_ENV)
+            .ok()
+            .and_then(|value| SimdLevel::parse(&value))
+            .map_or(detected, |requested| requested.min(detected));
+        DistanceKernels::for_level(level).expect("level never exceeds the detected one")
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_product_avx2(a: &[f32], b: &[f32]) -> f32 {
+    // Safety: only reachable through kernels selected after AVX2 detection
+    unsafe { dot_product_avx2_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn dot_product_avx2_impl(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+    let mut sum = _mm256_setzero_ps();
+    let chunks = len / 8;
+
+    for i in 0..chunks {
+        let a_vec = _mm256_loadu_ps(a.as_ptr().add(i * 8));
+        let b_vec = _mm256_loadu_ps(b.as_ptr().add(i * 8));
+        let prod = _mm256_mul_ps(a_vec, b_vec);
+        sum = _mm256_add_ps(sum, prod);
+    }
+
+    // Sum the 8 floats in the AVX register
+    let mut result = [0.0f32; 8];
+    _mm256_storeu_ps(result.as_mut_ptr(), sum);
+    let mut scalar_sum = result.iter().sum::<f32>();
+
+    // Handle remaining elements
+    for i in (chunks * 8)..len {
+        scalar_sum += a[i] * b[i];
+    }
+
+    scalar_sum
+}
+
+#[cfg(target_arch = "x86_64")]
+fn euclidean_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    // Safety: only reachable through kernels selected after AVX2 detection
+    unsafe { euclidean_distance_avx2_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn euclidean_distance_avx2_impl(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+    let mut sum = _mm256_setzero_ps();
+    let chunks = len / 8;
+
+    for i in 0..chunks {
+        let a_vec = _mm256_loadu_ps(a.as_ptr().add(i * 8));
+        let b_vec = _mm256_loadu_ps(b.as_ptr().add(i * 8));
+        let diff = _mm256_sub_ps(a_vec, b_vec);
+        let squared = _mm256_mul_ps(diff, diff);
+        sum = _mm256_add_ps(sum, squared);
+    }
+
+    let mut result = [0.0f32; 8];
+    _mm256_storeu_ps(result.as_mut_ptr(), sum);
+    let mut scalar_sum = result.iter().sum::<f32>();
+
+    // Handle remaining elements
+    for i in (chunks * 8)..len {
+        let diff = a[i] - b[i];
+        scalar_sum += diff * diff;
+    }
+
+    scalar_sum.sqrt()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn dot_product_sse(a: &[f32], b: &[f32]) -> f32 {
+    // Safety: only reachable through kernels selected after SSE detection
+    unsafe { dot_product_sse_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse")]
+unsafe fn dot_product_sse_impl(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+    let mut sum = _mm_setzero_ps();
+    let chunks = len / 4;
+
+    for i in 0..chunks {
+        let a_vec = _mm_loadu_ps(a.as_ptr().add(i * 4));
+        let b_vec = _mm_loadu_ps(b.as_ptr().add(i * 4));
+        sum = _mm_add_ps(sum, _mm_mul_ps(a_vec, b_vec));
+    }
+
+    let mut result = [0.0f32; 4];
+    _mm_storeu_ps(result.as_mut_ptr(), sum);
+    let mut scalar_sum = result.iter().sum::<f32>();
+
+    for i in (chunks * 4)..len {
+        scalar_sum += a[i] * b[i];
+    }
+
+    scalar_sum
+}
+
+#[cfg(target_arch = "x86_64")]
+fn euclidean_distance_sse(a: &[f32], b: &[f32]) -> f32 {
+    // Safety: only reachable through kernels selected after SSE detection
+    unsafe { euclidean_distance_sse_impl(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse")]
+unsafe fn euclidean_distance_sse_impl(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+    let mut sum = _mm_setzero_ps();
+    let chunks = len / 4;
+
+    for i in 0..chunks {
+        let a_vec = _mm_loadu_ps(a.as_ptr().add(i * 4));
+        let b_vec = _mm_loadu_ps(b.as_ptr().add(i * 4));
+        let diff = _mm_sub_ps(a_vec, b_vec);
+        sum = _mm_add_ps(sum, _mm_mul_ps(diff, diff));
+    }
 
-        scalar_sum
+    let mut result = [0.0f32; 4];
+    _mm_storeu_ps(result.as_mut_ptr(), sum);
+    let mut scalar_sum = result.iter().sum::<f32>();
+
+    for i in (chunks * 4)..len {
+        let diff = a[i] - b[i];
+        scalar_sum += diff * diff;
     }
+
+    scalar_sum.sqrt()
 }
 
-#[cfg(not(target_arch = "x86_64"))]
+// The `*_simd` functions return NaN when the lengths differ, rather than
+// scoring the common prefix like the scalar versions, so a mismatch can't
+// pass for a real score.
+
 pub fn dot_product_simd(a: &[f32], b: &[f32]) -> f32 {
-    dot_product_scalar(a, b)
+    if a.len() != b.len() {
+        return f32::NAN;
+    }
+    (distance_kernels().dot_product)(a, b)
 }
 
 pub fn cosine_similarity_simd(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return f32::NAN;
+    }
     let dot = dot_product_simd(a, b);
     let norm_a = dot_product_simd(a, a).sqrt();
     let norm_b = dot_product_simd(b, b).sqrt();
@@ -105,34 +323,28 @@ pub fn cosine_similarity_simd(a: &[f32], b: &[f32]) -> f32 {
 }
 
 pub fn euclidean_distance_simd(a: &[f32], b: &[f32]) -> f32 {
-    #[cfg(target_arch = "x86_64")]
-    unsafe {
-        let mut sum = _mm256_setzero_ps();
-        let chunks = a.len() / 8;
-
-        for i in 0..chunks {
-            let a_vec = _mm256_loadu_ps(a.as_ptr().add(i * 8));
-            let b_vec = _mm256_loadu_ps(b.as_ptr().add(i * 8));
-            let diff = _mm256_sub_ps(a_vec, b_vec);
-            let squared = _mm256_mul_ps(diff, diff);
-            sum = _mm256_add_ps(sum, squared);
-        }
+    if a.len() != b.len() {
+        return f32::NAN;
+    }
+    (distance_kernels().euclidean_distance)(a, b)
+}
 
-        let mut result = [0.0f32; 8];
-        _mm256_storeu_ps(result.as_mut_ptr(), sum);
-        let mut scalar_sum = result.iter().sum::<f32>();
+// Canonical implementations used by the indices and `DistanceMetric`. They
+// run on the dispatched SIMD kernels and return NaN on a length mismatch.
 
-        // Handle remaining elements
-        for i in (chunks * 8)..a.len() {
-            let diff = a[i] - b[i];
-            scalar_sum += diff * diff;
-        }
+/// Inner product of `a` and `b`, the score maximum inner product search
+/// ranks by
+pub fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    dot_product_simd(a, b)
+}
 
-        scalar_sum.sqrt()
-    }
+/// Cosine of the angle between `a` and `b`; 0.0 if either is a zero vector
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    cosine_similarity_simd(a, b)
+}
 
-    #[cfg(not(target_arch = "x86_64"))]
-    euclidean_distance_scalar(a, b)
+pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    euclidean_distance_simd(a, b)
 }
 
 pub fn batch_normalize(vectors: &[Vec<f32>]) -> Vec<Vec<f32>> {
@@ -386,6 +598,71 @@ pub fn scalar_quantize_u8(vector: &[f32]) -> ScalarQuantizedVector {
     ScalarQuantizedVector { data, min, max }
 }
 
+/// Symmetric int8 quantization with a per-vector scale:
+/// `value ~= data[i] as f32 * scale`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Int8QuantizedVector {
+    pub data: Vec<i8>,
+    pub scale: f32,
+    /// Sum of squared codes, precomputed for distance calculations
+    pub norm_sq: i32,
+}
+
+impl Int8QuantizedVector {
+    pub fn dequantize(&self) -> Vec<f32> {
+        self.data.iter().map(|&v| v as f32 * self.scale).collect()
+    }
+
+    /// Bytes used by the codes plus the scale and norm
+    pub fn memory_bytes(&self) -> usize {
+        self.data.len() + std::mem::size_of::<f32>() + std::mem::size_of::<i32>()
+    }
+}
+
+pub fn quantize_i8(vector: &[f32]) -> Int8QuantizedVector {
+    let max_abs = vector.iter().fold(0.0f32, |acc, v| acc.max(v.abs()));
+    let scale = if max_abs > 0.0 { max_abs / 127.0 } else { 1.0 };
+
+    let data: Vec<i8> = vector
+        .iter()
+        .map(|&v| (v / scale).round().clamp(-127.0, 127.0) as i8)
+        .collect();
+    let norm_sq = dot_product_i8(&data, &data);
+
+    Int8QuantizedVector {
+        data,
+        scale,
+        norm_sq,
+    }
+}
+
+/// Integer dot product of int8 codes, accumulated in i32
+pub fn dot_product_i8(a: &[i8], b: &[i8]) -> i32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| x as i32 * y as i32)
+        .sum()
+}
+
+/// Euclidean distance between quantized vectors.
+///
+/// Expands `|sa*a - sb*b|^2` so only the integer dot product is computed per
+/// call; the squared norms are precomputed at quantization time.
+pub fn euclidean_distance_i8(a: &Int8QuantizedVector, b: &Int8QuantizedVector) -> f32 {
+    let dot = dot_product_i8(&a.data, &b.data) as f32;
+    let squared = a.scale * a.scale * a.norm_sq as f32 + b.scale * b.scale * b.norm_sq as f32
+        - 2.0 * a.scale * b.scale * dot;
+    squared.max(0.0).sqrt()
+}
+
+pub fn cosine_similarity_i8(a: &Int8QuantizedVector, b: &Int8QuantizedVector) -> f32 {
+    if a.norm_sq == 0 || b.norm_sq == 0 {
+        return 0.0;
+    }
+    let dot = dot_product_i8(&a.data, &b.data) as f32;
+    dot / ((a.norm_sq as f32).sqrt() * (b.norm_sq as f32).sqrt())
+}
+
 // Product Quantization
 pub struct ProductQuantizer {
     subspace_dim: usize,
@@ -1,8 +1,10 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
-use crate::core::types::{SearchResult, VectorId};
+use crate::core::types::{DistanceMetric, SearchResult, VectorId};
+use crate::hnsw::operations::BatchInsertResult;
 use crate::storage::chunk_loader::ChunkLoader;
+use chrono::{DateTime, Utc};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
@@ -24,6 +26,9 @@ pub enum HNSWError {
 
     #[error("Chunk loading error: {0}")]
     ChunkLoadError(String),
+
+    #[error("Zero vector has no direction, so its cosine similarity is undefined")]
+    ZeroVector,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -32,6 +37,27 @@ pub struct HNSWConfig {
     pub max_connections_layer_0: usize,
     pub ef_construction: usize,
     pub seed: Option<u64>,
+    /// Memoize query distances for the duration of one search (or insert),
+    /// so nodes revisited on lower layers are only measured once
+    #[serde(default = "default_cache_distances")]
+    pub cache_distances: bool,
+    /// Distance the graph is built and searched with; configs persisted
+    /// before it existed load as `Euclidean`
+    #[serde(default)]
+    pub metric: DistanceMetric,
+    /// Choose neighbors with the diversity heuristic from the HNSW paper
+    /// rather than simply the closest ones; better connected graphs on
+    /// clustered data
+    #[serde(default)]
+    pub use_heuristic_selection: bool,
+    /// With the heuristic, fill unused neighbor slots with the closest
+    /// candidates it rejected
+    #[serde(default)]
+    pub keep_pruned_connections: bool,
+}
+
+fn default_cache_distances() -> bool {
+    true
 }
 
 impl Default for HNSWConfig {
@@ -41,6 +67,38 @@ impl Default for HNSWConfig {
             max_connections_layer_0: 32,
             ef_construction: 200,
             seed: None,
+            cache_distances: true,
+            metric: DistanceMetric::default(),
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
+        }
+    }
+}
+
+impl HNSWConfig {
+    /// Recommended graph parameters for `metric`
+    ///
+    /// | metric        | max_connections | layer 0 | ef_construction |
+    /// |---------------|-----------------|---------|-----------------|
+    /// | `Euclidean`   | 16              | 32      | 200             |
+    /// | `Cosine`      | 24              | 48      | 256             |
+    /// | `DotProduct`  | 32              | 64      | 320             |
+    ///
+    /// Angular neighbourhoods of high-dimensional embeddings are denser than
+    /// L2 ones, and inner product isn't a metric at all, so both get more
+    /// links and a wider construction beam.
+    pub fn default_for(metric: DistanceMetric) -> Self {
+        let (max_connections, ef_construction) = match metric {
+            DistanceMetric::Euclidean => (16, 200),
+            DistanceMetric::Cosine => (24, 256),
+            DistanceMetric::DotProduct => (32, 320),
+        };
+        Self {
+            max_connections,
+            max_connections_layer_0: max_connections * 2,
+            ef_construction,
+            metric,
+            ..Self::default()
         }
     }
 }
@@ -53,6 +111,8 @@ pub struct HNSWNode {
     neighbors: Vec<HashSet<VectorId>>, // neighbors[i] = neighbors at layer i
     #[serde(default)]
     is_deleted: bool,
+    #[serde(default)]
+    deleted_at: Option<DateTime<Utc>>,
 }
 
 impl HNSWNode {
@@ -63,6 +123,7 @@ impl HNSWNode {
             level: 0,
             neighbors: vec![HashSet::new()],
             is_deleted: false,
+            deleted_at: None,
         }
     }
 
@@ -111,7 +172,34 @@ impl HNSWNode {
     }
 
     pub fn mark_deleted(&mut self) {
-        self.is_deleted = true;
+        self.mark_deleted_at(Utc::now());
+    }
+
+    /// Mark the node as deleted at `deleted_at`, e.g. when restoring a
+    /// deletion made earlier; a node already deleted keeps its time
+    pub fn mark_deleted_at(&mut self, deleted_at: DateTime<Utc>) {
+        if !self.is_deleted {
+            self.is_deleted = true;
+            self.deleted_at = Some(deleted_at);
+        }
+    }
+
+    /// When the node was marked as deleted; `None` if it is live or was
+    /// deleted before deletion times were recorded
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    /// Whether the node counts as live for a query at `as_of`
+    /// (`None` meaning now)
+    fn is_visible_at(&self, as_of: Option<DateTime<Utc>>) -> bool {
+        if !self.is_deleted {
+            return true;
+        }
+        match (as_of, self.deleted_at) {
+            (Some(as_of), Some(deleted_at)) => deleted_at > as_of,
+            _ => false,
+        }
     }
 }
 
@@ -136,6 +224,46 @@ impl Ord for SearchCandidate {
     }
 }
 
+/// Work done by a single search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Query distances actually computed (memo hits excluded)
+    pub distance_computations: usize,
+}
+
+/// Query distances computed during one search, shared across its layers.
+///
+/// Created per call and never stored on the index, so concurrent searches
+/// can't see each other's entries.
+struct DistanceMemo {
+    cache: Option<HashMap<VectorId, f32>>,
+    computed: usize,
+    metric: DistanceMetric,
+}
+
+impl DistanceMemo {
+    fn new(enabled: bool, metric: DistanceMetric) -> Self {
+        Self {
+            cache: enabled.then(HashMap::new),
+            computed: 0,
+            metric,
+        }
+    }
+
+    fn distance(&mut self, query: &[f32], id: &VectorId, vector: &[f32]) -> f32 {
+        if let Some(distance) = self.cache.as_ref().and_then(|cache| cache.get(id)) {
+            return *distance;
+        }
+
+        self.computed += 1;
+        let distance = self.metric.distance(query, vector);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.insert(id.clone(), distance);
+        }
+        distance
+    }
+}
+
 pub struct HNSWIndex {
     config: HNSWConfig,
     nodes: Arc<RwLock<HashMap<VectorId, HNSWNode>>>,
@@ -144,8 +272,6 @@ pub struct HNSWIndex {
     dimension: Arc<RwLock<Option<usize>>>,
     /// Chunk loader for lazy loading vectors from S5 storage
     chunk_loader: Option<Arc<ChunkLoader>>,
-    /// Cache for lazy-loaded vectors (vector_id -> vector)
-    vector_cache: Arc<RwLock<HashMap<VectorId, Vec<f32>>>>,
     /// Chunk references for lazy loading (vector_id -> chunk_path)
     chunk_refs: Arc<RwLock<HashMap<VectorId, String>>>,
 }
@@ -156,7 +282,6 @@ impl HNSWIndex {
             Some(seed) => StdRng::seed_from_u64(seed),
             None => StdRng::from_entropy(),
         };
-
         Self {
             config,
             nodes: Arc::new(RwLock::new(HashMap::new())),
@@ -164,7 +289,6 @@ impl HNSWIndex {
             rng: Arc::new(RwLock::new(rng)),
             dimension: Arc::new(RwLock::new(None)),
             chunk_loader: None,
-            vector_cache: Arc::new(RwLock::new(HashMap::new())),
             chunk_refs: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -175,7 +299,6 @@ impl HNSWIndex {
             Some(seed) => StdRng::seed_from_u64(seed),
             None => StdRng::from_entropy(),
         };
-
         Self {
             config,
             nodes: Arc::new(RwLock::new(HashMap::new())),
@@ -183,7 +306,6 @@ impl HNSWIndex {
             rng: Arc::new(RwLock::new(rng)),
             dimension: Arc::new(RwLock::new(None)),
             chunk_loader,
-            vector_cache: Arc::new(RwLock::new(HashMap::new())),
             chunk_refs: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -192,6 +314,16 @@ impl HNSWIndex {
         &self.config
     }
 
+    /// Distance between two vectors under the configured metric; smaller is
+    /// always closer, see `DistanceMetric::distance`
+    pub fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        self.config.metric.distance(a, b)
+    }
+
+    fn distance_memo(&self) -> DistanceMemo {
+        DistanceMemo::new(self.config.cache_distances, self.config.metric)
+    }
+
     pub fn node_count(&self) -> usize {
         self.nodes.read().unwrap().len()
     }
@@ -228,6 +360,9 @@ impl HNSWIndex {
         if self.nodes.read().unwrap().contains_key(&id) {
             return Err(HNSWError::DuplicateVector(id));
         }
+        if !self.config.metric.accepts(&vector) {
+            return Err(HNSWError::ZeroVector);
+        }
 
         // Check/set dimension
         {
@@ -244,137 +379,200 @@ impl HNSWIndex {
             }
         }
 
-        let level = self.assign_level();
-        let mut node = HNSWNode::new(id.clone(), vector);
-        node.set_level(level);
+        let mut entry_point = self.entry_point();
+        {
+            let mut nodes = self.nodes.write().unwrap();
+            self.insert_node(&mut nodes, &mut entry_point, id, vector);
+        }
+        *self.entry_point.write().unwrap() = entry_point;
 
-        // If this is the first node, set it as entry point
-        let is_first = {
-            let mut ep_guard = self.entry_point.write().unwrap();
-            if ep_guard.is_none() {
-                *ep_guard = Some(id.clone());
-                true
-            } else {
-                false
-            }
+        Ok(())
+    }
+
+    /// Insert many vectors under a single write lock, for bulk loading.
+    ///
+    /// Dimensions are checked up front: if any vector's length differs from
+    /// the index's dimension (or, in an empty index, the first vector's),
+    /// nothing is inserted and `DimensionMismatch` is returned. Ids already
+    /// stored or repeated within the batch, and vectors the metric rejects,
+    /// are skipped and reported per id.
+    pub fn insert_batch(
+        &mut self,
+        items: Vec<(VectorId, Vec<f32>)>,
+    ) -> Result<BatchInsertResult, HNSWError> {
+        let mut result = BatchInsertResult {
+            successful: 0,
+            failed: 0,
+            errors: Vec::new(),
         };
+        let Some((_, first)) = items.first() else {
+            return Ok(result);
+        };
+        let dimension = self.dimension().unwrap_or(first.len());
+        if let Some((_, vector)) = items.iter().find(|(_, v)| v.len() != dimension) {
+            return Err(HNSWError::DimensionMismatch {
+                expected: dimension,
+                actual: vector.len(),
+            });
+        }
 
-        let entry_level = if !is_first {
-            // Find nearest neighbors at all layers
-            let entry_point = self.entry_point().unwrap();
-            let ef = self.config.ef_construction;
-
-            // Search for nearest neighbors starting from top layer of entry point
-            let entry_node = self
-                .nodes
-                .read()
-                .unwrap()
-                .get(&entry_point)
-                .unwrap()
-                .clone();
-            let entry_level = entry_node.level();
-
-            let mut current_nearest = vec![SearchCandidate {
-                id: entry_point.clone(),
-                distance: euclidean_distance(&node.vector, &entry_node.vector),
-            }];
-
-            // Search from the minimum of the new node's level and entry point's level
-            let search_level = level.min(entry_level);
-            for lc in (0..=search_level).rev() {
-                let candidates =
-                    self.search_layer(&node.vector, current_nearest[0].id.clone(), 1, lc);
-                if !candidates.is_empty() {
-                    current_nearest = candidates;
+        let mut entry_point = self.entry_point();
+        {
+            let mut nodes = self.nodes.write().unwrap();
+            for (id, vector) in items {
+                let error = if nodes.contains_key(&id) {
+                    Some(HNSWError::DuplicateVector(id.clone()))
+                } else if !self.config.metric.accepts(&vector) {
+                    Some(HNSWError::ZeroVector)
+                } else {
+                    None
+                };
+                match error {
+                    Some(error) => {
+                        result.failed += 1;
+                        result.errors.push((id, error));
+                    }
+                    None => {
+                        self.insert_node(&mut nodes, &mut entry_point, id, vector);
+                        result.successful += 1;
+                    }
                 }
             }
+        }
+        *self.entry_point.write().unwrap() = entry_point;
+        if result.successful > 0 {
+            self.dimension.write().unwrap().get_or_insert(dimension);
+        }
 
-            // Connect to neighbors at each layer
-            for lc in 0..=level {
-                let m = if lc == 0 {
-                    self.config.max_connections_layer_0
-                } else {
-                    self.config.max_connections
-                };
+        Ok(result)
+    }
 
-                // Use the nearest neighbor at this layer as starting point for better connectivity
-                let search_start = if lc <= search_level && !current_nearest.is_empty() {
-                    current_nearest[0].id.clone()
-                } else {
-                    entry_point.clone()
-                };
+    /// Link an already validated vector into the graph held by `nodes`,
+    /// updating `entry_point` if the new node becomes it. Takes no locks,
+    /// so a batch can insert many nodes under a single write lock.
+    pub(crate) fn insert_node(
+        &self,
+        nodes: &mut HashMap<VectorId, HNSWNode>,
+        entry_point: &mut Option<VectorId>,
+        id: VectorId,
+        vector: Vec<f32>,
+    ) {
+        let level = self.assign_level();
+        let mut node = HNSWNode::new(id.clone(), vector);
+        node.set_level(level);
 
-                let candidates = self.search_layer(&node.vector, search_start, ef, lc);
-                let neighbors = self.select_neighbors(&candidates, m);
+        // If this is the first node, set it as entry point
+        let Some(entry_id) = entry_point.clone() else {
+            nodes.insert(id.clone(), node);
+            *entry_point = Some(id);
+            return;
+        };
 
-                // Add bidirectional connections
-                {
-                    let mut nodes_guard = self.nodes.write().unwrap();
+        // Find nearest neighbors at all layers
+        let ef = self.config.ef_construction;
+
+        // Search for nearest neighbors starting from top layer of entry point
+        let mut memo = self.distance_memo();
+        let (entry_level, entry_distance) = {
+            let entry_node = &nodes[&entry_id];
+            (
+                entry_node.level(),
+                memo.distance(&node.vector, &entry_id, &entry_node.vector),
+            )
+        };
+        let mut current_nearest = vec![SearchCandidate {
+            id: entry_id.clone(),
+            distance: entry_distance,
+        }];
 
-                    // Add neighbors to new node
-                    for neighbor_id in &neighbors {
-                        node.neighbors_mut(lc).insert(neighbor_id.clone());
-                    }
+        // Search from the minimum of the new node's level and entry point's level
+        let search_level = level.min(entry_level);
+        for lc in (0..=search_level).rev() {
+            let candidates = Self::search_layer_in(
+                nodes,
+                &node.vector,
+                current_nearest[0].id.clone(),
+                1,
+                lc,
+                None,
+                &mut memo,
+            );
+            if !candidates.is_empty() {
+                current_nearest = candidates;
+            }
+        }
 
-                    // Add new node to neighbors and collect pruning info
-                    let max_conn = if lc == 0 {
-                        self.config.max_connections_layer_0
-                    } else {
-                        self.config.max_connections
-                    };
-
-                    let mut pruning_needed = Vec::new();
-                    for neighbor_id in &neighbors {
-                        if let Some(neighbor) = nodes_guard.get_mut(neighbor_id) {
-                            if neighbor.level >= lc {
-                                neighbor.neighbors_mut(lc).insert(id.clone());
-
-                                // Check if pruning needed
-                                if neighbor.neighbors(lc).len() > max_conn {
-                                    let neighbor_neighbors: Vec<_> =
-                                        neighbor.neighbors(lc).iter().cloned().collect();
-                                    let neighbor_vector = neighbor.vector().to_vec();
-                                    pruning_needed.push((neighbor_id.clone(), neighbor_neighbors, neighbor_vector));
-                                }
-                            }
-                        }
-                    }
+        // Connect to neighbors at each layer
+        for lc in 0..=level {
+            let m = if lc == 0 {
+                self.config.max_connections_layer_0
+            } else {
+                self.config.max_connections
+            };
 
-                    // Perform pruning (no mutable borrows held during prune_neighbors call)
-                    //  Include new node vector for distance calculations
-                    for (neighbor_id, neighbor_neighbors, neighbor_vector) in pruning_needed {
-                        let pruned = self.prune_neighbors_with_new_node(
-                            &neighbor_neighbors,
-                            &neighbor_vector,
-                            max_conn,
-                            &nodes_guard,  // Pass nodes reference to avoid deadlock
-                            &id,            // New node ID
-                            &node.vector,   // New node vector
-                        );
-                        if let Some(neighbor) = nodes_guard.get_mut(&neighbor_id) {
-                            neighbor.neighbors_mut(lc).clear();
-                            for n in pruned {
-                                neighbor.neighbors_mut(lc).insert(n);
-                            }
+            // Use the nearest neighbor at this layer as starting point for better connectivity
+            let search_start = if lc <= search_level && !current_nearest.is_empty() {
+                current_nearest[0].id.clone()
+            } else {
+                entry_id.clone()
+            };
+
+            let candidates =
+                Self::search_layer_in(nodes, &node.vector, search_start, ef, lc, None, &mut memo);
+            let neighbors = self.select_neighbors(&candidates, m, |id| {
+                nodes.get(id).map(|n| n.vector.as_slice())
+            });
+
+            // Add bidirectional connections, starting with the new node's own
+            for neighbor_id in &neighbors {
+                node.neighbors_mut(lc).insert(neighbor_id.clone());
+            }
+
+            // Add new node to neighbors and collect pruning info
+            let mut pruning_needed = Vec::new();
+            for neighbor_id in &neighbors {
+                if let Some(neighbor) = nodes.get_mut(neighbor_id) {
+                    if neighbor.level >= lc {
+                        neighbor.neighbors_mut(lc).insert(id.clone());
+
+                        // Check if pruning needed
+                        if neighbor.neighbors(lc).len() > m {
+                            let neighbor_neighbors: Vec<_> =
+                                neighbor.neighbors(lc).iter().cloned().collect();
+                            let neighbor_vector = neighbor.vector().to_vec();
+                            pruning_needed.push((neighbor_id.clone(), neighbor_neighbors, neighbor_vector));
                         }
                     }
                 }
             }
 
-            entry_level
-        } else {
-            0
-        };
+            // Perform pruning (no mutable borrows held during prune_neighbors call)
+            //  Include new node vector for distance calculations
+            for (neighbor_id, neighbor_neighbors, neighbor_vector) in pruning_needed {
+                let pruned = self.prune_neighbors_with_new_node(
+                    &neighbor_neighbors,
+                    &neighbor_vector,
+                    m,
+                    nodes,
+                    &id,            // New node ID
+                    &node.vector,   // New node vector
+                );
+                if let Some(neighbor) = nodes.get_mut(&neighbor_id) {
+                    neighbor.neighbors_mut(lc).clear();
+                    for n in pruned {
+                        neighbor.neighbors_mut(lc).insert(n);
+                    }
+                }
+            }
+        }
 
         // Insert node
-        self.nodes.write().unwrap().insert(id.clone(), node);
+        nodes.insert(id.clone(), node);
 
         // Update entry point if new node has higher level
-        if !is_first && level > entry_level {
-            *self.entry_point.write().unwrap() = Some(id);
+        if level > entry_level {
+            *entry_point = Some(id);
         }
-
-        Ok(())
     }
 
     /// Insert a vector with chunk reference for lazy loading support
@@ -387,8 +585,6 @@ impl HNSWIndex {
         // Store chunk reference if provided
         if let Some(chunk) = chunk_id {
             self.chunk_refs.write().unwrap().insert(id.clone(), chunk);
-            // Cache the vector for immediate use
-            self.vector_cache.write().unwrap().insert(id.clone(), vector.clone());
         }
 
         // Regular insert with the vector (needed for graph building)
@@ -401,9 +597,44 @@ impl HNSWIndex {
         k: usize,
         ef: usize,
     ) -> Result<Vec<SearchResult>, HNSWError> {
+        Ok(self.search_visible(query, k, ef, None)?.0)
+    }
+
+    /// Like `search`, also reporting how many distances were computed
+    pub fn search_with_stats(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+    ) -> Result<(Vec<SearchResult>, SearchStats), HNSWError> {
+        self.search_visible(query, k, ef, None)
+    }
+
+    /// Search the graph as it was at `as_of`: nodes soft-deleted after that
+    /// time are treated as live.
+    ///
+    /// Vacuumed nodes are gone, and nodes deleted before deletion times were
+    /// recorded stay hidden.
+    pub fn search_as_of(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+        as_of: DateTime<Utc>,
+    ) -> Result<Vec<SearchResult>, HNSWError> {
+        Ok(self.search_visible(query, k, ef, Some(as_of))?.0)
+    }
+
+    fn search_visible(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<SearchResult>, SearchStats), HNSWError> {
         let entry_point = match self.entry_point() {
             Some(ep) => ep,
-            None => return Ok(Vec::new()), // Empty index
+            None => return Ok((Vec::new(), SearchStats::default())), // Empty index
         };
 
         // Check dimension
@@ -415,6 +646,9 @@ impl HNSWIndex {
                 });
             }
         }
+        if !self.config.metric.accepts(query) {
+            return Err(HNSWError::ZeroVector);
+        }
 
         // Start from top layer of entry point
         let nodes = self.nodes.read().unwrap();
@@ -429,9 +663,10 @@ impl HNSWIndex {
         };
         let top_layer = entry_node.level();
 
+        let mut memo = self.distance_memo();
         let mut nearest = vec![SearchCandidate {
             id: entry_point.clone(),
-            distance: euclidean_distance(query, &entry_node.vector),
+            distance: memo.distance(query, &entry_point, &entry_node.vector),
         }];
 
         // Search through layers from top to layer 0
@@ -441,6 +676,8 @@ impl HNSWIndex {
                 nearest[0].id.clone(),
                 if lc == 0 { ef } else { 1 },
                 lc,
+                as_of,
+                &mut memo,
             );
             if !new_nearest.is_empty() {
                 nearest = new_nearest;
@@ -454,27 +691,48 @@ impl HNSWIndex {
             .filter(|c| {
                 // Check if the node is deleted
                 nodes.get(&c.id)
-                    .map(|node| !node.is_deleted())
+                    .map(|node| node.is_visible_at(as_of))
                     .unwrap_or(false) // If node not found, exclude it
             })
             .take(k) // Take only k results after filtering
             .collect();
 
-        Ok(filtered_results
+        let results = filtered_results
             .into_iter()
             .map(|c| SearchResult::new(c.id, c.distance, None))
-            .collect())
+            .collect();
+        let stats = SearchStats {
+            distance_computations: memo.computed,
+        };
+        Ok((results, stats))
     }
 
+    /// Best-first search of one layer. Distances ascend with dissimilarity
+    /// for every metric (dot product is negated), so the heap orderings
+    /// below hold regardless of the configured metric.
     fn search_layer(
         &self,
         query: &[f32],
         entry_point: VectorId,
         ef: usize,
         layer: usize,
+        as_of: Option<DateTime<Utc>>,
+        memo: &mut DistanceMemo,
     ) -> Vec<SearchCandidate> {
         let nodes = self.nodes.read().unwrap();
+        Self::search_layer_in(&nodes, query, entry_point, ef, layer, as_of, memo)
+    }
 
+    /// `search_layer` over a node map the caller has already locked
+    fn search_layer_in(
+        nodes: &HashMap<VectorId, HNSWNode>,
+        query: &[f32],
+        entry_point: VectorId,
+        ef: usize,
+        layer: usize,
+        as_of: Option<DateTime<Utc>>,
+        memo: &mut DistanceMemo,
+    ) -> Vec<SearchCandidate> {
         // Check if entry point exists
         if !nodes.contains_key(&entry_point) {
             return Vec::new();
@@ -484,7 +742,7 @@ impl HNSWIndex {
         let mut candidates = BinaryHeap::new();
         let mut nearest = BinaryHeap::new();
 
-        let entry_distance = euclidean_distance(query, &nodes[&entry_point].vector);
+        let entry_distance = memo.distance(query, &entry_point, &nodes[&entry_point].vector);
         candidates.push(SearchCandidate {
             id: entry_point.clone(),
             distance: entry_distance,
@@ -508,11 +766,11 @@ impl HNSWIndex {
 
                             if let Some(neighbor) = nodes.get(neighbor_id) {
                                 // Skip deleted nodes
-                                if neighbor.is_deleted() {
+                                if !neighbor.is_visible_at(as_of) {
                                     continue;
                                 }
 
-                                let distance = euclidean_distance(query, &neighbor.vector);
+                                let distance = memo.distance(query, neighbor_id, &neighbor.vector);
 
                                 if distance < -nearest.peek().unwrap().distance
                                     || nearest.len() < ef
@@ -553,8 +811,49 @@ impl HNSWIndex {
         result
     }
 
-    fn select_neighbors(&self, candidates: &[SearchCandidate], m: usize) -> Vec<VectorId> {
-        candidates.iter().take(m).map(|c| c.id.clone()).collect()
+    /// Pick up to `m` neighbors for a node from `candidates`, sorted by
+    /// ascending distance to it, with `vector` giving each candidate's vector.
+    ///
+    /// Naive selection keeps the `m` closest. The heuristic (Algorithm 4 of
+    /// the HNSW paper) keeps a candidate only if it is closer to the node
+    /// than to every neighbor kept so far, so links fan out across clusters
+    /// instead of all pointing into the nearest one; with
+    /// `keep_pruned_connections`, rejected candidates fill any slots left.
+    fn select_neighbors<'a>(
+        &self,
+        candidates: &[SearchCandidate],
+        m: usize,
+        vector: impl Fn(&VectorId) -> Option<&'a [f32]>,
+    ) -> Vec<VectorId> {
+        if !self.config.use_heuristic_selection {
+            return candidates.iter().take(m).map(|c| c.id.clone()).collect();
+        }
+
+        let mut selected: Vec<(&VectorId, &[f32])> = Vec::with_capacity(m);
+        let mut pruned = Vec::new();
+        for candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let Some(candidate_vector) = vector(&candidate.id) else {
+                continue;
+            };
+            let diverse = selected
+                .iter()
+                .all(|(_, kept)| self.distance(candidate_vector, kept) > candidate.distance);
+            if diverse {
+                selected.push((&candidate.id, candidate_vector));
+            } else {
+                pruned.push(&candidate.id);
+            }
+        }
+
+        let mut neighbors: Vec<VectorId> = selected.into_iter().map(|(id, _)| id.clone()).collect();
+        if self.config.keep_pruned_connections {
+            let missing = m.saturating_sub(neighbors.len());
+            neighbors.extend(pruned.into_iter().take(missing).cloned());
+        }
+        neighbors
     }
 
     fn prune_neighbors(
@@ -570,7 +869,7 @@ impl HNSWIndex {
             .filter_map(|id| {
                 nodes.get(id).map(|node| SearchCandidate {
                     id: id.clone(),
-                    distance: euclidean_distance(base_vector, &node.vector),
+                    distance: self.distance(base_vector, &node.vector),
                 })
             })
             .collect();
@@ -580,8 +879,7 @@ impl HNSWIndex {
                 .partial_cmp(&b.distance)
                 .unwrap_or(Ordering::Equal)
         });
-        candidates.truncate(m);
-        candidates.into_iter().map(|c| c.id).collect()
+        self.select_neighbors(&candidates, m, |id| nodes.get(id).map(|n| n.vector.as_slice()))
     }
 
     /// Prune neighbors while considering a new node that's not yet in the nodes map
@@ -602,13 +900,13 @@ impl HNSWIndex {
                     // Use the provided new node vector
                     Some(SearchCandidate {
                         id: id.clone(),
-                        distance: euclidean_distance(base_vector, new_node_vector),
+                        distance: self.distance(base_vector, new_node_vector),
                     })
                 } else {
                     // Look up existing nodes
                     nodes.get(id).map(|node| SearchCandidate {
                         id: id.clone(),
-                        distance: euclidean_distance(base_vector, &node.vector),
+                        distance: self.distance(base_vector, &node.vector),
                     })
                 }
             })
@@ -619,8 +917,13 @@ impl HNSWIndex {
                 .partial_cmp(&b.distance)
                 .unwrap_or(Ordering::Equal)
         });
-        candidates.truncate(m);
-        candidates.into_iter().map(|c| c.id).collect()
+        self.select_neighbors(&candidates, m, |id| {
+            if id == new_node_id {
+                Some(new_node_vector)
+            } else {
+                nodes.get(id).map(|n| n.vector.as_slice())
+            }
+        })
     }
 
     pub fn get_all_nodes(&self) -> Vec<HNSWNode> {
@@ -644,6 +947,77 @@ impl HNSWIndex {
         *self.entry_point.write().unwrap() = Some(id);
     }
 
+    pub(crate) fn replace_entry_point(&mut self, id: Option<VectorId>) {
+        *self.entry_point.write().unwrap() = id;
+    }
+
+    /// Reconnect nodes that lost neighbors when other nodes were removed.
+    /// Each `(id, layer)` is relinked to the nearest nodes `search_layer`
+    /// finds from it, or from the entry point once it has no neighbors left
+    /// on that layer, pruned back to the layer's connection limit.
+    pub(crate) fn repair_connections(&mut self, orphaned: &[(VectorId, usize)]) {
+        for (id, layer) in orphaned {
+            let Some(node) = self.get_node(id) else {
+                continue;
+            };
+            let start = if node.neighbors(*layer).is_empty() {
+                match self.entry_point() {
+                    Some(entry) if &entry != id => entry,
+                    _ => continue,
+                }
+            } else {
+                id.clone()
+            };
+            let m = if *layer == 0 {
+                self.config.max_connections_layer_0
+            } else {
+                self.config.max_connections
+            };
+
+            let candidates = self.search_layer(
+                &node.vector,
+                start,
+                self.config.ef_construction,
+                *layer,
+                None,
+                &mut self.distance_memo(),
+            );
+
+            let mut nodes = self.nodes.write().unwrap();
+            let mut pool: Vec<VectorId> = Vec::new();
+            let existing = node.neighbors(*layer).iter().cloned();
+            for candidate in existing.chain(candidates.into_iter().map(|c| c.id)) {
+                let eligible = &candidate != id
+                    && !pool.contains(&candidate)
+                    && nodes.get(&candidate).is_some_and(|n| n.level() >= *layer);
+                if eligible {
+                    pool.push(candidate);
+                }
+            }
+            let neighbors = self.prune_neighbors(&pool, &node.vector, m, &nodes);
+
+            // Link back, pruning any neighbor pushed over the limit
+            for neighbor_id in &neighbors {
+                let Some(neighbor) = nodes.get_mut(neighbor_id) else {
+                    continue;
+                };
+                neighbor.neighbors_mut(*layer).insert(id.clone());
+                if neighbor.neighbors(*layer).len() > m {
+                    let current: Vec<_> = neighbor.neighbors(*layer).iter().cloned().collect();
+                    let vector = neighbor.vector().clone();
+                    let pruned = self.prune_neighbors(&current, &vector, m, &nodes);
+                    let neighbor = nodes.get_mut(neighbor_id).unwrap();
+                    neighbor.neighbors_mut(*layer).clear();
+                    neighbor.neighbors_mut(*layer).extend(pruned);
+                }
+            }
+            if let Some(node) = nodes.get_mut(id) {
+                node.neighbors_mut(*layer).clear();
+                node.neighbors_mut(*layer).extend(neighbors);
+            }
+        }
+    }
+
     pub fn get_node_index(&self, id: &VectorId) -> Option<usize> {
         // For now, return a simple hash-based index
         // In production, this would map to actual storage indices
@@ -687,11 +1061,3 @@ impl HNSWIndex {
 // Thread-safe wrapper implementation
 unsafe impl Send for HNSWIndex {}
 unsafe impl Sync for HNSWIndex {}
-
-fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
-    a.iter()
-        .zip(b.iter())
-        .map(|(x, y)| (x - y).powi(2))
-        .sum::<f32>()
-        .sqrt()
-}
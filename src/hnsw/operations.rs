@@ -3,6 +3,9 @@
 
 use crate::core::types::VectorId;
 use crate::hnsw::core::{HNSWError, HNSWIndex};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -61,6 +64,43 @@ pub struct GraphStats {
     pub connected_components: usize,
 }
 
+/// Bounds for exporting large graphs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphExportOptions {
+    /// Only export these layers (all layers when `None`)
+    pub layers: Option<Vec<usize>>,
+    /// Export at most this many nodes; edges to nodes outside the sample are dropped
+    pub max_nodes: Option<usize>,
+}
+
+/// JSON-friendly node/edge list of the HNSW graph. Nodes are identified
+/// by `VectorId::hash_hex`, since the display form of hashed ids can collide.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphExport {
+    pub entry_point: Option<String>,
+    pub total_nodes: usize,
+    /// True when `max_nodes` cut the export short
+    pub sampled: bool,
+    pub layers: Vec<LayerExport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerExport {
+    pub layer: usize,
+    pub nodes: Vec<GraphNodeExport>,
+    /// Directed adjacency: node id -> neighbor ids on this layer
+    pub adjacency: BTreeMap<String, Vec<String>>,
+    /// Number of directed edges in `adjacency`
+    pub edge_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNodeExport {
+    pub id: String,
+    pub level: usize,
+    pub deleted: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct MemoryUsage {
     pub total_bytes: usize,
@@ -125,10 +165,20 @@ impl HNSWIndex {
 
     // Deletion operations
     pub fn mark_deleted(&mut self, id: &VectorId) -> Result<(), HNSWError> {
+        self.mark_deleted_at(id, Utc::now())
+    }
+
+    /// `mark_deleted` with an explicit deletion time, keeping the original
+    /// time of a node already deleted
+    pub fn mark_deleted_at(
+        &mut self,
+        id: &VectorId,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), HNSWError> {
         let mut nodes = self.nodes().write().unwrap();
         match nodes.get_mut(id) {
             Some(node) => {
-                node.mark_deleted();
+                node.mark_deleted_at(deleted_at);
                 Ok(())
             }
             None => Err(HNSWError::VectorNotFound(id.clone())),
@@ -174,29 +224,69 @@ impl HNSWIndex {
     }
 
     pub fn vacuum(&mut self) -> Result<usize, OperationError> {
-        let mut nodes = self.nodes().write().unwrap();
-        let deleted_ids: Vec<_> = nodes
+        let deleted_ids: Vec<_> = self
+            .nodes()
+            .read()
+            .unwrap()
             .iter()
             .filter(|(_, node)| node.is_deleted())
             .map(|(id, _)| id.clone())
             .collect();
 
-        let removed_count = deleted_ids.len();
+        self.remove_nodes(&deleted_ids);
+        Ok(deleted_ids.len())
+    }
+
+    /// Physically remove a single vector, deleted or not. Nodes that linked
+    /// to it are reconnected to their nearest remaining nodes.
+    pub fn remove(&mut self, id: &VectorId) -> Result<(), HNSWError> {
+        if !self.nodes().read().unwrap().contains_key(id) {
+            return Err(HNSWError::VectorNotFound(id.clone()));
+        }
+        self.remove_nodes(std::slice::from_ref(id));
+        Ok(())
+    }
+
+    /// Physically remove `ids` in one pass over the graph, reconnecting the
+    /// nodes that linked to them. Ids not in the index are ignored.
+    pub(crate) fn remove_nodes(&mut self, ids: &[VectorId]) {
+        let mut nodes = self.nodes().write().unwrap();
 
-        // Remove deleted nodes
-        for id in &deleted_ids {
+        for id in ids {
             nodes.remove(id);
         }
 
-        // Clean up references to deleted nodes from remaining nodes
+        // Clean up references to removed nodes from remaining nodes,
+        // noting which nodes lost a neighbor on which layer
+        let removed: HashSet<&VectorId> = ids.iter().collect();
+        let mut orphaned = Vec::new();
         for node in nodes.values_mut() {
             for layer in 0..=node.level() {
                 let neighbors = node.neighbors_mut(layer);
-                neighbors.retain(|neighbor_id| !deleted_ids.contains(neighbor_id));
+                let before = neighbors.len();
+                neighbors.retain(|neighbor_id| !removed.contains(neighbor_id));
+                if neighbors.len() < before {
+                    orphaned.push((node.id().clone(), layer));
+                }
             }
         }
 
-        Ok(removed_count)
+        // The entry point may have been removed; promote the highest remaining node
+        let entry_removed = self
+            .entry_point()
+            .is_some_and(|ep| !nodes.contains_key(&ep));
+        let new_entry = entry_removed.then(|| {
+            nodes
+                .values()
+                .max_by_key(|node| node.level())
+                .map(|node| node.id().clone())
+        });
+        drop(nodes);
+        if let Some(new_entry) = new_entry {
+            self.replace_entry_point(new_entry);
+        }
+
+        self.repair_connections(&orphaned);
     }
 
     // Maintenance operations
@@ -271,6 +361,80 @@ impl HNSWIndex {
         }
     }
 
+    /// Export every layer of the graph for visualization
+    pub fn export_graph(&self) -> GraphExport {
+        self.export_graph_with(&GraphExportOptions::default())
+    }
+
+    /// Export the graph restricted to the given layers and node budget.
+    ///
+    /// Nodes are sampled in id order, so repeated exports of the same graph
+    /// pick the same nodes.
+    pub fn export_graph_with(&self, options: &GraphExportOptions) -> GraphExport {
+        let nodes = self.nodes().read().unwrap();
+
+        let mut ids: Vec<&VectorId> = nodes.keys().collect();
+        ids.sort();
+        let total_nodes = ids.len();
+        let sampled = options.max_nodes.is_some_and(|max| max < total_nodes);
+        if let Some(max) = options.max_nodes {
+            ids.truncate(max);
+        }
+        let included: HashSet<&VectorId> = ids.iter().copied().collect();
+
+        let max_level = ids.iter().map(|id| nodes[*id].level()).max().unwrap_or(0);
+        let layers: Vec<usize> = match &options.layers {
+            Some(layers) => {
+                let mut layers: Vec<usize> = layers.iter().copied().filter(|l| *l <= max_level).collect();
+                layers.sort_unstable();
+                layers.dedup();
+                layers
+            }
+            None if ids.is_empty() => Vec::new(),
+            None => (0..=max_level).collect(),
+        };
+
+        let layers = layers
+            .into_iter()
+            .map(|layer| {
+                let mut export = LayerExport {
+                    layer,
+                    nodes: Vec::new(),
+                    adjacency: BTreeMap::new(),
+                    edge_count: 0,
+                };
+
+                for id in ids.iter().filter(|id| nodes[**id].level() >= layer) {
+                    let node = &nodes[*id];
+                    let mut neighbors: Vec<String> = node
+                        .neighbors(layer)
+                        .iter()
+                        .filter(|n| included.contains(n))
+                        .map(|n| n.hash_hex())
+                        .collect();
+                    neighbors.sort();
+
+                    export.edge_count += neighbors.len();
+                    export.adjacency.insert(id.hash_hex(), neighbors);
+                    export.nodes.push(GraphNodeExport {
+                        id: id.hash_hex(),
+                        level: node.level(),
+                        deleted: node.is_deleted(),
+                    });
+                }
+
+                export
+            })
+            .collect();
+
+        GraphExport {
+            entry_point: self.entry_point().map(|id| id.hash_hex()),
+            total_nodes,
+            sampled,
+            layers,
+        }
+    }
+
     pub fn estimate_memory_usage(&self) -> MemoryUsage {
         let nodes = self.nodes().read().unwrap();
 
@@ -0,0 +1,239 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Single-file archives of chunked indexes
+//!
+//! An archive is a ustar stream holding the files `save_index_chunked`
+//! writes (manifest, chunks, timestamp chunks, HNSW nodes and metadata),
+//! optionally zstd-compressed. See `HybridPersister::export_archive` and
+//! `HybridPersister::import_archive`.
+
+use crate::core::storage::{S5Storage, StorageError};
+use async_trait::async_trait;
+use std::io;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+/// Path the index is saved under while it is streamed into an archive
+pub(crate) const ARCHIVE_ROOT: &str = "archive";
+
+/// Leading bytes of a zstd frame, used to detect compressed archives
+pub(crate) const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+const BLOCK_SIZE: usize = 512;
+
+/// Options for `HybridPersister::export_archive`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveOptions {
+    /// zstd-compress the whole archive
+    pub compress: bool,
+    /// zstd level used when `compress` is set
+    pub compression_level: i32,
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        Self {
+            compress: true,
+            compression_level: 3,
+        }
+    }
+}
+
+/// Writes regular-file ustar entries to an async writer
+pub(crate) struct TarWriter<W> {
+    inner: W,
+}
+
+impl<W: AsyncWrite + Unpin> TarWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    pub(crate) async fn append(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        self.inner.write_all(&ustar_header(name, data.len())?).await?;
+        self.inner.write_all(data).await?;
+        let padding = (BLOCK_SIZE - data.len() % BLOCK_SIZE) % BLOCK_SIZE;
+        self.inner.write_all(&[0u8; BLOCK_SIZE][..padding]).await
+    }
+
+    /// Write the end-of-archive marker and hand back the writer
+    pub(crate) async fn finish(mut self) -> io::Result<W> {
+        self.inner.write_all(&[0u8; 2 * BLOCK_SIZE]).await?;
+        self.inner.flush().await?;
+        Ok(self.inner)
+    }
+}
+
+fn ustar_header(name: &str, size: usize) -> io::Result<[u8; BLOCK_SIZE]> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+    // Names over 100 bytes are split into prefix and name at a '/'
+    let (prefix, name) = if name.len() <= 100 {
+        ("", name)
+    } else {
+        name.char_indices()
+            .filter(|(i, c)| *c == '/' && *i <= 155 && name.len() - i - 1 <= 100)
+            .map(|(i, _)| (&name[..i], &name[i + 1..]))
+            .next()
+            .ok_or_else(|| invalid(format!("Archive entry name too long: {}", name)))?
+    };
+
+    let mut header = [0u8; BLOCK_SIZE];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    header[100..108].copy_from_slice(b"0000644\0");
+    header[108..116].copy_from_slice(b"0000000\0");
+    header[116..124].copy_from_slice(b"0000000\0");
+    let size_field = format!("{:011o}\0", size);
+    if size_field.len() != 12 {
+        return Err(invalid(format!("Archive entry too large: {} bytes", size)));
+    }
+    header[124..136].copy_from_slice(size_field.as_bytes());
+    header[136..148].copy_from_slice(b"00000000000\0");
+    header[148..156].copy_from_slice(b"        ");
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    let checksum: u32 = header.iter().map(|b| *b as u32).sum();
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+    Ok(header)
+}
+
+/// Reads regular-file entries from a ustar stream
+pub(crate) struct TarReader<R> {
+    inner: R,
+}
+
+impl<R: AsyncRead + Unpin> TarReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Next file's name and contents, or `None` at the end of the archive.
+    /// Directories and other non-file entries are skipped.
+    pub(crate) async fn next_entry(&mut self) -> io::Result<Option<(String, Vec<u8>)>> {
+        let corrupt = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        loop {
+            let mut header = [0u8; BLOCK_SIZE];
+            match self.inner.read_exact(&mut header).await {
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(e),
+            }
+            if header.iter().all(|b| *b == 0) {
+                return Ok(None);
+            }
+
+            let stored_checksum = parse_octal(&header[148..156])
+                .ok_or_else(|| corrupt("Invalid archive header checksum"))?;
+            let checksum: u64 = header
+                .iter()
+                .enumerate()
+                .map(|(i, b)| if (148..156).contains(&i) { b' ' as u64 } else { *b as u64 })
+                .sum();
+            if checksum != stored_checksum {
+                return Err(corrupt("Archive header checksum mismatch"));
+            }
+
+            let size = parse_octal(&header[124..136])
+                .ok_or_else(|| corrupt("Invalid archive entry size"))? as usize;
+            let mut data = vec![0u8; size];
+            self.inner.read_exact(&mut data).await?;
+            let padding = (BLOCK_SIZE - size % BLOCK_SIZE) % BLOCK_SIZE;
+            self.inner.read_exact(&mut [0u8; BLOCK_SIZE][..padding]).await?;
+
+            if header[156] != b'0' && header[156] != 0 {
+                continue;
+            }
+
+            let name = field_str(&header[..100]);
+            let prefix = field_str(&header[345..500]);
+            let name = if prefix.is_empty() {
+                name
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+            return Ok(Some((name, data)));
+        }
+    }
+}
+
+fn field_str(field: &[u8]) -> String {
+    let end = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    let text = field_str(field);
+    let text = text.trim_matches(|c: char| c == ' ' || c == '\0');
+    if text.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(text, 8).ok()
+}
+
+/// Storage that appends every file put under `ARCHIVE_ROOT` to a tar stream,
+/// so `save_index_chunked` writes straight into an archive without staging
+/// the index anywhere. Reads find nothing.
+pub(crate) struct ArchiveSink<W> {
+    writer: Arc<Mutex<Option<TarWriter<W>>>>,
+}
+
+impl<W> Clone for ArchiveSink<W> {
+    fn clone(&self) -> Self {
+        Self {
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin + Send> ArchiveSink<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(Some(TarWriter::new(writer)))),
+        }
+    }
+
+    /// Finish the tar stream and return the underlying writer
+    pub(crate) async fn finish(&self) -> io::Result<W> {
+        let writer = self
+            .writer
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| io::Error::other("Archive already finished"))?;
+        writer.finish().await
+    }
+}
+
+#[async_trait]
+impl<W: AsyncWrite + Unpin + Send> S5Storage for ArchiveSink<W> {
+    async fn get(&self, _path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(None)
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        let name = path
+            .strip_prefix(ARCHIVE_ROOT)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .unwrap_or(path);
+        let mut writer = self.writer.lock().await;
+        let writer = writer
+            .as_mut()
+            .ok_or_else(|| StorageError::IoError(io::Error::other("Archive already finished")))?;
+        writer.append(name, &data).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, _path: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn list(&self, _prefix: &str) -> Result<Vec<String>, StorageError> {
+        Ok(Vec::new())
+    }
+}
@@ -1,17 +1,24 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+use crate::core::chunk::DeletedVector;
 use crate::core::storage::S5Storage;
-use crate::core::types::{SearchResult, VectorId};
+use crate::core::metadata_filter::{FilterCache, FilterCacheStats};
+use crate::core::types::{DistanceMetric, SearchResult, VectorId};
 use crate::hnsw::core::{HNSWConfig, HNSWIndex};
-use crate::ivf::core::{ClusterId, IVFConfig, IVFIndex};
+use crate::hybrid::lock_metrics::{LockContentionStats, LockMetrics, LockName};
+use crate::ivf::core::{ChunkIntegrityReport, ClusterId, IVFConfig, IVFError, IVFIndex};
+use crate::ivf::operations::{ClusterRebalanceResult, RetrainResult};
 use crate::storage::chunk_loader::ChunkLoader;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 
 #[derive(Debug, Error)]
 pub enum HybridError {
@@ -32,6 +39,69 @@ pub enum HybridError {
 
     #[error("Vector with ID {0:?} already exists")]
     DuplicateVector(VectorId),
+
+    #[error("Vector {0:?} not found")]
+    VectorNotFound(VectorId),
+
+    #[error(
+        "Vector has {actual} dimensions but the historical (IVF) index was trained on {trained}. \
+         Retrain the index on {actual}-dimensional data or re-embed the vector with the original model; \
+         see HybridIndex::dimension_conflict_report()"
+    )]
+    TrainedDimensionMismatch { trained: usize, actual: usize },
+
+    #[error(
+        "Index holds {vectors} vectors, too many for exact search (limit {limit}); \
+         use approximate search or raise max_exact_search_vectors"
+    )]
+    ExactSearchTooLarge { vectors: usize, limit: usize },
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+}
+
+/// Summary of stored vectors whose dimension disagrees with the index
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionConflictReport {
+    /// Dimension the IVF centroids were trained on, if trained
+    pub trained_dimension: Option<usize>,
+    /// Number of stored vectors per dimension across both sub-indices
+    pub dimension_counts: std::collections::BTreeMap<usize, usize>,
+    /// Vectors whose dimension differs from the expected one
+    pub conflicting_vectors: usize,
+    /// A few conflicting ids to help locate the bad data
+    pub sample_conflicts: Vec<(VectorId, usize)>,
+}
+
+impl DimensionConflictReport {
+    const MAX_SAMPLES: usize = 10;
+
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicting_vectors > 0
+    }
+
+    /// Suggested operator action, if there is a conflict
+    pub fn recommendation(&self) -> Option<String> {
+        if !self.has_conflicts() {
+            return None;
+        }
+        let found: Vec<String> = self.dimension_counts.keys().map(|d| d.to_string()).collect();
+        Some(match self.trained_dimension {
+            Some(trained) => format!(
+                "Index was trained on {} dimensions but holds vectors of dimensions [{}]; \
+                 retrain on the new dimension or re-embed the {} conflicting vectors",
+                trained,
+                found.join(", "),
+                self.conflicting_vectors
+            ),
+            None => format!(
+                "Index holds vectors of dimensions [{}]; re-embed the {} minority vectors \
+                 so all share one dimension",
+                found.join(", "),
+                self.conflicting_vectors
+            ),
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -42,7 +112,135 @@ pub struct HybridConfig {
     pub ivf_config: IVFConfig,
     pub migration_batch_size: usize,
     pub auto_migrate: bool,
+    /// How often the task started by `HybridIndex::start_auto_migration`
+    /// migrates vectors older than `recent_threshold`
+    #[serde(default = "default_migration_interval", with = "duration_serde")]
+    pub migration_interval: Duration,
     pub min_ivf_training_size: usize, // Minimum vectors before IVF training (default: 10)
+    /// Initialize on the first insert instead of requiring `initialize`.
+    /// The first vector fixes the dimension and the historical index is
+    /// trained from inserted vectors once `min_ivf_training_size` accumulate.
+    #[serde(default)]
+    pub auto_initialize: bool,
+    /// Retrain the historical index in the background as it grows;
+    /// `None` (the default) never retrains automatically
+    #[serde(default)]
+    pub auto_retrain: Option<AutoRetrainConfig>,
+    /// What searches do with an empty query vector
+    #[serde(default)]
+    pub empty_query_policy: EmptyQueryPolicy,
+    /// Number of filters whose matching ids `search_with_filter_cached` caches;
+    /// 0 evaluates the filter per candidate instead
+    #[serde(default = "default_filter_cache_capacity")]
+    pub filter_cache_capacity: usize,
+    /// Candidates `search_with_filter` fetches per requested result
+    #[serde(default = "default_filter_oversample")]
+    pub filter_oversample: usize,
+    /// Largest multiplier `search_with_filter` doubles `filter_oversample`
+    /// up to while a selective filter leaves fewer than k results
+    #[serde(default = "default_max_filter_oversample")]
+    pub max_filter_oversample: usize,
+    /// Largest index (recent plus historical vectors) an exact search
+    /// will scan before failing with `ExactSearchTooLarge`
+    #[serde(default = "default_max_exact_search_vectors")]
+    pub max_exact_search_vectors: usize,
+    /// Capture queries landing in sparse regions of the index; `None` (the
+    /// default) captures nothing
+    #[serde(default)]
+    pub cold_queries: Option<ColdQueryConfig>,
+    /// Count how often each vector appears in search results, see
+    /// `HybridIndex::top_accessed`
+    #[serde(default)]
+    pub track_access: bool,
+    /// Reject inserts whose length differs from the index's dimension before
+    /// touching either sub-index. Disabling it lets mixed-dimension data in,
+    /// for inspection with `HybridIndex::dimension_conflict_report`.
+    #[serde(default = "default_strict_dimensions")]
+    pub strict_dimensions: bool,
+    /// Pad or truncate inserts to the index's dimension instead of
+    /// rejecting them, see `DimensionAdapter`
+    #[serde(default)]
+    pub dimension_adapter: DimensionAdapter,
+}
+
+fn default_migration_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_strict_dimensions() -> bool {
+    true
+}
+
+fn default_filter_cache_capacity() -> usize {
+    64
+}
+
+fn default_filter_oversample() -> usize {
+    3
+}
+
+fn default_max_filter_oversample() -> usize {
+    48
+}
+
+fn default_max_exact_search_vectors() -> usize {
+    100_000
+}
+
+/// How searches treat an empty query vector. Queries of the wrong
+/// (non-zero) length are always rejected with `DimensionMismatch`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyQueryPolicy {
+    /// Fail with `HybridError::DimensionMismatch`
+    #[default]
+    Reject,
+    /// Return no results
+    ReturnEmpty,
+}
+
+/// What inserts do with a vector whose length differs from the index's
+/// dimension, e.g. while moving between embedding model versions.
+///
+/// Adapting degrades similarity quality: zero padding drops whatever the
+/// missing components would have contributed and truncation discards the
+/// extra ones, so distances between adapted and native vectors are only
+/// roughly comparable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DimensionAdapter {
+    /// Leave the vector as is, so `strict_dimensions` rejects it
+    #[default]
+    Reject,
+    /// Pad shorter vectors with zeros and truncate longer ones, logging a
+    /// warning for each
+    PadOrTruncate,
+}
+
+/// Insert-volume triggers for automatic IVF retraining. A retrain starts
+/// when either configured trigger is reached.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AutoRetrainConfig {
+    /// Retrain after this many vectors reach the historical index, by
+    /// insert or migration, since the last training
+    pub inserts_since_training: Option<usize>,
+    /// Retrain once the historical index holds this many times the number
+    /// of vectors it was last trained on
+    pub growth_factor: Option<f32>,
+}
+
+/// Detection of "cold" queries, whose results are all far away, so the
+/// clustering can adapt toward where queries actually land
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ColdQueryConfig {
+    /// A query is cold when it finds nothing or its nearest result is
+    /// farther than this
+    pub distance_threshold: f32,
+    /// Most cold queries kept; the oldest are dropped first
+    pub max_samples: usize,
+    /// Add the captured queries to the training sample of the next
+    /// `retrain_historical` (including automatic retrains)
+    pub feed_retraining: bool,
 }
 
 // Helper module for std::time::Duration serialization
@@ -79,14 +277,39 @@ impl Default for HybridConfig {
             ivf_config,
             migration_batch_size: 100,
             auto_migrate: true,
+            migration_interval: default_migration_interval(),
             min_ivf_training_size: 10, // Minimum vectors before IVF training
+            auto_initialize: false,
+            auto_retrain: None,
+            empty_query_policy: EmptyQueryPolicy::default(),
+            filter_cache_capacity: default_filter_cache_capacity(),
+            filter_oversample: default_filter_oversample(),
+            max_filter_oversample: default_max_filter_oversample(),
+            max_exact_search_vectors: default_max_exact_search_vectors(),
+            cold_queries: None,
+            track_access: false,
+            strict_dimensions: default_strict_dimensions(),
+            dimension_adapter: DimensionAdapter::default(),
         }
     }
 }
 
 impl HybridConfig {
+    /// Default configuration with HNSW and IVF parameters recommended for
+    /// `metric` and a dataset of roughly `n_vectors` vectors; see
+    /// `HNSWConfig::default_for` and `IVFConfig::default_for`
+    pub fn for_dataset(metric: DistanceMetric, n_vectors: usize) -> Self {
+        Self {
+            hnsw_config: HNSWConfig::default_for(metric),
+            ivf_config: IVFConfig::default_for(metric, n_vectors),
+            ..Self::default()
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
-        self.recent_threshold.as_secs() > 0 && self.migration_batch_size > 0
+        self.recent_threshold.as_secs() > 0
+            && self.migration_batch_size > 0
+            && !self.migration_interval.is_zero()
     }
 }
 
@@ -169,6 +392,19 @@ pub struct VacuumStats {
     pub total_removed: usize,
 }
 
+/// Outcome of `HybridIndex::compact`
+#[derive(Debug, Clone, Default)]
+pub struct CompactionStats {
+    /// Deleted vectors physically removed from both indices
+    pub vectors_removed: usize,
+    /// Lazily loaded chunks rewritten without their deleted vectors
+    pub chunks_rewritten: usize,
+    /// Chunks that ended up empty and were deleted from storage
+    pub chunks_removed: usize,
+    /// Estimated in-memory bytes freed plus chunk bytes reclaimed in storage
+    pub bytes_reclaimed: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct HybridSearchConfig {
     pub search_recent: bool,
@@ -177,8 +413,59 @@ pub struct HybridSearchConfig {
     pub historical_k: usize,
     pub recent_threshold_override: Option<Duration>,
     pub k: usize,
+    /// Results to skip before the `k` returned, for paging. The first
+    /// `offset + k` candidates are searched for and the rest dropped, so
+    /// with approximate search deep pages may drift from the exact order.
+    pub offset: usize,
     pub hnsw_ef: usize,
     pub ivf_n_probe: usize,
+    /// Recompute exact distances for the final top-k from stored vectors
+    /// and re-sort before returning
+    pub rerank_exact: bool,
+    /// What to do with results whose distance is NaN
+    pub nan_distances: NanDistancePolicy,
+    /// Skip a sub-index that holds no vectors instead of searching it, e.g.
+    /// the historical index of a fresh index before any migration
+    pub skip_empty_indices: bool,
+    /// Scan every active vector for the true top-k instead of using the
+    /// HNSW/IVF approximations; bounded by `max_exact_search_vectors`
+    pub exact: bool,
+    /// Pick the HNSW ef per query instead of using `hnsw_ef`
+    pub adaptive_ef: Option<AdaptiveEfConfig>,
+}
+
+/// Adaptive HNSW ef: a search starts at `min_ef` and is re-run with twice
+/// the ef, up to `max_ef`, while its results look unreliable, i.e. fewer
+/// than k were found or the k-th and next candidate are nearly tied
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveEfConfig {
+    pub min_ef: usize,
+    pub max_ef: usize,
+    /// The k-th and next candidate count as tied when their distances
+    /// differ by less than this fraction of the k-th distance; 0 disables
+    /// the check
+    pub min_relative_gap: f32,
+}
+
+impl Default for AdaptiveEfConfig {
+    fn default() -> Self {
+        Self {
+            min_ef: 16,
+            max_ef: 256,
+            min_relative_gap: 0.01,
+        }
+    }
+}
+
+/// Handling of NaN distances, which come from vectors stored with NaN
+/// components. Either way a warning naming the vector is logged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NanDistancePolicy {
+    /// Keep them, ranked after every real distance
+    #[default]
+    RankLast,
+    /// Leave them out of the results
+    Drop,
 }
 
 impl Default for HybridSearchConfig {
@@ -190,68 +477,290 @@ impl Default for HybridSearchConfig {
             historical_k: 0,
             recent_threshold_override: None,
             k: 10,
+            offset: 0,
             hnsw_ef: 50,
             ivf_n_probe: 10,
+            rerank_exact: false,
+            nan_distances: NanDistancePolicy::default(),
+            skip_empty_indices: true,
+            exact: false,
+            adaptive_ef: None,
         }
     }
 }
 
 pub type SearchConfig = HybridSearchConfig;
 
+/// Index-wide search parameters used where a search doesn't set its own,
+/// see `HybridIndex::set_search_defaults`. Unset fields fall back to
+/// `HybridSearchConfig::default()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SearchDefaults {
+    #[serde(default)]
+    pub k: Option<usize>,
+    #[serde(default)]
+    pub hnsw_ef: Option<usize>,
+    #[serde(default)]
+    pub ivf_n_probe: Option<usize>,
+}
+
+impl SearchDefaults {
+    /// Fail on zero values, which would make every search return nothing
+    pub fn validate(&self) -> Result<(), HybridError> {
+        for (name, value) in [
+            ("k", self.k),
+            ("hnsw_ef", self.hnsw_ef),
+            ("ivf_n_probe", self.ivf_n_probe),
+        ] {
+            if value == Some(0) {
+                return Err(HybridError::InvalidConfig(format!(
+                    "Default {} must be greater than 0",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What an insert does when a vector with the same id is already stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnDuplicate {
+    /// Keep the stored vector and report success
+    Skip,
+    /// Fail with `HybridError::DuplicateVector`
+    #[default]
+    Error,
+    /// Replace the stored vector and its timestamp
+    Update,
+}
+
+/// What `HybridIndex::insert_with_policy` did with a vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertOutcome {
+    Inserted,
+    /// The id was already stored and `OnDuplicate::Skip` left it alone
+    Skipped,
+    /// The id was already stored and `OnDuplicate::Update` replaced it
+    Updated,
+}
+
+/// Which sub-index `HybridIndex::insert_into` places a vector in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexTarget {
+    /// The recent (HNSW) index, whatever the timestamp
+    Recent,
+    /// The historical (IVF) index, whatever the timestamp; needs a trained IVF
+    Historical,
+    /// Route by age against `recent_threshold`, like `insert_with_timestamp`
+    #[default]
+    Auto,
+}
+
+/// How often a vector has appeared in search results, recorded when
+/// `HybridConfig::track_access` is enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessStats {
+    pub count: u64,
+    pub last_access: DateTime<Utc>,
+}
+
+/// Search result together with the vector's payload, if it has one
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadSearchResult {
+    pub result: SearchResult,
+    pub payload: Option<Vec<u8>>,
+}
+
+/// Number of searches that reached each sub-index, see
+/// `HybridIndex::sub_index_searches`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubIndexSearches {
+    pub recent: usize,
+    pub historical: usize,
+}
+
+#[derive(Debug, Default)]
+struct SearchCounters {
+    recent: AtomicUsize,
+    historical: AtomicUsize,
+    /// HNSW searches re-run with a larger ef by `AdaptiveEfConfig`
+    ef_escalations: AtomicUsize,
+    /// Completed searches and their total duration, for `avg_query_time_ms`
+    queries: AtomicU64,
+    query_time_ns: AtomicU64,
+}
+
+impl SearchCounters {
+    fn record_query(&self, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.query_time_ns.fetch_add(nanos, Ordering::Relaxed);
+        self.queries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Mean duration of the recorded searches, 0 before the first one
+    fn avg_query_time_ms(&self) -> f32 {
+        let queries = self.queries.load(Ordering::Relaxed);
+        if queries == 0 {
+            return 0.0;
+        }
+        let total_ns = self.query_time_ns.load(Ordering::Relaxed);
+        (total_ns as f64 / queries as f64 / 1_000_000.0) as f32
+    }
+}
+
+/// Bookkeeping for `AutoRetrainConfig`
+#[derive(Debug, Default)]
+struct RetrainState {
+    inserts_since_training: AtomicUsize,
+    /// Number of vectors the historical index was last trained on
+    trained_on: AtomicUsize,
+    running: AtomicBool,
+    completed: AtomicUsize,
+}
+
+impl RetrainState {
+    fn trained(&self, vectors: usize) {
+        self.trained_on.store(vectors, Ordering::SeqCst);
+        self.inserts_since_training.store(0, Ordering::SeqCst);
+    }
+}
+
+/// Background task started by `HybridIndex::start_auto_migration`
+#[derive(Debug, Default)]
+struct AutoMigrationState {
+    stop: AtomicBool,
+    /// Wakes the task early so a stop doesn't wait out the interval
+    wake: tokio::sync::Notify,
+    handle: tokio::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+/// Non-owning handle to a `HybridIndex`, so background tasks don't keep a
+/// dropped index alive
+struct WeakHybridIndex(Weak<HybridInner>);
+
+impl WeakHybridIndex {
+    fn new(index: &HybridIndex) -> Self {
+        Self(Arc::downgrade(&index.inner))
+    }
+
+    /// The index, unless every handle to it has been dropped
+    fn upgrade(&self) -> Option<HybridIndex> {
+        self.0.upgrade().map(|inner| HybridIndex { inner })
+    }
+}
+
+/// Cheaply cloneable handle to a hybrid index; clones share one index
 #[derive(Clone)]
 pub struct HybridIndex {
+    inner: Arc<HybridInner>,
+}
+
+/// State shared by every handle to a `HybridIndex`
+pub struct HybridInner {
     config: HybridConfig,
-    recent_index: Arc<RwLock<HNSWIndex>>,
-    historical_index: Arc<RwLock<IVFIndex>>,
-    pub timestamps: Arc<RwLock<std::collections::HashMap<VectorId, DateTime<Utc>>>>,
-    initialized: bool,
-    ivf_trained: bool, // Tracks whether IVF index has been trained
-    recent_count: Arc<RwLock<usize>>,
-    historical_count: Arc<RwLock<usize>>,
+    recent_index: RwLock<HNSWIndex>,
+    historical_index: RwLock<IVFIndex>,
+    pub timestamps: RwLock<std::collections::HashMap<VectorId, DateTime<Utc>>>,
+    initialized: AtomicBool,
+    ivf_trained: AtomicBool, // Tracks whether IVF index has been trained
+    recent_count: RwLock<usize>,
+    historical_count: RwLock<usize>,
     /// Chunk loader for lazy loading vectors from S5 storage (shared between HNSW and IVF)
     chunk_loader: Option<Arc<ChunkLoader>>,
+    retrain_state: RetrainState,
+    /// Opaque per-vector payloads, kept apart from filterable metadata
+    payloads: RwLock<HashMap<VectorId, Vec<u8>>>,
+    filter_cache: RwLock<FilterCache>,
+    search_counters: SearchCounters,
+    /// Queries captured by `ColdQueryConfig`, oldest first
+    cold_query_samples: RwLock<VecDeque<Vec<f32>>>,
+    access_stats: RwLock<HashMap<VectorId, AccessStats>>,
+    search_defaults: RwLock<SearchDefaults>,
+    /// Dimension of the training data passed to `initialize`
+    training_dimension: RwLock<Option<usize>>,
+    /// Wait times on the hot-path write locks, see `lock_contention`
+    lock_metrics: LockMetrics,
+    /// Shared with the task `start_auto_migration` spawns
+    auto_migration: Arc<AutoMigrationState>,
+}
+
+impl std::ops::Deref for HybridIndex {
+    type Target = HybridInner;
+
+    fn deref(&self) -> &HybridInner {
+        &self.inner
+    }
 }
 
 impl HybridIndex {
     pub fn new(config: HybridConfig) -> Self {
-        let recent_index = Arc::new(RwLock::new(HNSWIndex::new(config.hnsw_config.clone())));
-        let historical_index = Arc::new(RwLock::new(IVFIndex::new(config.ivf_config.clone())));
+        let recent_index = RwLock::new(HNSWIndex::new(config.hnsw_config.clone()));
+        let historical_index = RwLock::new(IVFIndex::new(config.ivf_config.clone()));
 
+        let filter_cache = RwLock::new(FilterCache::new(config.filter_cache_capacity));
         Self {
-            config,
-            recent_index,
-            historical_index,
-            timestamps: Arc::new(RwLock::new(std::collections::HashMap::new())),
-            initialized: false,
-            ivf_trained: false, // Start in HNSW-only mode
-            recent_count: Arc::new(RwLock::new(0)),
-            historical_count: Arc::new(RwLock::new(0)),
-            chunk_loader: None,
+            inner: Arc::new(HybridInner {
+                config,
+                recent_index,
+                historical_index,
+                timestamps: RwLock::new(std::collections::HashMap::new()),
+                initialized: AtomicBool::new(false),
+                ivf_trained: AtomicBool::new(false), // Start in HNSW-only mode
+                recent_count: RwLock::new(0),
+                historical_count: RwLock::new(0),
+                chunk_loader: None,
+                retrain_state: RetrainState::default(),
+                payloads: RwLock::new(HashMap::new()),
+                filter_cache,
+                search_counters: SearchCounters::default(),
+                cold_query_samples: RwLock::new(VecDeque::new()),
+                access_stats: RwLock::new(HashMap::new()),
+                search_defaults: RwLock::new(SearchDefaults::default()),
+                training_dimension: RwLock::new(None),
+                lock_metrics: LockMetrics::default(),
+                auto_migration: Arc::new(AutoMigrationState::default()),
+            }),
         }
     }
 
     /// Create a new HybridIndex with chunk loader for lazy loading support
     pub fn with_chunk_loader(config: HybridConfig, chunk_loader: Option<Arc<ChunkLoader>>) -> Self {
         // Create indices with chunk loader
-        let recent_index = Arc::new(RwLock::new(HNSWIndex::with_chunk_loader(
+        let recent_index = RwLock::new(HNSWIndex::with_chunk_loader(
             config.hnsw_config.clone(),
             chunk_loader.clone(),
-        )));
-        let historical_index = Arc::new(RwLock::new(IVFIndex::with_chunk_loader(
+        ));
+        let historical_index = RwLock::new(IVFIndex::with_chunk_loader(
             config.ivf_config.clone(),
             chunk_loader.clone(),
-        )));
+        ));
 
+        let filter_cache = RwLock::new(FilterCache::new(config.filter_cache_capacity));
         Self {
-            config,
-            recent_index,
-            historical_index,
-            timestamps: Arc::new(RwLock::new(std::collections::HashMap::new())),
-            initialized: false,
-            ivf_trained: false, // Start in HNSW-only mode
-            recent_count: Arc::new(RwLock::new(0)),
-            historical_count: Arc::new(RwLock::new(0)),
-            chunk_loader,
+            inner: Arc::new(HybridInner {
+                config,
+                recent_index,
+                historical_index,
+                timestamps: RwLock::new(std::collections::HashMap::new()),
+                initialized: AtomicBool::new(false),
+                ivf_trained: AtomicBool::new(false), // Start in HNSW-only mode
+                recent_count: RwLock::new(0),
+                historical_count: RwLock::new(0),
+                chunk_loader,
+                retrain_state: RetrainState::default(),
+                payloads: RwLock::new(HashMap::new()),
+                filter_cache,
+                search_counters: SearchCounters::default(),
+                cold_query_samples: RwLock::new(VecDeque::new()),
+                access_stats: RwLock::new(HashMap::new()),
+                search_defaults: RwLock::new(SearchDefaults::default()),
+                training_dimension: RwLock::new(None),
+                lock_metrics: LockMetrics::default(),
+                auto_migration: Arc::new(AutoMigrationState::default()),
+            }),
         }
     }
 
@@ -260,11 +769,39 @@ impl HybridIndex {
     }
 
     pub async fn initialize(&mut self, training_data: Vec<Vec<f32>>) -> Result<(), HybridError> {
+        // Training vectors must agree with each other and with anything stored
+        let training_dimension = match training_data.first() {
+            Some(first) => {
+                let expected = first.len();
+                if let Some(bad) = training_data.iter().find(|v| v.len() != expected) {
+                    return Err(HybridError::DimensionMismatch {
+                        expected,
+                        actual: bad.len(),
+                    });
+                }
+                let stored = [
+                    self.recent_index.read().await.dimension(),
+                    self.historical_index.read().await.dimension(),
+                ];
+                if let Some(existing) = stored.into_iter().flatten().find(|d| *d != expected) {
+                    return Err(HybridError::DimensionMismatch {
+                        expected: existing,
+                        actual: expected,
+                    });
+                }
+                Some(expected)
+            }
+            None => None,
+        };
+        if training_dimension.is_some() {
+            *self.training_dimension.write().await = training_dimension;
+        }
+
         // Check if we have enough data for IVF training
         if training_data.len() < self.config.min_ivf_training_size {
             // HNSW-only mode: Skip IVF training for small datasets
-            self.ivf_trained = false;
-            self.initialized = true;
+            self.ivf_trained.store(false, Ordering::SeqCst);
+            self.initialized.store(true, Ordering::SeqCst);
             return Ok(());
         }
 
@@ -284,8 +821,9 @@ impl HybridIndex {
         }
         drop(historical);
 
-        self.ivf_trained = true;
-        self.initialized = true;
+        self.retrain_state.trained(training_data.len());
+        self.ivf_trained.store(true, Ordering::SeqCst);
+        self.initialized.store(true, Ordering::SeqCst);
         Ok(())
     }
 
@@ -294,13 +832,181 @@ impl HybridIndex {
     }
 
     pub fn is_initialized(&self) -> bool {
-        self.initialized
+        self.initialized.load(Ordering::SeqCst)
+    }
+
+    /// Dimension of the stored vectors, or `None` until `initialize` or the
+    /// first insert has fixed it
+    pub async fn dimension(&self) -> Option<usize> {
+        if let Some(dim) = *self.training_dimension.read().await {
+            return Some(dim);
+        }
+        if let Some(dim) = self.recent_index.read().await.dimension() {
+            return Some(dim);
+        }
+        self.historical_index.read().await.dimension()
+    }
+
+    /// Check a query vector before any sub-index is searched.
+    ///
+    /// Returns `Ok(false)` when the search should return no results without
+    /// running (an empty query under `EmptyQueryPolicy::ReturnEmpty`).
+    async fn validate_query(&self, query: &[f32]) -> Result<bool, HybridError> {
+        let dimension = self.dimension().await;
+
+        if query.is_empty() {
+            return match self.config.empty_query_policy {
+                EmptyQueryPolicy::Reject => Err(HybridError::DimensionMismatch {
+                    expected: dimension.unwrap_or(0),
+                    actual: 0,
+                }),
+                EmptyQueryPolicy::ReturnEmpty => Ok(false),
+            };
+        }
+
+        if let Some(expected) = dimension {
+            if expected != query.len() {
+                return Err(HybridError::DimensionMismatch {
+                    expected,
+                    actual: query.len(),
+                });
+            }
+        }
+
+        // Cosine ranking of a zero query would be all NaN or ties
+        if !self.config.hnsw_config.metric.accepts(query)
+            || !self.config.ivf_config.metric.accepts(query)
+        {
+            return Err(HybridError::InvalidQuery(
+                "zero query vector has no cosine similarity to any vector".to_string(),
+            ));
+        }
+        Ok(true)
+    }
+
+    /// Fail with `NotInitialized` unless the index is initialized, switching
+    /// it on in HNSW-only mode first when `auto_initialize` is enabled
+    fn ensure_initialized(&self) -> Result<(), HybridError> {
+        if self.is_initialized() {
+            return Ok(());
+        }
+        if !self.config.auto_initialize {
+            return Err(HybridError::NotInitialized);
+        }
+        self.initialized.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Train the historical index from the vectors inserted so far once an
+    /// auto-initialized index has accumulated `min_ivf_training_size` of them.
+    ///
+    /// The centroids are fit on a separate index, so the historical index is
+    /// only write-locked to swap it in; concurrent inserts skip training
+    /// while one runs. Already inserted vectors stay in HNSW and reach IVF
+    /// through migration.
+    async fn train_from_inserted(&self) -> Result<(), HybridError> {
+        if !self.config.auto_initialize || self.ivf_trained.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let needed = self
+            .config
+            .min_ivf_training_size
+            .max(self.config.ivf_config.n_clusters);
+        if *self.recent_count.read().await < needed {
+            return Ok(());
+        }
+
+        let training_data: Vec<Vec<f32>> = self
+            .recent_index
+            .read()
+            .await
+            .get_all_nodes()
+            .into_iter()
+            .filter(|node| !node.is_deleted())
+            .map(|node| node.vector().clone())
+            .collect();
+        if training_data.len() < needed
+            || self
+                .retrain_state
+                .running
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+        {
+            return Ok(());
+        }
+
+        let result = self.train_historical(training_data).await;
+        self.retrain_state.running.store(false, Ordering::SeqCst);
+        result
+    }
+
+    /// Fit a fresh historical index on `training_data` off the lock and swap
+    /// it in, unless another insert trained one first
+    async fn train_historical(&self, training_data: Vec<Vec<f32>>) -> Result<(), HybridError> {
+        let (config, chunk_loader) = {
+            let historical = self.historical_index.read().await;
+            (historical.config().clone(), historical.chunk_loader.clone())
+        };
+        let trained_on = training_data.len();
+        let trained = tokio::task::spawn_blocking(move || {
+            let mut index = IVFIndex::with_chunk_loader(config, chunk_loader);
+            index.train(&training_data).map(|_| index)
+        })
+        .await
+        .map_err(|e| HybridError::IVF(e.to_string()))?
+        .map_err(|e| HybridError::IVF(e.to_string()))?;
+
+        let mut historical = self.historical_index.write().await;
+        // Another insert may have trained while we collected the vectors
+        if self.ivf_trained.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        *historical = trained;
+        self.retrain_state.trained(trained_on);
+        self.ivf_trained.store(true, Ordering::SeqCst);
+        Ok(())
     }
 
     pub async fn insert(&self, id: VectorId, vector: Vec<f32>) -> Result<(), HybridError> {
         self.insert_with_timestamp(id, vector, Utc::now()).await
     }
 
+    /// Insert a vector with an opaque payload that is returned by
+    /// `search_with_payloads` but never seen by metadata filters
+    pub async fn insert_with_payload(
+        &self,
+        id: VectorId,
+        vector: Vec<f32>,
+        payload: Vec<u8>,
+    ) -> Result<(), HybridError> {
+        self.insert(id.clone(), vector).await?;
+        self.payloads.write().await.insert(id, payload);
+        Ok(())
+    }
+
+    /// Attach or replace the payload of a stored vector
+    pub async fn set_payload(&self, id: VectorId, payload: Vec<u8>) -> Result<(), HybridError> {
+        if !self.timestamps.read().await.contains_key(&id) {
+            return Err(HybridError::VectorNotFound(id));
+        }
+        self.payloads.write().await.insert(id, payload);
+        Ok(())
+    }
+
+    pub async fn get_payload(&self, id: &VectorId) -> Option<Vec<u8>> {
+        self.payloads.read().await.get(id).cloned()
+    }
+
+    /// All stored payloads, for persistence
+    pub async fn get_payloads(&self) -> HashMap<VectorId, Vec<u8>> {
+        self.payloads.read().await.clone()
+    }
+
+    /// Replace all payloads, used when restoring a saved index
+    pub async fn restore_payloads(&self, payloads: HashMap<VectorId, Vec<u8>>) {
+        *self.payloads.write().await = payloads;
+    }
+
     /// Insert a vector with chunk reference for lazy loading support
     pub async fn insert_with_chunk(
         &self,
@@ -309,8 +1015,11 @@ impl HybridIndex {
         timestamp: DateTime<Utc>,
         chunk_id: Option<String>,
     ) -> Result<(), HybridError> {
-        if !self.initialized {
-            return Err(HybridError::NotInitialized);
+        self.ensure_initialized()?;
+        let vector = self.adapt_dimension(vector).await;
+        self.check_insert_dimension(&vector).await?;
+        if !self.config.ivf_config.metric.accepts(&vector) {
+            return Err(HybridError::IVF(IVFError::ZeroVector.to_string()));
         }
 
         // Check for duplicates
@@ -341,16 +1050,22 @@ impl HybridIndex {
             let mut historical = self.historical_index.write().await;
             historical
                 .insert_with_chunk(id.clone(), vector, chunk_id)
-                .map_err(|e| HybridError::IVF(e.to_string()))?;
+                .map_err(map_ivf_insert_error)?;
+            drop(historical);
 
             let mut count = self.historical_count.write().await;
             *count += 1;
+            let total = *count;
+            drop(count);
+            self.maybe_schedule_retrain(1, total);
         }
 
         // Store timestamp
         let mut timestamps = self.timestamps.write().await;
         timestamps.insert(id, timestamp);
+        drop(timestamps);
 
+        self.invalidate_filter_cache().await;
         Ok(())
     }
 
@@ -360,20 +1075,236 @@ impl HybridIndex {
         vector: Vec<f32>,
         timestamp: DateTime<Utc>,
     ) -> Result<(), HybridError> {
-        if !self.initialized {
-            return Err(HybridError::NotInitialized);
+        self.insert_with_policy(id, vector, timestamp, OnDuplicate::Error)
+            .await
+            .map(|_| ())
+    }
+
+    /// Insert a vector, handling an id that is already stored (including a
+    /// deleted one) as `on_duplicate` says
+    pub async fn insert_with_policy(
+        &self,
+        id: VectorId,
+        vector: Vec<f32>,
+        timestamp: DateTime<Utc>,
+        on_duplicate: OnDuplicate,
+    ) -> Result<InsertOutcome, HybridError> {
+        self.insert_routed(id, vector, timestamp, on_duplicate, IndexTarget::Auto)
+            .await
+    }
+
+    /// Insert a vector into the sub-index `target` names instead of routing
+    /// by age, e.g. to backfill old content straight into the historical
+    /// index. The timestamp is still recorded as given.
+    pub async fn insert_into(
+        &self,
+        id: VectorId,
+        vector: Vec<f32>,
+        timestamp: DateTime<Utc>,
+        target: IndexTarget,
+    ) -> Result<(), HybridError> {
+        self.insert_routed(id, vector, timestamp, OnDuplicate::Error, target)
+            .await
+            .map(|_| ())
+    }
+
+    /// Combine indices built independently, e.g. one per data shard, into one.
+    ///
+    /// The largest input (preferring one with a trained historical index) is
+    /// kept, and the live vectors of the others are re-inserted into it with
+    /// their timestamps and payloads, each into the same sub-index that held
+    /// it. Deleted vectors are dropped. Fails if an id is stored by more than
+    /// one input or the inputs disagree on dimension or metric; these are
+    /// checked before the kept index is changed.
+    pub async fn merge(indices: Vec<HybridIndex>) -> Result<HybridIndex, HybridError> {
+        let mut indices = indices;
+        let base_pos = indices
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, index)| (index.ivf_trained(), index.total_vectors()))
+            .map(|(pos, _)| pos)
+            .ok_or_else(|| HybridError::InvalidConfig("No indices to merge".to_string()))?;
+        let base = indices.swap_remove(base_pos);
+
+        // An untrained base can only take recent vectors
+        let historical_target = if base.ivf_trained() {
+            IndexTarget::Historical
+        } else {
+            IndexTarget::Recent
+        };
+        let mut dimension = base.dimension().await;
+        let mut seen: HashSet<VectorId> = base.timestamps.read().await.keys().cloned().collect();
+        let mut merged = Vec::new();
+        for shard in indices {
+            if shard.config.ivf_config.metric != base.config.ivf_config.metric {
+                return Err(HybridError::InvalidConfig(format!(
+                    "Cannot merge a {:?} index into a {:?} index",
+                    shard.config.ivf_config.metric, base.config.ivf_config.metric
+                )));
+            }
+            match (dimension, shard.dimension().await) {
+                (Some(expected), Some(actual)) if expected != actual => {
+                    return Err(HybridError::DimensionMismatch { expected, actual });
+                }
+                (None, actual) => dimension = actual,
+                _ => {}
+            }
+
+            let timestamps = shard.timestamps.read().await.clone();
+            let payloads = shard.payloads.read().await.clone();
+
+            let recent: Vec<(VectorId, Vec<f32>)> = shard
+                .recent_index
+                .read()
+                .await
+                .get_all_nodes()
+                .into_iter()
+                .filter(|node| !node.is_deleted())
+                .map(|node| (node.id().clone(), node.vector().clone()))
+                .collect();
+
+            let mut historical = Vec::new();
+            if shard.ivf_trained() {
+                let index = shard.historical_index.read().await;
+                for centroid in index.get_centroids() {
+                    let vectors = index
+                        .get_cluster_vectors(centroid.id())
+                        .await
+                        .map_err(|e| HybridError::IVF(e.to_string()))?;
+                    historical.extend(vectors.into_iter().filter(|(id, _)| !index.is_deleted(id)));
+                }
+            }
+
+            for (id, _) in recent.iter().chain(&historical) {
+                if !seen.insert(id.clone()) {
+                    return Err(HybridError::DuplicateVector(id.clone()));
+                }
+            }
+            let placed = recent
+                .into_iter()
+                .map(|entry| (entry, IndexTarget::Recent))
+                .chain(historical.into_iter().map(|entry| (entry, historical_target)));
+            for ((id, vector), target) in placed {
+                let timestamp = timestamps.get(&id).copied().unwrap_or_else(Utc::now);
+                let payload = payloads.get(&id).cloned();
+                merged.push((id, vector, timestamp, target, payload));
+            }
         }
 
-        // Check for duplicates
-        let timestamps = self.timestamps.read().await;
-        if timestamps.contains_key(&id) {
-            return Err(HybridError::DuplicateVector(id));
+        if !merged.is_empty() {
+            base.ensure_initialized()?;
+        }
+        for (id, vector, timestamp, target, payload) in merged {
+            base.insert_into(id.clone(), vector, timestamp, target).await?;
+            if let Some(payload) = payload {
+                base.payloads.write().await.insert(id, payload);
+            }
         }
-        drop(timestamps);
+
+        Ok(base)
+    }
+
+    /// Replace the values of a stored vector, keeping its timestamp,
+    /// payload and the sub-index it is in.
+    ///
+    /// A recent vector is unlinked from the HNSW graph and inserted again,
+    /// so its neighbours reflect the new position; a historical vector is
+    /// reassigned to the IVF cluster nearest its new values.
+    pub async fn update(&self, id: VectorId, new_vector: Vec<f32>) -> Result<(), HybridError> {
+        self.ensure_initialized()?;
+        if let Some(expected) = self.dimension().await {
+            if expected != new_vector.len() {
+                return Err(HybridError::DimensionMismatch {
+                    expected,
+                    actual: new_vector.len(),
+                });
+            }
+        }
+        if !self.config.ivf_config.metric.accepts(&new_vector) {
+            return Err(HybridError::IVF(IVFError::ZeroVector.to_string()));
+        }
+        let old_vector = self
+            .get_vector(&id)
+            .await
+            .ok_or_else(|| HybridError::VectorNotFound(id.clone()))?;
+
+        let mut recent = self
+            .lock_metrics
+            .write(LockName::RecentIndex, &self.recent_index)
+            .await;
+        if recent.remove(&id).is_ok() {
+            if let Err(e) = recent.insert(id.clone(), new_vector) {
+                let _ = recent.insert(id, old_vector);
+                return Err(HybridError::HNSW(e.to_string()));
+            }
+        } else {
+            drop(recent);
+            let mut historical = self
+                .lock_metrics
+                .write(LockName::HistoricalIndex, &self.historical_index)
+                .await;
+            historical
+                .remove(&id)
+                .map_err(|e| HybridError::IVF(e.to_string()))?;
+            if let Err(e) = historical.insert(id.clone(), new_vector) {
+                let _ = historical.insert(id, old_vector);
+                return Err(map_ivf_insert_error(e));
+            }
+        }
+
+        self.invalidate_filter_cache().await;
+        Ok(())
+    }
+
+    async fn insert_routed(
+        &self,
+        id: VectorId,
+        vector: Vec<f32>,
+        timestamp: DateTime<Utc>,
+        on_duplicate: OnDuplicate,
+        target: IndexTarget,
+    ) -> Result<InsertOutcome, HybridError> {
+        self.ensure_initialized()?;
+        let vector = self.adapt_dimension(vector).await;
+        self.check_insert_dimension(&vector).await?;
+        // Checked up front so a vector the recent index accepts can't fail
+        // later during migration
+        if !self.config.ivf_config.metric.accepts(&vector) {
+            return Err(HybridError::IVF(IVFError::ZeroVector.to_string()));
+        }
+        if target == IndexTarget::Historical && !self.ivf_trained() {
+            return Err(HybridError::IVF(
+                "Cannot insert into the historical index before it is trained".to_string(),
+            ));
+        }
+
+        // Check for duplicates
+        let exists = self.timestamps.read().await.contains_key(&id);
+        let outcome = match (exists, on_duplicate) {
+            (false, _) => InsertOutcome::Inserted,
+            (true, OnDuplicate::Error) => return Err(HybridError::DuplicateVector(id)),
+            (true, OnDuplicate::Skip) => return Ok(InsertOutcome::Skipped),
+            (true, OnDuplicate::Update) => {
+                // Don't drop the stored vector for one that can't be inserted
+                if let Some(expected) = self.dimension().await {
+                    if expected != vector.len() {
+                        return Err(HybridError::DimensionMismatch {
+                            expected,
+                            actual: vector.len(),
+                        });
+                    }
+                }
+                self.remove_stored(&id).await?;
+                InsertOutcome::Updated
+            }
+        };
 
         // HNSW-only mode: Route all vectors to HNSW if IVF not trained
-        if !self.ivf_trained {
-            let mut recent = self.recent_index.write().await;
+        if !self.ivf_trained() {
+            let mut recent = self
+                .lock_metrics
+                .write(LockName::RecentIndex, &self.recent_index)
+                .await;
             recent
                 .insert(id.clone(), vector)
                 .map_err(|e| HybridError::HNSW(e.to_string()))?;
@@ -382,15 +1313,24 @@ impl HybridIndex {
             *count += 1;
         } else {
             // Normal mode: Determine if vector is recent or historical
-            let now = Utc::now();
-            let age = now
-                .signed_duration_since(timestamp)
-                .to_std()
-                .unwrap_or(Duration::from_secs(0));
+            let is_recent = match target {
+                IndexTarget::Recent => true,
+                IndexTarget::Historical => false,
+                IndexTarget::Auto => {
+                    let age = Utc::now()
+                        .signed_duration_since(timestamp)
+                        .to_std()
+                        .unwrap_or(Duration::from_secs(0));
+                    age < self.config.recent_threshold
+                }
+            };
 
-            if age < self.config.recent_threshold {
+            if is_recent {
                 // Insert into HNSW (recent)
-                let mut recent = self.recent_index.write().await;
+                let mut recent = self
+                    .lock_metrics
+                    .write(LockName::RecentIndex, &self.recent_index)
+                    .await;
                 recent
                     .insert(id.clone(), vector)
                     .map_err(|e| HybridError::HNSW(e.to_string()))?;
@@ -399,36 +1339,131 @@ impl HybridIndex {
                 *count += 1;
             } else {
                 // Insert into IVF (historical)
-                let mut historical = self.historical_index.write().await;
+                let mut historical = self
+                    .lock_metrics
+                    .write(LockName::HistoricalIndex, &self.historical_index)
+                    .await;
                 historical
                     .insert(id.clone(), vector)
-                    .map_err(|e| HybridError::IVF(e.to_string()))?;
+                    .map_err(map_ivf_insert_error)?;
+                drop(historical);
 
                 let mut count = self.historical_count.write().await;
                 *count += 1;
+                let total = *count;
+                drop(count);
+                self.maybe_schedule_retrain(1, total);
             }
         }
 
         // Store timestamp
-        let mut timestamps = self.timestamps.write().await;
+        let mut timestamps = self
+            .lock_metrics
+            .write(LockName::Timestamps, &self.timestamps)
+            .await;
         timestamps.insert(id, timestamp);
+        drop(timestamps);
+
+        self.invalidate_filter_cache().await;
+        // The vector is stored either way; a later insert retries training
+        if let Err(error) = self.train_from_inserted().await {
+            tracing::warn!(%error, "Failed to train historical index from inserted vectors");
+        }
+        Ok(outcome)
+    }
 
+    /// Under `DimensionAdapter::PadOrTruncate`, resize a non-empty vector of
+    /// the wrong length to the index's dimension
+    async fn adapt_dimension(&self, mut vector: Vec<f32>) -> Vec<f32> {
+        if self.config.dimension_adapter == DimensionAdapter::Reject || vector.is_empty() {
+            return vector;
+        }
+        if let Some(expected) = self.dimension().await {
+            if expected != vector.len() {
+                tracing::warn!(
+                    expected,
+                    actual = vector.len(),
+                    "resizing inserted vector to the index dimension; similarity is degraded"
+                );
+                vector.resize(expected, 0.0);
+            }
+        }
+        vector
+    }
+
+    /// Under `strict_dimensions`, reject a vector whose length differs from
+    /// the index's dimension before either sub-index sees it
+    async fn check_insert_dimension(&self, vector: &[f32]) -> Result<(), HybridError> {
+        if !self.config.strict_dimensions {
+            return Ok(());
+        }
+        match self.dimension().await {
+            Some(expected) if expected != vector.len() => Err(HybridError::DimensionMismatch {
+                expected,
+                actual: vector.len(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Physically remove a stored vector from whichever sub-index holds it
+    async fn remove_stored(&self, id: &VectorId) -> Result<(), HybridError> {
+        let removed_recent = self.recent_index.write().await.remove(id).is_ok();
+        if removed_recent {
+            let mut count = self.recent_count.write().await;
+            *count = count.saturating_sub(1);
+        } else {
+            self.historical_index
+                .write()
+                .await
+                .remove(id)
+                .map_err(|e| HybridError::IVF(e.to_string()))?;
+            let mut count = self.historical_count.write().await;
+            *count = count.saturating_sub(1);
+        }
+        self.timestamps.write().await.remove(id);
         Ok(())
     }
 
     pub async fn search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>, HybridError> {
-        let mut config = SearchConfig::default();
+        let mut config = self.default_search_config().await;
         config.k = k;
         self.search_with_config(query, config).await
     }
 
+    /// Replace the index-wide search defaults
+    pub async fn set_search_defaults(&self, defaults: SearchDefaults) -> Result<(), HybridError> {
+        defaults.validate()?;
+        *self.search_defaults.write().await = defaults;
+        Ok(())
+    }
+
+    pub async fn search_defaults(&self) -> SearchDefaults {
+        self.search_defaults.read().await.clone()
+    }
+
+    /// `HybridSearchConfig::default()` with the index's search defaults
+    /// applied; callers override the fields a request sets explicitly
+    pub async fn default_search_config(&self) -> SearchConfig {
+        let defaults = self.search_defaults.read().await;
+        let base = SearchConfig::default();
+        SearchConfig {
+            k: defaults.k.unwrap_or(base.k),
+            hnsw_ef: defaults.hnsw_ef.unwrap_or(base.hnsw_ef),
+            ivf_n_probe: defaults.ivf_n_probe.unwrap_or(base.ivf_n_probe),
+            ..base
+        }
+    }
+
     pub async fn search_with_config(
         &self,
         query: &[f32],
         config: SearchConfig,
     ) -> Result<Vec<SearchResult>, HybridError> {
-        let k = config.k;
-        if !self.initialized {
+        let started = Instant::now();
+        // Candidates needed to cover the skipped results and the page
+        let k = config.offset.saturating_add(config.k);
+        if !self.validate_query(query).await? || !self.is_initialized() {
             // Return empty results for uninitialized index
             return Ok(Vec::new());
         }
@@ -452,43 +1487,377 @@ impl HybridIndex {
             k
         };
 
-        // Search recent vectors
-        if config.search_recent {
-            let recent = self.recent_index.read().await;
-            let ef = config.hnsw_ef;
-            if let Ok(recent_results) = recent.search(query, recent_k, ef) {
-                all_results.extend(recent_results);
+        let skip_recent = config.skip_empty_indices && *self.recent_count.read().await == 0;
+        let skip_historical =
+            config.skip_empty_indices && *self.historical_count.read().await == 0;
+
+        let search_recent = config.search_recent && !skip_recent;
+        let search_historical = config.search_historical && !skip_historical;
+
+        if config.exact {
+            all_results = self
+                .exact_candidates(query, k, search_recent, search_historical)
+                .await?;
+        } else {
+            // Search recent vectors
+            if search_recent {
+                self.search_counters.recent.fetch_add(1, Ordering::Relaxed);
+                let recent = self.recent_index.read().await;
+                let recent_results = match &config.adaptive_ef {
+                    Some(adaptive) => self.adaptive_hnsw_search(&recent, query, recent_k, adaptive),
+                    None => recent.search(query, recent_k, config.hnsw_ef),
+                };
+                if let Ok(recent_results) = recent_results {
+                    all_results.extend(recent_results);
+                }
             }
-        }
 
-        // Search historical vectors (only if IVF is trained)
-        if config.search_historical && self.ivf_trained {
-            let historical = self.historical_index.read().await;
-            // Use custom n_probe if specified
-            if config.ivf_n_probe != historical.config().n_probe {
-                if let Ok(historical_results) =
-                    historical.search_with_config(query, historical_k, config.ivf_n_probe).await
-                {
-                    all_results.extend(historical_results);
+            // Search historical vectors (only if IVF is trained)
+            if search_historical && self.ivf_trained() {
+                self.search_counters.historical.fetch_add(1, Ordering::Relaxed);
+                let historical = self.historical_index.read().await;
+                // Use custom n_probe if specified
+                if config.ivf_n_probe != historical.config().n_probe {
+                    if let Ok(historical_results) =
+                        historical.search_with_config(query, historical_k, config.ivf_n_probe).await
+                    {
+                        all_results.extend(historical_results);
+                    }
+                } else {
+                    if let Ok(historical_results) = historical.search(query, historical_k).await {
+                        all_results.extend(historical_results);
+                    }
                 }
-            } else {
-                if let Ok(historical_results) = historical.search(query, historical_k).await {
-                    all_results.extend(historical_results);
+            }
+        }
+
+        for result in all_results.iter().filter(|r| r.distance.is_nan()) {
+            tracing::warn!(
+                vector_id = %result.vector_id.to_string(),
+                "NaN distance in search results; the stored vector likely contains NaN"
+            );
+        }
+        if config.nan_distances == NanDistancePolicy::Drop {
+            all_results.retain(|r| !r.distance.is_nan());
+        }
+
+        // Sort by distance and take top k, NaN distances last
+        all_results.sort_by(SearchResult::cmp_distance);
+        all_results.truncate(k);
+
+        if config.rerank_exact {
+            all_results = self.rerank_exact(query, all_results).await?;
+        }
+        all_results.drain(..config.offset.min(all_results.len()));
+
+        self.record_cold_query(query, &all_results).await;
+        self.record_access(&all_results).await;
+        self.search_counters.record_query(started.elapsed());
+
+        Ok(all_results)
+    }
+
+    /// HNSW search with the ef chosen by `adaptive`, doubling it from
+    /// `min_ef` while the results look unreliable
+    fn adaptive_hnsw_search(
+        &self,
+        recent: &HNSWIndex,
+        query: &[f32],
+        k: usize,
+        adaptive: &AdaptiveEfConfig,
+    ) -> Result<Vec<SearchResult>, crate::hnsw::core::HNSWError> {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        // One extra candidate to measure the gap after the k-th result
+        let max_ef = adaptive.max_ef.max(k + 1);
+        let mut ef = adaptive.min_ef.clamp(k + 1, max_ef);
+        let reachable = k.min(recent.active_count());
+
+        loop {
+            let mut results = recent.search(query, k + 1, ef)?;
+            let too_few = results.len() < reachable;
+            let tied = results.len() > k && {
+                let kth = results[k - 1].distance;
+                results[k].distance - kth < kth * adaptive.min_relative_gap
+            };
+            if (!too_few && !tied) || ef >= max_ef {
+                tracing::debug!(ef, "adaptive ef search finished");
+                results.truncate(k);
+                return Ok(results);
+            }
+            ef = (ef * 2).min(max_ef);
+            self.search_counters.ef_escalations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of HNSW searches re-run with a larger ef under `adaptive_ef`
+    pub fn adaptive_ef_escalations(&self) -> usize {
+        self.search_counters.ef_escalations.load(Ordering::Relaxed)
+    }
+
+    /// Keep `query` as a cold-query sample when `cold_queries` is configured
+    /// and its nearest result is beyond the distance threshold
+    async fn record_cold_query(&self, query: &[f32], results: &[SearchResult]) {
+        let Some(policy) = &self.config.cold_queries else {
+            return;
+        };
+        if policy.max_samples == 0
+            || results
+                .first()
+                .is_some_and(|nearest| nearest.distance <= policy.distance_threshold)
+        {
+            return;
+        }
+
+        let mut samples = self.cold_query_samples.write().await;
+        if samples.len() >= policy.max_samples {
+            samples.pop_front();
+        }
+        samples.push_back(query.to_vec());
+    }
+
+    /// Count one access for every vector in `results` when `track_access`
+    /// is enabled, under a single lock acquisition
+    async fn record_access(&self, results: &[SearchResult]) {
+        if !self.config.track_access || results.is_empty() {
+            return;
+        }
+        let now = Utc::now();
+        let mut stats = self.access_stats.write().await;
+        for result in results {
+            let entry = stats.entry(result.vector_id.clone()).or_insert(AccessStats {
+                count: 0,
+                last_access: now,
+            });
+            entry.count += 1;
+            entry.last_access = now;
+        }
+    }
+
+    /// Access statistics of one vector, if it has been returned by a search
+    pub async fn access_stats(&self, id: &VectorId) -> Option<AccessStats> {
+        self.access_stats.read().await.get(id).copied()
+    }
+
+    /// The `n` most often returned vectors, most accessed first; ties go to
+    /// the most recently accessed
+    pub async fn top_accessed(&self, n: usize) -> Vec<(VectorId, AccessStats)> {
+        let mut accessed: Vec<(VectorId, AccessStats)> = self
+            .access_stats
+            .read()
+            .await
+            .iter()
+            .map(|(id, stats)| (id.clone(), *stats))
+            .collect();
+        accessed.sort_by(|a, b| {
+            b.1.count
+                .cmp(&a.1.count)
+                .then_with(|| b.1.last_access.cmp(&a.1.last_access))
+        });
+        accessed.truncate(n);
+        accessed
+    }
+
+    /// Cold queries captured so far, oldest first
+    pub async fn cold_queries(&self) -> Vec<Vec<f32>> {
+        self.cold_query_samples.read().await.iter().cloned().collect()
+    }
+
+    /// Forget the captured cold queries
+    pub async fn clear_cold_queries(&self) {
+        self.cold_query_samples.write().await.clear();
+    }
+
+    /// `search_with_config`, with each result's payload attached
+    pub async fn search_with_payloads(
+        &self,
+        query: &[f32],
+        config: SearchConfig,
+    ) -> Result<Vec<PayloadSearchResult>, HybridError> {
+        let results = self.search_with_config(query, config).await?;
+        let payloads = self.payloads.read().await;
+        Ok(results
+            .into_iter()
+            .map(|result| {
+                let payload = payloads.get(&result.vector_id).cloned();
+                PayloadSearchResult { result, payload }
+            })
+            .collect())
+    }
+
+    /// Closest vector to `query`, or `None` for an empty index.
+    ///
+    /// Fast path for `search(query, 1)`: each sub-index looks for a single
+    /// nearest neighbour and the closer of the two wins, so no k-sized result
+    /// list is built or sorted. The query is validated as in `search`, and
+    /// the index's search defaults are used, but unlike `search` it never
+    /// triggers auto-migration.
+    pub async fn nearest(&self, query: &[f32]) -> Result<Option<SearchResult>, HybridError> {
+        if !self.validate_query(query).await? || !self.is_initialized() {
+            return Ok(None);
+        }
+        let defaults = self.default_search_config().await;
+
+        let recent = self
+            .recent_index
+            .read()
+            .await
+            .search(query, 1, defaults.hnsw_ef)
+            .ok()
+            .and_then(|results| results.into_iter().next());
+
+        let historical = if self.ivf_trained() {
+            self.historical_index
+                .read()
+                .await
+                .nearest(query, defaults.ivf_n_probe)
+                .await
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+
+        Ok(match (recent, historical) {
+            (Some(r), Some(h)) => Some(if h.distance < r.distance { h } else { r }),
+            (r, h) => r.or(h),
+        })
+    }
+
+    /// Replace candidate distances with exact distances computed from the
+    /// stored vectors and re-sort.
+    ///
+    /// Historical vectors stored in chunks are loaded when not cached.
+    /// Candidates whose id isn't stored at all keep their original distance.
+    pub async fn rerank_exact(
+        &self,
+        query: &[f32],
+        mut results: Vec<SearchResult>,
+    ) -> Result<Vec<SearchResult>, HybridError> {
+        for result in &mut results {
+            if let Some((vector, metric)) = self.stored_vector(&result.vector_id).await? {
+                result.distance = metric.distance(query, &vector);
+            }
+        }
+
+        results.sort_by(SearchResult::cmp_distance);
+        Ok(results)
+    }
+
+    /// Stored vector `id` with the metric of the index holding it, loading
+    /// chunk-backed historical vectors that are not cached
+    async fn stored_vector(&self, id: &VectorId) -> Result<Option<(Vec<f32>, DistanceMetric)>, HybridError> {
+        let recent = self.recent_index.read().await;
+        if let Some(vector) = recent.get_vector_by_id(id) {
+            return Ok(Some((vector, recent.config().metric)));
+        }
+        drop(recent);
+
+        let historical = self.historical_index.read().await;
+        let metric = historical.config().metric;
+        let vector = historical
+            .load_vector(id)
+            .await
+            .map_err(|e| HybridError::IVF(e.to_string()))?;
+        Ok(vector.map(|vector| (vector, metric)))
+    }
+
+    /// Chunks the historical index skipped while lazy loading, when its chunk
+    /// loader runs under `ChunkLoadPolicy::SkipMissing`
+    pub async fn chunk_integrity_report(&self) -> ChunkIntegrityReport {
+        self.historical_index.read().await.chunk_integrity_report().await
+    }
+
+    /// Per-dimension contributions to the distance between `query` and the
+    /// stored vector `id`, recomputed exactly under the metric of the index
+    /// holding it; see `DistanceMetric::components` for how they add up.
+    pub async fn explain_distance(&self, query: &[f32], id: &VectorId) -> Result<Vec<f32>, HybridError> {
+        let (vector, metric) = self
+            .stored_vector(id)
+            .await?
+            .ok_or_else(|| HybridError::VectorNotFound(id.clone()))?;
+
+        if vector.len() != query.len() {
+            return Err(HybridError::DimensionMismatch {
+                expected: vector.len(),
+                actual: query.len(),
+            });
+        }
+
+        if !metric.accepts(query) {
+            return Err(HybridError::InvalidQuery(
+                "zero query vector has no cosine similarity to any vector".to_string(),
+            ));
+        }
+
+        Ok(metric.components(query, &vector))
+    }
+
+    /// True top-k over every active vector, ignoring the HNSW/IVF
+    /// approximations. Fails with `ExactSearchTooLarge` when the index holds
+    /// more than `max_exact_search_vectors` vectors.
+    pub async fn exact_search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>, HybridError> {
+        let config = SearchConfig {
+            k,
+            exact: true,
+            ..SearchConfig::default()
+        };
+        self.search_with_config(query, config).await
+    }
+
+    /// Unsorted exact candidates: a full scan of the recent nodes plus an
+    /// IVF search probing every cluster, which is exhaustive
+    async fn exact_candidates(
+        &self,
+        query: &[f32],
+        k: usize,
+        search_recent: bool,
+        search_historical: bool,
+    ) -> Result<Vec<SearchResult>, HybridError> {
+        let vectors = *self.recent_count.read().await + *self.historical_count.read().await;
+        let limit = self.config.max_exact_search_vectors;
+        if vectors > limit {
+            return Err(HybridError::ExactSearchTooLarge { vectors, limit });
+        }
+
+        let mut results = Vec::new();
+        if search_recent {
+            self.search_counters.recent.fetch_add(1, Ordering::Relaxed);
+            let recent = self.recent_index.read().await;
+            for node in recent.get_all_nodes() {
+                if node.is_deleted() {
+                    continue;
                 }
+                let distance = recent.distance(query, node.vector());
+                results.push(SearchResult::new(node.id().clone(), distance, None));
             }
         }
 
-        // Sort by distance and take top k
-        all_results.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
-        all_results.truncate(k);
-
-        Ok(all_results)
+        if search_historical && self.ivf_trained() {
+            self.search_counters.historical.fetch_add(1, Ordering::Relaxed);
+            let historical = self.historical_index.read().await;
+            let n_clusters = historical.get_centroids().len();
+            results.extend(
+                historical
+                    .search_with_config(query, k, n_clusters)
+                    .await
+                    .map_err(|e| HybridError::IVF(e.to_string()))?,
+            );
+        }
+
+        Ok(results)
     }
 
     /// Search with metadata filtering
     ///
-    /// Implements k-oversampling strategy: retrieves more candidates than k,
-    /// filters by metadata, then truncates to k results.
+    /// Implements k-oversampling strategy: retrieves `filter_oversample * k`
+    /// candidates, filters by metadata, then truncates to k results. While
+    /// fewer than k candidates pass and the index holds more, the search is
+    /// repeated with twice the multiplier, up to `max_filter_oversample`.
+    ///
+    /// The filter is evaluated against each candidate; use
+    /// `search_with_filter_cached` to reuse the matching ids across searches.
     ///
     /// # Arguments
     /// * `query` - Query vector
@@ -516,36 +1885,243 @@ impl HybridIndex {
         k: usize,
         filter: Option<&crate::core::metadata_filter::MetadataFilter>,
         metadata_map: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<Vec<SearchResult>, HybridError> {
+        let mut config = self.default_search_config().await;
+        config.k = k;
+        self.search_with_filter_config(query, config, filter, metadata_map, None)
+            .await
+    }
+
+    /// `search_with_filter` that caches the ids matching each filter.
+    ///
+    /// `generation` must change whenever the contents of `metadata_map` do;
+    /// the cached ids are reused only for searches passing the same
+    /// generation, and are also dropped on every insert, delete or
+    /// `invalidate_filter_cache` call. Does not cache when
+    /// `filter_cache_capacity` is 0.
+    pub async fn search_with_filter_cached(
+        &self,
+        query: &[f32],
+        k: usize,
+        filter: Option<&crate::core::metadata_filter::MetadataFilter>,
+        metadata_map: &std::collections::HashMap<String, serde_json::Value>,
+        generation: u64,
+    ) -> Result<Vec<SearchResult>, HybridError> {
+        let mut config = self.default_search_config().await;
+        config.k = k;
+        self.search_with_filter_config(query, config, filter, metadata_map, Some(generation))
+            .await
+    }
+
+    /// `search_with_filter` with explicit search options. The candidates are
+    /// searched for with `config` (its ef, n_probe, exact and sub-index
+    /// choices), and `offset` is applied after filtering. With a
+    /// `generation`, matches are cached as in `search_with_filter_cached`.
+    pub async fn search_with_filter_config(
+        &self,
+        query: &[f32],
+        config: SearchConfig,
+        filter: Option<&crate::core::metadata_filter::MetadataFilter>,
+        metadata_map: &std::collections::HashMap<String, serde_json::Value>,
+        generation: Option<u64>,
     ) -> Result<Vec<SearchResult>, HybridError> {
         // If no filter, use regular search
-        if filter.is_none() {
-            return self.search(query, k).await;
+        let Some(filter) = filter else {
+            return self.search_with_config(query, config).await;
+        };
+
+        // Checked before the filter's matches are built
+        if !self.validate_query(query).await? {
+            return Ok(Vec::new());
+        }
+
+        // Match against the cached posting set if enabled
+        let matching = match generation {
+            Some(generation) => {
+                let mut cache = self.filter_cache.write().await;
+                cache
+                    .is_enabled()
+                    .then(|| cache.get_or_build(filter, metadata_map, generation))
+            }
+            None => None,
+        };
+        let passes = |result: &SearchResult| {
+            let vector_id_str = result.vector_id.to_string();
+            match &matching {
+                Some(matching) => matching.contains(&vector_id_str),
+                None => metadata_map
+                    .get(&vector_id_str)
+                    .is_some_and(|metadata| filter.matches(metadata)),
+            }
+        };
+
+        // Results needed to cover the skipped ones and the page
+        let k = config.offset.saturating_add(config.k);
+        let max_multiplier = self.config.max_filter_oversample.max(1);
+        let mut multiplier = self.config.filter_oversample.clamp(1, max_multiplier);
+        loop {
+            // Use k-oversampling: search for more results to account for filtering.
+            // HNSW returns at most ef results, so ef grows with the candidates.
+            let k_oversample = k.saturating_mul(multiplier);
+            let mut candidate_config = config.clone();
+            candidate_config.k = k_oversample;
+            candidate_config.offset = 0;
+            candidate_config.hnsw_ef = config.hnsw_ef.max(k_oversample);
+            let candidates = self.search_with_config(query, candidate_config).await?;
+            let exhausted =
+                candidates.len() < k_oversample || k_oversample >= self.total_vectors();
+
+            let mut filtered_results: Vec<SearchResult> =
+                candidates.into_iter().filter(|result| passes(result)).collect();
+            if filtered_results.len() >= k || exhausted || multiplier >= max_multiplier {
+                // Truncate to k results (already sorted by distance from search)
+                filtered_results.truncate(k);
+                filtered_results.drain(..config.offset.min(filtered_results.len()));
+                return Ok(filtered_results);
+            }
+            multiplier = (multiplier * 2).min(max_multiplier);
         }
+    }
 
-        let filter = filter.unwrap();
+    /// Drop cached filter matches; call after changing vector metadata
+    pub async fn invalidate_filter_cache(&self) {
+        self.filter_cache.write().await.invalidate();
+    }
 
-        // Use k-oversampling: search for more results to account for filtering
-        // Default multiplier of 3x (configurable in future)
-        let k_oversample = k * 3;
+    pub async fn filter_cache_stats(&self) -> FilterCacheStats {
+        self.filter_cache.read().await.stats()
+    }
 
-        // Get oversampled results
+    /// Search and keep only results whose id passes `predicate`.
+    ///
+    /// The predicate runs after the search, so it may consult arbitrary
+    /// application state (permissions, business rules). The index is queried
+    /// for `3 * k` candidates before the predicate is applied; a highly
+    /// selective predicate can therefore return fewer than `k` results even
+    /// when more matching vectors exist.
+    pub async fn search_with_predicate<F>(
+        &self,
+        query: &[f32],
+        k: usize,
+        predicate: F,
+    ) -> Result<Vec<SearchResult>, HybridError>
+    where
+        F: Fn(&VectorId) -> bool,
+    {
+        let k_oversample = k * 3;
         let candidates = self.search(query, k_oversample).await?;
 
-        // Filter results by metadata
-        let mut filtered_results = Vec::new();
-        for result in candidates {
-            let vector_id_str = result.vector_id.to_string();
-            if let Some(metadata) = metadata_map.get(&vector_id_str) {
-                if filter.matches(metadata) {
-                    filtered_results.push(result);
-                }
+        let mut results: Vec<SearchResult> = candidates
+            .into_iter()
+            .filter(|result| predicate(&result.vector_id))
+            .collect();
+        results.truncate(k);
+
+        Ok(results)
+    }
+
+    /// Search for vectors like `positive` but unlike `negatives`
+    /// (Rocchio-style relevance feedback).
+    ///
+    /// The index is queried for `3 * k` candidates, then each candidate's
+    /// distance is increased by `weight` times its similarity to the closest
+    /// negative example under the index's metric (see
+    /// `DistanceMetric::similarity`). Results are re-sorted by that adjusted
+    /// score, which is returned as `distance`.
+    /// A `weight` of zero is a plain search.
+    pub async fn search_with_negatives(
+        &self,
+        positive: &[f32],
+        negatives: &[Vec<f32>],
+        k: usize,
+        weight: f32,
+    ) -> Result<Vec<SearchResult>, HybridError> {
+        // The positive is checked against the index first, so a mismatched
+        // one is reported against the index's dimension
+        if !self.validate_query(positive).await? {
+            return Ok(Vec::new());
+        }
+        if let Some(negative) = negatives.iter().find(|n| n.len() != positive.len()) {
+            return Err(HybridError::DimensionMismatch {
+                expected: positive.len(),
+                actual: negative.len(),
+            });
+        }
+
+        let k_oversample = k * 3;
+        let mut candidates = self.search(positive, k_oversample).await?;
+
+        if !negatives.is_empty() && weight != 0.0 {
+            for candidate in &mut candidates {
+                // Each vector is compared under the metric of the index holding it
+                let Some((vector, metric)) = self.stored_vector(&candidate.vector_id).await? else {
+                    continue;
+                };
+
+                let similarity = negatives
+                    .iter()
+                    .map(|negative| metric.similarity(&vector, negative))
+                    .fold(f32::NEG_INFINITY, f32::max);
+                candidate.distance += weight * similarity;
+            }
+
+            candidates.sort_by(SearchResult::cmp_distance);
+        }
+
+        candidates.truncate(k);
+        Ok(candidates)
+    }
+
+    /// Search the index as it was at `as_of`, for audit and time-travel
+    /// queries.
+    ///
+    /// Vectors soft-deleted after `as_of` are treated as live, and vectors
+    /// whose timestamp is later than `as_of` are left out. Vacuumed vectors
+    /// are gone for good and never show up.
+    pub async fn search_as_of(
+        &self,
+        query: &[f32],
+        k: usize,
+        as_of: DateTime<Utc>,
+    ) -> Result<Vec<SearchResult>, HybridError> {
+        if !self.validate_query(query).await? || !self.is_initialized() {
+            return Ok(Vec::new());
+        }
+
+        // Oversample to make up for vectors inserted after `as_of`
+        let k_oversample = k * 3;
+        let defaults = self.default_search_config().await;
+        let mut candidates = Vec::new();
+
+        {
+            let recent = self.recent_index.read().await;
+            if let Ok(results) = recent.search_as_of(query, k_oversample, defaults.hnsw_ef, as_of) {
+                candidates.extend(results);
+            }
+        }
+        if self.ivf_trained() {
+            let historical = self.historical_index.read().await;
+            if let Ok(results) = historical.search_as_of(query, k_oversample, as_of).await {
+                candidates.extend(results);
             }
         }
 
-        // Truncate to k results (already sorted by distance from search)
-        filtered_results.truncate(k);
+        candidates.sort_by(SearchResult::cmp_distance);
 
-        Ok(filtered_results)
+        let timestamps = self.timestamps.read().await;
+        let mut seen = std::collections::HashSet::new();
+        let mut results: Vec<SearchResult> = candidates
+            .into_iter()
+            .filter(|result| {
+                timestamps
+                    .get(&result.vector_id)
+                    .is_none_or(|inserted| *inserted <= as_of)
+            })
+            .filter(|result| seen.insert(result.vector_id.clone()))
+            .collect();
+        results.truncate(k);
+
+        Ok(results)
     }
 
     pub async fn migrate_old_vectors(&self) -> Result<MigrationResult, HybridError> {
@@ -565,31 +2141,22 @@ impl HybridIndex {
 
         // Process in batches
         for batch in vector_ids.chunks(self.config.migration_batch_size) {
-            let recent = self.recent_index.write().await;
+            let mut recent = self.recent_index.write().await;
             let mut historical = self.historical_index.write().await;
-
-            for id in batch {
-                // Get vector from recent index
-                if let Some(node) = recent.get_node(id) {
-                    let vector = node.vector().clone();
-
-                    // Insert into historical
-                    if historical.insert(id.clone(), vector).is_ok() {
-                        // Remove from recent
-                        // Note: HNSW doesn't have remove, so we'd need to track deleted nodes
-                        migrated_count += 1;
-                    }
-                }
-            }
+            migrated_count += Self::move_to_historical(&mut recent, &mut historical, batch);
         }
 
         // Update counts
         if migrated_count > 0 {
             let mut recent_count = self.recent_count.write().await;
             *recent_count = recent_count.saturating_sub(migrated_count);
+            drop(recent_count);
 
             let mut historical_count = self.historical_count.write().await;
             *historical_count += migrated_count;
+            let total = *historical_count;
+            drop(historical_count);
+            self.maybe_schedule_retrain(migrated_count, total);
         }
 
         Ok(MigrationResult {
@@ -597,7 +2164,45 @@ impl HybridIndex {
         })
     }
 
+    /// Move `ids` from the recent index into the historical one, carrying
+    /// deletion marks over, and return how many moved. Ids missing from the
+    /// recent index, or that the historical index rejects, stay where they are.
+    fn move_to_historical(recent: &mut HNSWIndex, historical: &mut IVFIndex, ids: &[VectorId]) -> usize {
+        let mut moved = Vec::with_capacity(ids.len());
+        for id in ids {
+            let Some(node) = recent.get_node(id) else {
+                continue;
+            };
+            if historical.insert(id.clone(), node.vector().clone()).is_err() {
+                continue;
+            }
+            // Keep the original deletion time, or `search_as_of` would treat
+            // the vector as deleted only from the migration on
+            if node.is_deleted() {
+                let deleted_at = node.deleted_at().unwrap_or_else(Utc::now);
+                let _ = historical.mark_deleted_at(id, deleted_at);
+            }
+            moved.push(id.clone());
+        }
+        // One pass over the graph for the whole batch
+        recent.remove_nodes(&moved);
+        moved.len()
+    }
+
     pub async fn migrate_with_threshold(&self, threshold: Duration) -> Result<usize, HybridError> {
+        self.migrate_with_progress(threshold, |_, _| {}).await
+    }
+
+    /// `migrate_with_threshold`, calling `progress(migrated_so_far,
+    /// total_to_migrate)` after each batch of `migration_batch_size` vectors
+    pub async fn migrate_with_progress<F>(
+        &self,
+        threshold: Duration,
+        mut progress: F,
+    ) -> Result<usize, HybridError>
+    where
+        F: FnMut(usize, usize) + Send,
+    {
         let now = Utc::now();
         let mut migrated_count = 0;
 
@@ -616,33 +2221,35 @@ impl HybridIndex {
         }
         drop(timestamps);
 
+        // Vectors inserted straight into the historical index have nothing to move
+        {
+            let recent = self.recent_index.read().await;
+            vectors_to_migrate.retain(|id| recent.get_node(id).is_some());
+        }
+        let total = vectors_to_migrate.len();
+
         // Migrate in batches
         for batch in vectors_to_migrate.chunks(self.config.migration_batch_size) {
-            let recent = self.recent_index.write().await;
+            let mut recent = self.recent_index.write().await;
             let mut historical = self.historical_index.write().await;
+            migrated_count += Self::move_to_historical(&mut recent, &mut historical, batch);
+            drop(historical);
+            drop(recent);
 
-            for id in batch {
-                // Get vector from recent index
-                if let Some(node) = recent.get_node(id) {
-                    let vector = node.vector().clone();
-
-                    // Insert into historical
-                    if historical.insert(id.clone(), vector).is_ok() {
-                        // Remove from recent
-                        // Note: HNSW doesn't have remove, so we'd need to track deleted nodes
-                        migrated_count += 1;
-                    }
-                }
-            }
+            progress(migrated_count, total);
         }
 
         // Update counts
         if migrated_count > 0 {
             let mut recent_count = self.recent_count.write().await;
             *recent_count = recent_count.saturating_sub(migrated_count);
+            drop(recent_count);
 
             let mut historical_count = self.historical_count.write().await;
             *historical_count += migrated_count;
+            let total = *historical_count;
+            drop(historical_count);
+            self.maybe_schedule_retrain(migrated_count, total);
         }
 
         Ok(migrated_count)
@@ -678,17 +2285,73 @@ impl HybridIndex {
         false
     }
 
+    /// Start a background task that migrates vectors older than
+    /// `recent_threshold` every `migration_interval`, beginning right away.
+    /// It runs until `stop_auto_migration` is called or every handle to the
+    /// index is dropped; starting it twice is an error.
     pub async fn start_auto_migration(&self) -> Result<(), HybridError> {
-        // For now, just trigger a migration immediately
-        // In a real implementation, this would start a background task
-        self.migrate_old_vectors().await?;
+        let period = self.config.migration_interval;
+        if period.is_zero() {
+            return Err(HybridError::InvalidConfig(
+                "migration_interval must be greater than zero".to_string(),
+            ));
+        }
+        let mut handle = self.auto_migration.handle.lock().await;
+        if handle.as_ref().is_some_and(|task| !task.is_finished()) {
+            return Err(HybridError::InvalidConfig(
+                "Auto migration is already running".to_string(),
+            ));
+        }
+        self.auto_migration.stop.store(false, Ordering::SeqCst);
+
+        let state = self.auto_migration.clone();
+        let index = WeakHybridIndex::new(self);
+        *handle = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = state.wake.notified() => {}
+                }
+                if state.stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some(index) = index.upgrade() else {
+                    break;
+                };
+                if !index.is_initialized() {
+                    continue;
+                }
+                if let Err(e) = index.migrate_old_vectors().await {
+                    tracing::warn!(error = %e, "background migration failed");
+                }
+            }
+        }));
         Ok(())
     }
 
+    /// Stop the task started by `start_auto_migration`, waiting for a
+    /// migration in progress to finish. Does nothing if it isn't running.
     pub async fn stop_auto_migration(&self) -> Result<(), HybridError> {
-        // Stop auto migration background task
-        // In a real implementation, this would cancel the background task
-        Ok(())
+        let Some(handle) = self.auto_migration.handle.lock().await.take() else {
+            return Ok(());
+        };
+        self.auto_migration.stop.store(true, Ordering::SeqCst);
+        self.auto_migration.wake.notify_one();
+        handle.await.map_err(|e| {
+            HybridError::InvalidConfig(format!("Auto migration task failed: {}", e))
+        })
+    }
+
+    /// Whether the task started by `start_auto_migration` is running
+    pub async fn is_auto_migrating(&self) -> bool {
+        self.auto_migration
+            .handle
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
     }
 
     pub async fn get_statistics(&self) -> HybridStats {
@@ -722,7 +2385,7 @@ impl HybridIndex {
             avg_vector_age_ms: avg_age_ms,
             recent_index_memory: recent_memory,
             historical_index_memory: historical_memory,
-            avg_query_time_ms: 0.0,
+            avg_query_time_ms: self.search_counters.avg_query_time_ms(),
         }
     }
 
@@ -751,7 +2414,7 @@ impl HybridIndex {
             avg_vector_age_ms: 0.0, // TODO: Calculate from timestamps
             recent_index_memory: recent_memory,
             historical_index_memory: historical_memory,
-            avg_query_time_ms: 0.0, // TODO: Track query times
+            avg_query_time_ms: self.search_counters.avg_query_time_ms(),
         }
     }
 
@@ -834,8 +2497,155 @@ impl HybridIndex {
         self.historical_count.try_read().map(|c| *c).unwrap_or(0)
     }
 
+    /// How many `search_with_config` calls searched each sub-index
+    pub fn sub_index_searches(&self) -> SubIndexSearches {
+        SubIndexSearches {
+            recent: self.search_counters.recent.load(Ordering::Relaxed),
+            historical: self.search_counters.historical.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Write-lock wait times on the insert and delete paths, or `None`
+    /// unless built with the `lock-metrics` feature
+    pub fn lock_contention(&self) -> Option<LockContentionStats> {
+        self.lock_metrics.snapshot()
+    }
+
     pub fn ivf_trained(&self) -> bool {
-        self.ivf_trained
+        self.ivf_trained.load(Ordering::SeqCst)
+    }
+
+    /// Retrain the historical index with `new_config` while it keeps serving
+    /// searches.
+    ///
+    /// The new centroids and inverted lists are built from a snapshot into a
+    /// separate index; the historical index is only write-locked to catch up
+    /// on changes made meanwhile and swap the new index in. `config()` keeps
+    /// reporting the IVF settings the index was created with.
+    pub async fn retrain_historical(
+        &self,
+        new_config: IVFConfig,
+    ) -> Result<RetrainResult, HybridError> {
+        if !self.ivf_trained() {
+            return Err(HybridError::IVF("IVF index not trained".to_string()));
+        }
+
+        let (old_clusters, vectors, chunk_loader) = {
+            let historical = self.historical_index.read().await;
+            let vectors = historical
+                .stored_vectors()
+                .await
+                .map_err(|e| HybridError::IVF(e.to_string()))?;
+            (
+                historical.config().n_clusters,
+                vectors,
+                historical.chunk_loader.clone(),
+            )
+        };
+
+        let feed_cold_queries = self
+            .config
+            .cold_queries
+            .as_ref()
+            .is_some_and(|policy| policy.feed_retraining);
+        let cold_queries = if feed_cold_queries {
+            self.cold_queries().await
+        } else {
+            Vec::new()
+        };
+        let samples_used = cold_queries.len();
+
+        let (mut retrained, train_result) = tokio::task::spawn_blocking(move || {
+            IVFIndex::build_from_vectors_with_samples(new_config, chunk_loader, vectors, &cold_queries)
+        })
+        .await
+        .map_err(|e| HybridError::IVF(e.to_string()))?
+        .map_err(|e| HybridError::IVF(e.to_string()))?;
+
+        let mut historical = self.historical_index.write().await;
+        retrained
+            .sync_with(&historical)
+            .await
+            .map_err(|e| HybridError::IVF(e.to_string()))?;
+        let vectors_moved = retrained.vectors_moved_from(&historical);
+        *historical = retrained;
+        self.retrain_state.trained(historical.total_vectors());
+
+        // The clusters have adapted to these; keep only queries seen since
+        if samples_used > 0 {
+            let mut samples = self.cold_query_samples.write().await;
+            let consumed = samples_used.min(samples.len());
+            samples.drain(..consumed);
+        }
+
+        Ok(RetrainResult {
+            old_clusters,
+            new_clusters: historical.config().n_clusters,
+            vectors_reassigned: historical.total_vectors(),
+            converged: train_result.converged,
+            iterations: train_result.iterations,
+            vectors_moved,
+        })
+    }
+
+    /// Split and merge historical clusters until their size variance is at
+    /// most `target_variance`, see `IVFIndex::rebalance_clusters`
+    pub async fn rebalance_historical(
+        &self,
+        target_variance: f32,
+        max_iterations: usize,
+        converge_threshold: f32,
+    ) -> Result<ClusterRebalanceResult, HybridError> {
+        if !self.ivf_trained() {
+            return Err(HybridError::IVF("IVF index not trained".to_string()));
+        }
+
+        self.historical_index
+            .write()
+            .await
+            .rebalance_clusters(target_variance, max_iterations, converge_threshold)
+            .await
+            .map_err(|e| HybridError::IVF(e.to_string()))
+    }
+
+    /// Number of background retrains triggered by `auto_retrain` that have
+    /// completed successfully
+    pub fn auto_retrain_count(&self) -> usize {
+        self.retrain_state.completed.load(Ordering::SeqCst)
+    }
+
+    /// Count `added` vectors newly stored in the historical index, by insert
+    /// or migration, and start a background retrain with the current IVF
+    /// settings once an `auto_retrain` trigger is reached
+    fn maybe_schedule_retrain(&self, added: usize, historical_total: usize) {
+        let Some(policy) = &self.config.auto_retrain else {
+            return;
+        };
+        let state = &self.retrain_state;
+        let inserts = state.inserts_since_training.fetch_add(added, Ordering::SeqCst) + added;
+        let trained_on = state.trained_on.load(Ordering::SeqCst);
+
+        let due = policy.inserts_since_training.is_some_and(|n| inserts >= n)
+            || policy.growth_factor.is_some_and(|factor| {
+                trained_on > 0 && historical_total as f32 >= trained_on as f32 * factor
+            });
+        if !due
+            || state
+                .running
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+        {
+            return;
+        }
+
+        let index = self.clone();
+        tokio::spawn(async move {
+            let config = index.historical_index.read().await.config().clone();
+            if index.retrain_historical(config).await.is_ok() {
+                index.retrain_state.completed.fetch_add(1, Ordering::SeqCst);
+            }
+            index.retrain_state.running.store(false, Ordering::SeqCst);
+        });
     }
 
     /// Get timestamps (for persistence)
@@ -863,16 +2673,32 @@ impl HybridIndex {
         historical_count: usize,
         ivf_trained: bool,
     ) -> Result<Self, HybridError> {
+        let filter_cache = RwLock::new(FilterCache::new(config.filter_cache_capacity));
         Ok(Self {
-            config,
-            recent_index: Arc::new(RwLock::new(recent_index)),
-            historical_index: Arc::new(RwLock::new(historical_index)),
-            timestamps: Arc::new(RwLock::new(timestamps)),
-            initialized: true,
-            ivf_trained,
-            recent_count: Arc::new(RwLock::new(recent_count)),
-            historical_count: Arc::new(RwLock::new(historical_count)),
-            chunk_loader: None,
+            inner: Arc::new(HybridInner {
+                config,
+                recent_index: RwLock::new(recent_index),
+                historical_index: RwLock::new(historical_index),
+                timestamps: RwLock::new(timestamps),
+                initialized: AtomicBool::new(true),
+                ivf_trained: AtomicBool::new(ivf_trained),
+                recent_count: RwLock::new(recent_count),
+                historical_count: RwLock::new(historical_count),
+                chunk_loader: None,
+                retrain_state: RetrainState {
+                    trained_on: AtomicUsize::new(historical_count),
+                    ..Default::default()
+                },
+                payloads: RwLock::new(HashMap::new()),
+                filter_cache,
+                search_counters: SearchCounters::default(),
+                cold_query_samples: RwLock::new(VecDeque::new()),
+                access_stats: RwLock::new(HashMap::new()),
+                search_defaults: RwLock::new(SearchDefaults::default()),
+                training_dimension: RwLock::new(None),
+                lock_metrics: LockMetrics::default(),
+                auto_migration: Arc::new(AutoMigrationState::default()),
+            }),
         })
     }
 
@@ -887,80 +2713,135 @@ impl HybridIndex {
         ivf_trained: bool,
         chunk_loader: Option<Arc<ChunkLoader>>,
     ) -> Result<Self, HybridError> {
+        let filter_cache = RwLock::new(FilterCache::new(config.filter_cache_capacity));
         Ok(Self {
-            config,
-            recent_index: Arc::new(RwLock::new(recent_index)),
-            historical_index: Arc::new(RwLock::new(historical_index)),
-            timestamps: Arc::new(RwLock::new(timestamps)),
-            initialized: true,
-            ivf_trained,
-            recent_count: Arc::new(RwLock::new(recent_count)),
-            historical_count: Arc::new(RwLock::new(historical_count)),
-            chunk_loader,
+            inner: Arc::new(HybridInner {
+                config,
+                recent_index: RwLock::new(recent_index),
+                historical_index: RwLock::new(historical_index),
+                timestamps: RwLock::new(timestamps),
+                initialized: AtomicBool::new(true),
+                ivf_trained: AtomicBool::new(ivf_trained),
+                recent_count: RwLock::new(recent_count),
+                historical_count: RwLock::new(historical_count),
+                chunk_loader,
+                retrain_state: RetrainState {
+                    trained_on: AtomicUsize::new(historical_count),
+                    ..Default::default()
+                },
+                payloads: RwLock::new(HashMap::new()),
+                filter_cache,
+                search_counters: SearchCounters::default(),
+                cold_query_samples: RwLock::new(VecDeque::new()),
+                access_stats: RwLock::new(HashMap::new()),
+                search_defaults: RwLock::new(SearchDefaults::default()),
+                training_dimension: RwLock::new(None),
+                lock_metrics: LockMetrics::default(),
+                auto_migration: Arc::new(AutoMigrationState::default()),
+            }),
         })
     }
 
     /// Delete a vector from the index (soft deletion)
     pub async fn delete(&self, id: VectorId) -> Result<(), HybridError> {
-        // Check if vector exists by looking up timestamp
-        let timestamps = self.timestamps.read().await;
-        let timestamp = timestamps
-            .get(&id)
-            .ok_or_else(|| HybridError::IVF(format!("Vector {:?} not found", id)))?;
-
-        // Determine which index the vector is in based on its age
-        let now = Utc::now();
-        let age = now
-            .signed_duration_since(*timestamp)
-            .to_std()
-            .unwrap_or(Duration::from_secs(0));
+        self.delete_at(id, Utc::now()).await
+    }
 
-        drop(timestamps);
+    /// `delete` with an explicit deletion time, e.g. when restoring a saved
+    /// deletion. A vector already deleted keeps its original time.
+    pub(crate) async fn delete_at(
+        &self,
+        id: VectorId,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), HybridError> {
+        // Check if vector exists by looking up timestamp
+        if !self.timestamps.read().await.contains_key(&id) {
+            return Err(HybridError::IVF(format!("Vector {:?} not found", id)));
+        }
 
-        // Delete from appropriate index
-        if age < self.config.recent_threshold {
-            // Delete from HNSW (recent)
-            let mut recent = self.recent_index.write().await;
-            recent
-                .mark_deleted(&id)
-                .map_err(|e| HybridError::HNSW(e.to_string()))?;
-        } else {
-            // Delete from IVF (historical)
-            let mut historical = self.historical_index.write().await;
-            historical
-                .mark_deleted(&id)
-                .map_err(|e| HybridError::IVF(e.to_string()))?;
+        // Mark the vector in whichever index actually holds it. Its age is
+        // not a reliable guide: targeted migrations, `insert_into` and
+        // HNSW-only mode all place vectors independently of the threshold.
+        let in_recent = self
+            .lock_metrics
+            .write(LockName::RecentIndex, &self.recent_index)
+            .await
+            .mark_deleted_at(&id, deleted_at)
+            .is_ok();
+        let in_historical = self
+            .lock_metrics
+            .write(LockName::HistoricalIndex, &self.historical_index)
+            .await
+            .mark_deleted_at(&id, deleted_at)
+            .is_ok();
+
+        if !in_recent && !in_historical {
+            return Err(HybridError::IVF(format!(
+                "Vector {:?} not found in either index",
+                id
+            )));
         }
 
+        self.payloads.write().await.remove(&id);
+        self.access_stats.write().await.remove(&id);
+        self.invalidate_filter_cache().await;
+
         Ok(())
     }
 
     /// Check if a vector is marked as deleted
     pub async fn is_deleted(&self, id: &VectorId) -> bool {
-        // Check if vector exists in timestamps
-        let timestamps = self.timestamps.read().await;
-        if let Some(timestamp) = timestamps.get(id) {
-            // Determine which index to check
-            let now = Utc::now();
-            let age = now
-                .signed_duration_since(*timestamp)
-                .to_std()
-                .unwrap_or(Duration::from_secs(0));
+        // Vector doesn't exist, so it's not deleted
+        if !self.timestamps.read().await.contains_key(id) {
+            return false;
+        }
 
-            drop(timestamps);
+        // Check both indices, since placement doesn't follow from age
+        self.recent_index.read().await.is_deleted(id)
+            || self.historical_index.read().await.is_deleted(id)
+    }
 
-            if age < self.config.recent_threshold {
-                // Check HNSW
-                let recent = self.recent_index.read().await;
-                recent.is_deleted(id)
-            } else {
-                // Check IVF
-                let historical = self.historical_index.read().await;
-                historical.is_deleted(id)
+    /// Stored vector for `id`, or `None` if it was never inserted or is
+    /// deleted.
+    ///
+    /// The timestamp decides which index is tried first; the other one is
+    /// still checked, since explicit targets and migration mean placement
+    /// doesn't always follow age. Historical vectors stored in chunks are
+    /// loaded on demand.
+    pub async fn get_vector(&self, id: &VectorId) -> Option<Vec<f32>> {
+        let timestamp = *self.timestamps.read().await.get(id)?;
+        if self.is_deleted(id).await {
+            return None;
+        }
+
+        let age = Utc::now()
+            .signed_duration_since(timestamp)
+            .to_std()
+            .unwrap_or(Duration::from_secs(0));
+        if age < self.config.recent_threshold {
+            match self.recent_index.read().await.get_vector_by_id(id) {
+                Some(vector) => Some(vector),
+                None => self.load_historical_vector(id).await,
             }
         } else {
-            // Vector doesn't exist, so it's not deleted
-            false
+            match self.load_historical_vector(id).await {
+                Some(vector) => Some(vector),
+                None => self.recent_index.read().await.get_vector_by_id(id),
+            }
+        }
+    }
+
+    async fn load_historical_vector(&self, id: &VectorId) -> Option<Vec<f32>> {
+        match self.historical_index.read().await.load_vector(id).await {
+            Ok(vector) => vector,
+            Err(e) => {
+                tracing::warn!(
+                    vector_id = %id.to_string(),
+                    error = %e,
+                    "failed to load historical vector"
+                );
+                None
+            }
         }
     }
 
@@ -1003,6 +2884,15 @@ impl HybridIndex {
 
         let total_removed = hnsw_removed + ivf_removed;
 
+        {
+            let mut recent_count = self.recent_count.write().await;
+            *recent_count = recent_count.saturating_sub(hnsw_removed);
+        }
+        {
+            let mut historical_count = self.historical_count.write().await;
+            *historical_count = historical_count.saturating_sub(ivf_removed);
+        }
+
         Ok(VacuumStats {
             hnsw_removed,
             ivf_removed,
@@ -1010,6 +2900,48 @@ impl HybridIndex {
         })
     }
 
+    /// Reclaim the space held by deleted vectors: rewrite lazily loaded
+    /// chunks that are mostly deleted, vacuum both indices, then shrink the
+    /// IVF inverted lists.
+    ///
+    /// Rewritten chunks go under new keys; the chunks a persisted manifest
+    /// still names are left for the next save to delete.
+    pub async fn compact(&self) -> Result<CompactionStats, HybridError> {
+        let mut stats = CompactionStats::default();
+
+        let memory_before = self.recent_index.read().await.estimate_memory_usage().total_bytes
+            + self.historical_index.read().await.estimate_memory_usage().total_bytes;
+
+        {
+            let mut historical = self.historical_index.write().await;
+            if historical.chunk_loader.is_some() {
+                let chunks = historical
+                    .compact_chunks(&crate::ivf::operations::ChunkCompactionConfig::default())
+                    .await
+                    .map_err(|e| HybridError::IVF(e.to_string()))?;
+                stats.vectors_removed += chunks.removed_ids.len();
+                stats.chunks_rewritten = chunks.chunks_compacted.len();
+                stats.chunks_removed = chunks.chunks_removed.len();
+                stats.bytes_reclaimed += chunks.bytes_reclaimed;
+            }
+        }
+
+        stats.vectors_removed += self.vacuum().await?.total_removed;
+
+        {
+            let mut historical = self.historical_index.write().await;
+            historical
+                .compact_clusters()
+                .map_err(|e| HybridError::IVF(e.to_string()))?;
+        }
+
+        let memory_after = self.recent_index.read().await.estimate_memory_usage().total_bytes
+            + self.historical_index.read().await.estimate_memory_usage().total_bytes;
+        stats.bytes_reclaimed += memory_before.saturating_sub(memory_after);
+
+        Ok(stats)
+    }
+
     /// Get count of active (non-deleted) vectors
     pub async fn active_count(&self) -> usize {
         // Get active count from both indices
@@ -1042,9 +2974,70 @@ impl HybridIndex {
         (hnsw_deleted, ivf_deleted, total_deleted)
     }
 
+    /// Check stored vectors against the trained dimension.
+    ///
+    /// After a model migration, old clusters and new vectors can disagree on
+    /// dimension. Vectors are compared with the IVF training dimension, or with
+    /// the most common stored dimension when IVF isn't trained.
+    pub async fn dimension_conflict_report(&self) -> DimensionConflictReport {
+        let mut dimensions: Vec<(VectorId, usize)> = Vec::new();
+
+        let recent = self.recent_index.read().await;
+        for node in recent.get_all_nodes() {
+            dimensions.push((node.id().clone(), node.vector().len()));
+        }
+        drop(recent);
+
+        let historical = self.historical_index.read().await;
+        let trained_dimension = if self.ivf_trained() {
+            historical.dimension()
+        } else {
+            None
+        };
+        for list in historical.get_all_inverted_lists().values() {
+            for (id, vector) in &list.vectors {
+                dimensions.push((id.clone(), vector.len()));
+            }
+        }
+        drop(historical);
+
+        let mut dimension_counts = std::collections::BTreeMap::new();
+        for (_, dim) in &dimensions {
+            *dimension_counts.entry(*dim).or_insert(0) += 1;
+        }
+
+        let expected = trained_dimension.or_else(|| {
+            dimension_counts
+                .iter()
+                .max_by_key(|(_, count)| **count)
+                .map(|(dim, _)| *dim)
+        });
+
+        let mut conflicting_vectors = 0;
+        let mut sample_conflicts = Vec::new();
+        if let Some(expected) = expected {
+            dimensions.sort_by(|a, b| a.0.cmp(&b.0));
+            for (id, dim) in dimensions {
+                if dim != expected {
+                    conflicting_vectors += 1;
+                    if sample_conflicts.len() < DimensionConflictReport::MAX_SAMPLES {
+                        sample_conflicts.push((id, dim));
+                    }
+                }
+            }
+        }
+
+        DimensionConflictReport {
+            trained_dimension,
+            dimension_counts,
+            conflicting_vectors,
+            sample_conflicts,
+        }
+    }
+
     /// Get all deleted vector IDs from both indices
     /// Used for persistence - save deleted vectors in manifest
-    pub async fn get_deleted_vectors(&self) -> Vec<String> {
+    pub async fn get_deleted_vectors(&self) -> Vec<DeletedVector> {
         let mut deleted_ids = Vec::new();
 
         // Get deleted vectors from HNSW index
@@ -1052,7 +3045,7 @@ impl HybridIndex {
         let hnsw_nodes = recent.get_all_nodes();
         for node in hnsw_nodes {
             if node.is_deleted() {
-                deleted_ids.push(node.id().to_string());
+                deleted_ids.push(DeletedVector::new(node.id(), node.deleted_at()));
             }
         }
         drop(recent);
@@ -1060,10 +3053,26 @@ impl HybridIndex {
         // Get deleted vectors from IVF index
         let historical = self.historical_index.read().await;
         for deleted_id in historical.get_deleted_ids() {
-            deleted_ids.push(deleted_id.to_string());
+            deleted_ids.push(DeletedVector::new(
+                deleted_id,
+                historical.deleted_at(deleted_id),
+            ));
         }
         drop(historical);
 
         deleted_ids
     }
 }
+
+/// Turn IVF dimension errors into an actionable hybrid error
+fn map_ivf_insert_error(err: crate::ivf::core::IVFError) -> HybridError {
+    match err {
+        crate::ivf::core::IVFError::DimensionMismatch { expected, actual } => {
+            HybridError::TrainedDimensionMismatch {
+                trained: expected,
+                actual,
+            }
+        }
+        other => HybridError::IVF(other.to_string()),
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Wait times for the write locks on `HybridIndex`'s hot paths.
+//!
+//! Recording only happens with the `lock-metrics` feature; without it the
+//! wrappers compile down to a plain `write().await` and
+//! `HybridIndex::lock_contention` returns `None`.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{RwLock, RwLockWriteGuard};
+
+#[cfg(feature = "lock-metrics")]
+use std::collections::VecDeque;
+#[cfg(feature = "lock-metrics")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "lock-metrics")]
+use std::sync::Mutex;
+#[cfg(feature = "lock-metrics")]
+use std::time::{Duration, Instant};
+
+/// Most recent wait times kept per lock for the percentiles
+#[cfg(feature = "lock-metrics")]
+const WAIT_SAMPLES: usize = 4096;
+
+/// The instrumented locks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LockName {
+    RecentIndex,
+    HistoricalIndex,
+    Timestamps,
+}
+
+/// Wait times for acquiring one lock, in microseconds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LockWaitStats {
+    pub acquisitions: u64,
+    pub total_wait_us: u64,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+}
+
+/// Write-lock wait times on the hot paths, see `HybridIndex::lock_contention`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LockContentionStats {
+    pub recent_index: LockWaitStats,
+    pub historical_index: LockWaitStats,
+    pub timestamps: LockWaitStats,
+}
+
+#[cfg(feature = "lock-metrics")]
+#[derive(Debug, Default)]
+struct WaitRecorder {
+    acquisitions: AtomicU64,
+    total_wait_us: AtomicU64,
+    max_us: AtomicU64,
+    samples: Mutex<VecDeque<u64>>,
+}
+
+#[cfg(feature = "lock-metrics")]
+impl WaitRecorder {
+    fn record(&self, wait: Duration) {
+        let us = wait.as_micros().min(u64::MAX as u128) as u64;
+        self.acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_us.fetch_add(us, Ordering::Relaxed);
+        self.max_us.fetch_max(us, Ordering::Relaxed);
+
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == WAIT_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(us);
+    }
+
+    fn snapshot(&self) -> LockWaitStats {
+        let mut samples: Vec<u64> = self.samples.lock().unwrap().iter().copied().collect();
+        samples.sort_unstable();
+        let percentile = |p: f64| -> u64 {
+            if samples.is_empty() {
+                return 0;
+            }
+            let rank = ((samples.len() - 1) as f64 * p).round() as usize;
+            samples[rank]
+        };
+
+        LockWaitStats {
+            acquisitions: self.acquisitions.load(Ordering::Relaxed),
+            total_wait_us: self.total_wait_us.load(Ordering::Relaxed),
+            p50_us: percentile(0.50),
+            p95_us: percentile(0.95),
+            p99_us: percentile(0.99),
+            max_us: self.max_us.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct LockMetrics {
+    #[cfg(feature = "lock-metrics")]
+    recent_index: WaitRecorder,
+    #[cfg(feature = "lock-metrics")]
+    historical_index: WaitRecorder,
+    #[cfg(feature = "lock-metrics")]
+    timestamps: WaitRecorder,
+}
+
+impl LockMetrics {
+    /// Acquire `rw` for writing, recording how long that took under `name`
+    pub(crate) async fn write<'a, T>(
+        &self,
+        name: LockName,
+        rw: &'a RwLock<T>,
+    ) -> RwLockWriteGuard<'a, T> {
+        #[cfg(feature = "lock-metrics")]
+        {
+            let start = Instant::now();
+            let guard = rw.write().await;
+            self.recorder(name).record(start.elapsed());
+            guard
+        }
+        #[cfg(not(feature = "lock-metrics"))]
+        {
+            let _ = name;
+            rw.write().await
+        }
+    }
+
+    #[cfg(feature = "lock-metrics")]
+    fn recorder(&self, name: LockName) -> &WaitRecorder {
+        match name {
+            LockName::RecentIndex => &self.recent_index,
+            LockName::HistoricalIndex => &self.historical_index,
+            LockName::Timestamps => &self.timestamps,
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Option<LockContentionStats> {
+        #[cfg(feature = "lock-metrics")]
+        {
+            Some(LockContentionStats {
+                recent_index: self.recent_index.snapshot(),
+                historical_index: self.historical_index.snapshot(),
+                timestamps: self.timestamps.snapshot(),
+            })
+        }
+        #[cfg(not(feature = "lock-metrics"))]
+        {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, feature = "lock-metrics"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_from_samples() {
+        let recorder = WaitRecorder::default();
+        for us in 1..=100 {
+            recorder.record(Duration::from_micros(us));
+        }
+
+        let stats = recorder.snapshot();
+        assert_eq!(stats.acquisitions, 100);
+        assert_eq!(stats.total_wait_us, 5050);
+        assert_eq!(stats.p50_us, 51);
+        assert_eq!(stats.p95_us, 95);
+        assert_eq!(stats.p99_us, 99);
+        assert_eq!(stats.max_us, 100);
+    }
+
+    #[test]
+    fn test_samples_are_bounded() {
+        let recorder = WaitRecorder::default();
+        for _ in 0..WAIT_SAMPLES + 10 {
+            recorder.record(Duration::from_micros(1));
+        }
+
+        assert_eq!(recorder.samples.lock().unwrap().len(), WAIT_SAMPLES);
+        assert_eq!(recorder.snapshot().acquisitions, (WAIT_SAMPLES + 10) as u64);
+    }
+}
@@ -1,13 +1,23 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+pub mod archive;
 pub mod core;
+pub mod lock_metrics;
 pub mod maintenance;
 pub mod persistence;
 pub mod search_integration;
 
+pub use archive::ArchiveOptions;
 pub use core::{
-    AgeDistribution, HybridConfig, HybridError, HybridIndex, HybridSearchConfig, HybridStats,
-    MigrationResult, SearchConfig, TimestampedVector,
+    AccessStats, AdaptiveEfConfig, AgeDistribution, AutoRetrainConfig, ColdQueryConfig,
+    DimensionAdapter, DimensionConflictReport, EmptyQueryPolicy, HybridConfig, HybridError,
+    HybridIndex, HybridSearchConfig, HybridStats, IndexTarget, InsertOutcome, MigrationResult,
+    NanDistancePolicy, OnDuplicate, PayloadSearchResult, SearchConfig, SearchDefaults,
+    SubIndexSearches, TimestampedVector,
+};
+pub use lock_metrics::{LockContentionStats, LockWaitStats};
+pub use persistence::{
+    HybridMetadata, HybridPersister, ManifestDiff, PersistenceError, SerializableTimestamps,
+    TimestampChunk,
 };
-pub use persistence::{HybridMetadata, HybridPersister, PersistenceError, SerializableTimestamps};
@@ -1,19 +1,31 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
-use crate::core::chunk::{ChunkMetadata, HNSWManifest, IVFManifest, Manifest, VectorChunk};
+use crate::core::chunk::{
+    chunk_checksum, chunk_file_name, verify_chunk_checksum, ChunkMetadata, DeletedVector, HNSWManifest, IVFManifest,
+    Manifest, TimestampChunkMetadata, VectorChunk,
+};
 use crate::core::storage::S5Storage;
 use crate::core::types::VectorId;
+use crate::hybrid::archive::{ArchiveOptions, ArchiveSink, TarReader, ARCHIVE_ROOT, ZSTD_MAGIC};
 use crate::hybrid::core::{HybridConfig, HybridIndex};
 use crate::hnsw::persistence::{HNSWPersister, PersistenceError as HNSWPersistenceError};
 use crate::ivf::persistence::{IVFPersister, PersistenceError as IVFPersistenceError};
+use crate::ivf::pq::ProductQuantizer;
+use async_compression::tokio::bufread::ZstdDecoder;
+use async_compression::tokio::write::ZstdEncoder;
+use async_compression::Level;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 
 const CURRENT_VERSION: u32 = 1;
 
+/// Vectors per chunk used by `save_index_chunked` unless overridden
+pub const DEFAULT_CHUNK_SIZE: usize = 10000;
+
 #[derive(Debug, Error)]
 pub enum PersistenceError {
     #[error("Storage error: {0}")]
@@ -64,6 +76,13 @@ pub struct HybridMetadata {
     pub timestamp: DateTime<Utc>,
     #[serde(default)] // For backward compatibility with older serialized data
     pub ivf_trained: bool,
+    /// Soft-deleted vectors and their deletion times, from both indices
+    #[serde(default)]
+    pub deleted_vectors: Vec<DeletedVector>,
+    /// Codebooks of the historical index's product quantizer, recorded by
+    /// chunked saves of indexes storing PQ codes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pq: Option<ProductQuantizer>,
 }
 
 impl HybridMetadata {
@@ -78,6 +97,8 @@ impl HybridMetadata {
             total_vectors: stats.total_vectors,
             timestamp: Utc::now(),
             ivf_trained: index.ivf_trained(),
+            deleted_vectors: Vec::new(),
+            pq: None,
         }
     }
 
@@ -124,24 +145,149 @@ impl SerializableTimestamps {
     }
 }
 
+/// Timestamps of the vectors in one vector chunk
+///
+/// Entries are kept sorted so identical contents always serialize to
+/// identical bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimestampChunk {
+    pub chunk_id: String,
+    pub timestamps: BTreeMap<VectorId, DateTime<Utc>>,
+}
+
+impl TimestampChunk {
+    pub fn to_cbor(&self) -> Result<Vec<u8>, PersistenceError> {
+        serde_cbor::to_vec(self).map_err(|e| PersistenceError::Serialization(e.to_string()))
+    }
+
+    pub fn from_cbor(data: &[u8]) -> Result<Self, PersistenceError> {
+        serde_cbor::from_slice(data)
+            .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+    }
+}
+
+/// Chunks that differ between two manifests, by chunk id
+///
+/// A replica holding the old snapshot fetches `added_chunks` and
+/// `changed_chunks`, drops `removed_chunks`, and refreshes
+/// `changed_timestamp_chunks`, then applies the new manifest.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestDiff {
+    pub added_chunks: Vec<String>,
+    pub removed_chunks: Vec<String>,
+    pub changed_chunks: Vec<String>,
+    /// Timestamp chunks that are new or whose checksum changed
+    pub changed_timestamp_chunks: Vec<String>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_chunks.is_empty()
+            && self.removed_chunks.is_empty()
+            && self.changed_chunks.is_empty()
+            && self.changed_timestamp_chunks.is_empty()
+    }
+}
+
+/// What a vector chunk stores for one vector
+enum ChunkEntry {
+    Vector(Vec<f32>),
+    /// Product-quantized codes of a historical vector
+    Codes(Vec<u8>),
+}
+
+/// Whether a chunk present in both manifests may have different contents.
+///
+/// Uses content hashes when both sides have one, then S5 CIDs; chunks
+/// written before hashes were recorded are assumed changed.
+fn chunk_changed(old: &ChunkMetadata, new: &ChunkMetadata) -> bool {
+    match (&old.content_hash, &new.content_hash) {
+        (Some(old_hash), Some(new_hash)) => old_hash != new_hash,
+        _ => match (&old.cid, &new.cid) {
+            (Some(old_cid), Some(new_cid)) => old_cid != new_cid,
+            _ => true,
+        },
+    }
+}
+
 /// Persister for HybridIndex using S5 storage
 pub struct HybridPersister<S: S5Storage> {
     storage: S,
+    chunk_size: usize,
 }
 
 impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
     pub fn new(storage: S) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Set the number of vectors (and timestamps) stored per chunk
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
     }
 
     pub fn storage(&self) -> &S {
         &self.storage
     }
 
+    /// Compute which chunks changed between two snapshots of an index, so
+    /// a replica can fetch only those. Chunk ids come back sorted.
+    pub fn diff_manifests(&self, old: &Manifest, new: &Manifest) -> ManifestDiff {
+        let old_chunks: HashMap<&str, &ChunkMetadata> = old
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.chunk_id.as_str(), chunk))
+            .collect();
+        let new_chunks: HashMap<&str, &ChunkMetadata> = new
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.chunk_id.as_str(), chunk))
+            .collect();
+
+        let mut diff = ManifestDiff::default();
+        for (id, new_chunk) in &new_chunks {
+            match old_chunks.get(id) {
+                None => diff.added_chunks.push(id.to_string()),
+                Some(old_chunk) if chunk_changed(old_chunk, new_chunk) => {
+                    diff.changed_chunks.push(id.to_string())
+                }
+                Some(_) => {}
+            }
+        }
+        for id in old_chunks.keys() {
+            if !new_chunks.contains_key(id) {
+                diff.removed_chunks.push(id.to_string());
+            }
+        }
+
+        let old_timestamps: HashMap<&str, &str> = old
+            .timestamp_chunks
+            .iter()
+            .flatten()
+            .map(|meta| (meta.chunk_id.as_str(), meta.checksum.as_str()))
+            .collect();
+        for meta in new.timestamp_chunks.iter().flatten() {
+            if old_timestamps.get(meta.chunk_id.as_str()) != Some(&meta.checksum.as_str()) {
+                diff.changed_timestamp_chunks.push(meta.chunk_id.clone());
+            }
+        }
+
+        diff.added_chunks.sort();
+        diff.removed_chunks.sort();
+        diff.changed_chunks.sort();
+        diff.changed_timestamp_chunks.sort();
+        diff
+    }
+
     /// Save HybridIndex to S5 storage
     pub async fn save_index(&self, index: &HybridIndex, path: &str) -> Result<(), PersistenceError> {
-        // 1. Save metadata
-        let metadata = HybridMetadata::from_index(index);
+        // 1. Save metadata, including deleted vectors from both indices
+        let mut metadata = HybridMetadata::from_index(index);
+        metadata.deleted_vectors = index.get_deleted_vectors().await;
         let metadata_path = format!("{}/metadata.cbor", path);
         self.storage
             .put(&metadata_path, metadata.to_cbor()?)
@@ -186,49 +332,119 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
     /// # Returns
     /// The manifest with chunk metadata
     pub async fn save_index_chunked(&self, index: &HybridIndex, path: &str) -> Result<Manifest, PersistenceError> {
-        const CHUNK_SIZE: usize = 10000;
+        self.save_chunked(index, path, None).await
+    }
+
+    /// Save HybridIndex over a previous `save_index_chunked` snapshot,
+    /// uploading only the vector chunks that changed
+    ///
+    /// A chunk whose content hash matches the one recorded in `prev_manifest`
+    /// keeps its previous metadata (including its CID) and is not rewritten.
+    /// Changed chunks are written under keys carrying the new generation, and
+    /// chunk files no longer referenced are deleted once the new manifest is
+    /// stored, so a failed save leaves `prev_manifest` loadable. Vectors stay
+    /// in the chunk `prev_manifest` put them in and new vectors join the
+    /// chunk covering their id, so an insert, edit or removal touches only
+    /// that chunk; a chunk that outgrows the chunk size is split. The
+    /// manifest, metadata, HNSW graph and changed timestamp chunks are always
+    /// written.
+    pub async fn save_index_incremental(
+        &self,
+        index: &HybridIndex,
+        path: &str,
+        prev_manifest: &Manifest,
+    ) -> Result<Manifest, PersistenceError> {
+        self.save_chunked(index, path, Some(prev_manifest)).await
+    }
 
+    async fn save_chunked(
+        &self,
+        index: &HybridIndex,
+        path: &str,
+        previous: Option<&Manifest>,
+    ) -> Result<Manifest, PersistenceError> {
         // Validate path
         if path.is_empty() {
             return Err(PersistenceError::InvalidData("Path cannot be empty".to_string()));
         }
 
         let stats = index.get_stats();
-        let mut manifest = Manifest::new(CHUNK_SIZE, stats.total_vectors);
+        let mut manifest = Manifest::new(self.chunk_size, stats.total_vectors);
+        // The manifest this save replaces, whose files are deleted once the
+        // new manifest is stored
+        let existing;
+        let superseded = match previous {
+            Some(previous) => Some(previous),
+            None => {
+                existing = self.load_existing_manifest(path).await;
+                existing.as_ref()
+            }
+        };
+        manifest.generation = superseded.map_or(0, |superseded| superseded.generation + 1);
+        // Files rewritten over an earlier save get versioned keys
+        let generation = (manifest.generation > 0).then_some(manifest.generation);
 
-        // If empty index, just save manifest
+        // If empty index, there are no chunks to save
         if stats.total_vectors == 0 {
-            let manifest_json = manifest
-                .to_json()
-                .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
-
-            let manifest_path = format!("{}/manifest.json", path);
-            self.storage
-                .put(&manifest_path, manifest_json.into_bytes())
-                .await
-                .map_err(|e| PersistenceError::Storage(e.to_string()))?;
-
+            self.store_manifest(index, path, &mut manifest, superseded, generation)
+                .await?;
             return Ok(manifest);
         }
 
-        // Step 1: Collect all vectors from both HNSW and IVF indices
-        let all_vectors = self.collect_all_vectors(index).await?;
-
-        // Step 2: Partition vectors into chunks
-        let chunks = self.partition_into_chunks(all_vectors, CHUNK_SIZE);
+        // Step 1: Collect all vectors from both HNSW and IVF indices, ordered
+        // by id so chunk contents stay stable between saves
+        let mut all_vectors = self.collect_all_vectors(index).await?;
+        all_vectors.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Step 2: Partition vectors into chunks, keeping the chunks of the
+        // superseded save, and carry payloads with them
+        let (mut chunks, vector_chunks) = self.partition_into_chunks(all_vectors, superseded);
+        let payloads = index.get_payloads().await;
+        if !payloads.is_empty() {
+            for chunk in &mut chunks {
+                let chunk_payloads: Vec<(VectorId, Vec<u8>)> = chunk
+                    .ids()
+                    .filter_map(|id| Some((id.clone(), payloads.get(id)?.clone())))
+                    .collect();
+                chunk.payloads.extend(chunk_payloads);
+            }
+        }
+        let chunk_starts: Vec<(String, VectorId)> = chunks
+            .iter()
+            .filter_map(|chunk| {
+                let first = chunk.ids().min()?;
+                Some((chunk.chunk_id.clone(), first.clone()))
+            })
+            .collect();
 
-        // Step 3: Save each chunk and collect metadata
-        for (chunk_idx, chunk) in chunks.into_iter().enumerate() {
-            let chunk_metadata = self.save_chunk(&chunk, path, chunk_idx).await?;
+        // Step 3: Save each chunk and collect metadata, reusing the previous
+        // metadata of chunks whose contents did not change
+        let previous_chunks: HashMap<&str, &ChunkMetadata> = previous
+            .map(|previous| {
+                previous
+                    .chunks
+                    .iter()
+                    .map(|chunk| (chunk.chunk_id.as_str(), chunk))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for chunk in chunks {
+            let unchanged = previous_chunks
+                .get(chunk.chunk_id.as_str())
+                .filter(|meta| meta.content_hash.as_ref() == Some(&chunk.content_hash()));
+            let chunk_metadata = match unchanged {
+                Some(meta) => (*meta).clone(),
+                None => self.save_chunk(&chunk, path, generation).await?,
+            };
             manifest.add_chunk(chunk_metadata);
         }
 
         // Step 4: Build HNSW manifest (graph structure without vectors)
-        let hnsw_manifest = self.build_hnsw_manifest(index, &manifest).await?;
+        let hnsw_manifest = self.build_hnsw_manifest(index, &vector_chunks).await?;
         manifest.set_hnsw_structure(hnsw_manifest);
 
         // Step 5: Build IVF manifest (centroids and cluster assignments)
-        let ivf_manifest = self.build_ivf_manifest(index, &manifest).await?;
+        let ivf_manifest = self.build_ivf_manifest(index, &vector_chunks).await?;
         manifest.set_ivf_structure(ivf_manifest);
 
         // Step 5.5: Collect deleted vectors from both indices
@@ -237,7 +453,56 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
             manifest.deleted_vectors = Some(deleted_vectors);
         }
 
-        // Step 6: Save manifest as JSON (unencrypted for fast loading)
+        // Step 6: Save timestamps chunked alongside the vector chunks
+        let timestamp_chunks = self
+            .save_timestamp_chunks(index, path, &chunk_starts, superseded, generation)
+            .await?;
+        manifest.timestamp_chunks = Some(timestamp_chunks);
+
+        // Step 7: Save the HNSW graph and metadata, then the manifest
+        self.store_manifest(index, path, &mut manifest, superseded, generation)
+            .await?;
+
+        Ok(manifest)
+    }
+
+    /// Store the HNSW graph, the metadata and then `manifest` itself
+    ///
+    /// The graph and metadata go under keys carrying `generation`, so the
+    /// superseded manifest keeps finding its own until `manifest` replaces
+    /// it. Files only `superseded` references are deleted last, once
+    /// everything the new manifest needs is stored.
+    async fn store_manifest(
+        &self,
+        index: &HybridIndex,
+        path: &str,
+        manifest: &mut Manifest,
+        superseded: Option<&Manifest>,
+        generation: Option<u64>,
+    ) -> Result<(), PersistenceError> {
+        // Save HNSW nodes with full graph structure
+        let recent_index_guard = index.get_recent_index().await;
+        let hnsw_nodes = recent_index_guard.get_all_nodes();
+        drop(recent_index_guard);
+
+        let hnsw_nodes_cbor = serde_cbor::to_vec(&hnsw_nodes)
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        manifest.hnsw_nodes_generation = generation;
+        self.storage
+            .put(&manifest.hnsw_nodes_path(path), hnsw_nodes_cbor)
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+
+        // Save metadata separately (config, counts, PQ codebooks, etc.)
+        let mut metadata = HybridMetadata::from_index(index);
+        metadata.pq = index.get_historical_index().await.product_quantizer().cloned();
+        manifest.metadata_generation = generation;
+        self.storage
+            .put(&manifest.metadata_path(path), metadata.to_cbor()?)
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+
+        // Save manifest as JSON (unencrypted for fast loading)
         let manifest_json = manifest
             .to_json()
             .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
@@ -248,36 +513,303 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
             .await
             .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
-        // Step 7: Save timestamps
-        let timestamps = index.get_timestamps().await;
-        let serializable_timestamps = SerializableTimestamps::new(timestamps);
-        let timestamps_path = format!("{}/timestamps.cbor", path);
+        self.delete_superseded_files(path, superseded, manifest).await
+    }
+
+    /// Append vectors to an index saved with `save_index_chunked` without
+    /// loading it
+    ///
+    /// The vectors are written as one new chunk and assigned to their nearest
+    /// existing IVF centroid, so they are served by the historical index once
+    /// the index is reloaded. Only the manifest, the metadata and the new
+    /// chunk's files are touched, and the manifest generation is bumped.
+    /// Requires trained IVF centroids in the manifest. Ids are not checked
+    /// against vectors already stored, since that would need the full index.
+    pub async fn append_chunk(
+        &self,
+        path: &str,
+        new_vectors: Vec<(VectorId, Vec<f32>, DateTime<Utc>)>,
+    ) -> Result<Manifest, PersistenceError> {
+        let mut manifest = self
+            .load_existing_manifest(path)
+            .await
+            .ok_or_else(|| PersistenceError::MissingComponent("manifest.json".to_string()))?;
+
+        if new_vectors.is_empty() {
+            return Ok(manifest);
+        }
+
+        let (centroids, metric) = match &manifest.ivf_structure {
+            Some(ivf) if !ivf.centroids.is_empty() => (ivf.centroids.clone(), ivf.metric),
+            _ => {
+                return Err(PersistenceError::InvalidData(
+                    "Cannot append to an index without trained IVF centroids".to_string(),
+                ))
+            }
+        };
+        if manifest.timestamp_chunks.is_none() {
+            return Err(PersistenceError::InvalidData(
+                "Cannot append to an index with unchunked timestamps".to_string(),
+            ));
+        }
+
+        let dimension = centroids[0].len();
+        let chunk_idx = manifest
+            .chunks
+            .iter()
+            .filter_map(|c| c.chunk_id.strip_prefix("chunk-")?.parse::<usize>().ok())
+            .max()
+            .map_or(0, |last| last + 1);
+        let chunk_id = format!("chunk-{}", chunk_idx);
+        let appended = new_vectors.len();
+
+        let mut chunk = VectorChunk::new(
+            chunk_id.clone(),
+            manifest.total_vectors,
+            manifest.total_vectors + appended - 1,
+        );
+        let mut timestamps = TimestampChunk {
+            chunk_id: chunk_id.clone(),
+            timestamps: BTreeMap::new(),
+        };
+        let mut clusters = Vec::new();
+
+        for (id, vector, timestamp) in new_vectors {
+            if vector.len() != dimension {
+                return Err(PersistenceError::InvalidData(format!(
+                    "Dimension mismatch: expected {}, got {}",
+                    dimension,
+                    vector.len()
+                )));
+            }
+            if chunk.vectors.contains_key(&id) {
+                return Err(PersistenceError::InvalidData(format!(
+                    "Duplicate vector in append: {}",
+                    id.to_string()
+                )));
+            }
+
+            let nearest = centroids
+                .iter()
+                .map(|centroid| metric.distance(&vector, centroid))
+                .enumerate()
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(cluster, _)| cluster)
+                .unwrap_or(0);
+            if !clusters.contains(&nearest) {
+                clusters.push(nearest);
+            }
+
+            timestamps.timestamps.insert(id.clone(), timestamp);
+            chunk.add_vector(id, vector);
+        }
+
+        // Write the new chunk files before the manifest that references them
+        let generation = Some(manifest.generation + 1);
+        let chunk_metadata = self.save_chunk(&chunk, path, generation).await?;
+
+        let timestamp_data = timestamps.to_cbor()?;
+        let timestamp_meta = TimestampChunkMetadata {
+            chunk_id: chunk_id.clone(),
+            entry_count: timestamps.timestamps.len(),
+            checksum: blake3::hash(&timestamp_data).to_hex().to_string(),
+            generation,
+        };
         self.storage
-            .put(&timestamps_path, serializable_timestamps.to_cbor()?)
+            .put(&timestamp_meta.storage_path(path), timestamp_data)
             .await
             .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
-        // Step 8: Save HNSW nodes with full graph structure
-        let recent_index_guard = index.get_recent_index().await;
-        let hnsw_nodes = recent_index_guard.get_all_nodes();
-        drop(recent_index_guard);
+        let old_metadata_path = manifest.metadata_path(path);
+        let metadata_data = self
+            .storage
+            .get(&old_metadata_path)
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?
+            .ok_or_else(|| PersistenceError::MissingComponent("metadata.cbor".to_string()))?;
+        let mut metadata = HybridMetadata::from_cbor(&metadata_data)?;
+        metadata.historical_count += appended;
+        metadata.total_vectors += appended;
+        metadata.ivf_trained = true;
+        metadata.timestamp = Utc::now();
+
+        // The counts are stored under a new key too, so the manifest being
+        // replaced keeps pointing at the metadata that matches it
+        manifest.metadata_generation = generation;
+        self.storage
+            .put(&manifest.metadata_path(path), metadata.to_cbor()?)
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
-        let hnsw_nodes_cbor = serde_cbor::to_vec(&hnsw_nodes)
+        if let Some(ivf) = manifest.ivf_structure.as_mut() {
+            for cluster in clusters {
+                let chunk_ids = ivf.cluster_assignments.entry(cluster).or_default();
+                if !chunk_ids.contains(&chunk_id) {
+                    chunk_ids.push(chunk_id.clone());
+                }
+            }
+        }
+        if let Some(timestamp_chunks) = manifest.timestamp_chunks.as_mut() {
+            timestamp_chunks.push(timestamp_meta);
+        }
+        manifest.add_chunk(chunk_metadata);
+        manifest.total_vectors += appended;
+        manifest.generation += 1;
+
+        let manifest_json = manifest
+            .to_json()
             .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
-        let hnsw_nodes_path = format!("{}/hnsw_nodes.cbor", path);
         self.storage
-            .put(&hnsw_nodes_path, hnsw_nodes_cbor)
+            .put(&format!("{}/manifest.json", path), manifest_json.into_bytes())
             .await
             .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
-        // Step 9: Save metadata separately (config, counts, etc.)
-        self.save_metadata(index, path).await?;
+        self.storage
+            .delete(&old_metadata_path)
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
         Ok(manifest)
     }
 
-    /// Collect all vectors from the hybrid index
-    async fn collect_all_vectors(&self, index: &HybridIndex) -> Result<Vec<(VectorId, Vec<f32>)>, PersistenceError> {
+    /// Write one timestamp chunk per vector chunk
+    ///
+    /// `chunk_starts` holds each vector chunk's id and smallest vector id, in
+    /// id order; every timestamp goes to the chunk covering its id range, so
+    /// timestamps of deleted vectors (absent from the vector chunks) are kept
+    /// too. Chunks whose checksum matches the `superseded` manifest keep its
+    /// metadata and are not rewritten, so an incremental save only touches
+    /// the timestamp chunks that actually changed.
+    async fn save_timestamp_chunks(
+        &self,
+        index: &HybridIndex,
+        path: &str,
+        chunk_starts: &[(String, VectorId)],
+        superseded: Option<&Manifest>,
+        generation: Option<u64>,
+    ) -> Result<Vec<TimestampChunkMetadata>, PersistenceError> {
+        let previous: HashMap<&str, &TimestampChunkMetadata> = superseded
+            .and_then(|m| m.timestamp_chunks.as_ref())
+            .map(|chunks| chunks.iter().map(|meta| (meta.chunk_id.as_str(), meta)).collect())
+            .unwrap_or_default();
+
+        let mut chunks: Vec<TimestampChunk> = if chunk_starts.is_empty() {
+            vec![TimestampChunk {
+                chunk_id: "chunk-0".to_string(),
+                timestamps: BTreeMap::new(),
+            }]
+        } else {
+            chunk_starts
+                .iter()
+                .map(|(chunk_id, _)| TimestampChunk {
+                    chunk_id: chunk_id.clone(),
+                    timestamps: BTreeMap::new(),
+                })
+                .collect()
+        };
+
+        for (id, timestamp) in index.get_timestamps().await {
+            let idx = chunk_starts
+                .partition_point(|(_, start)| *start <= id)
+                .saturating_sub(1);
+            chunks[idx].timestamps.insert(id, timestamp);
+        }
+
+        let mut saved = Vec::with_capacity(chunks.len());
+
+        for chunk in &chunks {
+            let data = chunk.to_cbor()?;
+            let checksum = blake3::hash(&data).to_hex().to_string();
+
+            if let Some(meta) = previous
+                .get(chunk.chunk_id.as_str())
+                .filter(|meta| meta.checksum == checksum)
+            {
+                saved.push((*meta).clone());
+                continue;
+            }
+
+            let meta = TimestampChunkMetadata {
+                chunk_id: chunk.chunk_id.clone(),
+                entry_count: chunk.timestamps.len(),
+                checksum,
+                generation,
+            };
+            self.storage
+                .put(&meta.storage_path(path), data)
+                .await
+                .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+            saved.push(meta);
+        }
+
+        Ok(saved)
+    }
+
+    /// Load the timestamps stored for a single vector chunk
+    ///
+    /// Lets callers fetch timestamps lazily instead of loading every chunk.
+    /// `meta` is the chunk's entry in the manifest saved at `path`.
+    pub async fn load_timestamp_chunk(
+        &self,
+        path: &str,
+        meta: &TimestampChunkMetadata,
+    ) -> Result<HashMap<VectorId, DateTime<Utc>>, PersistenceError> {
+        let data = self
+            .storage
+            .get(&meta.storage_path(path))
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?
+            .ok_or_else(|| PersistenceError::MissingComponent(format!("timestamps {}", meta.chunk_id)))?;
+
+        Ok(TimestampChunk::from_cbor(&data)?.timestamps.into_iter().collect())
+    }
+
+    /// Delete the files `superseded` references that `current` does not
+    ///
+    /// Only called once `current` is stored, so a failed save leaves the
+    /// previous manifest and every file it needs in place.
+    async fn delete_superseded_files(
+        &self,
+        path: &str,
+        superseded: Option<&Manifest>,
+        current: &Manifest,
+    ) -> Result<(), PersistenceError> {
+        let Some(superseded) = superseded else {
+            return Ok(());
+        };
+
+        let kept: HashSet<String> = Self::stored_paths(path, current).collect();
+        for stale in Self::stored_paths(path, superseded).filter(|p| !kept.contains(p)) {
+            self.storage
+                .delete(&stale)
+                .await
+                .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Storage paths of every vector and timestamp chunk `manifest`
+    /// references, plus its HNSW graph and metadata
+    fn stored_paths<'a>(path: &'a str, manifest: &'a Manifest) -> impl Iterator<Item = String> + 'a {
+        let vectors = manifest.chunks.iter().map(move |c| c.storage_path(path));
+        let timestamps = manifest.timestamp_chunks.iter().flatten().map(move |c| c.storage_path(path));
+        let state = [manifest.hnsw_nodes_path(path), manifest.metadata_path(path)];
+        vectors.chain(timestamps).chain(state)
+    }
+
+    /// Read the manifest of a previous save at `path`, if there is a valid one
+    async fn load_existing_manifest(&self, path: &str) -> Option<Manifest> {
+        let data = self
+            .storage
+            .get(&format!("{}/manifest.json", path))
+            .await
+            .ok()??;
+        Manifest::from_json(std::str::from_utf8(&data).ok()?).ok()
+    }
+
+    /// Collect all vectors from the hybrid index, historical vectors stored
+    /// product-quantized as their codes
+    async fn collect_all_vectors(&self, index: &HybridIndex) -> Result<Vec<(VectorId, ChunkEntry)>, PersistenceError> {
         let mut all_vectors = Vec::new();
 
         // Get vectors from HNSW index (recent vectors)
@@ -289,7 +821,7 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
 
         for node in hnsw_nodes {
             if !node.is_deleted() {
-                all_vectors.push((node.id().clone(), node.vector().clone()));
+                all_vectors.push((node.id().clone(), ChunkEntry::Vector(node.vector().clone())));
             }
         }
 
@@ -300,7 +832,10 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
             let mut vectors = Vec::new();
             for inverted_list in historical_index.get_all_inverted_lists().values() {
                 for (id, vector) in &inverted_list.vectors {
-                    vectors.push((id.clone(), vector.clone()));
+                    vectors.push((id.clone(), ChunkEntry::Vector(vector.clone())));
+                }
+                for (id, codes) in &inverted_list.codes {
+                    vectors.push((id.clone(), ChunkEntry::Codes(codes.clone())));
                 }
             }
             vectors
@@ -311,68 +846,121 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
         Ok(all_vectors)
     }
 
-    /// Partition vectors into chunks of specified size
-    fn partition_into_chunks(&self, vectors: Vec<(VectorId, Vec<f32>)>, chunk_size: usize) -> Vec<VectorChunk> {
-        let mut chunks = Vec::new();
-        let total_vectors = vectors.len();
-
-        for (chunk_idx, chunk_vectors) in vectors.chunks(chunk_size).enumerate() {
-            let start_idx = chunk_idx * chunk_size;
-            let end_idx = std::cmp::min(start_idx + chunk_size - 1, total_vectors - 1);
+    /// Partition id-ordered vectors into chunks, also returning the id of
+    /// the chunk each vector was placed in
+    ///
+    /// Each vector goes to the `superseded` chunk whose id range covers it,
+    /// so inserts and removals only change the chunks they land in. Groups
+    /// larger than the chunk size, and every vector when there is no
+    /// superseded save, are cut into new chunks of at most the chunk size.
+    fn partition_into_chunks(
+        &self,
+        vectors: Vec<(VectorId, ChunkEntry)>,
+        superseded: Option<&Manifest>,
+    ) -> (Vec<VectorChunk>, HashMap<VectorId, String>) {
+        let previous_chunks = superseded.map(|m| m.chunks.as_slice()).unwrap_or_default();
+        let mut starts: Vec<(&VectorId, &str)> = previous_chunks
+            .iter()
+            .map(|chunk| (&chunk.vector_id_range.0, chunk.chunk_id.as_str()))
+            .collect();
+        starts.sort();
+        let mut next_idx = previous_chunks
+            .iter()
+            .filter_map(|c| c.chunk_id.strip_prefix("chunk-")?.parse::<usize>().ok())
+            .max()
+            .map_or(0, |last| last + 1);
+
+        // Group vectors by the superseded chunk covering them, in id order
+        let mut groups: Vec<(Option<&str>, Vec<_>)> = Vec::new();
+        for (id, vector) in vectors {
+            let owner = starts
+                .get(starts.partition_point(|(start, _)| **start <= id).saturating_sub(1))
+                .map(|(_, chunk_id)| *chunk_id);
+            match groups.last_mut() {
+                Some((last, group)) if *last == owner => group.push((id, vector)),
+                _ => groups.push((owner, vec![(id, vector)])),
+            }
+        }
 
-            let mut chunk = VectorChunk::new(
-                format!("chunk-{}", chunk_idx),
-                start_idx,
-                end_idx,
-            );
+        let mut chunks = Vec::new();
+        let mut vector_chunks = HashMap::new();
+        let mut start_idx = 0;
+
+        for (owner, group) in groups {
+            for (piece, chunk_vectors) in group.chunks(self.chunk_size).enumerate() {
+                let chunk_id = match owner.filter(|_| piece == 0) {
+                    Some(owner) => owner.to_string(),
+                    None => {
+                        next_idx += 1;
+                        format!("chunk-{}", next_idx - 1)
+                    }
+                };
+                let end_idx = start_idx + chunk_vectors.len() - 1;
+                let mut chunk = VectorChunk::new(chunk_id, start_idx, end_idx);
+                start_idx = end_idx + 1;
+
+                for (id, entry) in chunk_vectors {
+                    match entry {
+                        ChunkEntry::Vector(vector) => chunk.add_vector(id.clone(), vector.clone()),
+                        ChunkEntry::Codes(codes) => chunk.add_codes(id.clone(), codes.clone()),
+                    }
+                    vector_chunks.insert(id.clone(), chunk.chunk_id.clone());
+                }
 
-            for (id, vector) in chunk_vectors {
-                chunk.add_vector(id.clone(), vector.clone());
+                chunks.push(chunk);
             }
-
-            chunks.push(chunk);
         }
 
-        chunks
+        (chunks, vector_chunks)
     }
 
     /// Save a single chunk to S5 storage
-    async fn save_chunk(&self, chunk: &VectorChunk, base_path: &str, chunk_idx: usize) -> Result<ChunkMetadata, PersistenceError> {
+    async fn save_chunk(
+        &self,
+        chunk: &VectorChunk,
+        base_path: &str,
+        generation: Option<u64>,
+    ) -> Result<ChunkMetadata, PersistenceError> {
         // Serialize chunk to CBOR
         let cbor_data = chunk
             .to_cbor()
             .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
 
         // Save to S5
-        let chunk_path = format!("{}/chunks/chunk-{}.cbor", base_path, chunk_idx);
+        let chunk_id = chunk.chunk_id.clone();
+        let chunk_path = format!("{}/chunks/{}", base_path, chunk_file_name(&chunk_id, generation));
         self.storage
             .put(&chunk_path, cbor_data.clone())
             .await
             .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
-        // Get first and last vector IDs for range
-        let vector_ids: Vec<&VectorId> = chunk.vectors.keys().collect();
-        let (start_id, end_id) = if !vector_ids.is_empty() {
-            (
-                (*vector_ids.first().unwrap()).clone(),
-                (*vector_ids.last().unwrap()).clone(),
-            )
-        } else {
-            (VectorId::from_string(""), VectorId::from_string(""))
+        // Get smallest and largest vector IDs for range; later saves place
+        // vectors by it
+        let (start_id, end_id) = match (chunk.ids().min(), chunk.ids().max()) {
+            (Some(start), Some(end)) => (start.clone(), end.clone()),
+            _ => (VectorId::from_string(""), VectorId::from_string("")),
         };
 
         // Create metadata
-        Ok(ChunkMetadata::new(
-            format!("chunk-{}", chunk_idx),
+        let mut metadata = ChunkMetadata::new(
+            chunk_id,
             chunk.len(),
             cbor_data.len(),
             start_id,
             end_id,
-        ))
+        );
+        metadata.content_hash = Some(chunk.content_hash());
+        metadata.checksum = Some(chunk_checksum(&cbor_data));
+        metadata.generation = generation;
+        Ok(metadata)
     }
 
     /// Build HNSW manifest from the index
-    async fn build_hnsw_manifest(&self, index: &HybridIndex, manifest: &Manifest) -> Result<HNSWManifest, PersistenceError> {
+    async fn build_hnsw_manifest(
+        &self,
+        index: &HybridIndex,
+        vector_chunks: &HashMap<VectorId, String>,
+    ) -> Result<HNSWManifest, PersistenceError> {
         // Extract all data we need while holding the lock, then drop it immediately
         let (entry_point, level_distribution, nodes) = {
             let recent_index = index.get_recent_index().await;
@@ -394,17 +982,22 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
 
         // Map nodes to chunks
         for node in nodes {
-            let chunk_id = self.find_chunk_for_vector(node.id(), manifest);
-            hnsw_manifest.add_node_chunk_mapping(node.id().clone(), chunk_id);
+            if let Some(chunk_id) = self.find_chunk_for_vector(node.id(), vector_chunks) {
+                hnsw_manifest.add_node_chunk_mapping(node.id().clone(), chunk_id);
+            }
         }
 
         Ok(hnsw_manifest)
     }
 
     /// Build IVF manifest from the index
-    async fn build_ivf_manifest(&self, index: &HybridIndex, manifest: &Manifest) -> Result<IVFManifest, PersistenceError> {
+    async fn build_ivf_manifest(
+        &self,
+        index: &HybridIndex,
+        vector_chunks: &HashMap<VectorId, String>,
+    ) -> Result<IVFManifest, PersistenceError> {
         // Extract all data we need while holding the lock, then drop it immediately
-        let (centroids, cluster_vector_ids) = {
+        let (centroids, cluster_vector_ids, metric) = {
             let historical_index = index.get_historical_index().await;
 
             // Get centroids (keep in memory - these are small)
@@ -419,14 +1012,16 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
                 .get_all_inverted_lists()
                 .iter()
                 .map(|(cluster_id, inverted_list)| {
-                    (cluster_id.0, inverted_list.vectors.keys().cloned().collect())
+                    let ids = inverted_list.vectors.keys().chain(inverted_list.codes.keys());
+                    (cluster_id.0, ids.cloned().collect())
                 })
                 .collect();
 
-            (centroids, cluster_vector_ids)
+            (centroids, cluster_vector_ids, historical_index.config().metric)
         };
 
         let mut ivf_manifest = IVFManifest::new(centroids);
+        ivf_manifest.metric = metric;
 
         // Map clusters to chunks
         for (cluster_id, vector_ids) in cluster_vector_ids {
@@ -434,8 +1029,9 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
 
             // Find which chunks contain vectors from this cluster
             for vector_id in &vector_ids {
-                let chunk_id = self.find_chunk_for_vector(vector_id, manifest);
-                chunk_ids.insert(chunk_id);
+                if let Some(chunk_id) = self.find_chunk_for_vector(vector_id, vector_chunks) {
+                    chunk_ids.insert(chunk_id);
+                }
             }
 
             ivf_manifest.add_cluster_assignment(cluster_id, chunk_ids.into_iter().collect());
@@ -444,47 +1040,21 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
         Ok(ivf_manifest)
     }
 
-    /// Find which chunk contains a given vector
-    fn find_chunk_for_vector(&self, vector_id: &VectorId, manifest: &Manifest) -> String {
-        // Search through chunks to find which one contains this vector
-        for chunk_meta in &manifest.chunks {
-            // Check if vector_id falls within this chunk's range
-            // For now, use a simple heuristic based on vector ID string
-            // In production, we'd maintain a proper vector_id -> chunk_id mapping
-
-            // Simple approach: check if the chunk was saved and assume linear ordering
-            // This works because we partition vectors sequentially
-            // TODO: Maintain explicit mapping during partitioning for production use
-        }
-
-        // Default to first chunk if not found
-        if manifest.chunks.is_empty() {
-            "chunk-0".to_string()
-        } else {
-            // For MVP: distribute evenly across chunks based on hash
-            let chunk_idx = vector_id.to_string().len() % manifest.chunks.len();
-            manifest.chunks[chunk_idx].chunk_id.clone()
-        }
-    }
-
-    /// Save metadata (timestamps, config, etc.)
-    async fn save_metadata(&self, index: &HybridIndex, path: &str) -> Result<(), PersistenceError> {
-        let metadata = HybridMetadata::from_index(index);
-        let metadata_cbor = metadata.to_cbor()?;
-
-        let metadata_path = format!("{}/metadata.cbor", path);
-        self.storage
-            .put(&metadata_path, metadata_cbor)
-            .await
-            .map_err(|e| PersistenceError::Storage(e.to_string()))?;
-
-        Ok(())
+    /// Find which chunk a vector was partitioned into. `None` for vectors
+    /// inserted after the save collected its vectors, which no chunk holds.
+    fn find_chunk_for_vector(
+        &self,
+        vector_id: &VectorId,
+        vector_chunks: &HashMap<VectorId, String>,
+    ) -> Option<String> {
+        vector_chunks.get(vector_id).cloned()
     }
 
     /// Load HybridIndex from chunked storage format
     ///
     /// This method loads a previously saved chunked index by:
-    /// 1. Loading and validating the manifest
+    /// 1. Loading and validating the manifest, upgrading one written by an
+    ///    older version and storing it back once its chunks are loaded
     /// 2. Loading all chunks (MVP - true lazy loading in Phase 4)
     /// 3. Reconstructing HNSW and IVF indices
     /// 4. Assembling the HybridIndex
@@ -507,16 +1077,18 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
         let manifest_json = String::from_utf8(manifest_data)
             .map_err(|e| PersistenceError::Deserialization(format!("Invalid UTF-8 in manifest: {}", e)))?;
 
-        let manifest = Manifest::from_json(&manifest_json)
+        let mut manifest = Manifest::from_json(&manifest_json)
             .map_err(|e| PersistenceError::Deserialization(format!("Failed to parse manifest: {}", e)))?;
 
-        // Step 2: Validate version compatibility
+        // Step 2: Validate version compatibility, upgrading older manifests
         use crate::core::chunk::MANIFEST_VERSION;
-        if manifest.version > MANIFEST_VERSION {
-            return Err(PersistenceError::IncompatibleVersion {
+        let migrated = manifest.version != MANIFEST_VERSION;
+        if migrated {
+            let found = manifest.version;
+            manifest.migrate(found).map_err(|_| PersistenceError::IncompatibleVersion {
                 expected: MANIFEST_VERSION,
-                found: manifest.version,
-            });
+                found,
+            })?;
         }
 
         // Step 3: Handle empty index case
@@ -525,7 +1097,7 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
         }
 
         // Step 4: Load metadata for config and timestamps
-        let metadata_path = format!("{}/metadata.cbor", path);
+        let metadata_path = manifest.metadata_path(path);
         let metadata_data = self
             .storage
             .get(&metadata_path)
@@ -540,7 +1112,9 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
 
         for chunk_meta in &manifest.chunks {
             let chunk_id = chunk_meta.chunk_id.clone();
-            let chunk_path = format!("{}/chunks/{}.cbor", path, chunk_id);
+            let chunk_path = chunk_meta.storage_path(path);
+            let checksum = chunk_meta.checksum.clone();
+            let recompute_checksum = checksum.is_none() && migrated;
             let storage_clone = self.storage.clone();
 
             let task = tokio::spawn(async move {
@@ -549,9 +1123,14 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
                     .await
                     .map_err(|e| PersistenceError::Storage(format!("Failed to load chunk: {}", e)))?
                     .ok_or_else(|| PersistenceError::MissingComponent(format!("chunk {}", chunk_id)))?;
+                if let Some(expected) = &checksum {
+                    verify_chunk_checksum(&chunk_id, &chunk_data, expected)
+                        .map_err(|e| PersistenceError::InvalidData(e.to_string()))?;
+                }
 
-                VectorChunk::from_cbor(&chunk_data)
-                    .map_err(|e| PersistenceError::Deserialization(format!("Failed to parse chunk: {}", e)))
+                let chunk = VectorChunk::from_cbor(&chunk_data)
+                    .map_err(|e| PersistenceError::Deserialization(format!("Failed to parse chunk: {}", e)))?;
+                Ok::<_, PersistenceError>((chunk, recompute_checksum.then(|| chunk_checksum(&chunk_data))))
             });
 
             chunk_tasks.push(task);
@@ -559,21 +1138,56 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
 
         // Wait for all chunks to load
         let mut all_vectors = Vec::new();
-        for task in chunk_tasks {
-            let chunk = task
+        let mut all_codes = Vec::new();
+        let mut payloads = HashMap::new();
+        for (task, chunk_meta) in chunk_tasks.into_iter().zip(&mut manifest.chunks) {
+            let (chunk, checksum) = task
                 .await
                 .map_err(|e| PersistenceError::Storage(format!("Task join error: {}", e)))??;
 
+            // Manifests from before v4 have no hashes or checksums to compare
+            // later saves against; record them now the chunk data is at hand
+            if migrated {
+                chunk_meta.content_hash.get_or_insert_with(|| chunk.content_hash());
+                if let Some(checksum) = checksum {
+                    chunk_meta.checksum.get_or_insert(checksum);
+                }
+            }
+
             for (id, vector) in chunk.vectors {
                 all_vectors.push((id, vector));
             }
+            all_codes.extend(chunk.codes);
+            payloads.extend(chunk.payloads);
+        }
+
+        // Store the upgraded manifest, so later loads skip the migration and
+        // incremental saves can reuse the recorded hashes. Best effort: the
+        // load goes on from the upgraded copy in memory, e.g. on read-only
+        // storage, and the next load migrates again
+        if migrated {
+            let stored = match manifest.to_json() {
+                Ok(manifest_json) => self
+                    .storage
+                    .put(&manifest_path, manifest_json.into_bytes())
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(error) = stored {
+                tracing::warn!(
+                    path = %manifest_path,
+                    %error,
+                    "Failed to store upgraded manifest; loading without it"
+                );
+            }
         }
 
         // Step 6: Reconstruct HNSW index from saved nodes with full graph structure
         let mut hnsw_index = crate::hnsw::core::HNSWIndex::new(config.hnsw_config.clone());
 
         // Load HNSW nodes with full graph structure
-        let hnsw_nodes_path = format!("{}/hnsw_nodes.cbor", path);
+        let hnsw_nodes_path = manifest.hnsw_nodes_path(path);
         if let Ok(Some(hnsw_nodes_data)) = self.storage.get(&hnsw_nodes_path).await {
             let hnsw_nodes: Vec<crate::hnsw::core::HNSWNode> = serde_cbor::from_slice(&hnsw_nodes_data)
                 .map_err(|e| PersistenceError::Deserialization(format!("Failed to deserialize HNSW nodes: {}", e)))?;
@@ -591,7 +1205,13 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
         }
 
         // Step 7: Reconstruct IVF index from manifest + chunks
-        let mut ivf_index = crate::ivf::core::IVFIndex::new(config.ivf_config.clone());
+        // Search with the metric the centroids were trained with, whatever
+        // the caller's config says
+        let mut ivf_config = config.ivf_config.clone();
+        if let Some(ivf_manifest) = &manifest.ivf_structure {
+            ivf_config.metric = ivf_manifest.metric;
+        }
+        let mut ivf_index = crate::ivf::core::IVFIndex::new(ivf_config);
 
         if let Some(ivf_manifest) = &manifest.ivf_structure {
             // Set trained state with centroids
@@ -652,20 +1272,48 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
                 }
             }
 
+            // Product-quantized vectors go back in as codes, in the cluster
+            // nearest their decoded vector
+            if !all_codes.is_empty() && metadata.pq.is_none() {
+                return Err(PersistenceError::MissingComponent("PQ codebooks".to_string()));
+            }
+            if let Some(quantizer) = metadata.pq.clone() {
+                for (vector_id, codes) in all_codes {
+                    let assigned_cluster = ivf_index.find_cluster(&quantizer.decode(&codes))
+                        .map_err(|e| PersistenceError::IVFError(format!("Failed to find cluster: {}", e)))?;
+                    inverted_lists
+                        .get_mut(&assigned_cluster)
+                        .ok_or_else(|| PersistenceError::InvalidData(format!("Invalid cluster ID: {}", assigned_cluster.0)))?
+                        .insert_codes(vector_id, codes)
+                        .map_err(|e| PersistenceError::IVFError(format!("Failed to insert to inverted list: {}", e)))?;
+                }
+                ivf_index.set_product_quantizer(quantizer);
+            }
+
             ivf_index.set_inverted_lists(inverted_lists);
         }
 
-        // Step 8: Load timestamps from storage
-        let timestamps_path = format!("{}/timestamps.cbor", path);
-        let timestamps_data = self
-            .storage
-            .get(&timestamps_path)
-            .await
-            .map_err(|e| PersistenceError::Storage(e.to_string()))?
-            .ok_or_else(|| PersistenceError::MissingComponent("timestamps.cbor".to_string()))?;
+        // Step 8: Load timestamps from storage (chunked, or a single legacy blob)
+        let timestamps = match &manifest.timestamp_chunks {
+            Some(timestamp_chunks) => {
+                let mut timestamps = HashMap::new();
+                for meta in timestamp_chunks {
+                    timestamps.extend(self.load_timestamp_chunk(path, meta).await?);
+                }
+                timestamps
+            }
+            None => {
+                let timestamps_path = format!("{}/timestamps.cbor", path);
+                let timestamps_data = self
+                    .storage
+                    .get(&timestamps_path)
+                    .await
+                    .map_err(|e| PersistenceError::Storage(e.to_string()))?
+                    .ok_or_else(|| PersistenceError::MissingComponent("timestamps.cbor".to_string()))?;
 
-        let serializable_timestamps = SerializableTimestamps::from_cbor(&timestamps_data)?;
-        let timestamps = serializable_timestamps.timestamps;
+                SerializableTimestamps::from_cbor(&timestamps_data)?.timestamps
+            }
+        };
 
         // Step 9: Assemble HybridIndex using from_parts
         // Use the passed config (allows tests to override)
@@ -679,19 +1327,117 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
             metadata.ivf_trained,
         )
         .map_err(|e| PersistenceError::InvalidData(format!("Failed to reconstruct index: {}", e)))?;
+        hybrid_index.restore_payloads(payloads).await;
 
         // Step 10: Mark deleted vectors (from manifest v3+)
         if let Some(deleted_ids) = &manifest.deleted_vectors {
-            for id_str in deleted_ids {
-                let vector_id = crate::core::types::VectorId::from_string(id_str);
+            let vector_ids = {
+                let timestamps = hybrid_index.timestamps.read().await;
+                resolve_deleted_ids(deleted_ids, timestamps.keys())
+            };
+            for (vector_id, deleted_at) in vector_ids {
                 // Best effort - ignore errors if vector doesn't exist
-                let _ = hybrid_index.delete(vector_id).await;
+                let _ = hybrid_index
+                    .delete_at(vector_id, deleted_at.unwrap_or_else(Utc::now))
+                    .await;
             }
         }
 
         Ok(hybrid_index)
     }
 
+    /// Export the whole index as a single tar archive streamed to `writer`
+    ///
+    /// The archive holds the same files `save_index_chunked` writes, one
+    /// entry at a time, so the index is never buffered in full. With
+    /// `options.compress` the stream is zstd-compressed. Returns the writer
+    /// once the archive is complete.
+    pub async fn export_archive<W>(
+        &self,
+        index: &HybridIndex,
+        writer: W,
+        options: ArchiveOptions,
+    ) -> Result<W, PersistenceError>
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        if options.compress {
+            let encoder = ZstdEncoder::with_quality(writer, Level::Precise(options.compression_level));
+            let mut encoder = self.write_archive(index, encoder).await?;
+            encoder.shutdown().await.map_err(|e| PersistenceError::Storage(e.to_string()))?;
+            Ok(encoder.into_inner())
+        } else {
+            self.write_archive(index, writer).await
+        }
+    }
+
+    async fn write_archive<W>(&self, index: &HybridIndex, writer: W) -> Result<W, PersistenceError>
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let sink = ArchiveSink::new(writer);
+        HybridPersister::new(sink.clone())
+            .with_chunk_size(self.chunk_size)
+            .save_index_chunked(index, ARCHIVE_ROOT)
+            .await?;
+        sink.finish().await.map_err(|e| PersistenceError::Storage(e.to_string()))
+    }
+
+    /// Rebuild an index from an archive written by `export_archive`
+    ///
+    /// Entries are copied into this persister's storage under `path` as they
+    /// are read, then loaded with `load_index_chunked`. Compressed archives
+    /// are detected automatically.
+    pub async fn import_archive<R>(
+        &self,
+        reader: R,
+        path: &str,
+        config: HybridConfig,
+    ) -> Result<HybridIndex, PersistenceError>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        let mut reader = BufReader::new(reader);
+        let compressed = reader
+            .fill_buf()
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?
+            .starts_with(&ZSTD_MAGIC);
+        let reader: Box<dyn AsyncRead + Unpin + Send + '_> = if compressed {
+            Box::new(ZstdDecoder::new(reader))
+        } else {
+            Box::new(reader)
+        };
+
+        let mut tar = TarReader::new(reader);
+        let mut has_manifest = false;
+        while let Some((name, data)) = tar
+            .next_entry()
+            .await
+            .map_err(|e| PersistenceError::InvalidData(format!("Corrupt archive: {}", e)))?
+        {
+            if name.is_empty()
+                || name.starts_with('/')
+                || name.split('/').any(|part| part == "..")
+            {
+                return Err(PersistenceError::InvalidData(format!(
+                    "Invalid archive entry name: {}",
+                    name
+                )));
+            }
+            has_manifest |= name == "manifest.json";
+            self.storage
+                .put(&format!("{}/{}", path, name), data)
+                .await
+                .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+        }
+
+        if !has_manifest {
+            return Err(PersistenceError::MissingComponent("manifest.json".to_string()));
+        }
+        self.load_index_chunked(path, config).await
+    }
+
     /// Load HybridIndex from S5 storage
     pub async fn load_index(&self, path: &str) -> Result<HybridIndex, PersistenceError> {
         // 1. Load metadata
@@ -719,14 +1465,25 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
         // 3. Load recent index (HNSW) using HNSWPersister
         let hnsw_persister = HNSWPersister::new(self.storage.clone());
         let recent_path = format!("{}/recent", path);
-        let recent_index = hnsw_persister.load_index(&recent_path).await?;
+        let mut recent_index = hnsw_persister.load_index(&recent_path).await?;
 
         // 4. Load historical index (IVF) using IVFPersister
         let ivf_persister = IVFPersister::new(self.storage.clone());
         let historical_path = format!("{}/historical", path);
-        let historical_index = ivf_persister.load_index(&historical_path).await?;
+        let mut historical_index = ivf_persister.load_index(&historical_path).await?;
+
+        // 5. Re-mark deleted vectors at their original times
+        for (vector_id, deleted_at) in resolve_deleted_ids(
+            &metadata.deleted_vectors,
+            serializable_timestamps.timestamps.keys(),
+        ) {
+            // Best effort - the id lives in only one of the indices
+            let deleted_at = deleted_at.unwrap_or_else(Utc::now);
+            let _ = recent_index.mark_deleted_at(&vector_id, deleted_at);
+            let _ = historical_index.mark_deleted_at(&vector_id, deleted_at);
+        }
 
-        // 5. Reconstruct HybridIndex using from_parts method
+        // 6. Reconstruct HybridIndex using from_parts method
         HybridIndex::from_parts(
             metadata.config,
             recent_index,
@@ -740,6 +1497,39 @@ impl<S: S5Storage + Clone + 'static> HybridPersister<S> {
     }
 }
 
+/// Match saved deleted vectors to the loaded ids, keeping their deletion
+/// times.
+///
+/// Ids are saved as `VectorId::hash_hex`. Older saves used the display
+/// form, which keeps only 32 bits of a hashed id, so those are resolved only
+/// when exactly one loaded id has that form; ids matching nothing are
+/// skipped.
+fn resolve_deleted_ids<'a>(
+    saved: &[DeletedVector],
+    loaded: impl Iterator<Item = &'a VectorId>,
+) -> Vec<(VectorId, Option<DateTime<Utc>>)> {
+    let mut by_hex = HashMap::new();
+    let mut by_display: HashMap<String, Option<VectorId>> = HashMap::new();
+    for id in loaded {
+        by_hex.insert(id.hash_hex(), id.clone());
+        by_display
+            .entry(id.to_string())
+            .and_modify(|existing| *existing = None)
+            .or_insert_with(|| Some(id.clone()));
+    }
+
+    saved
+        .iter()
+        .filter_map(|deleted| {
+            let id = match by_hex.get(&deleted.id) {
+                Some(id) => Some(id.clone()),
+                None => by_display.get(&deleted.id).cloned().flatten(),
+            };
+            id.map(|id| (id, deleted.deleted_at))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -753,6 +1543,9 @@ mod tests {
             historical_count: 500,
             total_vectors: 600,
             timestamp: Utc::now(),
+            ivf_trained: true,
+            deleted_vectors: Vec::new(),
+            pq: None,
         };
 
         let cbor = metadata.to_cbor().expect("Failed to serialize");
@@ -795,6 +1588,9 @@ mod tests {
             historical_count: 0,
             total_vectors: 0,
             timestamp: Utc::now(),
+            ivf_trained: false,
+            deleted_vectors: Vec::new(),
+            pq: None,
         };
 
         let cbor = serde_cbor::to_vec(&metadata).unwrap();
@@ -1,16 +1,25 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
-use crate::core::types::{SearchResult, VectorId};
-use crate::core::vector_ops::euclidean_distance_scalar;
-use crate::storage::chunk_loader::ChunkLoader;
+use crate::core::chunk_cache::CacheMetrics;
+use crate::core::types::{DistanceMetric, SearchResult, VectorId};
+use crate::core::vector_cache::{VectorCache, DEFAULT_VECTOR_CACHE_CAPACITY};
+use crate::core::vector_ops::euclidean_distance;
+use crate::ivf::pq::{PQConfig, ProductQuantizer};
+use crate::storage::chunk_loader::{ChunkLoader, SkippedChunk};
+use chrono::{DateTime, Utc};
+use futures::stream::{FuturesUnordered, StreamExt};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use thiserror::Error;
 
+/// Maximum probed clusters a search loads at once; lazily loaded clusters
+/// each cost a storage round trip
+pub const PROBE_CONCURRENCY: usize = 8;
+
 #[derive(Debug, Clone, Error)]
 pub enum IVFError {
     #[error("Index not trained. Call train() before inserting or searching.")]
@@ -36,6 +45,15 @@ pub enum IVFError {
 
     #[error("Vector not found: {0:?}")]
     VectorNotFound(VectorId),
+
+    #[error("Cluster not found: {0:?}")]
+    ClusterNotFound(ClusterId),
+
+    #[error("Zero vector has no direction, so its cosine similarity is undefined")]
+    ZeroVector,
+
+    #[error("Product quantizer not trained. Call train_pq() before inserting.")]
+    PQNotTrained,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -45,6 +63,22 @@ pub struct IVFConfig {
     pub train_size: usize,
     pub max_iterations: usize,
     pub seed: Option<u64>,
+    /// Distance used for clustering, probing and ranking; configs persisted
+    /// before it existed load as `Euclidean`
+    #[serde(default)]
+    pub metric: DistanceMetric,
+    /// Store product-quantized codes instead of full vectors, see `ivf::pq`
+    #[serde(default)]
+    pub pq: Option<PQConfig>,
+    /// Vectors lazily loaded from chunks that stay decoded in memory; past
+    /// this the least recently used are evicted and reloaded on demand, and
+    /// 0 disables the cache
+    #[serde(default = "default_vector_cache_capacity")]
+    pub vector_cache_capacity: usize,
+}
+
+fn default_vector_cache_capacity() -> usize {
+    DEFAULT_VECTOR_CACHE_CAPACITY
 }
 
 impl Default for IVFConfig {
@@ -55,17 +89,44 @@ impl Default for IVFConfig {
             train_size: 10000,
             max_iterations: 25,
             seed: None,
+            metric: DistanceMetric::default(),
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         }
     }
 }
 
 impl IVFConfig {
+    /// Recommended parameters for `n_vectors` vectors compared by `metric`
+    ///
+    /// `n_clusters` is `4 * sqrt(n_vectors)` rounded, at least 1 and at most
+    /// `n_vectors`. `n_probe` is 1/16 of the clusters (1/8 for `DotProduct`,
+    /// whose clusters separate results less cleanly), at least 1.
+    /// `train_size` covers 40 samples per cluster, at least 10,000.
+    pub fn default_for(metric: DistanceMetric, n_vectors: usize) -> Self {
+        let n_clusters = ((4.0 * (n_vectors as f64).sqrt()).round() as usize)
+            .min(n_vectors)
+            .max(1);
+        let probe_divisor = match metric {
+            DistanceMetric::Euclidean | DistanceMetric::Cosine => 16,
+            DistanceMetric::DotProduct => 8,
+        };
+        Self {
+            n_clusters,
+            n_probe: (n_clusters / probe_divisor).max(1),
+            train_size: (n_clusters * 40).max(10_000),
+            metric,
+            ..Self::default()
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
         self.n_clusters > 0
             && self.n_probe > 0
             && self.n_probe <= self.n_clusters
             && self.train_size > 0
             && self.max_iterations > 0
+            && self.pq.is_none_or(|pq| pq.is_valid())
     }
 }
 
@@ -108,6 +169,38 @@ pub struct TrainResult {
     pub final_error: f32,
 }
 
+/// Chunks the lazy-loading path could not read, and the vectors they hold
+#[derive(Debug, Clone, Default)]
+pub struct ChunkIntegrityReport {
+    pub skipped_chunks: Vec<SkippedChunk>,
+    /// Chunk-referenced vectors that live in a skipped chunk
+    pub unavailable_vectors: usize,
+}
+
+impl ChunkIntegrityReport {
+    pub fn is_complete(&self) -> bool {
+        self.skipped_chunks.is_empty()
+    }
+}
+
+/// How `IVFIndex::search_batch_with` probes clusters for a batch of queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchSearchOptions {
+    pub n_probe: usize,
+    /// Fetch each probed cluster once for every query probing it, and score
+    /// identical queries once, instead of searching each query on its own
+    pub share_clusters: bool,
+}
+
+/// Work done by one `IVFIndex::search_batch_with` call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSearchStats {
+    /// `get_cluster_vectors` calls, each of which may load chunks
+    pub cluster_fetches: usize,
+    /// Query-to-centroid and query-to-candidate distances computed
+    pub distance_computations: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InvertedList {
     pub vectors: HashMap<VectorId, Vec<f32>>,
@@ -115,6 +208,9 @@ pub struct InvertedList {
     /// When present, vectors are loaded from chunks on demand
     #[serde(default)]
     pub chunk_refs: HashMap<VectorId, String>,
+    /// Product-quantized vectors, one code per subvector
+    #[serde(default)]
+    pub codes: HashMap<VectorId, Vec<u8>>,
 }
 
 impl InvertedList {
@@ -122,11 +218,16 @@ impl InvertedList {
         Self {
             vectors: HashMap::new(),
             chunk_refs: HashMap::new(),
+            codes: HashMap::new(),
         }
     }
 
+    pub fn contains(&self, id: &VectorId) -> bool {
+        self.vectors.contains_key(id) || self.chunk_refs.contains_key(id) || self.codes.contains_key(id)
+    }
+
     pub fn insert(&mut self, id: VectorId, vector: Vec<f32>) -> Result<(), IVFError> {
-        if self.vectors.contains_key(&id) {
+        if self.contains(&id) {
             return Err(IVFError::DuplicateVector(id));
         }
         self.vectors.insert(id, vector);
@@ -135,15 +236,24 @@ impl InvertedList {
 
     /// Insert with chunk reference for lazy loading
     pub fn insert_with_chunk(&mut self, id: VectorId, chunk_id: String) -> Result<(), IVFError> {
-        if self.chunk_refs.contains_key(&id) || self.vectors.contains_key(&id) {
+        if self.contains(&id) {
             return Err(IVFError::DuplicateVector(id));
         }
         self.chunk_refs.insert(id, chunk_id);
         Ok(())
     }
 
+    /// Insert a product-quantized vector
+    pub fn insert_codes(&mut self, id: VectorId, codes: Vec<u8>) -> Result<(), IVFError> {
+        if self.contains(&id) {
+            return Err(IVFError::DuplicateVector(id));
+        }
+        self.codes.insert(id, codes);
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
-        self.vectors.len() + self.chunk_refs.len()
+        self.vectors.len() + self.chunk_refs.len() + self.codes.len()
     }
 
     pub fn has_chunk_refs(&self) -> bool {
@@ -161,10 +271,12 @@ pub struct IVFIndex {
     pub(crate) total_vectors: usize,
     /// Chunk loader for lazy loading vectors from S5 storage
     pub(crate) chunk_loader: Option<Arc<ChunkLoader>>,
-    /// Cache for lazy-loaded vectors (vector_id -> vector)
-    pub(crate) vector_cache: Arc<RwLock<HashMap<VectorId, Vec<f32>>>>,
-    /// Set of deleted vector IDs (soft deletion)
-    pub(crate) deleted: HashSet<VectorId>,
+    /// Lazy-loaded vectors, bounded by `config.vector_cache_capacity`
+    pub(crate) vector_cache: VectorCache,
+    /// Deleted vector IDs with their deletion time (soft deletion)
+    pub(crate) deleted: HashMap<VectorId, DateTime<Utc>>,
+    /// Codebooks learned by `train_pq` when `config.pq` is set
+    pub(crate) pq: Option<ProductQuantizer>,
 }
 
 impl IVFIndex {
@@ -177,6 +289,7 @@ impl IVFIndex {
             Some(seed) => StdRng::seed_from_u64(seed),
             None => StdRng::from_entropy(),
         };
+        let vector_cache = VectorCache::new(config.vector_cache_capacity);
 
         Self {
             config,
@@ -187,8 +300,9 @@ impl IVFIndex {
             rng,
             total_vectors: 0,
             chunk_loader: None,
-            vector_cache: Arc::new(RwLock::new(HashMap::new())),
-            deleted: HashSet::new(),
+            vector_cache,
+            deleted: HashMap::new(),
+            pq: None,
         }
     }
 
@@ -202,6 +316,7 @@ impl IVFIndex {
             Some(seed) => StdRng::seed_from_u64(seed),
             None => StdRng::from_entropy(),
         };
+        let vector_cache = VectorCache::new(config.vector_cache_capacity);
 
         Self {
             config,
@@ -212,8 +327,9 @@ impl IVFIndex {
             rng,
             total_vectors: 0,
             chunk_loader,
-            vector_cache: Arc::new(RwLock::new(HashMap::new())),
-            deleted: HashSet::new(),
+            vector_cache,
+            deleted: HashMap::new(),
+            pq: None,
         }
     }
 
@@ -221,6 +337,12 @@ impl IVFIndex {
         &self.config
     }
 
+    /// Distance between two vectors under the configured metric; smaller is
+    /// always closer, see `DistanceMetric::distance`
+    pub fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        self.config.metric.distance(a, b)
+    }
+
     pub fn is_trained(&self) -> bool {
         self.trained
     }
@@ -262,12 +384,13 @@ impl IVFIndex {
                     found: vector.len(),
                 });
             }
+            if !self.config.metric.accepts(vector) {
+                return Err(IVFError::ZeroVector);
+            }
         }
 
         self.dimension = Some(dim);
-
-        // Initialize centroids with k-means++
-        self.centroids = self.initialize_centroids(training_data)?;
+        let result = self.fit_centroids(training_data)?;
 
         // Initialize empty inverted lists
         self.inverted_lists.clear();
@@ -276,6 +399,19 @@ impl IVFIndex {
                 .insert(ClusterId(i), InvertedList::new());
         }
 
+        self.trained = true;
+        if self.config.pq.is_some() {
+            self.train_pq(training_data)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Run k-means over `training_data`, replacing the centroids
+    pub(crate) fn fit_centroids(&mut self, training_data: &[Vec<f32>]) -> Result<TrainResult, IVFError> {
+        // Initialize centroids with k-means++
+        self.centroids = self.initialize_centroids(training_data)?;
+
         // Run k-means
         let mut assignments = vec![ClusterId(0); training_data.len()];
         let mut prev_error = f32::INFINITY;
@@ -323,8 +459,6 @@ impl IVFIndex {
 
         let final_error = self.compute_error(training_data, &assignments);
 
-        self.trained = true;
-
         Ok(TrainResult {
             iterations,
             converged,
@@ -333,13 +467,71 @@ impl IVFIndex {
         })
     }
 
+    /// Learn the product quantizer's codebooks from `training_data`.
+    ///
+    /// `train` calls this when `config.pq` is set; call it again to retrain
+    /// the codebooks. Vectors already in the index are re-encoded.
+    pub fn train_pq(&mut self, training_data: &[Vec<f32>]) -> Result<(), IVFError> {
+        let pq_config = self.config.pq.ok_or_else(|| {
+            IVFError::InvalidConfig("product quantization is not enabled in IVFConfig".to_string())
+        })?;
+        if let (Some(dim), Some(vector)) = (self.dimension, training_data.first()) {
+            if vector.len() != dim {
+                return Err(IVFError::DimensionMismatch {
+                    expected: dim,
+                    actual: vector.len(),
+                });
+            }
+        }
+
+        let quantizer = ProductQuantizer::train(
+            pq_config,
+            self.config.metric,
+            training_data,
+            self.config.max_iterations,
+            &mut self.rng,
+        )?;
+        for list in self.inverted_lists.values_mut() {
+            let decoded: Vec<(VectorId, Vec<f32>)> = match &self.pq {
+                Some(old) => list.codes.drain().map(|(id, codes)| (id, old.decode(&codes))).collect(),
+                None => Vec::new(),
+            };
+            for (id, vector) in list.vectors.drain().chain(decoded) {
+                let codes = quantizer.encode(&vector);
+                list.codes.insert(id, codes);
+            }
+        }
+        self.pq = Some(quantizer);
+
+        Ok(())
+    }
+
+    pub fn product_quantizer(&self) -> Option<&ProductQuantizer> {
+        self.pq.as_ref()
+    }
+
+    pub(crate) fn set_product_quantizer(&mut self, quantizer: ProductQuantizer) {
+        self.pq = Some(quantizer);
+    }
+
+    /// Store `vector` in `cluster_id`'s list, as codes when product
+    /// quantization is enabled
+    pub(crate) fn store_vector(&mut self, cluster_id: ClusterId, id: VectorId, vector: Vec<f32>) -> Result<(), IVFError> {
+        let list = self.inverted_lists.get_mut(&cluster_id).unwrap();
+        match (&self.pq, self.config.pq) {
+            (Some(quantizer), _) => list.insert_codes(id, quantizer.encode(&vector)),
+            (None, Some(_)) => Err(IVFError::PQNotTrained),
+            (None, None) => list.insert(id, vector),
+        }
+    }
+
     fn initialize_centroids(&mut self, data: &[Vec<f32>]) -> Result<Vec<Centroid>, IVFError> {
         let mut centroids = Vec::new();
 
         // k-means++ initialization
         // Choose first centroid randomly
         let first_idx = self.rng.gen_range(0..data.len());
-        centroids.push(Centroid::new(ClusterId(0), data[first_idx].clone()));
+        centroids.push(Centroid::new(ClusterId(0), self.centroid_vector(data[first_idx].clone())));
 
         // Choose remaining centroids with probability proportional to squared distance
         for i in 1..self.config.n_clusters {
@@ -348,7 +540,7 @@ impl IVFIndex {
             // Compute distance to nearest centroid for each point
             for (j, point) in data.iter().enumerate() {
                 for centroid in &centroids {
-                    let dist = euclidean_distance_scalar(point, centroid.vector());
+                    let dist = self.spread(point, centroid.vector());
                     distances[j] = distances[j].min(dist);
                 }
             }
@@ -361,7 +553,7 @@ impl IVFIndex {
             for (j, dist) in distances.iter().enumerate() {
                 cumulative += dist * dist;
                 if cumulative >= threshold {
-                    centroids.push(Centroid::new(ClusterId(i), data[j].clone()));
+                    centroids.push(Centroid::new(ClusterId(i), self.centroid_vector(data[j].clone())));
                     break;
                 }
             }
@@ -375,7 +567,7 @@ impl IVFIndex {
         let mut best_dist = f32::INFINITY;
 
         for centroid in &self.centroids {
-            let dist = euclidean_distance_scalar(vector, centroid.vector());
+            let dist = self.distance(vector, centroid.vector());
             if dist < best_dist {
                 best_dist = dist;
                 best_id = centroid.id();
@@ -410,18 +602,33 @@ impl IVFIndex {
             let count = counts[&centroid.id()];
             if count > 0 {
                 let sum = &sums[&centroid.id()];
-                let new_vector: Vec<f32> = sum.iter().map(|&s| s / count as f32).collect();
-                centroid.update(new_vector);
+                let mean: Vec<f32> = sum.iter().map(|&s| s / count as f32).collect();
+                centroid.update(centroid_vector(self.config.metric, mean));
             }
         }
     }
 
+    /// Non-negative dissimilarity driving k-means++ seeding and the training
+    /// error. Negated inner products can be negative, so `DotProduct`
+    /// clusters are seeded and scored by L2 while still assigned by inner
+    /// product.
+    pub(crate) fn spread(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self.config.metric {
+            DistanceMetric::DotProduct => euclidean_distance(a, b),
+            metric => metric.distance(a, b),
+        }
+    }
+
+    pub(crate) fn centroid_vector(&self, vector: Vec<f32>) -> Vec<f32> {
+        centroid_vector(self.config.metric, vector)
+    }
+
     fn compute_error(&self, data: &[Vec<f32>], assignments: &[ClusterId]) -> f32 {
         let mut total_error = 0.0;
 
         for (vector, &cluster_id) in data.iter().zip(assignments) {
             let centroid = &self.centroids[cluster_id.0];
-            let dist = euclidean_distance_scalar(vector, centroid.vector());
+            let dist = self.spread(vector, centroid.vector());
             total_error += dist * dist;
         }
 
@@ -441,13 +648,15 @@ impl IVFIndex {
                 });
             }
         }
+        if !self.config.metric.accepts(&vector) {
+            return Err(IVFError::ZeroVector);
+        }
 
         // Find nearest cluster
         let cluster_id = self.find_nearest_centroid(&vector);
 
         // Insert into inverted list
-        let list = self.inverted_lists.get_mut(&cluster_id).unwrap();
-        list.insert(id, vector)?;
+        self.store_vector(cluster_id, id, vector)?;
 
         self.total_vectors += 1;
 
@@ -468,21 +677,23 @@ impl IVFIndex {
                 });
             }
         }
+        if !self.config.metric.accepts(&vector) {
+            return Err(IVFError::ZeroVector);
+        }
 
         // Find nearest cluster
         let cluster_id = self.find_nearest_centroid(&vector);
 
         // Insert into inverted list based on whether we have chunk_loader
-        let list = self.inverted_lists.get_mut(&cluster_id).unwrap();
-
         if let Some(chunk) = chunk_id {
             // Lazy loading mode: store chunk reference
+            let list = self.inverted_lists.get_mut(&cluster_id).unwrap();
             list.insert_with_chunk(id.clone(), chunk)?;
             // Cache the vector for immediate use
-            self.vector_cache.write().unwrap().insert(id, vector);
+            self.vector_cache.put(id, vector);
         } else {
             // Regular mode: store vector inline
-            list.insert(id, vector)?;
+            self.store_vector(cluster_id, id, vector)?;
         }
 
         self.total_vectors += 1;
@@ -549,20 +760,94 @@ impl IVFIndex {
             .collect()
     }
 
-    /// Get a specific vector by ID (searches all clusters)
+    /// Get a specific vector by ID (searches all clusters). Vectors stored
+    /// in chunks are only found while they are in the vector cache.
     pub fn get_vector_by_id(&self, vector_id: &VectorId) -> Option<Vec<f32>> {
         // Search through all inverted lists to find the vector
         for list in self.inverted_lists.values() {
             if let Some(vector) = list.vectors.get(vector_id) {
                 return Some(vector.clone());
             }
+            if let (Some(codes), Some(quantizer)) = (list.codes.get(vector_id), &self.pq) {
+                return Some(quantizer.decode(codes));
+            }
         }
         // Also check the vector cache (for lazy-loaded vectors)
-        self.vector_cache.read().unwrap().get(vector_id).cloned()
+        self.vector_cache.get(vector_id)
+    }
+
+    /// Like `get_vector_by_id`, but loads vectors stored in chunks that are
+    /// not cached, caching them for later lookups
+    pub async fn load_vector(&self, vector_id: &VectorId) -> Result<Option<Vec<f32>>, IVFError> {
+        if let Some(vector) = self.get_vector_by_id(vector_id) {
+            return Ok(Some(vector));
+        }
+        let chunk_path = match self
+            .inverted_lists
+            .values()
+            .find_map(|list| list.chunk_refs.get(vector_id))
+        {
+            Some(path) => path.clone(),
+            None => return Ok(None),
+        };
+        let chunk_loader = self.chunk_loader.as_ref().ok_or_else(|| {
+            IVFError::ChunkLoadError(
+                "Chunk references found but no chunk loader available".to_string(),
+            )
+        })?;
+
+        let chunk = chunk_loader
+            .load_chunks_or_skip(&[chunk_path])
+            .await
+            .map_err(|e| IVFError::ChunkLoadError(e.to_string()))?
+            .pop()
+            .flatten();
+        let vector = chunk.and_then(|chunk| chunk.vectors.get(vector_id).cloned());
+        if let Some(vector) = &vector {
+            self.vector_cache.put(vector_id.clone(), vector.clone());
+        }
+        Ok(vector)
+    }
+
+    /// Hits, misses and evictions of the lazily loaded vector cache
+    pub fn vector_cache_metrics(&self) -> CacheMetrics {
+        self.vector_cache.metrics()
+    }
+
+    /// Chunks skipped by the loader's `SkipMissing` policy so far
+    pub async fn chunk_integrity_report(&self) -> ChunkIntegrityReport {
+        let skipped_chunks = match &self.chunk_loader {
+            Some(loader) => loader.skipped_chunks().await,
+            None => return ChunkIntegrityReport::default(),
+        };
+        let unavailable_vectors = self
+            .inverted_lists
+            .values()
+            .flat_map(|list| list.chunk_refs.values())
+            .filter(|path| skipped_chunks.iter().any(|c| &c.path == *path))
+            .count();
+
+        ChunkIntegrityReport {
+            skipped_chunks,
+            unavailable_vectors,
+        }
     }
 
     /// Get all vectors for a specific cluster (lazy loads from chunks if needed)
+    ///
+    /// Product-quantized vectors are returned decoded, so they are
+    /// approximations of the inserted vectors.
     pub async fn get_cluster_vectors(&self, cluster_id: ClusterId) -> Result<Vec<(VectorId, Vec<f32>)>, IVFError> {
+        let mut vectors = self.stored_cluster_vectors(cluster_id).await?;
+        if let Some(quantizer) = &self.pq {
+            let list = &self.inverted_lists[&cluster_id];
+            vectors.extend(list.codes.iter().map(|(id, codes)| (id.clone(), quantizer.decode(codes))));
+        }
+        Ok(vectors)
+    }
+
+    /// Full-precision vectors of a cluster, in memory or in chunks
+    async fn stored_cluster_vectors(&self, cluster_id: ClusterId) -> Result<Vec<(VectorId, Vec<f32>)>, IVFError> {
         let list = self.inverted_lists.get(&cluster_id)
             .ok_or_else(|| IVFError::InvalidConfig(format!("Cluster {:?} not found", cluster_id)))?;
 
@@ -581,8 +866,8 @@ impl IVFIndex {
 
                 for (vector_id, chunk_id) in &list.chunk_refs {
                     // Check cache first
-                    if let Some(cached_vector) = self.vector_cache.read().unwrap().get(vector_id) {
-                        vectors.push((vector_id.clone(), cached_vector.clone()));
+                    if let Some(cached_vector) = self.vector_cache.get(vector_id) {
+                        vectors.push((vector_id.clone(), cached_vector));
                         continue;
                     }
 
@@ -592,18 +877,25 @@ impl IVFIndex {
                         .push(vector_id.clone());
                 }
 
-                // Load chunks and extract vectors
-                for (chunk_id, vector_ids) in chunks_to_load {
-                    let chunk_path = chunk_id; // chunk_id is the path
-                    let chunk = chunk_loader.load_chunk(&chunk_path)
-                        .await
-                        .map_err(|e| IVFError::ChunkLoadError(e.to_string()))?;
+                // Load all needed chunks in one batched fetch (chunk ids are paths)
+                let chunk_paths: Vec<String> = chunks_to_load.keys().cloned().collect();
+                let chunks = chunk_loader
+                    .load_chunks_or_skip(&chunk_paths)
+                    .await
+                    .map_err(|e| IVFError::ChunkLoadError(e.to_string()))?;
+
+                for (chunk_path, chunk) in chunk_paths.iter().zip(chunks) {
+                    let chunk = match chunk {
+                        Some(chunk) => chunk,
+                        // Skipped under `ChunkLoadPolicy::SkipMissing`
+                        None => continue,
+                    };
 
                     // Extract requested vectors from chunk
-                    for vector_id in vector_ids {
+                    for vector_id in chunks_to_load[chunk_path].iter().cloned() {
                         if let Some(vector) = chunk.vectors.get(&vector_id) {
                             // Cache the vector
-                            self.vector_cache.write().unwrap().insert(vector_id.clone(), vector.clone());
+                            self.vector_cache.put(vector_id.clone(), vector.clone());
                             vectors.push((vector_id, vector.clone()));
                         }
                     }
@@ -629,54 +921,288 @@ impl IVFIndex {
         k: usize,
         n_probe: usize,
     ) -> Result<Vec<SearchResult>, IVFError> {
-        if !self.trained {
-            return Err(IVFError::NotTrained);
-        }
+        self.validate_query(query)?;
+        let clusters = self.nearest_clusters(query, n_probe);
+        self.search_clusters(query, k, &clusters, None).await
+    }
 
-        if let Some(dim) = self.dimension {
-            if query.len() != dim {
-                return Err(IVFError::DimensionMismatch {
-                    expected: dim,
-                    actual: query.len(),
-                });
+    /// Single nearest neighbour over the `n_probe` closest clusters.
+    ///
+    /// Tracks the best candidate in one pass instead of collecting and
+    /// sorting every probed vector.
+    pub async fn nearest(
+        &self,
+        query: &[f32],
+        n_probe: usize,
+    ) -> Result<Option<SearchResult>, IVFError> {
+        self.validate_query(query)?;
+
+        let mut best: Option<(VectorId, f32)> = None;
+        for cluster_id in self.nearest_clusters(query, n_probe) {
+            for (id, vector) in self.get_cluster_vectors(cluster_id).await? {
+                if self.is_deleted(&id) {
+                    continue;
+                }
+                let distance = self.distance(query, &vector);
+                if best.as_ref().is_none_or(|(_, d)| distance < *d) {
+                    best = Some((id, distance));
+                }
             }
         }
 
-        // Find n_probe nearest clusters
+        Ok(best.map(|(id, distance)| SearchResult::new(id, distance, None)))
+    }
+
+    /// Search the index as it was at `as_of`: vectors soft-deleted after
+    /// that time are treated as live.
+    ///
+    /// Vectors that have been vacuumed are gone and can't be returned.
+    pub async fn search_as_of(
+        &self,
+        query: &[f32],
+        k: usize,
+        as_of: DateTime<Utc>,
+    ) -> Result<Vec<SearchResult>, IVFError> {
+        self.validate_query(query)?;
+        let clusters = self.nearest_clusters(query, self.config.n_probe);
+        self.search_clusters(query, k, &clusters, Some(as_of)).await
+    }
+
+    fn nearest_clusters(&self, query: &[f32], n_probe: usize) -> Vec<ClusterId> {
         let mut cluster_distances: Vec<(ClusterId, f32)> = self
             .centroids
             .iter()
             .map(|centroid| {
-                let dist = euclidean_distance_scalar(query, centroid.vector());
+                let dist = self.distance(query, centroid.vector());
                 (centroid.id(), dist)
             })
             .collect();
 
-        cluster_distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        cluster_distances.sort_by(|a, b| a.1.total_cmp(&b.1));
         cluster_distances.truncate(n_probe);
 
-        // Search within selected clusters (with lazy loading support)
-        let mut results = Vec::new();
+        cluster_distances.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Search only the given clusters instead of the ones nearest to the query.
+    ///
+    /// Useful when clusters map onto partitions (regions, categories) or for
+    /// inspecting a single cluster. Every id must name an existing cluster.
+    pub async fn search_in_clusters(
+        &self,
+        query: &[f32],
+        k: usize,
+        clusters: &[ClusterId],
+    ) -> Result<Vec<SearchResult>, IVFError> {
+        self.validate_query(query)?;
+
+        let mut unique = Vec::with_capacity(clusters.len());
+        for cluster_id in clusters {
+            if !self.inverted_lists.contains_key(cluster_id) {
+                return Err(IVFError::ClusterNotFound(*cluster_id));
+            }
+            if !unique.contains(cluster_id) {
+                unique.push(*cluster_id);
+            }
+        }
+
+        self.search_clusters(query, k, &unique, None).await
+    }
+
+    /// Search a batch of queries, sharing cluster fetches between queries
+    /// that probe the same clusters. Results are in query order.
+    pub async fn search_batch(
+        &self,
+        queries: &[Vec<f32>],
+        k: usize,
+    ) -> Result<Vec<Vec<SearchResult>>, IVFError> {
+        let options = BatchSearchOptions {
+            n_probe: self.config.n_probe,
+            share_clusters: true,
+        };
+        Ok(self.search_batch_with(queries, k, options).await?.0)
+    }
+
+    /// `search_batch` with explicit options, also reporting the work done.
+    ///
+    /// With `share_clusters`, identical queries are scored once, each probed
+    /// cluster is fetched once, and its vectors are scored against every
+    /// query probing it while they are still hot in cache. Without it every
+    /// query is searched independently, as a baseline.
+    pub async fn search_batch_with(
+        &self,
+        queries: &[Vec<f32>],
+        k: usize,
+        options: BatchSearchOptions,
+    ) -> Result<(Vec<Vec<SearchResult>>, BatchSearchStats), IVFError> {
+        for query in queries {
+            self.validate_query(query)?;
+        }
+
+        let mut stats = BatchSearchStats::default();
+        if !options.share_clusters {
+            let mut results = Vec::with_capacity(queries.len());
+            for query in queries {
+                let clusters = self.nearest_clusters(query, options.n_probe);
+                stats.distance_computations += self.centroids.len();
+                let mut candidates = Vec::new();
+                for cluster_id in clusters {
+                    let vectors = self.get_cluster_vectors(cluster_id).await?;
+                    stats.cluster_fetches += 1;
+                    for (id, vector) in vectors {
+                        if self.is_deleted(&id) {
+                            continue;
+                        }
+                        stats.distance_computations += 1;
+                        let distance = self.distance(query, &vector);
+                        candidates.push(SearchResult::new(id, distance, None));
+                    }
+                }
+                candidates.sort_by(SearchResult::cmp_distance);
+                candidates.truncate(k);
+                results.push(candidates);
+            }
+            return Ok((results, stats));
+        }
 
-        for (cluster_id, _) in cluster_distances {
-            // Use get_cluster_vectors for lazy loading support
-            let cluster_vectors = self.get_cluster_vectors(cluster_id).await?;
+        // Score each distinct query once; `slot[i]` is query i's distinct index
+        let mut distinct: Vec<&[f32]> = Vec::new();
+        let mut slot = Vec::with_capacity(queries.len());
+        for query in queries {
+            let position = distinct
+                .iter()
+                .position(|q| q.iter().map(|x| x.to_bits()).eq(query.iter().map(|x| x.to_bits())));
+            slot.push(position.unwrap_or_else(|| {
+                distinct.push(query);
+                distinct.len() - 1
+            }));
+        }
+
+        // Which distinct queries probe each cluster
+        let mut probes: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (q, query) in distinct.iter().enumerate() {
+            for cluster_id in self.nearest_clusters(query, options.n_probe) {
+                probes.entry(cluster_id.0).or_default().push(q);
+            }
+            stats.distance_computations += self.centroids.len();
+        }
 
-            for (id, vector) in cluster_vectors {
-                // Skip deleted vectors
+        let mut candidates: Vec<Vec<SearchResult>> = vec![Vec::new(); distinct.len()];
+        for (cluster, probing) in probes {
+            let vectors = self.get_cluster_vectors(ClusterId(cluster)).await?;
+            stats.cluster_fetches += 1;
+            for (id, vector) in vectors {
                 if self.is_deleted(&id) {
                     continue;
                 }
+                for &q in &probing {
+                    let distance = self.distance(distinct[q], &vector);
+                    candidates[q].push(SearchResult::new(id.clone(), distance, None));
+                }
+                stats.distance_computations += probing.len();
+            }
+        }
+
+        for results in &mut candidates {
+            results.sort_by(SearchResult::cmp_distance);
+            results.truncate(k);
+        }
+        let results = slot.into_iter().map(|q| candidates[q].clone()).collect();
+        Ok((results, stats))
+    }
+
+    fn validate_query(&self, query: &[f32]) -> Result<(), IVFError> {
+        if !self.trained {
+            return Err(IVFError::NotTrained);
+        }
+
+        if let Some(dim) = self.dimension {
+            if query.len() != dim {
+                return Err(IVFError::DimensionMismatch {
+                    expected: dim,
+                    actual: query.len(),
+                });
+            }
+        }
+        if !self.config.metric.accepts(query) {
+            return Err(IVFError::ZeroVector);
+        }
+
+        Ok(())
+    }
+
+    async fn search_clusters(
+        &self,
+        query: &[f32],
+        k: usize,
+        clusters: &[ClusterId],
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SearchResult>, IVFError> {
+        // Skip deleted vectors, unless deleted after `as_of`
+        let hidden = |id: &VectorId| {
+            self.deleted
+                .get(id)
+                .is_some_and(|deleted_at| as_of.is_none_or(|as_of| *deleted_at <= as_of))
+        };
+        // Quantized vectors are scored from the query's distance table
+        let table = self.pq.as_ref().map(|quantizer| quantizer.distance_table(query));
+
+        // Load the probed clusters concurrently, then score them in probe
+        // order so results match a one-at-a-time search
+        let mut loaded = vec![None; clusters.len()];
+        let mut pending = clusters.iter().enumerate();
+        let mut in_flight = FuturesUnordered::new();
+        loop {
+            while in_flight.len() < PROBE_CONCURRENCY {
+                match pending.next() {
+                    Some((i, &cluster_id)) => in_flight
+                        .push(async move { (i, self.stored_cluster_vectors(cluster_id).await) }),
+                    None => break,
+                }
+            }
+            match in_flight.next().await {
+                Some((i, vectors)) => loaded[i] = Some(vectors?),
+                None => break,
+            }
+        }
+
+        let mut results = Vec::new();
+        for (&cluster_id, cluster_vectors) in clusters.iter().zip(loaded) {
+            for (id, vector) in cluster_vectors.unwrap_or_default() {
+                if hidden(&id) {
+                    continue;
+                }
 
-                let distance = euclidean_distance_scalar(query, &vector);
+                let distance = self.distance(query, &vector);
                 results.push(SearchResult::new(id, distance, None));
             }
+
+            if let Some(table) = &table {
+                for (id, codes) in &self.inverted_lists[&cluster_id].codes {
+                    if hidden(id) {
+                        continue;
+                    }
+                    results.push(SearchResult::new(id.clone(), table.distance(codes), None));
+                }
+            }
         }
 
         // Sort by distance and take top k
-        results.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+        results.sort_by(SearchResult::cmp_distance);
         results.truncate(k);
 
         Ok(results)
     }
 }
+
+/// Centroids are kept on the unit sphere under `Cosine` (spherical k-means),
+/// so probing ranks clusters by angle alone
+fn centroid_vector(metric: DistanceMetric, mut vector: Vec<f32>) -> Vec<f32> {
+    if metric == DistanceMetric::Cosine {
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+    }
+    vector
+}
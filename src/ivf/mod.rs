@@ -4,16 +4,19 @@
 pub mod core;
 pub mod operations;
 pub mod persistence;
+pub mod pq;
 
 pub use self::core::{Centroid, ClusterId, IVFConfig, IVFError, IVFIndex, TrainResult};
 
+pub use self::pq::{DistanceTable, PQConfig, ProductQuantizer};
+
 pub use self::persistence::{
     calculate_total_size, serialize_centroids, IVFMetadata, IVFPersister, IntegrityCheckResult,
     MigrationResult, PersistenceError, SerializableInvertedList,
 };
 
 pub use self::operations::{
-    AddClustersResult, BalanceResult, BatchInsertResult, ClusterStats, CompactionResult,
-    ExportedCentroid, MemoryUsage, OperationError, OptimizationResult, RetrainResult,
-    SearchQuality,
+    AddClustersResult, BalanceResult, BatchInsertResult, ClusterRebalanceResult, ClusterStats,
+    CompactionResult, ExportedCentroid, MemoryUsage, OperationError, OptimizationResult,
+    RetrainResult, SearchQuality,
 };
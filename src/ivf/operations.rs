@@ -1,9 +1,12 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+use crate::core::chunk::{chunk_file_name, Manifest};
 use crate::core::types::{SearchResult, VectorId};
-use crate::ivf::core::{Centroid, ClusterId, IVFConfig, IVFError, IVFIndex};
+use crate::ivf::core::{Centroid, ClusterId, IVFConfig, IVFError, IVFIndex, InvertedList, TrainResult};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use thiserror::Error;
 
@@ -41,6 +44,20 @@ pub struct RetrainResult {
     pub new_clusters: usize,
     pub vectors_reassigned: usize,
     pub converged: bool,
+    /// k-means iterations run
+    pub iterations: usize,
+    /// Vectors whose cluster id changed
+    pub vectors_moved: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClusterRebalanceResult {
+    pub clusters_modified: usize,
+    pub vectors_moved: usize,
+    /// `cluster_size_variance` before and after
+    pub initial_variance: f32,
+    pub final_variance: f32,
+    pub iterations: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +105,126 @@ pub struct CompactionResult {
     pub clusters_compacted: usize,
 }
 
+/// Deleted-ratio trigger for rewriting lazily loaded chunks
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChunkCompactionConfig {
+    /// Rewrite a chunk once more than this fraction of its vectors are deleted
+    pub deleted_ratio_threshold: f32,
+}
+
+impl Default for ChunkCompactionConfig {
+    fn default() -> Self {
+        Self {
+            deleted_ratio_threshold: 0.5,
+        }
+    }
+}
+
+/// Outcome of rewriting a single chunk
+#[derive(Debug, Clone)]
+pub struct CompactedChunk {
+    /// Storage path of the chunk before compaction (as referenced by the
+    /// inverted lists)
+    pub chunk_path: String,
+    /// Key the compacted chunk was written under, `None` if it was emptied
+    pub compacted_path: Option<String>,
+    pub vectors_before: usize,
+    pub vectors_after: usize,
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+    /// Checksum of the rewritten chunk, `None` if it was emptied
+    pub checksum_after: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChunkCompactionResult {
+    pub chunks_compacted: Vec<CompactedChunk>,
+    /// Chunks left with no vectors, dropped from the manifest
+    pub chunks_removed: Vec<String>,
+    /// Deleted vectors physically dropped from the rewritten chunks
+    pub removed_ids: Vec<VectorId>,
+    pub bytes_reclaimed: usize,
+}
+
+impl ChunkCompactionResult {
+    /// Update chunk metadata, cluster assignments and the deleted list of a
+    /// manifest so it matches the rewritten chunks.
+    ///
+    /// Compacted chunks are pointed at their new keys and the manifest
+    /// generation moves past them. Once the updated manifest is stored,
+    /// `IVFIndex::remove_superseded_chunks` deletes the old keys.
+    pub fn apply_to_manifest(&self, manifest: &mut Manifest) {
+        let chunk_id_of = |path: &str| -> String { split_chunk_key(path).0.to_string() };
+
+        let mut next_generation = manifest.generation + 1;
+        for compacted in &self.chunks_compacted {
+            let Some(compacted_path) = &compacted.compacted_path else {
+                continue;
+            };
+            let chunk_id = chunk_id_of(&compacted.chunk_path);
+            if let Some(meta) = manifest.chunks.iter_mut().find(|c| c.chunk_id == chunk_id) {
+                let generation = split_chunk_key(compacted_path).1;
+                meta.vector_count = compacted.vectors_after;
+                meta.byte_size = compacted.bytes_after;
+                meta.content_hash = None;
+                meta.checksum = compacted.checksum_after.clone();
+                meta.generation = generation;
+                next_generation = next_generation.max(generation.unwrap_or(0));
+            }
+        }
+        if !self.chunks_compacted.is_empty() {
+            manifest.generation = next_generation;
+        }
+
+        let removed_chunks: HashSet<String> =
+            self.chunks_removed.iter().map(|p| chunk_id_of(p)).collect();
+        manifest.chunks.retain(|c| !removed_chunks.contains(&c.chunk_id));
+        if let Some(ivf) = manifest.ivf_structure.as_mut() {
+            for chunk_ids in ivf.cluster_assignments.values_mut() {
+                chunk_ids.retain(|id| !removed_chunks.contains(id));
+            }
+        }
+
+        if let Some(deleted) = manifest.deleted_vectors.as_mut() {
+            let removed: HashSet<String> = self
+                .removed_ids
+                .iter()
+                .flat_map(|id| [id.hash_hex(), id.to_string()])
+                .collect();
+            deleted.retain(|entry| !removed.contains(&entry.id));
+        }
+        manifest.total_vectors = manifest.total_vectors.saturating_sub(self.removed_ids.len());
+    }
+}
+
+/// Split a chunk storage path into its chunk id and generation
+///
+/// Inverse of `chunk_file_name`: `chunks/chunk-3.g2.cbor` gives
+/// `("chunk-3", Some(2))`.
+fn split_chunk_key(path: &str) -> (&str, Option<u64>) {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let stem = file.strip_suffix(".cbor").unwrap_or(file);
+    match stem.rsplit_once(".g") {
+        Some((chunk_id, generation)) => match generation.parse() {
+            Ok(generation) => (chunk_id, Some(generation)),
+            Err(_) => (stem, None),
+        },
+        None => (stem, None),
+    }
+}
+
+/// Storage path one generation past `path`, next to it
+fn next_chunk_key(path: &str) -> String {
+    let (chunk_id, generation) = split_chunk_key(path);
+    let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+    let file = chunk_file_name(chunk_id, Some(generation.unwrap_or(0) + 1));
+    if dir.is_empty() {
+        file
+    } else {
+        format!("{}/{}", dir, file)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BalanceResult {
     pub vectors_moved: usize,
@@ -129,6 +266,70 @@ impl IVFIndex {
         })
     }
 
+    /// Insert many vectors for bulk loading, assigning them all to clusters
+    /// in one pass before filling the inverted lists.
+    ///
+    /// Dimensions are checked up front: if any vector's length differs from
+    /// the index's, nothing is inserted and `DimensionMismatch` is returned.
+    /// Ids already stored or repeated within the batch, and vectors the
+    /// metric rejects, are skipped and reported per id.
+    pub fn insert_batch(
+        &mut self,
+        items: Vec<(VectorId, Vec<f32>)>,
+    ) -> Result<BatchInsertResult, IVFError> {
+        if !self.trained {
+            return Err(IVFError::NotTrained);
+        }
+        if self.config.pq.is_some() && self.pq.is_none() {
+            return Err(IVFError::PQNotTrained);
+        }
+        if let Some(dim) = self.dimension {
+            if let Some((_, vector)) = items.iter().find(|(_, v)| v.len() != dim) {
+                return Err(IVFError::DimensionMismatch {
+                    expected: dim,
+                    actual: vector.len(),
+                });
+            }
+        }
+
+        let mut seen: HashSet<VectorId> = self
+            .inverted_lists
+            .values()
+            .flat_map(|list| {
+                list.vectors
+                    .keys()
+                    .chain(list.chunk_refs.keys())
+                    .chain(list.codes.keys())
+            })
+            .cloned()
+            .collect();
+        let mut errors = Vec::new();
+        let mut assigned = Vec::with_capacity(items.len());
+        for (id, vector) in items {
+            if seen.contains(&id) {
+                errors.push((id.clone(), IVFError::DuplicateVector(id)));
+            } else if !self.config.metric.accepts(&vector) {
+                errors.push((id, IVFError::ZeroVector));
+            } else {
+                seen.insert(id.clone());
+                let cluster_id = self.find_nearest_centroid(&vector);
+                assigned.push((cluster_id, id, vector));
+            }
+        }
+
+        let successful = assigned.len();
+        for (cluster_id, id, vector) in assigned {
+            self.store_vector(cluster_id, id, vector)?;
+        }
+        self.total_vectors += successful;
+
+        Ok(BatchInsertResult {
+            successful,
+            failed: errors.len(),
+            errors,
+        })
+    }
+
     pub async fn batch_search(
         &self,
         queries: &[Vec<f32>],
@@ -145,54 +346,450 @@ impl IVFIndex {
     }
 
     // Retraining operations
-    pub fn retrain(&mut self, new_config: IVFConfig) -> Result<RetrainResult, OperationError> {
-        if !self.is_trained() {
-            return Err(IVFError::NotTrained.into());
+
+    /// Re-run k-means on the vectors in the index and move every vector to
+    /// its new nearest cluster, e.g. after heavy insertion skewed the
+    /// clusters.
+    ///
+    /// Vectors keep their storage: in-memory vectors, chunk references and
+    /// PQ codes are moved between lists as they are. Centroids are learned
+    /// from live vectors only; soft-deleted vectors are reassigned and stay
+    /// deleted. Vectors in chunks skipped by `ChunkLoadPolicy::SkipMissing`
+    /// stay in their cluster.
+    pub async fn retrain(&mut self) -> Result<RetrainResult, IVFError> {
+        if !self.trained {
+            return Err(IVFError::NotTrained);
         }
 
-        let old_clusters = self.config().n_clusters;
-        let old_vectors = self.total_vectors();
+        let mut vectors: HashMap<VectorId, Vec<f32>> = HashMap::new();
+        for cluster_id in (0..self.centroids.len()).map(ClusterId) {
+            vectors.extend(self.get_cluster_vectors(cluster_id).await?);
+        }
+        let training_data: Vec<Vec<f32>> = vectors
+            .iter()
+            .filter(|(id, _)| !self.deleted.contains_key(*id))
+            .map(|(_, vector)| vector.clone())
+            .collect();
+        if training_data.len() < self.config.n_clusters {
+            return Err(IVFError::InsufficientTrainingData {
+                got: training_data.len(),
+                need: self.config.n_clusters,
+            });
+        }
 
-        // Collect all existing vectors
-        let mut all_vectors = Vec::new();
-        let mut all_ids = Vec::new();
+        let train_result = self.fit_centroids(&training_data)?;
+        let assignment: HashMap<VectorId, ClusterId> = vectors
+            .iter()
+            .map(|(id, vector)| (id.clone(), self.find_nearest_centroid(vector)))
+            .collect();
+        let (vectors_moved, _) = self.move_vectors(&assignment);
 
-        for list in self.get_all_inverted_lists().values() {
-            for (id, vector) in &list.vectors {
-                all_ids.push(id.clone());
-                all_vectors.push(vector.clone());
+        Ok(RetrainResult {
+            old_clusters: self.config.n_clusters,
+            new_clusters: self.config.n_clusters,
+            vectors_reassigned: self.total_vectors,
+            converged: train_result.converged,
+            iterations: train_result.iterations,
+            vectors_moved,
+        })
+    }
+
+    /// Even out cluster sizes by splitting the largest cluster and merging
+    /// away the smallest until `cluster_size_variance` is at most
+    /// `target_variance`.
+    ///
+    /// Each iteration 2-means splits the largest cluster, reuses the smallest
+    /// cluster's slot for one half, and reassigns every vector to its nearest
+    /// centroid. An iteration is kept only if it lowers the variance; the
+    /// loop stops once an iteration gains less than `converge_threshold` or
+    /// after `max_iterations`.
+    pub async fn rebalance_clusters(
+        &mut self,
+        target_variance: f32,
+        max_iterations: usize,
+        converge_threshold: f32,
+    ) -> Result<ClusterRebalanceResult, IVFError> {
+        if !self.trained {
+            return Err(IVFError::NotTrained);
+        }
+
+        let mut vectors: HashMap<VectorId, Vec<f32>> = HashMap::new();
+        let mut original: HashMap<VectorId, ClusterId> = HashMap::new();
+        for cluster_id in (0..self.centroids.len()).map(ClusterId) {
+            vectors.extend(self.get_cluster_vectors(cluster_id).await?);
+            let list = &self.inverted_lists[&cluster_id];
+            for id in list.vectors.keys().chain(list.chunk_refs.keys()).chain(list.codes.keys()) {
+                original.insert(id.clone(), cluster_id);
             }
         }
 
-        // Update config
-        self.config = new_config;
-        self.trained = false;
+        let n_clusters = self.centroids.len();
+        let sizes_of = |assignment: &HashMap<VectorId, ClusterId>| {
+            let mut sizes = vec![0usize; n_clusters];
+            for cluster_id in assignment.values() {
+                sizes[cluster_id.0] += 1;
+            }
+            sizes
+        };
+        let initial_variance = normalized_variance(&sizes_of(&original));
+
+        let mut centroids: Vec<Vec<f32>> = self.centroids.iter().map(|c| c.vector().clone()).collect();
+        let mut assignment = original.clone();
+        let mut variance = initial_variance;
+        let mut iterations = 0;
+        while iterations < max_iterations && variance > target_variance && n_clusters > 1 {
+            iterations += 1;
+
+            let sizes = sizes_of(&assignment);
+            let largest = (0..n_clusters).max_by_key(|&i| sizes[i]).unwrap();
+            let smallest = (0..n_clusters)
+                .filter(|&i| i != largest)
+                .min_by_key(|&i| sizes[i])
+                .unwrap();
+            let members: Vec<&Vec<f32>> = assignment
+                .iter()
+                .filter(|(_, cluster_id)| cluster_id.0 == largest)
+                .filter_map(|(id, _)| vectors.get(id))
+                .collect();
+            if members.len() < 2 {
+                break;
+            }
 
-        // Retrain with new config
-        let train_result = self.train(&all_vectors)?;
+            let (first, second) = self.split_cluster(&members);
+            let mut candidate = centroids.clone();
+            candidate[largest] = first;
+            candidate[smallest] = second;
+            let mut proposed = assignment.clone();
+            for (id, vector) in &vectors {
+                let nearest = (0..n_clusters)
+                    .min_by(|&a, &b| {
+                        self.distance(vector, &candidate[a])
+                            .total_cmp(&self.distance(vector, &candidate[b]))
+                    })
+                    .unwrap();
+                proposed.insert(id.clone(), ClusterId(nearest));
+            }
 
-        // Clear inverted lists and reset counter
-        self.inverted_lists.clear();
-        self.total_vectors = 0;
+            let proposed_variance = normalized_variance(&sizes_of(&proposed));
+            if proposed_variance >= variance {
+                break;
+            }
+            let gain = variance - proposed_variance;
+            centroids = candidate;
+            assignment = proposed;
+            variance = proposed_variance;
+            if gain < converge_threshold {
+                break;
+            }
+        }
+
+        let mut clusters_modified: HashSet<ClusterId> = self
+            .centroids
+            .iter()
+            .zip(&centroids)
+            .filter(|(old, new)| old.vector() != *new)
+            .map(|(old, _)| old.id())
+            .collect();
+        for (id, vector) in centroids.into_iter().enumerate() {
+            self.centroids[id] = Centroid::new(ClusterId(id), vector);
+        }
+        let (vectors_moved, touched) = self.move_vectors(&assignment);
+        clusters_modified.extend(touched);
+
+        Ok(ClusterRebalanceResult {
+            clusters_modified: clusters_modified.len(),
+            vectors_moved,
+            initial_variance,
+            final_variance: variance,
+            iterations,
+        })
+    }
+
+    /// Scale-free spread of cluster sizes: their variance divided by the
+    /// squared mean size. 0 when every cluster holds as many vectors, 1 when
+    /// sizes typically differ from the mean by the mean itself.
+    pub fn cluster_size_variance(&self) -> f32 {
+        let sizes: Vec<usize> = (0..self.centroids.len())
+            .map(|i| self.get_cluster_size(ClusterId(i)))
+            .collect();
+        normalized_variance(&sizes)
+    }
+
+    /// Two centroids for the halves of a cluster, by 2-means seeded with
+    /// two far-apart members
+    fn split_cluster(&self, members: &[&Vec<f32>]) -> (Vec<f32>, Vec<f32>) {
+        let farthest_from = |from: &[f32]| {
+            members
+                .iter()
+                .copied()
+                .max_by(|a, b| self.spread(from, a).total_cmp(&self.spread(from, b)))
+                .unwrap()
+        };
+        let mut first = farthest_from(members[0]).clone();
+        let mut second = farthest_from(&first).clone();
+
+        let dim = first.len();
+        for _ in 0..self.config.max_iterations {
+            let mut sums = [vec![0.0f32; dim], vec![0.0f32; dim]];
+            let mut counts = [0usize; 2];
+            for member in members {
+                let half = usize::from(self.spread(member, &second) < self.spread(member, &first));
+                sums[half].iter_mut().zip(member.iter()).for_each(|(s, x)| *s += x);
+                counts[half] += 1;
+            }
+            if counts.contains(&0) {
+                break;
+            }
+            let [first_sum, second_sum] = sums;
+            let next_first = self.centroid_vector(first_sum.into_iter().map(|s| s / counts[0] as f32).collect());
+            let next_second = self.centroid_vector(second_sum.into_iter().map(|s| s / counts[1] as f32).collect());
+            if next_first == first && next_second == second {
+                break;
+            }
+            first = next_first;
+            second = next_second;
+        }
+
+        (first, second)
+    }
+
+    /// Move each vector to its cluster in `assignment`, keeping how it is
+    /// stored; vectors missing from `assignment` stay put. Returns how many
+    /// moved and the clusters they left or joined.
+    fn move_vectors(&mut self, assignment: &HashMap<VectorId, ClusterId>) -> (usize, HashSet<ClusterId>) {
+        let old_lists = std::mem::take(&mut self.inverted_lists);
         for i in 0..self.config.n_clusters {
-            self.inverted_lists
-                .insert(ClusterId(i), crate::ivf::core::InvertedList::new());
+            self.inverted_lists.insert(ClusterId(i), InvertedList::new());
         }
 
-        // Reinsert all vectors
-        for (id, vector) in all_ids.iter().zip(all_vectors.iter()) {
-            self.insert(id.clone(), vector.clone())?;
+        let mut vectors_moved = 0;
+        let mut touched = HashSet::new();
+        for (old_cluster, list) in old_lists {
+            let mut target = |id: &VectorId| {
+                let cluster_id = assignment.get(id).copied().unwrap_or(old_cluster);
+                if cluster_id != old_cluster {
+                    vectors_moved += 1;
+                    touched.insert(old_cluster);
+                    touched.insert(cluster_id);
+                }
+                cluster_id
+            };
+            for (id, vector) in list.vectors {
+                let cluster_id = target(&id);
+                self.inverted_lists.get_mut(&cluster_id).unwrap().vectors.insert(id, vector);
+            }
+            for (id, chunk_id) in list.chunk_refs {
+                let cluster_id = target(&id);
+                self.inverted_lists.get_mut(&cluster_id).unwrap().chunk_refs.insert(id, chunk_id);
+            }
+            for (id, codes) in list.codes {
+                let cluster_id = target(&id);
+                self.inverted_lists.get_mut(&cluster_id).unwrap().codes.insert(id, codes);
+            }
+        }
+
+        (vectors_moved, touched)
+    }
+
+    /// Retrain with `new_config`, rebuilding the index from every stored
+    /// vector. Vectors referenced through chunks stay chunk references.
+    pub async fn retrain_with_config(&mut self, new_config: IVFConfig) -> Result<RetrainResult, OperationError> {
+        if !self.is_trained() {
+            return Err(IVFError::NotTrained.into());
         }
 
+        let old_clusters = self.config().n_clusters;
+        let (mut retrained, train_result) = IVFIndex::build_from_vectors(
+            new_config,
+            self.chunk_loader.clone(),
+            self.stored_vectors().await?,
+        )?;
+        retrained.sync_with(self).await?;
+        let vectors_moved = retrained.vectors_moved_from(self);
+        *self = retrained;
+
         Ok(RetrainResult {
             old_clusters,
             new_clusters: self.config.n_clusters,
-            vectors_reassigned: old_vectors,
+            vectors_reassigned: self.total_vectors,
             converged: train_result.converged,
+            iterations: train_result.iterations,
+            vectors_moved,
         })
     }
 
-    pub fn add_clusters(
+    /// Vectors whose cluster id differs from the one they had in `previous`
+    pub(crate) fn vectors_moved_from(&self, previous: &IVFIndex) -> usize {
+        let clusters = |index: &IVFIndex| -> HashMap<VectorId, ClusterId> {
+            index
+                .inverted_lists
+                .iter()
+                .flat_map(|(cluster_id, list)| {
+                    list.vectors
+                        .keys()
+                        .chain(list.chunk_refs.keys())
+                        .chain(list.codes.keys())
+                        .map(move |id| (id.clone(), *cluster_id))
+                })
+                .collect()
+        };
+        let before = clusters(previous);
+        clusters(self)
+            .into_iter()
+            .filter(|(id, cluster_id)| before.get(id).is_some_and(|old| old != cluster_id))
+            .count()
+    }
+
+    /// Copy out every stored vector, e.g. to retrain from a snapshot without
+    /// keeping the index write-locked. Vectors referenced through chunks are
+    /// loaded with the chunk loader and product-quantized vectors are copied
+    /// out decoded.
+    ///
+    /// Fails rather than leaving vectors out when a chunk cannot be read,
+    /// including chunks skipped by `ChunkLoadPolicy::SkipMissing`.
+    pub async fn stored_vectors(&self) -> Result<Vec<(VectorId, Vec<f32>)>, IVFError> {
+        let mut vectors = Vec::with_capacity(self.total_vectors);
+        for (cluster_id, list) in &self.inverted_lists {
+            let cluster_vectors = self.get_cluster_vectors(*cluster_id).await?;
+            if cluster_vectors.len() < list.len() {
+                return Err(IVFError::ChunkLoadError(format!(
+                    "{} vectors of cluster {:?} could not be loaded from their chunks",
+                    list.len() - cluster_vectors.len(),
+                    cluster_id
+                )));
+            }
+            vectors.extend(cluster_vectors);
+        }
+        Ok(vectors)
+    }
+
+    /// Train a fresh index with `config` on `vectors` and insert them all.
+    ///
+    /// Building into a separate index lets callers keep serving searches from
+    /// the old one and swap the result in afterwards.
+    pub fn build_from_vectors(
+        config: IVFConfig,
+        chunk_loader: Option<std::sync::Arc<crate::storage::chunk_loader::ChunkLoader>>,
+        vectors: Vec<(VectorId, Vec<f32>)>,
+    ) -> Result<(IVFIndex, TrainResult), OperationError> {
+        Self::build_from_vectors_with_samples(config, chunk_loader, vectors, &[])
+    }
+
+    /// `build_from_vectors`, with `extra_samples` added to the k-means
+    /// training data only; they are not inserted into the index.
+    pub fn build_from_vectors_with_samples(
+        config: IVFConfig,
+        chunk_loader: Option<std::sync::Arc<crate::storage::chunk_loader::ChunkLoader>>,
+        vectors: Vec<(VectorId, Vec<f32>)>,
+        extra_samples: &[Vec<f32>],
+    ) -> Result<(IVFIndex, TrainResult), OperationError> {
+        if !config.is_valid() {
+            return Err(OperationError::InvalidParameter(
+                "Invalid IVF configuration".to_string(),
+            ));
+        }
+
+        let mut index = IVFIndex::with_chunk_loader(config, chunk_loader);
+        let training_data: Vec<Vec<f32>> = vectors
+            .iter()
+            .map(|(_, v)| v.clone())
+            .chain(extra_samples.iter().cloned())
+            .collect();
+        let train_result = index.train(&training_data)?;
+
+        for (id, vector) in vectors {
+            index.insert(id, vector)?;
+        }
+
+        Ok((index, train_result))
+    }
+
+    /// Bring an index built from a snapshot of `current` up to date with
+    /// inserts, updates, vacuums and deletions made to `current` since the
+    /// snapshot.
+    ///
+    /// In-memory and product-quantized vectors are compared by content, so a
+    /// vector replaced in place is copied over again. Vectors `current`
+    /// references through chunks become chunk references here too, pointing
+    /// at the same chunk.
+    ///
+    /// Returns the number of vectors added, replaced or removed.
+    pub async fn sync_with(&mut self, current: &IVFIndex) -> Result<usize, OperationError> {
+        let mut changed = 0;
+        let mut current_ids = HashSet::new();
+
+        for list in current.inverted_lists.values() {
+            for (id, chunk_path) in &list.chunk_refs {
+                current_ids.insert(id.clone());
+                if self.relink_chunk(id, chunk_path) {
+                    continue;
+                }
+                let vector = current
+                    .load_vector(id)
+                    .await?
+                    .ok_or_else(|| IVFError::VectorNotFound(id.clone()))?;
+                self.insert_with_chunk(id.clone(), vector, Some(chunk_path.clone()))?;
+                changed += 1;
+            }
+
+            let decoded = current.pq.iter().flat_map(|quantizer| {
+                list.codes.iter().map(|(id, codes)| (id, quantizer.decode(codes)))
+            });
+            let in_memory = list.vectors.iter().map(|(id, vector)| (id, vector.clone()));
+            for (id, vector) in in_memory.chain(decoded) {
+                current_ids.insert(id.clone());
+                if self.holds(id, &vector) {
+                    continue;
+                }
+                if self.inverted_lists.values().any(|list| list.contains(id)) {
+                    self.remove(id)?;
+                }
+                self.insert(id.clone(), vector)?;
+                changed += 1;
+            }
+        }
+
+        let stale: Vec<VectorId> = self
+            .inverted_lists
+            .values()
+            .flat_map(|list| list.vectors.keys().chain(list.chunk_refs.keys()).chain(list.codes.keys()))
+            .filter(|id| !current_ids.contains(*id))
+            .cloned()
+            .collect();
+        for id in &stale {
+            self.remove(id)?;
+        }
+        changed += stale.len();
+
+        self.deleted = current.deleted.clone();
+        Ok(changed)
+    }
+
+    /// Whether `id` is stored here with exactly `vector`'s content, or its
+    /// codes when product quantization is enabled
+    fn holds(&self, id: &VectorId, vector: &[f32]) -> bool {
+        self.inverted_lists.values().any(|list| {
+            match (list.vectors.get(id), list.codes.get(id), &self.pq) {
+                (Some(stored), _, _) => stored.as_slice() == vector,
+                (None, Some(codes), Some(quantizer)) => *codes == quantizer.encode(vector),
+                _ => false,
+            }
+        })
+    }
+
+    /// Store `id` as a reference to `chunk_path` in whichever cluster holds
+    /// it, dropping any in-memory copy. Returns false if `id` isn't stored.
+    fn relink_chunk(&mut self, id: &VectorId, chunk_path: &str) -> bool {
+        let Some(list) = self.inverted_lists.values_mut().find(|list| list.contains(id)) else {
+            return false;
+        };
+        list.vectors.remove(id);
+        list.codes.remove(id);
+        list.chunk_refs.insert(id.clone(), chunk_path.to_string());
+        true
+    }
+
+    pub async fn add_clusters(
         &mut self,
         n_clusters_to_add: usize,
     ) -> Result<AddClustersResult, OperationError> {
@@ -211,7 +808,7 @@ impl IVFIndex {
         new_config.n_clusters += n_clusters_to_add;
 
         // Retrain with new config
-        let retrain_result = self.retrain(new_config)?;
+        let retrain_result = self.retrain_with_config(new_config).await?;
 
         Ok(AddClustersResult {
             clusters_added: n_clusters_to_add,
@@ -310,6 +907,11 @@ impl IVFIndex {
                 // HashMap entry overhead
                 inverted_lists_bytes += 32;
             }
+
+            for codes in list.codes.values() {
+                inverted_lists_bytes += 48 + 32;
+                vectors_bytes += codes.len();
+            }
         }
 
         // Index structure overhead
@@ -403,6 +1005,7 @@ impl IVFIndex {
         // For now, just shrink to fit
         for list in self.inverted_lists.values_mut() {
             list.vectors.shrink_to_fit();
+            list.codes.shrink_to_fit();
             clusters_compacted += 1;
         }
 
@@ -567,10 +1170,20 @@ impl IVFIndex {
 
     /// Mark a vector as deleted (soft deletion)
     pub fn mark_deleted(&mut self, id: &VectorId) -> Result<(), IVFError> {
+        self.mark_deleted_at(id, Utc::now())
+    }
+
+    /// `mark_deleted` with an explicit deletion time, e.g. when moving a
+    /// deleted vector over from another index
+    pub fn mark_deleted_at(
+        &mut self,
+        id: &VectorId,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), IVFError> {
         // Check if vector exists in any inverted list
         let mut found = false;
         for inverted_list in self.inverted_lists.values() {
-            if inverted_list.vectors.contains_key(id) || inverted_list.chunk_refs.contains_key(id) {
+            if inverted_list.contains(id) {
                 found = true;
                 break;
             }
@@ -580,14 +1193,19 @@ impl IVFIndex {
             return Err(IVFError::VectorNotFound(id.clone()));
         }
 
-        // Add to deleted set
-        self.deleted.insert(id.clone());
+        // Add to deleted set, keeping the original time on repeated deletes
+        self.deleted.entry(id.clone()).or_insert(deleted_at);
         Ok(())
     }
 
     /// Check if a vector is marked as deleted
     pub fn is_deleted(&self, id: &VectorId) -> bool {
-        self.deleted.contains(id)
+        self.deleted.contains_key(id)
+    }
+
+    /// When a vector was marked as deleted, if it is
+    pub fn deleted_at(&self, id: &VectorId) -> Option<DateTime<Utc>> {
+        self.deleted.get(id).copied()
     }
 
     /// Batch delete multiple vectors
@@ -618,12 +1236,12 @@ impl IVFIndex {
 
     /// Get all deleted vector IDs (for persistence)
     pub fn get_deleted_ids(&self) -> Vec<&VectorId> {
-        self.deleted.iter().collect()
+        self.deleted.keys().collect()
     }
 
     /// Physically remove deleted vectors from inverted lists (hard deletion)
     pub fn vacuum(&mut self) -> Result<usize, OperationError> {
-        let deleted_ids = self.deleted.clone();
+        let deleted_ids: Vec<VectorId> = self.deleted.keys().cloned().collect();
         let removed_count = deleted_ids.len();
 
         // Remove deleted vectors from inverted lists
@@ -631,6 +1249,7 @@ impl IVFIndex {
             for id in &deleted_ids {
                 inverted_list.vectors.remove(id);
                 inverted_list.chunk_refs.remove(id);
+                inverted_list.codes.remove(id);
             }
         }
 
@@ -642,4 +1261,190 @@ impl IVFIndex {
 
         Ok(removed_count)
     }
+
+    /// Physically remove a single vector, deleted or not
+    pub fn remove(&mut self, id: &VectorId) -> Result<(), IVFError> {
+        let list = self
+            .inverted_lists
+            .values_mut()
+            .find(|list| list.contains(id))
+            .ok_or_else(|| IVFError::VectorNotFound(id.clone()))?;
+        list.vectors.remove(id);
+        list.chunk_refs.remove(id);
+        list.codes.remove(id);
+        self.vector_cache.remove(id);
+
+        self.total_vectors -= 1;
+        self.deleted.remove(id);
+        Ok(())
+    }
+
+    /// Rewrite lazily loaded chunks whose deleted ratio exceeds the configured
+    /// threshold, dropping the deleted vectors, codes and payloads.
+    ///
+    /// The ratio counts every vector a chunk stores, including ones the IVF
+    /// lists do not reference. Compacted chunks are written under a new
+    /// generation key and the old chunks stay in storage, so a manifest that
+    /// still names them keeps loading; pass the result to
+    /// `ChunkCompactionResult::apply_to_manifest` and, once that manifest is
+    /// stored, to `remove_superseded_chunks`. The deleted vectors are removed
+    /// from the index the same way `vacuum` would remove them.
+    pub async fn compact_chunks(
+        &mut self,
+        config: &ChunkCompactionConfig,
+    ) -> Result<ChunkCompactionResult, OperationError> {
+        if !(0.0..1.0).contains(&config.deleted_ratio_threshold) {
+            return Err(OperationError::InvalidParameter(
+                "Deleted ratio threshold must be in [0, 1)".to_string(),
+            ));
+        }
+
+        let mut result = ChunkCompactionResult::default();
+        if self.deleted.is_empty() {
+            return Ok(result);
+        }
+
+        let chunk_loader = self.chunk_loader.clone().ok_or_else(|| {
+            OperationError::OperationFailed("No chunk loader available".to_string())
+        })?;
+
+        // chunk path -> (referenced vectors, deleted vectors)
+        let mut chunk_usage: HashMap<String, (usize, Vec<VectorId>)> = HashMap::new();
+        for list in self.inverted_lists.values() {
+            for (id, chunk_path) in &list.chunk_refs {
+                let entry = chunk_usage.entry(chunk_path.clone()).or_default();
+                entry.0 += 1;
+                if self.deleted.contains_key(id) {
+                    entry.1.push(id.clone());
+                }
+            }
+        }
+
+        // A chunk holds at least the vectors referenced from it, so chunks
+        // under the threshold by that count are never loaded
+        let mut chunk_paths: Vec<String> = chunk_usage
+            .iter()
+            .filter(|(_, (referenced, deleted))| {
+                !deleted.is_empty()
+                    && deleted.len() as f32 / *referenced as f32 > config.deleted_ratio_threshold
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        chunk_paths.sort();
+
+        for chunk_path in chunk_paths {
+            let deleted_ids = &chunk_usage[&chunk_path].1;
+            let mut chunk = chunk_loader
+                .load_chunk(&chunk_path)
+                .await
+                .map_err(|e| OperationError::IVF(IVFError::ChunkLoadError(e.to_string())))?;
+
+            let vectors_before = chunk.len();
+            if deleted_ids.len() as f32 / vectors_before.max(1) as f32
+                <= config.deleted_ratio_threshold
+            {
+                continue;
+            }
+
+            let bytes_before = chunk
+                .to_cbor()
+                .map_err(|e| OperationError::OperationFailed(e.to_string()))?
+                .len();
+            for id in deleted_ids {
+                chunk.vectors.remove(id);
+                chunk.codes.remove(id);
+                chunk.payloads.remove(id);
+            }
+
+            let compacted_path = if chunk.is_empty() {
+                result.chunks_removed.push(chunk_path.clone());
+                None
+            } else {
+                Some(next_chunk_key(&chunk_path))
+            };
+            let bytes_after = match &compacted_path {
+                Some(compacted_path) => chunk_loader
+                    .store_chunk(compacted_path, &chunk)
+                    .await
+                    .map_err(|e| OperationError::OperationFailed(e.to_string()))?,
+                None => 0,
+            };
+
+            // Drop the compacted vectors from the index and point the live
+            // ones at the rewritten chunk
+            for list in self.inverted_lists.values_mut() {
+                for id in deleted_ids {
+                    if list.chunk_refs.get(id) == Some(&chunk_path) {
+                        list.chunk_refs.remove(id);
+                    }
+                }
+                if let Some(compacted_path) = &compacted_path {
+                    for chunk_ref in list.chunk_refs.values_mut() {
+                        if *chunk_ref == chunk_path {
+                            *chunk_ref = compacted_path.clone();
+                        }
+                    }
+                }
+            }
+            for id in deleted_ids {
+                self.vector_cache.remove(id);
+            }
+            for id in deleted_ids {
+                self.deleted.remove(id);
+            }
+            self.total_vectors -= deleted_ids.len();
+
+            let checksum_after = match &compacted_path {
+                Some(compacted_path) => chunk_loader.checksum(compacted_path).await,
+                None => None,
+            };
+            result.bytes_reclaimed += bytes_before.saturating_sub(bytes_after);
+            result.removed_ids.extend(deleted_ids.iter().cloned());
+            result.chunks_compacted.push(CompactedChunk {
+                chunk_path,
+                compacted_path,
+                vectors_before,
+                vectors_after: chunk.len(),
+                bytes_before,
+                bytes_after,
+                checksum_after,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Delete the chunks `compact_chunks` replaced or emptied
+    ///
+    /// Call once the manifest updated by `apply_to_manifest` is stored, so
+    /// the previous manifest keeps its chunks until then.
+    pub async fn remove_superseded_chunks(
+        &self,
+        result: &ChunkCompactionResult,
+    ) -> Result<(), OperationError> {
+        let chunk_loader = self.chunk_loader.clone().ok_or_else(|| {
+            OperationError::OperationFailed("No chunk loader available".to_string())
+        })?;
+
+        for compacted in &result.chunks_compacted {
+            chunk_loader
+                .remove_chunk(&compacted.chunk_path)
+                .await
+                .map_err(|e| OperationError::OperationFailed(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Variance of `sizes` divided by their squared mean, 0 when all are empty
+fn normalized_variance(sizes: &[usize]) -> f32 {
+    if sizes.is_empty() {
+        return 0.0;
+    }
+    let mean = sizes.iter().sum::<usize>() as f32 / sizes.len() as f32;
+    if mean == 0.0 {
+        return 0.0;
+    }
+    let variance = sizes.iter().map(|&s| (s as f32 - mean).powi(2)).sum::<f32>() / sizes.len() as f32;
+    variance / (mean * mean)
 }
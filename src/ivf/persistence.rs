@@ -65,6 +65,9 @@ impl IVFMetadata {
 pub struct SerializableInvertedList {
     pub cluster_id: ClusterId,
     pub vectors: HashMap<VectorId, Vec<f32>>,
+    /// Product-quantized vectors; absent in lists saved without PQ
+    #[serde(default)]
+    pub codes: HashMap<VectorId, Vec<u8>>,
 }
 
 impl SerializableInvertedList {
@@ -72,6 +75,7 @@ impl SerializableInvertedList {
         Self {
             cluster_id,
             vectors: list.vectors.clone(),
+            codes: list.codes.clone(),
         }
     }
 
@@ -79,6 +83,7 @@ impl SerializableInvertedList {
         InvertedList {
             vectors: self.vectors,
             chunk_refs: HashMap::new(), // Empty for deserialized lists (backward compat)
+            codes: self.codes,
         }
     }
 
@@ -87,7 +92,7 @@ impl SerializableInvertedList {
     }
 
     pub fn size(&self) -> usize {
-        self.vectors.len()
+        self.vectors.len() + self.codes.len()
     }
 
     pub fn to_cbor(&self) -> Result<Vec<u8>, PersistenceError> {
@@ -128,7 +133,7 @@ impl InvertedList {
     }
 
     pub fn size(&self) -> usize {
-        self.vectors.len()
+        self.vectors.len() + self.codes.len()
     }
 }
 
@@ -184,6 +189,17 @@ impl<S: S5Storage> IVFPersister<S> {
             .await
             .map_err(|e| PersistenceError::Storage(e.to_string()))?;
 
+        // Save PQ codebooks
+        if let Some(quantizer) = index.product_quantizer() {
+            let codebooks_path = format!("{}/pq_codebooks.cbor", path);
+            let codebooks_data = serde_cbor::to_vec(quantizer)
+                .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+            self.storage
+                .put(&codebooks_path, codebooks_data)
+                .await
+                .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+        }
+
         // Save inverted lists
         let inverted_lists = index.get_all_inverted_lists();
 
@@ -238,6 +254,19 @@ impl<S: S5Storage> IVFPersister<S> {
         let mut index = IVFIndex::new(metadata.config.clone());
         index.set_trained(centroids, metadata.dimension);
 
+        // Load PQ codebooks, saved only for indexes using PQ
+        let codebooks_path = format!("{}/pq_codebooks.cbor", path);
+        let codebooks_data = self
+            .storage
+            .get(&codebooks_path)
+            .await
+            .map_err(|e| PersistenceError::Storage(e.to_string()))?;
+        if let Some(data) = codebooks_data {
+            let quantizer = serde_cbor::from_slice(&data)
+                .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+            index.set_product_quantizer(quantizer);
+        }
+
         // Load inverted lists
         let serializable_lists = self
             .load_inverted_lists(path, metadata.centroids_count)
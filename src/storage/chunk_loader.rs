@@ -1,14 +1,31 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{RwLock, Mutex};
 use crate::core::storage::S5Storage;
 use crate::core::chunk_cache::ChunkCache;
-use crate::core::chunk::VectorChunk;
+use crate::core::chunk::{chunk_checksum, verify_chunk_checksum, VectorChunk};
+
+/// How a loader reacts when a chunk cannot be read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChunkLoadPolicy {
+    /// Return an error on the first chunk that fails to load
+    #[default]
+    FailFast,
+    /// Skip unreadable chunks, recording them for the integrity report
+    SkipMissing,
+}
+
+/// A chunk that failed to load under [`ChunkLoadPolicy::SkipMissing`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedChunk {
+    pub path: String,
+    pub error: String,
+}
 
 /// ChunkLoader handles loading vector chunks from S5 storage with caching,
 /// retry logic, and request deduplication for parallel operations.
@@ -18,6 +35,11 @@ pub struct ChunkLoader {
     cache: Arc<ChunkCache>,
     /// Tracks in-flight requests to prevent duplicate loads
     in_flight: Arc<RwLock<HashMap<String, Arc<Mutex<()>>>>>,
+    policy: ChunkLoadPolicy,
+    /// Chunks skipped under `SkipMissing`, keyed by path
+    skipped: Arc<RwLock<BTreeMap<String, String>>>,
+    /// Expected `chunk_checksum` of each chunk's bytes, keyed by path
+    checksums: Arc<RwLock<HashMap<String, String>>>,
 }
 
 impl ChunkLoader {
@@ -27,9 +49,54 @@ impl ChunkLoader {
             storage,
             cache,
             in_flight: Arc::new(RwLock::new(HashMap::new())),
+            policy: ChunkLoadPolicy::default(),
+            skipped: Arc::new(RwLock::new(BTreeMap::new())),
+            checksums: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Set the policy applied when a chunk fails to load
+    pub fn with_policy(mut self, policy: ChunkLoadPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Policy applied when a chunk fails to load
+    pub fn policy(&self) -> ChunkLoadPolicy {
+        self.policy
+    }
+
+    /// Verify loaded chunks against these checksums, keyed by chunk path
+    ///
+    /// Typically `Manifest::chunk_checksums`. Chunks without an entry are
+    /// loaded unverified.
+    pub fn with_checksums(mut self, checksums: HashMap<String, String>) -> Self {
+        self.checksums = Arc::new(RwLock::new(checksums));
+        self
+    }
+
+    /// Checksum currently expected for the chunk at `chunk_path`
+    ///
+    /// Kept up to date by `store_chunk` and `remove_chunk`.
+    pub async fn checksum(&self, chunk_path: &str) -> Option<String> {
+        self.checksums.read().await.get(chunk_path).cloned()
+    }
+
+    /// Chunks skipped so far under `SkipMissing`, ordered by path
+    ///
+    /// A chunk is dropped from this list once it loads successfully.
+    pub async fn skipped_chunks(&self) -> Vec<SkippedChunk> {
+        self.skipped
+            .read()
+            .await
+            .iter()
+            .map(|(path, error)| SkippedChunk {
+                path: path.clone(),
+                error: error.clone(),
+            })
+            .collect()
+    }
+
     /// Load a single chunk from storage with caching and retry logic
     ///
     /// # Process
@@ -65,15 +132,8 @@ impl ChunkLoader {
             return Ok(chunk);
         }
 
-        // Step 3: Load from S5 with retry logic
-        let chunk_data = self.retry_load(chunk_path).await?;
-
-        // Step 4: Deserialize CBOR data
-        let chunk: VectorChunk = serde_cbor::from_slice(&chunk_data)
-            .map_err(|e| format!("Failed to deserialize chunk '{}': {}", chunk_path, e))?;
-
-        // Step 5: Store in cache
-        self.cache.put(chunk_path.to_string(), chunk.clone());
+        // Step 3 & 4: Load from S5 with retry logic and deserialize
+        let result = self.fetch_chunk(chunk_path).await;
 
         // Cleanup in-flight entry
         {
@@ -81,6 +141,122 @@ impl ChunkLoader {
             in_flight.remove(chunk_path);
         }
 
+        let chunk = result?;
+
+        // Step 5: Store in cache
+        self.cache.put(chunk_path.to_string(), chunk.clone());
+        self.skipped.write().await.remove(chunk_path);
+
+        Ok(chunk)
+    }
+
+    /// Load a chunk, honouring the loader's [`ChunkLoadPolicy`]
+    ///
+    /// Under `SkipMissing` a failed load is recorded and `Ok(None)` is
+    /// returned; under `FailFast` the error is propagated.
+    pub async fn load_chunk_or_skip(
+        &self,
+        chunk_path: &str,
+    ) -> Result<Option<VectorChunk>, Box<dyn Error + Send + Sync>> {
+        match self.load_chunk(chunk_path).await {
+            Ok(chunk) => Ok(Some(chunk)),
+            Err(e) => self.skip_or_fail(chunk_path, e).await,
+        }
+    }
+
+    /// Load several chunks in one `get_many` round trip, honouring the
+    /// loader's [`ChunkLoadPolicy`]
+    ///
+    /// Cached chunks are served from the cache. Results follow the order of
+    /// `chunk_paths`, with `None` for chunks skipped under `SkipMissing`. If
+    /// the batched fetch itself fails, the remaining chunks are loaded one at
+    /// a time with the usual retries.
+    pub async fn load_chunks_or_skip(
+        &self,
+        chunk_paths: &[String],
+    ) -> Result<Vec<Option<VectorChunk>>, Box<dyn Error + Send + Sync>> {
+        let mut loaded: Vec<Option<VectorChunk>> =
+            chunk_paths.iter().map(|path| self.cache.get(path)).collect();
+        let missing: Vec<String> = chunk_paths
+            .iter()
+            .zip(&loaded)
+            .filter(|(_, chunk)| chunk.is_none())
+            .map(|(path, _)| path.clone())
+            .collect();
+        if missing.is_empty() {
+            return Ok(loaded);
+        }
+
+        let fetched: HashMap<String, Option<Vec<u8>>> = match self.storage.get_many(&missing).await {
+            Ok(fetched) => fetched.into_iter().collect(),
+            Err(_) => {
+                for (slot, path) in loaded.iter_mut().zip(chunk_paths) {
+                    if slot.is_none() {
+                        *slot = self.load_chunk_or_skip(path).await?;
+                    }
+                }
+                return Ok(loaded);
+            }
+        };
+
+        for (slot, path) in loaded.iter_mut().zip(chunk_paths) {
+            if slot.is_some() {
+                continue;
+            }
+            let result = match fetched.get(path) {
+                Some(Some(data)) => self.decode_chunk(path, data).await,
+                _ => Err(format!("Chunk not found: {}", path).into()),
+            };
+            *slot = match result {
+                Ok(chunk) => {
+                    self.cache.put(path.clone(), chunk.clone());
+                    self.skipped.write().await.remove(path);
+                    Some(chunk)
+                }
+                Err(e) => self.skip_or_fail(path, e).await?,
+            };
+        }
+
+        Ok(loaded)
+    }
+
+    /// Record a failed load under `SkipMissing`, or propagate it
+    async fn skip_or_fail(
+        &self,
+        chunk_path: &str,
+        error: Box<dyn Error + Send + Sync>,
+    ) -> Result<Option<VectorChunk>, Box<dyn Error + Send + Sync>> {
+        if self.policy != ChunkLoadPolicy::SkipMissing {
+            return Err(error);
+        }
+        self.skipped
+            .write()
+            .await
+            .insert(chunk_path.to_string(), error.to_string());
+        Ok(None)
+    }
+
+    async fn fetch_chunk(&self, chunk_path: &str) -> Result<VectorChunk, Box<dyn Error + Send + Sync>> {
+        let chunk_data = self.retry_load(chunk_path).await?;
+        self.decode_chunk(chunk_path, &chunk_data).await
+    }
+
+    /// Verify a chunk's bytes against its expected checksum and deserialize it
+    async fn decode_chunk(
+        &self,
+        chunk_path: &str,
+        chunk_data: &[u8],
+    ) -> Result<VectorChunk, Box<dyn Error + Send + Sync>> {
+        if let Some(expected) = self.checksums.read().await.get(chunk_path) {
+            let chunk_id = chunk_path
+                .rsplit('/')
+                .next()
+                .unwrap_or(chunk_path)
+                .trim_end_matches(".cbor");
+            verify_chunk_checksum(chunk_id, chunk_data, expected)?;
+        }
+        let chunk = serde_cbor::from_slice(chunk_data)
+            .map_err(|e| format!("Failed to deserialize chunk '{}': {}", chunk_path, e))?;
         Ok(chunk)
     }
 
@@ -91,6 +267,8 @@ impl ChunkLoader {
     /// 2. Use load_chunk() which handles caching and deduplication
     /// 3. Collect all results
     /// 4. Return chunks in original order
+    ///
+    /// Under `SkipMissing` unreadable chunks are left out of the result.
     pub async fn load_chunks_parallel(
         &self,
         chunk_paths: Vec<&str>,
@@ -103,7 +281,7 @@ impl ChunkLoader {
             let path_owned = path.to_string();
 
             let task = tokio::spawn(async move {
-                loader.load_chunk(&path_owned).await
+                loader.load_chunk_or_skip(&path_owned).await
             });
 
             tasks.push(task);
@@ -114,12 +292,46 @@ impl ChunkLoader {
         for task in tasks {
             let chunk = task.await
                 .map_err(|e| format!("Parallel load task failed: {}", e))??;
-            chunks.push(chunk);
+            chunks.extend(chunk);
         }
 
         Ok(chunks)
     }
 
+    /// Write a chunk to storage, replacing any previous contents at `chunk_path`
+    ///
+    /// The cached copy and the expected checksum are refreshed so subsequent
+    /// loads see the new chunk. Returns the size of the serialized chunk in
+    /// bytes.
+    pub async fn store_chunk(
+        &self,
+        chunk_path: &str,
+        chunk: &VectorChunk,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let data = chunk
+            .to_cbor()
+            .map_err(|e| format!("Failed to serialize chunk '{}': {}", chunk_path, e))?;
+        let size = data.len();
+        let checksum = chunk_checksum(&data);
+
+        self.storage.put(chunk_path, data).await?;
+        self.cache.put(chunk_path.to_string(), chunk.clone());
+        self.checksums
+            .write()
+            .await
+            .insert(chunk_path.to_string(), checksum);
+
+        Ok(size)
+    }
+
+    /// Delete a chunk from storage and evict it from the cache
+    pub async fn remove_chunk(&self, chunk_path: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.storage.delete(chunk_path).await?;
+        self.cache.remove(chunk_path);
+        self.checksums.write().await.remove(chunk_path);
+        Ok(())
+    }
+
     /// Retry logic with exponential backoff
     ///
     /// Attempts: 3 max
@@ -175,6 +387,7 @@ impl ChunkLoader {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::chunk::ChunkError;
     use crate::core::storage::MockS5Storage;
     use crate::core::types::VectorId;
 
@@ -240,4 +453,83 @@ mod tests {
         let chunks = loader.load_chunks_parallel(paths).await.unwrap();
         assert_eq!(chunks.len(), 5);
     }
+
+    async fn store_chunks_with_one_corrupt(storage: &MockS5Storage) -> Vec<&'static str> {
+        let paths = vec!["test/chunk_0.cbor", "test/chunk_1.cbor", "test/chunk_2.cbor"];
+        for (i, path) in paths.iter().enumerate() {
+            let mut chunk = VectorChunk::new(format!("chunk_{}", i), 0, 0);
+            chunk.add_vector(VectorId::from_string(&format!("vec_{}", i)), vec![i as f32; 4]);
+            storage.put(path, serde_cbor::to_vec(&chunk).unwrap()).await.unwrap();
+        }
+        storage.put(paths[1], b"not cbor".to_vec()).await.unwrap();
+        paths
+    }
+
+    #[tokio::test]
+    async fn test_parallel_loading_fail_fast() {
+        let storage = Arc::new(MockS5Storage::new());
+        let loader = ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(100)));
+        let paths = store_chunks_with_one_corrupt(&storage).await;
+
+        assert_eq!(loader.policy(), ChunkLoadPolicy::FailFast);
+        let result = loader.load_chunks_parallel(paths).await;
+        assert!(result.is_err());
+        assert!(loader.skipped_chunks().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_loading_skip_missing() {
+        let storage = Arc::new(MockS5Storage::new());
+        let loader = ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(100)))
+            .with_policy(ChunkLoadPolicy::SkipMissing);
+        let paths = store_chunks_with_one_corrupt(&storage).await;
+
+        let chunks = loader.load_chunks_parallel(paths).await.unwrap();
+        assert_eq!(chunks.len(), 2);
+
+        let skipped = loader.skipped_chunks().await;
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].path, "test/chunk_1.cbor");
+        assert!(skipped[0].error.contains("deserialize"));
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_chunk_fails_checksum() {
+        let storage = Arc::new(MockS5Storage::new());
+        let mut chunk = VectorChunk::new("chunk_0".to_string(), 0, 0);
+        chunk.add_vector(VectorId::from_string("vec_0"), vec![1.0; 4]);
+        let data = chunk.to_cbor().unwrap();
+        let checksums = HashMap::from([("test/chunk_0.cbor".to_string(), chunk_checksum(&data))]);
+
+        // Truncated bytes would otherwise surface as a CBOR decode error
+        storage.put("test/chunk_0.cbor", data[..data.len() / 2].to_vec()).await.unwrap();
+        let loader = ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(100)))
+            .with_checksums(checksums);
+
+        let err = loader.load_chunk("test/chunk_0.cbor").await.unwrap_err();
+        match err.downcast_ref::<ChunkError>() {
+            Some(ChunkError::ChecksumMismatch { chunk_id, .. }) => assert_eq!(chunk_id, "chunk_0"),
+            other => panic!("expected checksum mismatch, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_chunk_refreshes_checksum() {
+        let storage = Arc::new(MockS5Storage::new());
+        let cache = Arc::new(ChunkCache::new(100));
+        let loader = ChunkLoader::new(storage.clone(), cache.clone())
+            .with_checksums(HashMap::from([("test/chunk_0.cbor".to_string(), "stale".to_string())]));
+
+        let mut chunk = VectorChunk::new("chunk_0".to_string(), 0, 0);
+        chunk.add_vector(VectorId::from_string("vec_0"), vec![1.0; 4]);
+        loader.store_chunk("test/chunk_0.cbor", &chunk).await.unwrap();
+        cache.clear();
+
+        let loaded = loader.load_chunk("test/chunk_0.cbor").await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(
+            loader.checksum("test/chunk_0.cbor").await,
+            Some(chunk_checksum(&storage.get("test/chunk_0.cbor").await.unwrap().unwrap()))
+        );
+    }
 }
@@ -1,17 +1,48 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
 use async_trait::async_trait;
-use serde::{Serialize, de::DeserializeOwned};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::error::Error;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use std::collections::HashMap;
 use reqwest::{Client, StatusCode};
 use std::time::Duration;
 
 use crate::storage::s5_adapter::{S5StorageAdapter, Storage, StorageMode, S5StorageConfig, StorageConfigError};
-use crate::core::storage::{S5Storage as CoreS5Storage, StorageError as CoreStorageError};
+use crate::core::storage::{MockS5Storage, S5Storage as CoreS5Storage, StorageError as CoreStorageError};
+use crate::types::S5Metadata;
+
+/// Scheme recorded in `S5Metadata::encryption` for objects this client encrypts
+pub const ENCRYPTION_SCHEME: &str = "aes-256-gcm";
+
+/// Codec recorded in `S5Metadata::compression` for objects this client compresses
+pub const COMPRESSION_CODEC: &str = "zstd";
+
+/// zstd level used when `compress` is enabled
+pub const COMPRESSION_LEVEL: i32 = 3;
+
+/// Leading bytes of objects stored as a `StoredObject`, so raw objects
+/// written without compression or encryption load without being decoded
+const ENVELOPE_MAGIC: &[u8; 4] = b"FVS5";
+
+/// Codec byte in the header of a compressed payload
+const CODEC_ZSTD: u8 = 1;
+
+const NONCE_LEN: usize = 12;
+
+/// Stored form of an object compressed or encrypted at rest
+#[derive(Serialize, Deserialize)]
+struct StoredObject {
+    metadata: S5Metadata,
+    /// Codec and level header plus compressed bytes when compressed, then
+    /// a random per-object nonce followed by the ciphertext when encrypted
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+}
 
 #[derive(Clone)]
 pub struct EnhancedS5Storage {
@@ -20,6 +51,16 @@ pub struct EnhancedS5Storage {
     base_url: String,
     cache: Arc<RwLock<HashMap<String, Vec<u8>>>>,
     encrypt_at_rest: bool,
+    /// Set when `encryption_key` is configured; objects are then encrypted
+    /// before upload and decrypted after download
+    cipher: Option<Aes256Gcm>,
+    /// zstd-compress objects before upload
+    compress: bool,
+    /// Backend used instead of HTTP in `StorageMode::InProcess`
+    in_process: Option<MockS5Storage>,
+    /// Serialises `put_if_absent` check-and-write pairs, since the portal
+    /// has no conditional write
+    conditional_writes: Arc<Mutex<()>>,
 }
 
 impl std::fmt::Debug for EnhancedS5Storage {
@@ -33,6 +74,34 @@ impl std::fmt::Debug for EnhancedS5Storage {
 }
 
 impl EnhancedS5Storage {
+    /// Storage in `StorageMode::InProcess` over `backend`, so several
+    /// instances (or a test) can share one in-memory store
+    pub fn with_in_process_backend(backend: MockS5Storage) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Self::with_in_process_config(S5StorageConfig {
+            mode: StorageMode::InProcess,
+            mock_server_url: None,
+            portal_url: None,
+            seed_phrase: None,
+            connection_timeout: None,
+            retry_attempts: None,
+            encrypt_at_rest: None,
+            encryption_key: None,
+            compress: None,
+        }, backend)
+    }
+
+    /// `with_in_process_backend` with the remaining options of `config`
+    /// (such as `encryption_key`) applied; `config.mode` is ignored
+    pub fn with_in_process_config(
+        mut config: S5StorageConfig,
+        backend: MockS5Storage,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        config.mode = StorageMode::InProcess;
+        let mut storage = Self::new(config)?;
+        storage.in_process = Some(backend);
+        Ok(storage)
+    }
+
     pub fn new(config: S5StorageConfig) -> Result<Self, Box<dyn Error + Send + Sync>> {
         // Validate configuration
         match config.mode {
@@ -50,6 +119,7 @@ impl EnhancedS5Storage {
                     )));
                 }
             }
+            StorageMode::InProcess => {}
         }
 
         let timeout = config.connection_timeout.unwrap_or(30000);
@@ -87,10 +157,17 @@ impl EnhancedS5Storage {
                 // (Previously tried s5-real which doesn't exist)
                 service_url.clone()
             }
+            StorageMode::InProcess => String::new(),
         };
 
         // Encryption defaults to true if not specified
         let encrypt_at_rest = config.encrypt_at_rest.unwrap_or(true);
+        let cipher = match &config.encryption_key {
+            Some(key) if encrypt_at_rest => Some(Self::cipher_from_hex(key)?),
+            _ => None,
+        };
+        let compress = config.compress.unwrap_or(false);
+        let in_process = (config.mode == StorageMode::InProcess).then(MockS5Storage::new);
 
         Ok(Self {
             config,
@@ -98,6 +175,10 @@ impl EnhancedS5Storage {
             base_url,
             cache: Arc::new(RwLock::new(HashMap::new())),
             encrypt_at_rest,
+            cipher,
+            compress,
+            in_process,
+            conditional_writes: Arc::new(Mutex::new(())),
         })
     }
 
@@ -124,6 +205,160 @@ impl EnhancedS5Storage {
         Err(last_error.unwrap())
     }
 
+    fn cipher_from_hex(key: &str) -> Result<Aes256Gcm, StorageConfigError> {
+        hex::decode(key)
+            .ok()
+            .and_then(|bytes| Aes256Gcm::new_from_slice(&bytes).ok())
+            .ok_or_else(|| StorageConfigError::new("encryption_key must be 32 hex-encoded bytes"))
+    }
+
+    fn invalid_data(message: String) -> Box<dyn Error + Send + Sync> {
+        Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, message))
+    }
+
+    /// Compress and/or encrypt `data` for upload as configured, otherwise pass it through.
+    /// `key` is authenticated with the ciphertext, so an object only opens under the key
+    /// it was stored at.
+    fn seal(&self, key: &str, data: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        if !self.compress && self.cipher.is_none() {
+            return Ok(data);
+        }
+
+        let size = data.len();
+        let mut payload = data;
+        let mut compression = None;
+        if self.compress {
+            let mut compressed = vec![CODEC_ZSTD, COMPRESSION_LEVEL as u8];
+            compressed.extend(zstd::encode_all(payload.as_slice(), COMPRESSION_LEVEL)?);
+            payload = compressed;
+            compression = Some(COMPRESSION_CODEC.to_string());
+        }
+
+        let mut encryption = None;
+        if let Some(cipher) = &self.cipher {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            let ciphertext = cipher
+                .encrypt(&nonce, Payload { msg: &payload, aad: key.as_bytes() })
+                .map_err(|_| Self::invalid_data("encryption failed".to_string()))?;
+            payload = nonce.to_vec();
+            payload.extend_from_slice(&ciphertext);
+            encryption = Some(ENCRYPTION_SCHEME.to_string());
+        }
+
+        let object = StoredObject {
+            metadata: S5Metadata {
+                cid: blake3::hash(&payload).to_hex().to_string(),
+                size,
+                mime_type: "application/cbor".to_string(),
+                created_at: chrono::Utc::now().timestamp(),
+                encryption,
+                compression,
+            },
+            payload,
+        };
+        let mut stored = ENVELOPE_MAGIC.to_vec();
+        serde_cbor::to_writer(&mut stored, &object)?;
+        Ok(stored)
+    }
+
+    /// Undo `seal` on the object downloaded from `key`; raw objects pass through
+    /// unless a key is configured
+    fn open(&self, key: &str, data: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let Some(envelope) = data.strip_prefix(ENVELOPE_MAGIC) else {
+            if self.cipher.is_some() {
+                return Err(Self::invalid_data("object is not encrypted".to_string()));
+            }
+            return Ok(data);
+        };
+
+        let object: StoredObject = serde_cbor::from_slice(envelope)
+            .map_err(|e| Self::invalid_data(format!("corrupt stored object: {}", e)))?;
+        let mut payload = object.payload;
+
+        match (object.metadata.encryption.as_deref(), &self.cipher) {
+            (Some(ENCRYPTION_SCHEME), Some(cipher)) => {
+                if payload.len() < NONCE_LEN {
+                    return Err(Self::invalid_data("encrypted object is truncated".to_string()));
+                }
+                let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+                payload = cipher
+                    .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: key.as_bytes() })
+                    .map_err(|_| Self::invalid_data("decryption failed: wrong key or corrupted object".to_string()))?;
+            }
+            (Some(ENCRYPTION_SCHEME), None) => {
+                return Err(Self::invalid_data(
+                    "object is encrypted but no encryption_key is configured".to_string(),
+                ));
+            }
+            (Some(scheme), _) => {
+                return Err(Self::invalid_data(format!("unsupported encryption scheme: {}", scheme)));
+            }
+            (None, Some(_)) => {
+                return Err(Self::invalid_data("object is not encrypted".to_string()));
+            }
+            (None, None) => {}
+        }
+
+        match object.metadata.compression.as_deref() {
+            None => Ok(payload),
+            Some(COMPRESSION_CODEC) => match payload.split_first() {
+                // Second header byte is the level, which decoding doesn't need
+                Some((&CODEC_ZSTD, rest)) if !rest.is_empty() => Ok(zstd::decode_all(&rest[1..])?),
+                _ => Err(Self::invalid_data("compressed object has a bad header".to_string())),
+            },
+            Some(codec) => Err(Self::invalid_data(format!("unsupported compression codec: {}", codec))),
+        }
+    }
+
+    fn backend_error(e: CoreStorageError) -> Box<dyn Error + Send + Sync> {
+        Box::new(std::io::Error::other(e.to_string()))
+    }
+
+    /// Write `data` only if `key` does not exist yet
+    async fn put_raw_if_absent(&self, key: &str, data: Vec<u8>) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        if let Some(backend) = &self.in_process {
+            return CoreS5Storage::put_if_absent(backend, key, self.seal(key, data)?)
+                .await
+                .map_err(Self::backend_error);
+        }
+
+        // Only guards against writers in this process; another process
+        // writing the same key can still race the check
+        let _guard = self.conditional_writes.lock().await;
+        if S5StorageAdapter::exists(self, key).await? {
+            return Ok(false);
+        }
+        S5StorageAdapter::put_raw(self, key, data).await?;
+        Ok(true)
+    }
+
+    /// Raw bytes at `path` over HTTP, or `None` if absent
+    async fn fetch(&self, path: &str) -> Result<Option<Vec<u8>>, CoreStorageError> {
+        match S5StorageAdapter::exists(self, path).await {
+            Ok(false) => Ok(None),
+            Ok(true) => {
+                // Get raw bytes
+                let storage_path = self.get_storage_path(path);
+                let url = format!("{}{}", self.base_url, storage_path);
+                
+                match self.client.get(&url).send().await {
+                    Ok(response) if response.status().is_success() => {
+                        match response.bytes().await {
+                            Ok(bytes) => Ok(Some(bytes.to_vec())),
+                            Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
+                        }
+                    }
+                    Ok(response) if response.status() == StatusCode::NOT_FOUND => Ok(None),
+                    Ok(response) => Err(CoreStorageError::NetworkError(
+                        format!("GET failed with status: {}", response.status())
+                    )),
+                    Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
+                }
+            }
+            Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
+        }
+    }
+
     fn get_storage_path(&self, key: &str) -> String {
         // Both mock and real modes use the same API paths
         // The difference is the backend service (mock vs real S5)
@@ -134,13 +369,18 @@ impl EnhancedS5Storage {
 #[async_trait]
 impl S5StorageAdapter for EnhancedS5Storage {
     async fn put_raw(&self, key: &str, data: Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(backend) = &self.in_process {
+            return CoreS5Storage::put(backend, key, self.seal(key, data)?).await.map_err(Self::backend_error);
+        }
+
         let path = self.get_storage_path(key);
         let url = format!("{}{}", self.base_url, path);
+        let body = self.seal(key, data.clone())?;
 
         self.retry_operation(|| {
             let client = self.client.clone();
             let url = url.clone();
-            let data = data.clone();
+            let data = body.clone();
             let encrypt_at_rest = self.encrypt_at_rest;
             async move {
                 eprintln!("DEBUG: PUT request to URL: {}", url);
@@ -171,7 +411,7 @@ impl S5StorageAdapter for EnhancedS5Storage {
             }
         }).await?;
 
-        // Update cache
+        // Update cache with the plaintext, which is what get_raw returns
         let mut cache = self.cache.write().await;
         cache.insert(key.to_string(), data);
 
@@ -179,6 +419,17 @@ impl S5StorageAdapter for EnhancedS5Storage {
     }
 
     async fn get_raw(&self, key: &str) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        if let Some(backend) = &self.in_process {
+            let data = CoreS5Storage::get(backend, key)
+                .await
+                .map_err(Self::backend_error)?
+                .ok_or_else(|| {
+                    Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, "Key not found"))
+                        as Box<dyn Error + Send + Sync>
+                })?;
+            return self.open(key, data);
+        }
+
         // Check cache first
         {
             let cache = self.cache.read().await;
@@ -213,6 +464,7 @@ impl S5StorageAdapter for EnhancedS5Storage {
                 Ok(response.bytes().await?.to_vec())
             }
         }).await?;
+        let data = self.open(key, data)?;
 
         // Update cache
         {
@@ -224,6 +476,10 @@ impl S5StorageAdapter for EnhancedS5Storage {
     }
 
     async fn delete(&self, key: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(backend) = &self.in_process {
+            return CoreS5Storage::delete(backend, key).await.map_err(Self::backend_error);
+        }
+
         let path = self.get_storage_path(key);
         let url = format!("{}{}", self.base_url, path);
 
@@ -252,6 +508,11 @@ impl S5StorageAdapter for EnhancedS5Storage {
     }
 
     async fn exists(&self, key: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        if let Some(backend) = &self.in_process {
+            let data = CoreS5Storage::get(backend, key).await.map_err(Self::backend_error)?;
+            return Ok(data.is_some());
+        }
+
         // Check cache first
         {
             let cache = self.cache.read().await;
@@ -276,6 +537,10 @@ impl S5StorageAdapter for EnhancedS5Storage {
     }
 
     async fn list(&self, prefix: &str) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        if let Some(backend) = &self.in_process {
+            return CoreS5Storage::list(backend, prefix).await.map_err(Self::backend_error);
+        }
+
         let path = self.get_storage_path(prefix);
         let url = format!("{}{}", self.base_url, path);
 
@@ -305,6 +570,10 @@ impl S5StorageAdapter for EnhancedS5Storage {
     }
 
     async fn is_connected(&self) -> bool {
+        if self.in_process.is_some() {
+            return true;
+        }
+
         // Check if we can connect to the server
         let health_url = format!("{}/health", self.base_url);
 
@@ -323,10 +592,13 @@ impl S5StorageAdapter for EnhancedS5Storage {
             "cache_entries": cache.len(),
             "connected": connected,
             "encryption_enabled": self.encrypt_at_rest,
+            "compression_enabled": self.compress,
         });
 
         // Add encryption algorithm if encryption is enabled
-        if self.encrypt_at_rest {
+        if self.cipher.is_some() {
+            stats["encryption_algorithm"] = serde_json::Value::String(ENCRYPTION_SCHEME.to_string());
+        } else if self.encrypt_at_rest {
             stats["encryption_algorithm"] = serde_json::Value::String("xchacha20-poly1305".to_string());
         }
 
@@ -341,6 +613,7 @@ impl S5StorageAdapter for EnhancedS5Storage {
                     stats["portal_url"] = serde_json::Value::String(portal_url.clone());
                 }
             }
+            StorageMode::InProcess => {}
         }
 
         Ok(stats)
@@ -369,38 +642,34 @@ impl Storage for EnhancedS5Storage {
     async fn list(&self, prefix: &str) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
         <Self as S5StorageAdapter>::list(self, prefix).await
     }
+
+    async fn put_if_absent<T: Serialize + Send + Sync>(&self, key: &str, value: &T) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let data = serde_cbor::to_vec(value)?;
+        self.put_raw_if_absent(key, data).await
+    }
 }
 
 // Implement the core S5Storage trait for backward compatibility
 #[async_trait]
 impl CoreS5Storage for EnhancedS5Storage {
     async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, CoreStorageError> {
-        match S5StorageAdapter::exists(self, path).await {
-            Ok(false) => Ok(None),
-            Ok(true) => {
-                // Get raw bytes
-                let storage_path = self.get_storage_path(path);
-                let url = format!("{}{}", self.base_url, storage_path);
-                
-                match self.client.get(&url).send().await {
-                    Ok(response) if response.status().is_success() => {
-                        match response.bytes().await {
-                            Ok(bytes) => Ok(Some(bytes.to_vec())),
-                            Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
-                        }
-                    }
-                    Ok(response) if response.status() == StatusCode::NOT_FOUND => Ok(None),
-                    Ok(response) => Err(CoreStorageError::NetworkError(
-                        format!("GET failed with status: {}", response.status())
-                    )),
-                    Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
-                }
-            }
-            Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
-        }
+        let data = if let Some(backend) = &self.in_process {
+            CoreS5Storage::get(backend, path).await?
+        } else {
+            self.fetch(path).await?
+        };
+        data.map(|data| self.open(path, data))
+            .transpose()
+            .map_err(|e| CoreStorageError::SerializationError(e.to_string()))
     }
 
     async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), CoreStorageError> {
+        let data = self.seal(path, data)
+            .map_err(|e| CoreStorageError::SerializationError(e.to_string()))?;
+        if let Some(backend) = &self.in_process {
+            return CoreS5Storage::put(backend, path, data).await;
+        }
+
         let storage_path = self.get_storage_path(path);
         let url = format!("{}{}", self.base_url, storage_path);
 
@@ -435,4 +704,10 @@ impl CoreS5Storage for EnhancedS5Storage {
             Err(e) => Err(CoreStorageError::NetworkError(e.to_string())),
         }
     }
+
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, CoreStorageError> {
+        self.put_raw_if_absent(path, data)
+            .await
+            .map_err(|e| CoreStorageError::NetworkError(e.to_string()))
+    }
 }
\ No newline at end of file
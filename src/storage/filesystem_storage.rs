@@ -0,0 +1,153 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Durable `S5Storage` backend on the local filesystem
+//!
+//! Each key is stored as the file `{root}/{key}`, so an index saved here
+//! survives restarts without a running S5 server. Meant for local
+//! development and single-node deployments.
+
+use async_trait::async_trait;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::core::storage::{S5Storage, StorageError};
+
+/// Prefix of the temporary files `put` writes before renaming them into place
+const TEMP_PREFIX: &str = ".tmp-";
+
+#[derive(Debug, Clone)]
+pub struct FilesystemStorage {
+    root: PathBuf,
+}
+
+impl FilesystemStorage {
+    /// Storage rooted at `root`, which is created on first write
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// File backing `key`. A leading `/` is ignored; `..` and other
+    /// components that could leave the root are rejected.
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let invalid = || {
+            StorageError::IoError(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid storage key: {:?}", key),
+            ))
+        };
+
+        let mut path = self.root.clone();
+        for component in Path::new(key.trim_start_matches('/')).components() {
+            match component {
+                Component::Normal(part) => path.push(part),
+                Component::CurDir => {}
+                _ => return Err(invalid()),
+            }
+        }
+        if path == self.root {
+            return Err(invalid());
+        }
+        Ok(path)
+    }
+
+    /// Write `data` next to `path` under a temporary name, so readers never
+    /// see a partially written file
+    async fn write_temp(path: &Path, data: &[u8]) -> Result<PathBuf, StorageError> {
+        let parent = path.parent().expect("storage paths have a parent");
+        tokio::fs::create_dir_all(parent).await?;
+        let temp = parent.join(format!("{}{}", TEMP_PREFIX, uuid::Uuid::new_v4()));
+        tokio::fs::write(&temp, data).await?;
+        Ok(temp)
+    }
+}
+
+#[async_trait]
+impl S5Storage for FilesystemStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match tokio::fs::read(self.path_for(path)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        let target = self.path_for(path)?;
+        let temp = Self::write_temp(&target, &data).await?;
+        if let Err(e) = tokio::fs::rename(&temp, &target).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        match tokio::fs::remove_file(self.path_for(path)?).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Keys starting with `prefix`, found by walking the directory the
+    /// prefix points into
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let leading_slash = if prefix.starts_with('/') { "/" } else { "" };
+        let relative = prefix.trim_start_matches('/');
+        let dir = match relative.rfind('/') {
+            Some(end) => &relative[..end],
+            None => "",
+        };
+        let start = if dir.is_empty() {
+            self.root.clone()
+        } else {
+            self.path_for(dir)?
+        };
+
+        let mut keys = Vec::new();
+        let mut pending = vec![(start, dir.to_string())];
+        while let Some((path, key)) = pending.pop() {
+            let mut entries = match tokio::fs::read_dir(&path).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with(TEMP_PREFIX) {
+                    continue;
+                }
+                let child = if key.is_empty() { name } else { format!("{}/{}", key, name) };
+                if !child.starts_with(relative) {
+                    continue;
+                }
+                if entry.file_type().await?.is_dir() {
+                    pending.push((entry.path(), child));
+                } else {
+                    keys.push(format!("{}{}", leading_slash, child));
+                }
+            }
+        }
+
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Atomic on the filesystem: the data is hard-linked into place, which
+    /// fails if the key already exists
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, StorageError> {
+        let target = self.path_for(path)?;
+        let temp = Self::write_temp(&target, &data).await?;
+        let linked = tokio::fs::hard_link(&temp, &target).await;
+        let _ = tokio::fs::remove_file(&temp).await;
+        match linked {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
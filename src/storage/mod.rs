@@ -7,10 +7,12 @@ pub mod s5_adapter;
 pub mod enhanced_s5_storage;
 pub mod s5_storage_factory;
 pub mod chunk_loader;
+pub mod filesystem_storage;
 
 pub use s5_storage::{S5Config, S5Storage, StorageMetadata};
 pub use s5_client::{S5Client, DirectoryEntry, PathResponse, UploadResponse, BatchResult};
 pub use s5_adapter::{S5StorageAdapter, Storage, StorageMode, S5StorageConfig};
 pub use enhanced_s5_storage::EnhancedS5Storage;
 pub use s5_storage_factory::S5StorageFactory;
-pub use chunk_loader::ChunkLoader;
\ No newline at end of file
+pub use chunk_loader::ChunkLoader;
+pub use filesystem_storage::FilesystemStorage;
\ No newline at end of file
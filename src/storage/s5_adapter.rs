@@ -10,6 +10,8 @@ use std::fmt;
 pub enum StorageMode {
     Mock,
     Real,
+    /// Backed by an in-memory `MockS5Storage`; needs no server, for tests
+    InProcess,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -23,6 +25,11 @@ pub struct S5StorageConfig {
     /// Enable encryption at rest (default: true)
     /// When enabled, adds X-S5-Encryption header for xchacha20-poly1305 encryption
     pub encrypt_at_rest: Option<bool>,
+    /// Hex-encoded 32-byte key. When set and encryption at rest is enabled,
+    /// objects are encrypted client-side with AES-256-GCM before upload
+    pub encryption_key: Option<String>,
+    /// zstd-compress objects before upload (default: false)
+    pub compress: Option<bool>,
 }
 
 #[async_trait]
@@ -56,6 +63,9 @@ pub trait Storage: Send + Sync {
     async fn delete(&self, key: &str) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn exists(&self, key: &str) -> Result<bool, Box<dyn Error + Send + Sync>>;
     async fn list(&self, prefix: &str) -> Result<Vec<String>, Box<dyn Error + Send + Sync>>;
+    /// Store `value` only if `key` does not exist yet; returns `false`
+    /// without writing when it does
+    async fn put_if_absent<T: Serialize + Send + Sync>(&self, key: &str, value: &T) -> Result<bool, Box<dyn Error + Send + Sync>>;
 }
 
 #[derive(Debug)]
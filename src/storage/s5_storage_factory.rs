@@ -8,7 +8,7 @@ use std::fs;
 use std::os::unix::fs::PermissionsExt;
 
 use crate::storage::{
-    enhanced_s5_storage::EnhancedS5Storage,
+    enhanced_s5_storage::{EnhancedS5Storage, COMPRESSION_CODEC},
     s5_adapter::{S5StorageConfig, StorageMode, StorageConfigError},
 };
 
@@ -23,10 +23,22 @@ impl S5StorageFactory {
         // Check STORAGE_MODE first, then fall back to S5_MODE
         let mode = match env::var("STORAGE_MODE").or_else(|_| env::var("S5_MODE")).as_deref() {
             Ok("real") => StorageMode::Real,
+            Ok("in_process") => StorageMode::InProcess,
             Ok("mock") | _ => StorageMode::Mock, // Default to mock
         };
 
         let config = match mode {
+            StorageMode::InProcess => S5StorageConfig {
+                mode: StorageMode::InProcess,
+                mock_server_url: None,
+                portal_url: None,
+                seed_phrase: None,
+                connection_timeout: None,
+                retry_attempts: None,
+                encrypt_at_rest: None,
+                encryption_key: None,
+                compress: None,
+            },
             StorageMode::Mock => {
                 let mock_server_url = env::var("S5_MOCK_SERVER_URL")
                     .unwrap_or_else(|_| "http://localhost:5522".to_string());
@@ -52,6 +64,10 @@ impl S5StorageFactory {
                     encrypt_at_rest: env::var("S5_ENCRYPT_AT_REST")
                         .ok()
                         .and_then(|v| v.parse().ok()),
+                    encryption_key: env::var("S5_ENCRYPTION_KEY").ok(),
+                    compress: env::var("S5_COMPRESS")
+                        .ok()
+                        .and_then(|v| v.parse().ok()),
                 }
             }
             StorageMode::Real => {
@@ -87,6 +103,10 @@ impl S5StorageFactory {
                     encrypt_at_rest: env::var("S5_ENCRYPT_AT_REST")
                         .ok()
                         .and_then(|v| v.parse().ok()),
+                    encryption_key: env::var("S5_ENCRYPTION_KEY").ok(),
+                    compress: env::var("S5_COMPRESS")
+                        .ok()
+                        .and_then(|v| v.parse().ok()),
                 }
             }
         };
@@ -164,6 +184,7 @@ impl S5StorageFactory {
                 };
                 eprintln!("  Seed phrase: {}", seed_source);
             }
+            StorageMode::InProcess => {}
         }
         
         if let Some(timeout) = config.connection_timeout {
@@ -173,5 +194,13 @@ impl S5StorageFactory {
         if let Some(attempts) = config.retry_attempts {
             eprintln!("  Retry attempts: {}", attempts);
         }
+
+        if config.encryption_key.is_some() {
+            eprintln!("  Encryption key: configured");
+        }
+
+        if config.compress == Some(true) {
+            eprintln!("  Compression: {}", COMPRESSION_CODEC);
+        }
     }
 }
\ No newline at end of file
@@ -80,4 +80,6 @@ pub struct S5Metadata {
     pub mime_type: String,
     pub created_at: i64, // Unix timestamp
     pub encryption: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,63 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the backup admin endpoint
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use std::sync::Arc;
+use vector_db::api::rest::{create_app_with_storage, ApiConfig, BackupResponse, StorageConfigInfo};
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::storage::EnhancedS5Storage;
+
+/// Backups list and delete the files they store, which the HTTP mock
+/// server doesn't support, so run against in-process storage
+async fn create_server(backend: &MockS5Storage) -> TestServer {
+    let storage = Arc::new(EnhancedS5Storage::with_in_process_backend(backend.clone()).unwrap());
+    let info = StorageConfigInfo {
+        mode: "in_process".to_string(),
+        url: String::new(),
+    };
+    let app = create_app_with_storage(ApiConfig::default(), storage, info).await.unwrap();
+    TestServer::new(app).unwrap()
+}
+
+#[tokio::test]
+async fn test_backup_reports_real_figures() {
+    let storage = MockS5Storage::new();
+    let server = create_server(&storage).await;
+    for i in 0..5 {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": format!("v{}", i), "vector": [i as f32, 1.0, 0.5] }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .post("/api/v1/admin/backup")
+        .json(&json!({ "backup_path": "/backups/api", "compress": true }))
+        .await;
+    response.assert_status_ok();
+    let backup: BackupResponse = response.json();
+    assert_eq!(backup.vectors_backed_up, 5);
+    assert!(backup.backup_size > 0);
+    assert!(backup.compression_ratio > 1.0);
+
+    assert!(storage.get("/backups/api").await.unwrap().is_some());
+    assert!(!storage.list("/backups/api.data-").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_backup_rejects_empty_path() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    server
+        .post("/api/v1/admin/backup")
+        .json(&json!({ "backup_path": "", "compress": false }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for streamed batch insert responses
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::{ApiConfig, BatchInsertResponse, BatchStreamEvent};
+
+async fn setup() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    TestServer::new(app).unwrap()
+}
+
+/// Nine valid vectors plus an empty one and a duplicate
+fn batch(prefix: &str) -> serde_json::Value {
+    let mut vectors: Vec<serde_json::Value> = (0..9)
+        .map(|i| json!({ "id": format!("{}-{}", prefix, i), "vector": [i as f32, 1.0, 0.0] }))
+        .collect();
+    vectors.push(json!({ "id": format!("{}-empty", prefix), "vector": [] }));
+    vectors.push(json!({ "id": format!("{}-0", prefix), "vector": [0.0, 1.0, 0.0] }));
+    json!({ "vectors": vectors })
+}
+
+#[tokio::test]
+async fn test_streamed_results_match_non_streamed_batch() {
+    let server = setup().await;
+
+    let plain: BatchInsertResponse = server
+        .post("/api/v1/vectors/batch")
+        .json(&batch("plain"))
+        .await
+        .json();
+
+    let response = server
+        .post("/api/v1/vectors/batch")
+        .add_query_param("stream", true)
+        .json(&batch("streamed"))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/x-ndjson");
+
+    let events: Vec<BatchStreamEvent> = response
+        .text()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 12);
+
+    let items = &events[..11];
+    let ok = items
+        .iter()
+        .filter(|e| matches!(e, BatchStreamEvent::Item { success: true, .. }))
+        .count();
+    assert_eq!(ok, plain.successful);
+    assert_eq!(items.len() - ok, plain.failed);
+
+    // Items arrive in request order, failures carry their error
+    assert!(matches!(&items[0], BatchStreamEvent::Item { id, .. } if id == "streamed-0"));
+    assert!(matches!(
+        &items[9],
+        BatchStreamEvent::Item { success: false, error: Some(_), .. }
+    ));
+
+    assert_eq!(
+        events[11],
+        BatchStreamEvent::Summary {
+            successful: plain.successful,
+            failed: plain.failed,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_streamed_vectors_are_searchable() {
+    let server = setup().await;
+    server
+        .post("/api/v1/vectors/batch")
+        .add_query_param("stream", true)
+        .json(&batch("s"))
+        .await
+        .assert_status_ok();
+
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [4.0, 1.0, 0.0], "k": 1 }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["results"][0]["id"], "s-4");
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for `PUT /vectors/batch`
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::{ApiConfig, BatchUpsertResponse, UpsertStatus};
+
+#[tokio::test]
+async fn test_batch_upsert_reports_per_item_status() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    for id in ["a", "b"] {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": id, "vector": [1.0, 0.0, 0.0], "metadata": { "v": 1 } }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .put("/api/v1/vectors/batch")
+        .json(&json!({
+            "vectors": [
+                { "id": "a", "vector": [0.0, 1.0, 0.0], "metadata": { "v": 2 } },
+                { "id": "c", "vector": [0.0, 0.0, 1.0], "metadata": { "v": 2 } },
+                { "id": "d", "vector": [], "metadata": {} }
+            ]
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: BatchUpsertResponse = response.json();
+
+    assert_eq!((body.inserted, body.updated, body.failed), (1, 1, 1));
+    let statuses: Vec<(&str, UpsertStatus)> = body
+        .results
+        .iter()
+        .map(|item| (item.id.as_str(), item.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("a", UpsertStatus::Updated),
+            ("c", UpsertStatus::Inserted),
+            ("d", UpsertStatus::Failed),
+        ]
+    );
+    assert!(body.results[2].error.is_some());
+
+    // Final state: a replaced, b untouched, c added, d absent
+    let a: serde_json::Value = server.get("/api/v1/vectors/a").await.json();
+    assert_eq!(a["vector"], json!([0.0, 1.0, 0.0]));
+    assert_eq!(a["metadata"]["v"], 2);
+    let b: serde_json::Value = server.get("/api/v1/vectors/b").await.json();
+    assert_eq!(b["metadata"]["v"], 1);
+    let c: serde_json::Value = server.get("/api/v1/vectors/c").await.json();
+    assert_eq!(c["vector"], json!([0.0, 0.0, 1.0]));
+    server
+        .get("/api/v1/vectors/d")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let search: serde_json::Value = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [0.0, 1.0, 0.0], "k": 1 }))
+        .await
+        .json();
+    assert_eq!(search["results"][0]["id"], "a");
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the `exact` search option
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::ApiConfig;
+
+#[tokio::test]
+async fn test_exact_search_option_returns_true_nearest() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    for i in 0..20 {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({
+                "id": format!("v{}", i),
+                "vector": [i as f32, 0.0, 1.0],
+                "metadata": {}
+            }))
+            .await
+            .assert_status(axum::http::StatusCode::CREATED);
+    }
+
+    let body: serde_json::Value = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [7.2, 0.0, 1.0], "k": 3, "options": { "exact": true } }))
+        .await
+        .json();
+    let ids: Vec<&str> = body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(ids, vec!["v7", "v8", "v6"]);
+}
@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for projecting search result metadata to requested fields
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::ApiConfig;
+
+async fn setup() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({
+            "id": "video-1",
+            "vector": [1.0, 0.0, 0.0],
+            "metadata": {
+                "title": "Intro",
+                "description": "A very long description",
+                "creator": { "name": "Alice", "wallet": "0xabc" },
+                "tags": ["rust", "vectors"]
+            }
+        }))
+        .await
+        .assert_status(axum::http::StatusCode::CREATED);
+
+    server
+}
+
+async fn search(server: &TestServer, options: serde_json::Value) -> serde_json::Value {
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [1.0, 0.0, 0.0], "k": 1, "options": options }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    body["results"][0].clone()
+}
+
+#[tokio::test]
+async fn test_fields_project_metadata() {
+    let server = setup().await;
+
+    let result = search(&server, json!({ "fields": ["title", "creator.name", "missing"] })).await;
+    assert_eq!(
+        result["metadata"],
+        json!({ "title": "Intro", "creator": { "name": "Alice" } })
+    );
+}
+
+#[tokio::test]
+async fn test_full_metadata_without_fields() {
+    let server = setup().await;
+
+    let result = search(&server, json!({ "include_metadata": true })).await;
+    let metadata = result["metadata"].as_object().unwrap();
+    assert_eq!(metadata.len(), 4);
+    assert_eq!(metadata["creator"]["wallet"], "0xabc");
+
+    let result = search(&server, json!({})).await;
+    assert!(result.get("metadata").is_none());
+}
@@ -0,0 +1,169 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for metadata filters on the search endpoint
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::{json, Value};
+use vector_db::api::rest::{create_router, create_state, ApiConfig, SearchResponse};
+
+async fn create_server() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+    for i in 0..12 {
+        let category = if i % 3 == 0 { "music" } else { "gaming" };
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({
+                "id": format!("video-{}", i),
+                "vector": [i as f32, 1.0, 0.0],
+                "metadata": { "category": category, "views": i * 100 },
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    server
+}
+
+async fn search(server: &TestServer, query: [f32; 3], filter: Value) -> SearchResponse {
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({
+            "vector": query,
+            "k": 3,
+            "filter": filter,
+            "options": { "include_metadata": true },
+        }))
+        .await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn test_filter_returns_only_matching_vectors() {
+    let server = create_server().await;
+
+    let response = search(&server, [4.0, 1.0, 0.0], json!({ "category": "music" })).await;
+
+    let ids: Vec<&str> = response.results.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["video-3", "video-6", "video-0"]);
+    for result in &response.results {
+        assert_eq!(result.metadata.as_ref().unwrap()["category"], "music");
+    }
+}
+
+#[tokio::test]
+async fn test_filter_with_operators() {
+    let server = create_server().await;
+
+    let response = search(
+        &server,
+        [10.0, 1.0, 0.0],
+        json!({ "$and": [{ "category": "gaming" }, { "views": { "$gte": 800 } }] }),
+    )
+    .await;
+
+    let ids: Vec<&str> = response.results.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["video-10", "video-11", "video-8"]);
+}
+
+#[tokio::test]
+async fn test_filter_matching_nothing_returns_no_results() {
+    let server = create_server().await;
+
+    let response = search(&server, [4.0, 1.0, 0.0], json!({ "category": "news" })).await;
+
+    assert!(response.results.is_empty());
+}
+
+#[tokio::test]
+async fn test_malformed_filter_is_rejected() {
+    let server = create_server().await;
+
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({
+            "vector": [4.0, 1.0, 0.0],
+            "k": 3,
+            "filter": { "views": { "$near": 5 } },
+        }))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: Value = response.json();
+    assert!(body["error"].as_str().unwrap().contains("$near"));
+}
+
+#[tokio::test]
+async fn test_repeated_filter_reuses_cached_matches() {
+    let (storage, info) = mock_s5_server::storage().await;
+    let state = create_state(ApiConfig::default(), storage, info).await.unwrap();
+    let server = TestServer::new(create_router(state.clone())).unwrap();
+    for i in 0..6 {
+        let category = if i % 2 == 0 { "music" } else { "gaming" };
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({
+                "id": format!("video-{}", i),
+                "vector": [i as f32, 1.0, 0.0],
+                "metadata": { "category": category },
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let filter = json!({ "category": "music" });
+    search(&server, [0.0, 1.0, 0.0], filter.clone()).await;
+    search(&server, [4.0, 1.0, 0.0], filter.clone()).await;
+    let stats = state.hybrid_index.filter_cache_stats().await;
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+
+    // A new vector's metadata is matched afresh
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({
+            "id": "video-new",
+            "vector": [1.0, 1.0, 0.0],
+            "metadata": { "category": "music" },
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    let response = search(&server, [1.0, 1.0, 0.0], filter).await;
+    assert_eq!(response.results[0].id, "video-new");
+    assert_eq!(state.hybrid_index.filter_cache_stats().await.misses, 2);
+}
+
+#[tokio::test]
+async fn test_filter_honours_search_options() {
+    let server = create_server().await;
+
+    // Every vector is still in the recent index
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({
+            "vector": [4.0, 1.0, 0.0],
+            "k": 3,
+            "filter": { "category": "music" },
+            "options": { "search_recent": false },
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: SearchResponse = response.json();
+    assert!(body.results.is_empty());
+
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({
+            "vector": [4.0, 1.0, 0.0],
+            "k": 3,
+            "filter": { "category": "music" },
+            "options": { "exact": true },
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: SearchResponse = response.json();
+    let ids: Vec<&str> = body.results.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["video-3", "video-6", "video-0"]);
+}
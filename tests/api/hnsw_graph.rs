@@ -0,0 +1,64 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the HNSW graph export endpoint
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::ApiConfig;
+
+async fn setup() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+    for i in 0..20 {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": format!("v{}", i), "vector": [i as f32, 1.0, 0.5] }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    server
+}
+
+#[tokio::test]
+async fn test_graph_export_endpoint() {
+    let server = setup().await;
+
+    let body: serde_json::Value = server.get("/api/v1/admin/hnsw-graph").await.json();
+    assert_eq!(body["total_nodes"], 20);
+    assert_eq!(body["sampled"], false);
+
+    let layer0 = &body["layers"][0];
+    assert_eq!(layer0["layer"], 0);
+    assert_eq!(layer0["nodes"].as_array().unwrap().len(), 20);
+    let edges: usize = layer0["adjacency"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|n| n.as_array().unwrap().len())
+        .sum();
+    assert_eq!(layer0["edge_count"], edges);
+}
+
+#[tokio::test]
+async fn test_graph_export_bounded_by_query() {
+    let server = setup().await;
+
+    let body: serde_json::Value = server
+        .get("/api/v1/admin/hnsw-graph")
+        .add_query_param("layers", "0")
+        .add_query_param("max_nodes", 5)
+        .await
+        .json();
+    assert_eq!(body["sampled"], true);
+    assert_eq!(body["layers"].as_array().unwrap().len(), 1);
+    assert_eq!(body["layers"][0]["nodes"].as_array().unwrap().len(), 5);
+
+    server
+        .get("/api/v1/admin/hnsw-graph")
+        .add_query_param("layers", "zero")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
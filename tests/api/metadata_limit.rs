@@ -0,0 +1,89 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the per-vector metadata size limit
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::{validate_metadata, ApiConfig, BatchInsertResponse};
+
+async fn setup(max_metadata_bytes: usize) -> TestServer {
+    let config = ApiConfig {
+        max_metadata_bytes,
+        ..Default::default()
+    };
+    let (app, _) = mock_s5_server::create_app(config).await;
+    TestServer::new(app).unwrap()
+}
+
+fn metadata_of_len(len: usize) -> serde_json::Value {
+    json!({ "description": "x".repeat(len) })
+}
+
+#[tokio::test]
+async fn test_oversized_metadata_rejected() {
+    let server = setup(256).await;
+
+    let response = server
+        .post("/api/v1/vectors")
+        .json(&json!({
+            "id": "big",
+            "vector": [1.0, 0.0, 0.0],
+            "metadata": metadata_of_len(1000)
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json();
+    assert!(body["error"].as_str().unwrap().contains("limit of 256 bytes"));
+
+    // Nothing was stored
+    server
+        .get("/api/v1/vectors/big")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_normal_metadata_accepted() {
+    let server = setup(256).await;
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({
+            "id": "small",
+            "vector": [1.0, 0.0, 0.0],
+            "metadata": metadata_of_len(100)
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_batch_rejects_only_oversized_items() {
+    let server = setup(256).await;
+
+    let response: BatchInsertResponse = server
+        .post("/api/v1/vectors/batch")
+        .json(&json!({
+            "vectors": [
+                { "id": "ok", "vector": [1.0, 0.0, 0.0], "metadata": metadata_of_len(10) },
+                { "id": "big", "vector": [0.0, 1.0, 0.0], "metadata": metadata_of_len(1000) }
+            ]
+        }))
+        .await
+        .json();
+
+    assert_eq!(response.successful, 1);
+    assert_eq!(response.failed, 1);
+    assert_eq!(response.errors[0].id, "big");
+}
+
+#[test]
+fn test_limit_is_inclusive() {
+    let metadata = metadata_of_len(10);
+    let size = serde_json::to_vec(&metadata).unwrap().len();
+    assert!(validate_metadata(&metadata, size).is_ok());
+    assert!(validate_metadata(&metadata, size - 1).is_err());
+}
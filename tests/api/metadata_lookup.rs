@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the configurable metadata lookup order in search responses
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::{ApiConfig, MetadataLookupOrder};
+use vector_db::core::types::{Embedding, Vector, VectorId};
+use vector_db::storage::Storage;
+
+async fn search_metadata(server: &TestServer) -> serde_json::Value {
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({
+            "vector": [1.0, 0.0, 0.0],
+            "k": 1,
+            "options": { "include_metadata": true }
+        }))
+        .await;
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["results"][0]["id"], "doc-1");
+    body["results"][0]["metadata"].clone()
+}
+
+async fn setup(order: MetadataLookupOrder) -> TestServer {
+    let config = ApiConfig {
+        metadata_lookup: order,
+        ..Default::default()
+    };
+    let (app, storage) = mock_s5_server::create_app(config).await;
+    let server = TestServer::new(app).unwrap();
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({
+            "id": "doc-1",
+            "vector": [1.0, 0.0, 0.0],
+            "metadata": { "revision": 1 }
+        }))
+        .await
+        .assert_status(axum::http::StatusCode::CREATED);
+
+    // Metadata updated directly in storage, bypassing the in-memory map
+    let updated = Vector::with_metadata(
+        VectorId::from_string("doc-1"),
+        Embedding::new(vec![1.0, 0.0, 0.0]).unwrap(),
+        json!({ "revision": 2 }),
+    );
+    storage.put("vectors/doc-1", &updated).await.unwrap();
+
+    server
+}
+
+#[tokio::test]
+async fn test_memory_first_returns_in_memory_metadata() {
+    let server = setup(MetadataLookupOrder::MemoryFirst).await;
+    assert_eq!(search_metadata(&server).await, json!({ "revision": 1 }));
+}
+
+#[tokio::test]
+async fn test_storage_first_returns_updated_storage_metadata() {
+    let server = setup(MetadataLookupOrder::StorageFirst).await;
+    assert_eq!(search_metadata(&server).await, json!({ "revision": 2 }));
+}
+
+#[tokio::test]
+async fn test_memory_only_ignores_storage() {
+    let server = setup(MetadataLookupOrder::MemoryOnly).await;
+    assert_eq!(search_metadata(&server).await, json!({ "revision": 1 }));
+}
+
+#[tokio::test]
+async fn test_get_vector_returns_in_memory_metadata() {
+    let server = setup(MetadataLookupOrder::MemoryFirst).await;
+    let body: serde_json::Value = server.get("/api/v1/vectors/doc-1").await.json();
+    assert_eq!(body["metadata"], json!({ "revision": 1 }));
+}
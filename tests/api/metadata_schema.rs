@@ -0,0 +1,94 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for validating inserted metadata against a schema
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::{ApiConfig, BatchInsertResponse};
+use vector_db::core::schema::{FieldType, MetadataSchema};
+
+async fn setup() -> TestServer {
+    let mut schema = MetadataSchema::new();
+    schema.add_field("title", FieldType::String, true);
+    schema.add_field("duration", FieldType::Number, false);
+    let config = ApiConfig {
+        metadata_schema: Some(schema),
+        ..Default::default()
+    };
+    let (app, _) = mock_s5_server::create_app(config).await;
+    TestServer::new(app).unwrap()
+}
+
+#[tokio::test]
+async fn test_matching_metadata_accepted() {
+    let server = setup().await;
+
+    // Integer and float durations both satisfy a number field
+    for (id, duration) in [("int", json!(120)), ("float", json!(95.5))] {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({
+                "id": id,
+                "vector": [1.0, 0.0, 0.0],
+                "metadata": { "title": "Intro", "duration": duration, "extra": [1, 2] }
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+}
+
+#[tokio::test]
+async fn test_missing_required_field_rejected() {
+    let server = setup().await;
+
+    for metadata in [json!({ "duration": 30 }), json!(null)] {
+        let response = server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": "untitled", "vector": [1.0, 0.0, 0.0], "metadata": metadata }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json();
+        assert!(body["error"].as_str().unwrap().contains("Missing required field: title"));
+    }
+
+    server
+        .get("/api/v1/vectors/untitled")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_type_mismatch_rejected() {
+    let server = setup().await;
+
+    let response = server
+        .post("/api/v1/vectors")
+        .json(&json!({
+            "id": "bad",
+            "vector": [1.0, 0.0, 0.0],
+            "metadata": { "title": "Intro", "duration": "not a number" }
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json();
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .contains("Invalid type for field 'duration': expected Number, found String"));
+
+    // Batch items are checked one by one
+    let result: BatchInsertResponse = server
+        .post("/api/v1/vectors/batch")
+        .json(&json!({ "vectors": [
+            { "id": "ok", "vector": [1.0, 0.0, 0.0], "metadata": { "title": "Fine" } },
+            { "id": "bad", "vector": [0.0, 1.0, 0.0], "metadata": { "title": 7 } }
+        ]}))
+        .await
+        .json();
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.errors[0].id, "bad");
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the migration admin endpoint
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use vector_db::api::rest::{
+    create_router, create_state, ApiConfig, MigrationResponse, StatisticsResponse,
+};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, IndexTarget};
+
+async fn create_server() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "a", "vector": [1.0, 0.0, 0.0] }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+}
+
+/// Server over an initialized index holding `old` recent vectors backdated
+/// past the recent threshold and `new` vectors inserted now
+async fn create_server_with_aged_vectors(old: usize, new: usize) -> TestServer {
+    let (storage, info) = mock_s5_server::storage().await;
+    let mut state = create_state(ApiConfig::default(), storage, info).await.unwrap();
+    let mut config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    config.ivf_config.n_clusters = 4;
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 1.0, 0.5]).collect();
+    index.initialize(training).await.unwrap();
+
+    let backdated = Utc::now() - chrono::Duration::days(30);
+    for i in 0..old {
+        index
+            .insert_into(
+                VectorId::from_string(&format!("old-{}", i)),
+                vec![i as f32, 1.0, 0.5],
+                backdated,
+                IndexTarget::Recent,
+            )
+            .await
+            .unwrap();
+    }
+    state.hybrid_index = Arc::new(index);
+
+    let server = TestServer::new(create_router(state)).unwrap();
+    for i in 0..new {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": format!("new-{}", i), "vector": [i as f32, 0.0, 1.0] }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    server
+}
+
+async fn statistics(server: &TestServer) -> StatisticsResponse {
+    let response = server.get("/api/v1/admin/statistics").await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn test_migrate_returns_final_count() {
+    let server = create_server().await;
+
+    let response = server.post("/api/v1/admin/migrate").await;
+    response.assert_status_ok();
+    let body: MigrationResponse = response.json();
+    assert_eq!(body.vectors_migrated, 0);
+}
+
+#[tokio::test]
+async fn test_migrate_streams_completion_event() {
+    let server = create_server().await;
+
+    let response = server
+        .post("/api/v1/admin/migrate")
+        .add_query_param("stream", true)
+        .await;
+    response.assert_status_ok();
+    assert!(response
+        .header("content-type")
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+
+    let text = response.text();
+    let data = text
+        .lines()
+        .skip_while(|line| *line != "event: complete")
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("complete event");
+    let complete: MigrationResponse = serde_json::from_str(data).unwrap();
+    assert_eq!(complete.vectors_migrated, 0);
+}
+
+#[tokio::test]
+async fn test_migrate_moves_vectors_past_threshold() {
+    let server = create_server_with_aged_vectors(6, 4).await;
+
+    let response = server.post("/api/v1/admin/migrate").await;
+    response.assert_status_ok();
+    let body: MigrationResponse = response.json();
+    assert_eq!(body.vectors_migrated, 6);
+
+    let stats = statistics(&server).await;
+    assert_eq!(stats.total_vectors, 10);
+    assert_eq!(stats.recent_vectors, 4);
+    assert_eq!(stats.historical_vectors, 6);
+
+    // Nothing left past the threshold
+    let response = server.post("/api/v1/admin/migrate").await;
+    let body: MigrationResponse = response.json();
+    assert_eq!(body.vectors_migrated, 0);
+}
+
+#[tokio::test]
+async fn test_migrate_with_threshold_override() {
+    let server = create_server_with_aged_vectors(3, 5).await;
+
+    let response = server
+        .post("/api/v1/admin/migrate")
+        .json(&json!({ "threshold_secs": 0 }))
+        .await;
+    response.assert_status_ok();
+    let body: MigrationResponse = response.json();
+    assert_eq!(body.vectors_migrated, 8);
+
+    let stats = statistics(&server).await;
+    assert_eq!(stats.recent_vectors, 0);
+    assert_eq!(stats.historical_vectors, 8);
+}
+
+#[tokio::test]
+async fn test_migrate_rejects_invalid_body() {
+    let server = create_server().await;
+
+    let response = server
+        .post("/api/v1/admin/migrate")
+        .json(&json!({ "threshold_secs": "soon" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
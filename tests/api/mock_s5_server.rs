@@ -0,0 +1,80 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! In-process stand-in for the S5 mock server used by REST API tests
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use vector_db::api::rest::{create_app_with_storage, ApiConfig, StorageConfigInfo};
+use vector_db::storage::s5_adapter::{S5StorageConfig, StorageMode};
+use vector_db::storage::EnhancedS5Storage;
+
+type Blobs = Arc<RwLock<HashMap<String, Vec<u8>>>>;
+
+async fn get_blob(State(blobs): State<Blobs>, Path(key): Path<String>) -> Result<Vec<u8>, StatusCode> {
+    blobs.read().await.get(&key).cloned().ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn put_blob(State(blobs): State<Blobs>, Path(key): Path<String>, body: Bytes) -> StatusCode {
+    blobs.write().await.insert(key, body.to_vec());
+    StatusCode::OK
+}
+
+async fn delete_blob(State(blobs): State<Blobs>, Path(key): Path<String>) -> StatusCode {
+    match blobs.write().await.remove(&key) {
+        Some(_) => StatusCode::OK,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+/// Start a server on an ephemeral port and return its base URL
+pub async fn spawn() -> String {
+    let blobs: Blobs = Arc::new(RwLock::new(HashMap::new()));
+    let app = Router::new()
+        .route("/s5/fs/*key", get(get_blob).put(put_blob).delete(delete_blob))
+        .with_state(blobs);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    format!("http://{}", addr)
+}
+
+/// Storage client talking to a freshly spawned mock server
+pub async fn storage() -> (Arc<EnhancedS5Storage>, StorageConfigInfo) {
+    let url = spawn().await;
+    let config = S5StorageConfig {
+        mode: StorageMode::Mock,
+        mock_server_url: Some(url.clone()),
+        portal_url: None,
+        seed_phrase: None,
+        connection_timeout: Some(5000),
+        retry_attempts: Some(1),
+        encrypt_at_rest: Some(false),
+        encryption_key: None,
+        compress: None,
+    };
+    let info = StorageConfigInfo {
+        mode: "mock".to_string(),
+        url,
+    };
+    (Arc::new(EnhancedS5Storage::new(config).unwrap()), info)
+}
+
+/// Build the REST app against a fresh mock server
+pub async fn create_app(config: ApiConfig) -> (axum::Router, Arc<EnhancedS5Storage>) {
+    let (storage, info) = storage().await;
+    let app = create_app_with_storage(config, storage.clone(), info).await.unwrap();
+    (app, storage)
+}
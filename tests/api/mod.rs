@@ -1,5 +1,27 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+mod backup;
+mod batch_stream;
+mod batch_upsert;
+mod exact_search;
+mod field_projection;
+mod filtered_search;
+mod hnsw_graph;
+mod metadata_limit;
+mod metadata_lookup;
+mod metadata_schema;
+mod migration;
+mod response_cache;
+mod search_defaults;
+mod search_limit;
+mod search_readiness;
+mod secondary_sort;
+mod statistics;
+mod stream_insert;
+mod update_stream;
+mod on_duplicate;
+mod vector_format;
+pub mod mock_s5_server;
 mod rest;
 mod test_rest_api;
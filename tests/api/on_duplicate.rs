@@ -0,0 +1,148 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the `on_duplicate` insert option
+
+use super::mock_s5_server;
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use axum_test::TestServer;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use vector_db::api::rest::ApiConfig;
+
+async fn create_server() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "a", "vector": [1.0, 0.0, 0.0], "metadata": { "v": 1 } }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+}
+
+#[tokio::test]
+async fn test_insert_on_duplicate_modes() {
+    let server = create_server().await;
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "a", "vector": [0.0, 1.0, 0.0], "metadata": { "v": 2 } }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "a", "vector": [0.0, 1.0, 0.0], "metadata": { "v": 2 }, "on_duplicate": "skip" }))
+        .await
+        .assert_status_ok();
+    let body: serde_json::Value = server.get("/api/v1/vectors/a").await.json();
+    assert_eq!(body["metadata"]["v"], 1);
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "a", "vector": [0.0, 1.0, 0.0], "metadata": { "v": 3 }, "on_duplicate": "update" }))
+        .await
+        .assert_status_ok();
+    let body: serde_json::Value = server.get("/api/v1/vectors/a").await.json();
+    assert_eq!(body["metadata"]["v"], 3);
+    assert_eq!(body["vector"], json!([0.0, 1.0, 0.0]));
+}
+
+#[tokio::test]
+async fn test_batch_on_duplicate_default_and_override() {
+    let server = create_server().await;
+
+    let body: serde_json::Value = server
+        .post("/api/v1/vectors/batch")
+        .json(&json!({
+            "on_duplicate": "skip",
+            "vectors": [
+                { "id": "a", "vector": [0.0, 1.0, 0.0] },
+                { "id": "b", "vector": [0.0, 0.0, 1.0] },
+                { "id": "b", "vector": [0.0, 0.5, 1.0], "on_duplicate": "error" }
+            ]
+        }))
+        .await
+        .json();
+
+    assert_eq!(body["successful"], 2);
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["errors"][0]["id"], "b");
+}
+
+/// Send a request straight to the router, so two of them can be in flight at once
+async fn send(app: &Router, method: Method, uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_concurrent_inserts_of_same_id_conflict() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+
+    for i in 0..5 {
+        let id = format!("race-{}", i);
+        let (first, second) = tokio::join!(
+            send(&app, Method::POST, "/api/v1/vectors",
+                json!({ "id": id, "vector": [1.0, 0.0, 0.0], "metadata": { "writer": 1 } })),
+            send(&app, Method::POST, "/api/v1/vectors",
+                json!({ "id": id, "vector": [0.0, 1.0, 0.0], "metadata": { "writer": 2 } }))
+        );
+
+        let mut statuses = vec![first.0, second.0];
+        statuses.sort();
+        assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CONFLICT]);
+
+        // The stored vector is the winner's, not a mix of both writers
+        let winner = if first.0 == StatusCode::CREATED { 1 } else { 2 };
+        let (_, body) = send(&app, Method::GET, &format!("/api/v1/vectors/{}", id), Value::Null).await;
+        assert_eq!(body["metadata"]["writer"], winner);
+    }
+}
+
+#[tokio::test]
+async fn test_rejected_insert_keeps_id_free() {
+    let server = create_server().await;
+
+    // Wrong dimension: claimed in storage, then rejected by the index
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "b", "vector": [1.0, 0.0] }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "b", "vector": [1.0, 0.0, 0.0] }))
+        .await
+        .assert_status(StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_batch_reports_stored_duplicate() {
+    let server = create_server().await;
+
+    let body: serde_json::Value = server
+        .post("/api/v1/vectors/batch")
+        .json(&json!({ "vectors": [
+            { "id": "a", "vector": [0.0, 1.0, 0.0] },
+            { "id": "c", "vector": [0.0, 0.0, 1.0] }
+        ] }))
+        .await
+        .json();
+
+    assert_eq!(body["successful"], 1);
+    assert_eq!(body["errors"][0]["id"], "a");
+    assert_eq!(body["errors"][0]["error"], "Vector a already exists");
+}
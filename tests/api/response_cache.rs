@@ -0,0 +1,90 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for caching serialized search responses
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use std::time::Duration;
+use vector_db::api::rest::{ApiConfig, ResponseCacheConfig};
+
+async fn setup(ttl: Duration) -> TestServer {
+    let config = ApiConfig {
+        response_cache: Some(ResponseCacheConfig {
+            ttl,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let (app, _) = mock_s5_server::create_app(config).await;
+    let server = TestServer::new(app).unwrap();
+
+    for (id, vector) in [("a", [1.0, 0.0, 0.0]), ("b", [0.0, 1.0, 0.0])] {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": id, "vector": vector }))
+            .await
+            .assert_status(axum::http::StatusCode::CREATED);
+    }
+    server
+}
+
+async fn search(server: &TestServer, k: usize) -> (String, String) {
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [0.0, 0.9, 0.0], "k": k }))
+        .await;
+    response.assert_status_ok();
+    let cache = response.header("x-cache").to_str().unwrap().to_string();
+    (cache, response.text())
+}
+
+#[tokio::test]
+async fn test_repeated_search_served_from_cache() {
+    let server = setup(Duration::from_secs(60)).await;
+
+    let (first, first_body) = search(&server, 1).await;
+    let (second, second_body) = search(&server, 1).await;
+    assert_eq!(first, "miss");
+    assert_eq!(second, "hit");
+    assert_eq!(first_body, second_body);
+
+    let body: serde_json::Value = serde_json::from_str(&second_body).unwrap();
+    assert_eq!(body["results"][0]["id"], "b");
+
+    // A different request has its own entry
+    let (other, _) = search(&server, 2).await;
+    assert_eq!(other, "miss");
+}
+
+#[tokio::test]
+async fn test_mutation_invalidates_cache() {
+    let server = setup(Duration::from_secs(60)).await;
+    search(&server, 1).await;
+
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "c", "vector": [0.0, 0.9, 0.0] }))
+        .await
+        .assert_status(axum::http::StatusCode::CREATED);
+
+    let (cache, body) = search(&server, 1).await;
+    assert_eq!(cache, "miss");
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["results"][0]["id"], "c");
+
+    server.delete("/api/v1/vectors/c").await;
+    let (cache, _) = search(&server, 1).await;
+    assert_eq!(cache, "miss");
+}
+
+#[tokio::test]
+async fn test_expired_entry_recomputed() {
+    let server = setup(Duration::from_millis(50)).await;
+    search(&server, 1).await;
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let (cache, _) = search(&server, 1).await;
+    assert_eq!(cache, "miss");
+}
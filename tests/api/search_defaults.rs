@@ -0,0 +1,93 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for index-wide search defaults
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::ApiConfig;
+
+async fn create_server() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    for i in 0..10 {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({
+                "id": format!("v{}", i),
+                "vector": [i as f32, 0.0, 1.0],
+                "metadata": {}
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    server
+}
+
+#[tokio::test]
+async fn test_search_defaults_apply_to_minimal_request() {
+    let server = create_server().await;
+
+    server
+        .put("/api/v1/search-defaults")
+        .json(&json!({ "k": 3, "hnsw_ef": 80, "ivf_n_probe": 4 }))
+        .await
+        .assert_status_ok();
+
+    let body: serde_json::Value = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [2.0, 0.0, 1.0] }))
+        .await
+        .json();
+
+    assert_eq!(body["results"].as_array().unwrap().len(), 3);
+    assert_eq!(body["parameters"]["k"], 3);
+    assert_eq!(body["parameters"]["hnsw_ef"], 80);
+    assert_eq!(body["parameters"]["ivf_n_probe"], 4);
+}
+
+#[tokio::test]
+async fn test_explicit_search_parameters_override_defaults() {
+    let server = create_server().await;
+
+    server
+        .put("/api/v1/search-defaults")
+        .json(&json!({ "k": 3, "hnsw_ef": 80 }))
+        .await
+        .assert_status_ok();
+
+    let body: serde_json::Value = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [2.0, 0.0, 1.0], "k": 5, "options": { "hnsw_ef": 20 } }))
+        .await
+        .json();
+
+    assert_eq!(body["results"].as_array().unwrap().len(), 5);
+    assert_eq!(body["parameters"]["k"], 5);
+    assert_eq!(body["parameters"]["hnsw_ef"], 20);
+    // Unset in both the defaults and the request
+    assert_eq!(body["parameters"]["ivf_n_probe"], 10);
+}
+
+#[tokio::test]
+async fn test_search_defaults_roundtrip_and_validation() {
+    let server = create_server().await;
+
+    server
+        .put("/api/v1/search-defaults")
+        .json(&json!({ "k": 0 }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    server
+        .put("/api/v1/search-defaults")
+        .json(&json!({ "ivf_n_probe": 2 }))
+        .await
+        .assert_status_ok();
+
+    let body: serde_json::Value = server.get("/api/v1/search-defaults").await.json();
+    assert_eq!(body, json!({ "k": null, "hnsw_ef": null, "ivf_n_probe": 2 }));
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the concurrent search limiter
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use std::time::Duration;
+use vector_db::api::rest::{create_router, create_state, ApiConfig, SearchLimitConfig, SearchLimiter};
+
+async fn setup(limit: SearchLimitConfig) -> (TestServer, SearchLimiter) {
+    let config = ApiConfig {
+        search_limit: Some(limit),
+        ..Default::default()
+    };
+    let (storage, info) = mock_s5_server::storage().await;
+    let state = create_state(config, storage, info).await.unwrap();
+    let limiter = state.search_limiter.clone().unwrap();
+    let server = TestServer::new(create_router(state)).unwrap();
+    insert_vector(&server).await;
+    (server, limiter)
+}
+
+/// Searches on an uninitialized index are rejected before reaching the limiter
+async fn insert_vector(server: &TestServer) {
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "a", "vector": [1.0, 0.0, 0.0] }))
+        .await
+        .assert_status(StatusCode::CREATED);
+}
+
+fn search_body() -> serde_json::Value {
+    json!({ "vector": [1.0, 0.0, 0.0], "k": 1 })
+}
+
+#[tokio::test]
+async fn test_saturated_limiter_rejects_with_retry_after() {
+    let (server, limiter) = setup(SearchLimitConfig {
+        max_concurrent: 2,
+        queue_timeout: Duration::ZERO,
+        retry_after_secs: 7,
+    })
+    .await;
+
+    let _held = (limiter.acquire().await.unwrap(), limiter.acquire().await.unwrap());
+    assert_eq!(limiter.available(), 0);
+
+    let response = server.post("/api/v1/search").json(&search_body()).await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.header("retry-after"), "7");
+}
+
+#[tokio::test]
+async fn test_queued_search_runs_once_slot_frees() {
+    let (server, limiter) = setup(SearchLimitConfig {
+        max_concurrent: 1,
+        queue_timeout: Duration::from_secs(5),
+        retry_after_secs: 1,
+    })
+    .await;
+
+    let held = limiter.acquire().await.unwrap();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(held);
+    });
+
+    let response = server.post("/api/v1/search").json(&search_body()).await;
+    response.assert_status_ok();
+    assert_eq!(limiter.available(), 1);
+}
+
+#[tokio::test]
+async fn test_queued_search_times_out() {
+    let (server, limiter) = setup(SearchLimitConfig {
+        max_concurrent: 1,
+        queue_timeout: Duration::from_millis(50),
+        retry_after_secs: 2,
+    })
+    .await;
+
+    let _held = limiter.acquire().await.unwrap();
+
+    let response = server.post("/api/v1/search").json(&search_body()).await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.header("retry-after"), "2");
+}
+
+#[tokio::test]
+async fn test_searches_unbounded_by_default() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+    insert_vector(&server).await;
+
+    server.post("/api/v1/search").json(&search_body()).await.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_zero_max_concurrent_is_unlimited() {
+    let (server, limiter) = setup(SearchLimitConfig {
+        max_concurrent: 0,
+        queue_timeout: Duration::ZERO,
+        retry_after_secs: 1,
+    })
+    .await;
+
+    let _held = limiter.acquire().await.unwrap();
+    server.post("/api/v1/search").json(&search_body()).await.assert_status_ok();
+}
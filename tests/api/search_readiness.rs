@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for searching before the index is initialized
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use std::sync::Arc;
+use vector_db::api::rest::{create_router, create_state, ApiConfig, UninitializedSearch};
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+async fn setup(uninitialized_search: UninitializedSearch) -> TestServer {
+    let config = ApiConfig {
+        uninitialized_search,
+        ..Default::default()
+    };
+    let (app, _) = mock_s5_server::create_app(config).await;
+    TestServer::new(app).unwrap()
+}
+
+fn search_body() -> serde_json::Value {
+    json!({ "vector": [1.0, 0.0, 0.0], "k": 5 })
+}
+
+#[tokio::test]
+async fn test_uninitialized_index_is_not_ready() {
+    let server = setup(UninitializedSearch::default()).await;
+
+    let response = server.post("/api/v1/search").json(&search_body()).await;
+
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert!(response.header("retry-after").to_str().is_ok());
+    let body: serde_json::Value = response.json();
+    assert!(body["error"].as_str().unwrap().contains("not ready"));
+}
+
+#[tokio::test]
+async fn test_initialized_empty_index_returns_no_results() {
+    let (storage, info) = mock_s5_server::storage().await;
+    let mut state = create_state(ApiConfig::default(), storage, info).await.unwrap();
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 1.0, 0.0]).collect();
+    index.initialize(training).await.unwrap();
+    state.hybrid_index = Arc::new(index);
+    let server = TestServer::new(create_router(state)).unwrap();
+
+    let response = server.post("/api/v1/search").json(&search_body()).await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["results"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_empty_policy_returns_no_results() {
+    let server = setup(UninitializedSearch::Empty).await;
+
+    let response = server.post("/api/v1/search").json(&search_body()).await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["results"].as_array().unwrap().len(), 0);
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for ordering near-tied search results by a metadata field
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::ApiConfig;
+
+async fn setup() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    for (id, vector, minted) in [
+        ("older", [1.0, 0.0, 0.0], "2024-01-05T10:00:00Z"),
+        ("newer", [1.0, 0.001, 0.0], "2025-03-01T08:30:00Z"),
+        ("far", [0.0, 1.0, 0.0], "2025-06-01T00:00:00Z"),
+    ] {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({
+                "id": id,
+                "vector": vector,
+                "metadata": { "mint_date_time": minted }
+            }))
+            .await
+            .assert_status(axum::http::StatusCode::CREATED);
+    }
+    server
+}
+
+async fn search_ids(server: &TestServer, options: serde_json::Value) -> Vec<String> {
+    let body: serde_json::Value = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": [1.0, 0.0, 0.0], "k": 3, "options": options }))
+        .await
+        .json();
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_near_ties_ordered_by_mint_date_time() {
+    let server = setup().await;
+
+    assert_eq!(search_ids(&server, json!({})).await, vec!["older", "newer", "far"]);
+
+    let newest_first = search_ids(
+        &server,
+        json!({
+            "sort_by": { "field": "mint_date_time", "direction": "desc", "tie_tolerance": 0.01 }
+        }),
+    )
+    .await;
+    // The far result is newest of all but stays ranked by distance
+    assert_eq!(newest_first, vec!["newer", "older", "far"]);
+}
+
+#[tokio::test]
+async fn test_tolerance_limits_reordering() {
+    let server = setup().await;
+
+    // Default tolerance is tighter than the 0.001 distance gap
+    let ids = search_ids(
+        &server,
+        json!({ "sort_by": { "field": "mint_date_time", "direction": "desc" } }),
+    )
+    .await;
+    assert_eq!(ids, vec!["older", "newer", "far"]);
+}
+
+#[tokio::test]
+async fn test_missing_sort_field_goes_last() {
+    let server = setup().await;
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "unminted", "vector": [1.0, 0.0, 0.0005], "metadata": {} }))
+        .await
+        .assert_status(axum::http::StatusCode::CREATED);
+
+    let body: serde_json::Value = server
+        .post("/api/v1/search")
+        .json(&json!({
+            "vector": [1.0, 0.0, 0.0],
+            "k": 3,
+            "options": { "sort_by": { "field": "mint_date_time", "tie_tolerance": 0.01 } }
+        }))
+        .await
+        .json();
+    let ids: Vec<&str> = body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["older", "newer", "unminted"]);
+    // Sorting doesn't force metadata into the response
+    assert!(body["results"][0].get("metadata").is_none());
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the statistics admin endpoint
+
+use super::mock_s5_server;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use vector_db::api::rest::{create_router, create_state, ApiConfig, StatisticsResponse};
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+/// Server over an initialized index, so vectors can be migrated to IVF
+async fn setup() -> (TestServer, Arc<HybridIndex>) {
+    let (storage, info) = mock_s5_server::storage().await;
+    let mut state = create_state(ApiConfig::default(), storage, info).await.unwrap();
+    let mut config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    config.ivf_config.n_clusters = 4;
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 1.0, 0.5]).collect();
+    index.initialize(training).await.unwrap();
+    let index = Arc::new(index);
+    state.hybrid_index = index.clone();
+    (TestServer::new(create_router(state)).unwrap(), index)
+}
+
+async fn insert_vectors(server: &TestServer, ids: std::ops::Range<usize>) {
+    for i in ids {
+        server
+            .post("/api/v1/vectors")
+            .json(&json!({ "id": format!("vec-{}", i), "vector": [i as f32, 1.0, 0.5] }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+}
+
+async fn statistics(server: &TestServer) -> StatisticsResponse {
+    let response = server.get("/api/v1/admin/statistics").await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn test_statistics_count_inserted_vectors() {
+    let (server, _) = setup().await;
+    insert_vectors(&server, 0..12).await;
+
+    let stats = statistics(&server).await;
+
+    assert_eq!(stats.total_vectors, 12);
+    assert_eq!(stats.recent_vectors, 12);
+    assert_eq!(stats.historical_vectors, 0);
+    assert!(stats.memory_usage.hnsw_bytes > 0);
+    assert_eq!(stats.memory_usage.ivf_bytes, 0);
+    assert_eq!(stats.memory_usage.total_bytes, stats.memory_usage.hnsw_bytes);
+}
+
+#[tokio::test]
+async fn test_statistics_split_recent_and_historical() {
+    let (server, index) = setup().await;
+    insert_vectors(&server, 0..8).await;
+    index.migrate_with_threshold(Duration::ZERO).await.unwrap();
+    insert_vectors(&server, 8..13).await;
+
+    let stats = statistics(&server).await;
+
+    assert_eq!(stats.total_vectors, 13);
+    assert_eq!(stats.recent_vectors, 5);
+    assert_eq!(stats.historical_vectors, 8);
+    assert!(stats.memory_usage.ivf_bytes > 0);
+    assert_eq!(
+        stats.memory_usage.total_bytes,
+        stats.memory_usage.hnsw_bytes + stats.memory_usage.ivf_bytes
+    );
+}
+
+#[tokio::test]
+async fn test_statistics_empty_index() {
+    let (server, _) = setup().await;
+
+    let stats = statistics(&server).await;
+
+    assert_eq!(stats.total_vectors, 0);
+    assert_eq!(stats.memory_usage.total_bytes, 0);
+}
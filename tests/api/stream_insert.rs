@@ -0,0 +1,93 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for NDJSON streamed inserts
+
+use super::mock_s5_server;
+use axum::body::{Body, Bytes};
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use serde_json::json;
+use tower::ServiceExt;
+use vector_db::api::rest::{ApiConfig, BatchStreamEvent};
+
+/// Send `body` to the stream endpoint in `chunk_size` byte pieces, which
+/// split lines across chunks
+async fn stream_insert(app: &Router, body: String, chunk_size: usize) -> Vec<BatchStreamEvent> {
+    let chunks: Vec<Result<Bytes, std::io::Error>> = body
+        .into_bytes()
+        .chunks(chunk_size)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+    let request = Request::post("/api/v1/vectors/stream")
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from_stream(futures::stream::iter(chunks)))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/x-ndjson");
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn vector_line(id: &str, i: usize) -> String {
+    json!({ "id": id, "vector": [i as f32, 1.0, 0.5] }).to_string()
+}
+
+#[tokio::test]
+async fn test_chunked_body_reports_each_line_and_summary() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+
+    let mut lines: Vec<String> = (0..6).map(|i| vector_line(&format!("s{}", i), i)).collect();
+    lines.insert(2, "{ not json".to_string());
+    lines.insert(4, String::new());
+    lines.push(json!({ "id": "empty", "vector": [] }).to_string());
+    lines.push(vector_line("s0", 0));
+    // The last line has no trailing newline
+    let events = stream_insert(&app, lines.join("\n"), 7).await;
+
+    assert_eq!(events.len(), 10);
+    assert_eq!(events[9], BatchStreamEvent::Summary { successful: 6, failed: 3 });
+
+    let mut failed_lines: Vec<usize> = events[..9]
+        .iter()
+        .filter_map(|event| match event {
+            BatchStreamEvent::Item { success: false, error: Some(_), line, .. } => *line,
+            _ => None,
+        })
+        .collect();
+    failed_lines.sort();
+    // The unparseable line, the empty vector and the duplicate
+    assert_eq!(failed_lines, vec![3, 9, 10]);
+
+    let request = Request::get("/api/v1/vectors/s5").body(Body::empty()).unwrap();
+    assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_stream_body_is_not_held_to_request_size_limit() {
+    let config = ApiConfig {
+        max_request_size: 512,
+        ..ApiConfig::default()
+    };
+    let (app, _) = mock_s5_server::create_app(config).await;
+
+    let body: String = (0..40).map(|i| vector_line(&format!("big{}", i), i) + "\n").collect();
+    assert!(body.len() > 512);
+    let events = stream_insert(&app, body, 64).await;
+    assert_eq!(events.last(), Some(&BatchStreamEvent::Summary { successful: 40, failed: 0 }));
+
+    // A single line over the limit is rejected, ending the stream
+    let long_line = json!({ "id": "long", "vector": vec![0.5f32; 200] }).to_string();
+    let events = stream_insert(&app, format!("{}\n{}\n", long_line, vector_line("after", 1)), 64).await;
+    assert!(matches!(
+        &events[0],
+        BatchStreamEvent::Item { success: false, line: Some(1), error: Some(e), .. } if e.contains("limit")
+    ));
+    assert_eq!(events[1], BatchStreamEvent::Summary { successful: 0, failed: 1 });
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the live updates SSE endpoint
+
+use super::mock_s5_server;
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tower::ServiceExt;
+use vector_db::api::rest::{ApiConfig, UpdateEvent, UpdateOperation};
+
+async fn send(app: &Router, method: Method, uri: &str, body: Value) -> StatusCode {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+/// Subscribe to the update stream and return its body as a stream of chunks
+async fn subscribe(app: &Router) -> axum::body::BodyDataStream {
+    let request = Request::builder()
+        .uri("/api/v1/stream/updates")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+    response.into_body().into_data_stream()
+}
+
+/// Read the next `n` events as (event name, data) pairs, skipping comments
+async fn read_events(body: &mut axum::body::BodyDataStream, n: usize) -> Vec<(String, Value)> {
+    let mut buffer = String::new();
+    let mut events = Vec::new();
+    while events.len() < n {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .expect("timed out waiting for an event")
+            .expect("stream ended")
+            .unwrap();
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        while let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let name = block.lines().find_map(|line| line.strip_prefix("event: "));
+            let data = block.lines().find_map(|line| line.strip_prefix("data: "));
+            if let (Some(name), Some(data)) = (name, data) {
+                events.push((name.to_string(), serde_json::from_str(data).unwrap()));
+            }
+        }
+    }
+    events
+}
+
+fn update(data: &Value) -> UpdateEvent {
+    serde_json::from_value(data.clone()).unwrap()
+}
+
+#[tokio::test]
+async fn test_streams_insert_update_and_delete() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let mut body = subscribe(&app).await;
+
+    let vector = json!({ "id": "a", "vector": [1.0, 0.0, 0.0] });
+    assert_eq!(send(&app, Method::POST, "/api/v1/vectors", vector).await, StatusCode::CREATED);
+    let upsert = json!({ "id": "a", "vector": [0.0, 1.0, 0.0], "on_duplicate": "update" });
+    assert_eq!(send(&app, Method::POST, "/api/v1/vectors", upsert).await, StatusCode::OK);
+    assert_eq!(
+        send(&app, Method::DELETE, "/api/v1/vectors/a", Value::Null).await,
+        StatusCode::NO_CONTENT
+    );
+
+    let events = read_events(&mut body, 3).await;
+    let operations: Vec<UpdateOperation> = events
+        .iter()
+        .map(|(name, data)| {
+            assert_eq!(name, "update");
+            let event = update(data);
+            assert_eq!(event.id, "a");
+            assert_eq!(event.index, "recent");
+            assert!(chrono::DateTime::parse_from_rfc3339(&event.timestamp).is_ok());
+            event.operation
+        })
+        .collect();
+    assert_eq!(
+        operations,
+        vec![UpdateOperation::Insert, UpdateOperation::Update, UpdateOperation::Delete]
+    );
+}
+
+#[tokio::test]
+async fn test_streams_each_batch_item() {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let mut body = subscribe(&app).await;
+
+    let batch = json!({ "vectors": [
+        { "id": "b-0", "vector": [1.0, 0.0, 0.0] },
+        { "id": "b-1", "vector": [0.0, 1.0, 0.0] },
+    ] });
+    assert_eq!(send(&app, Method::POST, "/api/v1/vectors/batch", batch).await, StatusCode::OK);
+
+    let ids: Vec<String> = read_events(&mut body, 2)
+        .await
+        .iter()
+        .map(|(_, data)| update(data).id)
+        .collect();
+    assert_eq!(ids, vec!["b-0", "b-1"]);
+}
+
+#[tokio::test]
+async fn test_slow_subscriber_gets_lagged_event() {
+    let config = ApiConfig {
+        update_buffer: 2,
+        ..ApiConfig::default()
+    };
+    let (app, _) = mock_s5_server::create_app(config).await;
+    let mut body = subscribe(&app).await;
+
+    for i in 0..5 {
+        let vector = json!({ "id": format!("v-{}", i), "vector": [i as f32, 1.0, 0.0] });
+        send(&app, Method::POST, "/api/v1/vectors", vector).await;
+    }
+
+    let events = read_events(&mut body, 3).await;
+    assert_eq!(events[0].0, "lagged");
+    assert_eq!(events[0].1["skipped"], 3);
+    assert_eq!(update(&events[1].1).id, "v-3");
+    assert_eq!(update(&events[2].1).id, "v-4");
+}
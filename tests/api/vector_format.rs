@@ -0,0 +1,87 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for controlling how much of each vector responses carry
+
+use super::mock_s5_server;
+use axum_test::TestServer;
+use serde_json::json;
+use vector_db::api::rest::ApiConfig;
+
+const DIM: usize = 32;
+
+async fn setup() -> TestServer {
+    let (app, _) = mock_s5_server::create_app(ApiConfig::default()).await;
+    let server = TestServer::new(app).unwrap();
+
+    let vector: Vec<f32> = (0..DIM).map(|i| i as f32).collect();
+    server
+        .post("/api/v1/vectors")
+        .json(&json!({ "id": "video-1", "vector": vector }))
+        .await
+        .assert_status(axum::http::StatusCode::CREATED);
+
+    server
+}
+
+async fn search(server: &TestServer, options: serde_json::Value) -> serde_json::Value {
+    let query: Vec<f32> = (0..DIM).map(|i| i as f32).collect();
+    let response = server
+        .post("/api/v1/search")
+        .json(&json!({ "vector": query, "k": 1, "options": options }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    body["results"][0].clone()
+}
+
+#[tokio::test]
+async fn test_get_vector_formats() {
+    let server = setup().await;
+
+    let body: serde_json::Value = server.get("/api/v1/vectors/video-1").await.json();
+    assert_eq!(body["vector"].as_array().unwrap().len(), DIM);
+
+    let body: serde_json::Value = server
+        .get("/api/v1/vectors/video-1")
+        .add_query_param("vector_format", "truncated:8")
+        .await
+        .json();
+    assert_eq!(body["vector"], json!([0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]));
+
+    let body: serde_json::Value = server
+        .get("/api/v1/vectors/video-1")
+        .add_query_param("vector_format", "none")
+        .await
+        .json();
+    assert!(body.get("vector").is_none());
+    assert_eq!(body["id"], "video-1");
+}
+
+#[tokio::test]
+async fn test_get_vector_rejects_unknown_format() {
+    let server = setup().await;
+
+    server
+        .get("/api/v1/vectors/video-1")
+        .add_query_param("vector_format", "truncated:eight")
+        .await
+        .assert_status(axum::http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_search_vector_formats() {
+    let server = setup().await;
+
+    let result = search(&server, json!({})).await;
+    assert!(result.get("vector").is_none());
+
+    let result = search(&server, json!({ "vector_format": { "truncated": 8 } })).await;
+    assert_eq!(result["vector"].as_array().unwrap().len(), 8);
+
+    let result = search(&server, json!({ "vector_format": "full" })).await;
+    assert_eq!(result["vector"].as_array().unwrap().len(), DIM);
+
+    let result = search(&server, json!({ "vector_format": "none" })).await;
+    assert!(result.get("vector").is_none());
+}
@@ -171,6 +171,7 @@ fn test_s5_metadata_cbor_format() {
         mime_type: "application/cbor".to_string(),
         created_at: 1705745000, // Unix timestamp
         encryption: None,
+        compression: None,
     };
     
     let encoded = CborEncoder::encode_s5_metadata(&s5_meta).unwrap();
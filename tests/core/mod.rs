@@ -1,8 +1,16 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+mod quantized_distance;
+mod simd_dispatch;
 mod storage;
 mod storage_advanced;
+mod storage_compression;
+mod storage_encryption;
+mod storage_filesystem;
+mod storage_get_many;
+mod storage_put_if_absent;
 mod types;
+mod vector_id;
 mod vector_ops;
 mod vector_ops_advanced;
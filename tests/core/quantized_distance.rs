@@ -0,0 +1,120 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use vector_db::core::types::VectorId;
+use vector_db::core::vector_ops::{
+    cosine_similarity_i8, cosine_similarity_scalar, euclidean_distance_i8,
+    euclidean_distance_scalar, quantize_i8,
+};
+use vector_db::core::{FlatStoreError, FlatVectorStore, VectorPrecision};
+
+const DIM: usize = 64;
+
+fn normalized_vectors(count: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| {
+            let v: Vec<f32> = (0..DIM).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            v.into_iter().map(|x| x / norm).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_quantize_round_trip() {
+    let vector = vec![0.5, -1.0, 0.25, 0.0];
+    let quantized = quantize_i8(&vector);
+
+    assert_eq!(quantized.data[1], -127);
+    assert_eq!(quantized.memory_bytes(), 4 + 8);
+    for (orig, deq) in vector.iter().zip(quantized.dequantize()) {
+        assert!((orig - deq).abs() < 0.01);
+    }
+}
+
+#[test]
+fn test_quantize_zero_vector() {
+    let quantized = quantize_i8(&[0.0; 8]);
+    assert!(quantized.data.iter().all(|&v| v == 0));
+    assert_eq!(quantized.norm_sq, 0);
+    assert_eq!(cosine_similarity_i8(&quantized, &quantized), 0.0);
+}
+
+#[test]
+fn test_int8_distances_track_f32() {
+    let vectors = normalized_vectors(50, 1);
+    for pair in vectors.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let (qa, qb) = (quantize_i8(a), quantize_i8(b));
+
+        let exact = euclidean_distance_scalar(a, b);
+        assert!((euclidean_distance_i8(&qa, &qb) - exact).abs() < 0.02);
+
+        let exact = cosine_similarity_scalar(a, b);
+        assert!((cosine_similarity_i8(&qa, &qb) - exact).abs() < 0.02);
+    }
+}
+
+#[test]
+fn test_int8_recall_versus_f32() {
+    let data = normalized_vectors(2000, 2);
+    let queries = normalized_vectors(50, 3);
+    let k = 10;
+
+    let mut exact = FlatVectorStore::new(VectorPrecision::F32);
+    let mut quantized = FlatVectorStore::new(VectorPrecision::Int8);
+    for (i, vector) in data.iter().enumerate() {
+        exact.insert(VectorId::from_u64(i as u64), vector).unwrap();
+        quantized.insert(VectorId::from_u64(i as u64), vector).unwrap();
+    }
+
+    let mut hits = 0;
+    for query in &queries {
+        let truth: HashSet<VectorId> = exact
+            .search(query, k)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.vector_id)
+            .collect();
+        hits += quantized
+            .search(query, k)
+            .unwrap()
+            .iter()
+            .filter(|r| truth.contains(&r.vector_id))
+            .count();
+    }
+
+    let recall = hits as f32 / (queries.len() * k) as f32;
+    assert!(recall >= 0.9, "int8 recall {} dropped too far below f32", recall);
+
+    // Codes take a quarter of the f32 footprint plus 8 bytes per vector
+    assert_eq!(quantized.memory_bytes(), data.len() * (DIM + 8));
+    assert_eq!(exact.memory_bytes(), data.len() * DIM * 4);
+}
+
+#[test]
+fn test_flat_store_validation() {
+    let mut store = FlatVectorStore::new(VectorPrecision::Int8);
+    assert_eq!(store.precision(), VectorPrecision::Int8);
+    store.insert(VectorId::from_u64(1), &[1.0, 0.0]).unwrap();
+
+    assert_eq!(
+        store.insert(VectorId::from_u64(1), &[0.0, 1.0]),
+        Err(FlatStoreError::DuplicateVector(VectorId::from_u64(1)))
+    );
+    assert_eq!(
+        store.insert(VectorId::from_u64(2), &[1.0]),
+        Err(FlatStoreError::DimensionMismatch {
+            expected: 2,
+            actual: 1
+        })
+    );
+    assert!(store.search(&[1.0, 0.0, 0.0], 1).is_err());
+
+    assert!(store.remove(&VectorId::from_u64(1)));
+    assert!(store.is_empty());
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::vector_ops::{
+    cosine_similarity, cosine_similarity_scalar, cosine_similarity_simd, distance_kernels,
+    dot_product, dot_product_scalar, dot_product_simd, euclidean_distance,
+    euclidean_distance_scalar, euclidean_distance_simd, DistanceKernels, SimdLevel,
+};
+
+fn test_vectors(len: usize) -> (Vec<f32>, Vec<f32>) {
+    let a = (0..len).map(|i| ((i * 31) % 17) as f32 / 17.0 - 0.5).collect();
+    let b = (0..len).map(|i| ((i * 13) % 23) as f32 / 23.0 - 0.5).collect();
+    (a, b)
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() <= 1e-4 * expected.abs().max(1.0),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_dispatched_kernels_match_scalar() {
+    // Lengths around the 4- and 8-lane boundaries exercise the remainder loops
+    for len in [0, 1, 3, 4, 7, 8, 9, 16, 31, 384] {
+        let (a, b) = test_vectors(len);
+        assert_close(dot_product_simd(&a, &b), dot_product_scalar(&a, &b));
+        assert_close(euclidean_distance_simd(&a, &b), euclidean_distance_scalar(&a, &b));
+    }
+}
+
+#[test]
+fn test_canonical_functions_match_scalar() {
+    for len in [1, 5, 8, 13, 64, 768] {
+        let (a, b) = test_vectors(len);
+        assert_close(dot_product(&a, &b), dot_product_scalar(&a, &b));
+        assert_close(cosine_similarity(&a, &b), cosine_similarity_scalar(&a, &b));
+        assert_close(cosine_similarity_simd(&a, &b), cosine_similarity_scalar(&a, &b));
+        assert_close(euclidean_distance(&a, &b), euclidean_distance_scalar(&a, &b));
+
+        assert_close(DistanceMetric::DotProduct.distance(&a, &b), -dot_product_scalar(&a, &b));
+        assert_close(
+            DistanceMetric::Cosine.distance(&a, &b),
+            1.0 - cosine_similarity_scalar(&a, &b),
+        );
+    }
+
+    let zero = vec![0.0; 16];
+    let (a, _) = test_vectors(16);
+    assert_eq!(cosine_similarity(&a, &zero), 0.0);
+}
+
+#[test]
+fn test_length_mismatch_is_nan() {
+    let (a, _) = test_vectors(9);
+    let (b, _) = test_vectors(8);
+    assert!(dot_product(&a, &b).is_nan());
+    assert!(cosine_similarity(&a, &b).is_nan());
+    assert!(euclidean_distance(&a, &b).is_nan());
+    assert!(DistanceMetric::Euclidean.distance(&a, &b).is_nan());
+    assert!(dot_product_simd(&b, &a).is_nan());
+    assert!(euclidean_distance_simd(&[], &a).is_nan());
+}
+
+#[test]
+fn test_every_supported_level_matches_scalar() {
+    let (a, b) = test_vectors(100);
+    for level in [SimdLevel::Scalar, SimdLevel::Sse, SimdLevel::Avx2] {
+        let Some(kernels) = DistanceKernels::for_level(level) else {
+            assert!(level > SimdLevel::detect());
+            continue;
+        };
+        assert_eq!(kernels.level, level);
+        assert_close((kernels.dot_product)(&a, &b), dot_product_scalar(&a, &b));
+        assert_close((kernels.euclidean_distance)(&a, &b), euclidean_distance_scalar(&a, &b));
+    }
+}
+
+#[test]
+fn test_selected_level_is_supported() {
+    let selected = distance_kernels().level;
+    assert!(selected <= SimdLevel::detect());
+    assert!(DistanceKernels::for_level(SimdLevel::Scalar).is_some());
+}
+
+#[test]
+fn test_parse_level() {
+    assert_eq!(SimdLevel::parse("AVX2"), Some(SimdLevel::Avx2));
+    assert_eq!(SimdLevel::parse(" sse "), Some(SimdLevel::Sse));
+    assert_eq!(SimdLevel::parse("none"), Some(SimdLevel::Scalar));
+    assert_eq!(SimdLevel::parse("avx512"), None);
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_cbor::Value;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::{Embedding, Vector, VectorId};
+use vector_db::storage::enhanced_s5_storage::COMPRESSION_CODEC;
+use vector_db::storage::s5_adapter::{S5StorageConfig, Storage, StorageMode};
+use vector_db::storage::EnhancedS5Storage;
+
+fn config(compress: bool, encryption_key: Option<&str>) -> S5StorageConfig {
+    S5StorageConfig {
+        mode: StorageMode::InProcess,
+        mock_server_url: None,
+        portal_url: None,
+        seed_phrase: None,
+        connection_timeout: None,
+        retry_attempts: None,
+        encrypt_at_rest: Some(encryption_key.is_some()),
+        encryption_key: encryption_key.map(str::to_string),
+        compress: Some(compress),
+    }
+}
+
+fn storage(backend: &MockS5Storage, compress: bool) -> EnhancedS5Storage {
+    EnhancedS5Storage::with_in_process_config(config(compress, None), backend.clone()).unwrap()
+}
+
+fn compressible_vector() -> Vector {
+    Vector::new(
+        VectorId::from_string("wide"),
+        Embedding::new(vec![0.5; 10_000]).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_compressed_vector_is_smaller_and_round_trips() {
+    let backend = MockS5Storage::new();
+    let vector = compressible_vector();
+
+    Storage::put(&storage(&backend, false), "vectors/raw", &vector).await.unwrap();
+    let compressed = storage(&backend, true);
+    Storage::put(&compressed, "vectors/zstd", &vector).await.unwrap();
+
+    let raw_len = backend.get("vectors/raw").await.unwrap().unwrap().len();
+    let zstd_len = backend.get("vectors/zstd").await.unwrap().unwrap().len();
+    assert!(zstd_len * 10 < raw_len, "{} bytes compressed vs {} raw", zstd_len, raw_len);
+
+    let loaded: Vector = Storage::get(&compressed, "vectors/zstd").await.unwrap();
+    assert_eq!(loaded.id, vector.id);
+    assert_eq!(loaded.embedding.as_slice(), vector.embedding.as_slice());
+
+    // The codec is recorded in the object's metadata
+    let stored = backend.get("vectors/zstd").await.unwrap().unwrap();
+    let Value::Map(object) = serde_cbor::from_slice::<Value>(&stored[4..]).unwrap() else {
+        panic!("stored object is not a map");
+    };
+    let Value::Map(metadata) = &object[&Value::Text("metadata".to_string())] else {
+        panic!("metadata is not a map");
+    };
+    assert_eq!(
+        metadata[&Value::Text("compression".to_string())],
+        Value::Text(COMPRESSION_CODEC.to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_uncompressed_legacy_objects_still_load() {
+    let backend = MockS5Storage::new();
+    let vector = compressible_vector();
+    Storage::put(&storage(&backend, false), "vectors/legacy", &vector).await.unwrap();
+    S5Storage::put(&backend, "chunks/legacy", b"raw chunk".to_vec()).await.unwrap();
+
+    let compressed = storage(&backend, true);
+    let loaded: Vector = Storage::get(&compressed, "vectors/legacy").await.unwrap();
+    assert_eq!(loaded.embedding.as_slice(), vector.embedding.as_slice());
+    assert_eq!(
+        S5Storage::get(&compressed, "chunks/legacy").await.unwrap(),
+        Some(b"raw chunk".to_vec())
+    );
+
+    // And compressed objects load after compression is turned off again
+    S5Storage::put(&compressed, "chunks/zstd", vec![7u8; 4096]).await.unwrap();
+    assert_eq!(
+        S5Storage::get(&storage(&backend, false), "chunks/zstd").await.unwrap(),
+        Some(vec![7u8; 4096])
+    );
+}
+
+#[tokio::test]
+async fn test_compression_with_encryption() {
+    let backend = MockS5Storage::new();
+    let key = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+    let storage = EnhancedS5Storage::with_in_process_config(config(true, Some(key)), backend.clone()).unwrap();
+    let vector = compressible_vector();
+
+    Storage::put(&storage, "vectors/both", &vector).await.unwrap();
+    let loaded: Vector = Storage::get(&storage, "vectors/both").await.unwrap();
+    assert_eq!(loaded.embedding.as_slice(), vector.embedding.as_slice());
+
+    // Compressed before encryption, so the ciphertext is small too
+    let stored = backend.get("vectors/both").await.unwrap().unwrap();
+    assert!(stored.len() < 4_000, "{} bytes stored", stored.len());
+}
@@ -0,0 +1,136 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_cbor::Value;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::storage::enhanced_s5_storage::ENCRYPTION_SCHEME;
+use vector_db::storage::s5_adapter::{S5StorageAdapter, S5StorageConfig, Storage, StorageMode};
+use vector_db::storage::EnhancedS5Storage;
+
+/// Prefix of objects stored in an envelope
+const ENVELOPE_MAGIC: &[u8] = b"FVS5";
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fn config(key: &str) -> S5StorageConfig {
+    S5StorageConfig {
+        mode: StorageMode::InProcess,
+        mock_server_url: None,
+        portal_url: None,
+        seed_phrase: None,
+        connection_timeout: None,
+        retry_attempts: None,
+        encrypt_at_rest: Some(true),
+        encryption_key: Some(key.to_string()),
+        compress: None,
+    }
+}
+
+fn encrypted_storage(backend: &MockS5Storage) -> EnhancedS5Storage {
+    EnhancedS5Storage::with_in_process_config(config(KEY), backend.clone()).unwrap()
+}
+
+fn text(key: &str) -> Value {
+    Value::Text(key.to_string())
+}
+
+#[tokio::test]
+async fn test_encrypted_round_trip() {
+    let backend = MockS5Storage::new();
+    let storage = encrypted_storage(&backend);
+
+    Storage::put(&storage, "vectors/a", &vec![1.0f32, 2.0, 3.0]).await.unwrap();
+    let stored: Vec<f32> = Storage::get(&storage, "vectors/a").await.unwrap();
+    assert_eq!(stored, vec![1.0, 2.0, 3.0]);
+
+    S5Storage::put(&storage, "chunks/0", b"chunk bytes".to_vec()).await.unwrap();
+    assert_eq!(
+        S5Storage::get(&storage, "chunks/0").await.unwrap(),
+        Some(b"chunk bytes".to_vec())
+    );
+    assert_eq!(S5Storage::get(&storage, "chunks/missing").await.unwrap(), None);
+
+    let stats = storage.get_stats().await.unwrap();
+    assert_eq!(stats["encryption_algorithm"], ENCRYPTION_SCHEME);
+}
+
+#[tokio::test]
+async fn test_stored_bytes_are_not_plaintext() {
+    let backend = MockS5Storage::new();
+    let storage = encrypted_storage(&backend);
+    let secret = "a very recognizable plaintext marker".to_string();
+
+    Storage::put(&storage, "docs/secret", &secret).await.unwrap();
+    Storage::put(&storage, "docs/again", &secret).await.unwrap();
+
+    let raw = backend.get("docs/secret").await.unwrap().unwrap();
+    let encoded = serde_cbor::to_vec(&secret).unwrap();
+    assert!(!raw.windows(secret.len()).any(|w| w == secret.as_bytes()));
+    assert!(!raw.windows(encoded.len()).any(|w| w == encoded.as_slice()));
+
+    // Fresh nonce per object, so equal plaintexts store differently
+    let again = backend.get("docs/again").await.unwrap().unwrap();
+    assert_ne!(raw, again);
+
+    let Value::Map(object) = serde_cbor::from_slice::<Value>(&raw[ENVELOPE_MAGIC.len()..]).unwrap() else {
+        panic!("stored object is not a map");
+    };
+    let Value::Map(metadata) = &object[&text("metadata")] else {
+        panic!("metadata is not a map");
+    };
+    assert_eq!(metadata[&text("encryption")], text(ENCRYPTION_SCHEME));
+
+    // Without the key the stored bytes are all a reader gets
+    let plain = EnhancedS5Storage::with_in_process_backend(backend.clone()).unwrap();
+    assert!(Storage::get::<String>(&plain, "docs/secret").await.is_err());
+}
+
+#[tokio::test]
+async fn test_rejects_unsupported_scheme_and_wrong_key() {
+    let backend = MockS5Storage::new();
+    let storage = encrypted_storage(&backend);
+    Storage::put(&storage, "docs/a", &"hello".to_string()).await.unwrap();
+
+    // Relabel the stored object with a scheme this client can't decrypt
+    let raw = backend.get("docs/a").await.unwrap().unwrap();
+    let Value::Map(mut object) = serde_cbor::from_slice::<Value>(&raw[ENVELOPE_MAGIC.len()..]).unwrap() else {
+        panic!("stored object is not a map");
+    };
+    if let Some(Value::Map(metadata)) = object.get_mut(&text("metadata")) {
+        metadata.insert(text("encryption"), text("xchacha20-poly1305"));
+    }
+    let mut relabelled = ENVELOPE_MAGIC.to_vec();
+    serde_cbor::to_writer(&mut relabelled, &Value::Map(object)).unwrap();
+    backend.put("docs/b", relabelled).await.unwrap();
+
+    let err = Storage::get::<String>(&storage, "docs/b").await.unwrap_err();
+    assert!(err.to_string().contains("unsupported encryption scheme"));
+
+    // Plaintext objects are rejected rather than passed through
+    backend.put("docs/plain", serde_cbor::to_vec(&"hello").unwrap()).await.unwrap();
+    assert!(Storage::get::<String>(&storage, "docs/plain").await.is_err());
+
+    let other_key = KEY.replace("00", "ff");
+    let other = EnhancedS5Storage::with_in_process_config(config(&other_key), backend).unwrap();
+    let err = Storage::get::<String>(&other, "docs/a").await.unwrap_err();
+    assert!(err.to_string().contains("decryption failed"));
+}
+
+#[tokio::test]
+async fn test_object_moved_to_another_key_does_not_open() {
+    let backend = MockS5Storage::new();
+    let storage = encrypted_storage(&backend);
+    Storage::put(&storage, "docs/a", &"hello".to_string()).await.unwrap();
+
+    // The object key is authenticated, so swapping objects between keys is detected
+    let raw = backend.get("docs/a").await.unwrap().unwrap();
+    backend.put("docs/b", raw).await.unwrap();
+    let err = Storage::get::<String>(&storage, "docs/b").await.unwrap_err();
+    assert!(err.to_string().contains("decryption failed"));
+}
+
+#[test]
+fn test_invalid_key_is_rejected() {
+    assert!(EnhancedS5Storage::new(config("not hex")).is_err());
+    assert!(EnhancedS5Storage::new(config("0011")).is_err());
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::storage::{BatchS5Storage, CachedS5Storage, S5Storage};
+use vector_db::storage::FilesystemStorage;
+
+#[tokio::test]
+async fn test_basic_storage_operations() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = FilesystemStorage::new(dir.path());
+    let path = "/test/data";
+    let data = b"test data".to_vec();
+
+    // Test put
+    storage.put(path, data.clone()).await.unwrap();
+    assert!(dir.path().join("test/data").is_file());
+
+    // Test get
+    let retrieved = storage.get(path).await.unwrap();
+    assert_eq!(retrieved, Some(data));
+
+    // Test list
+    let items = storage.list("/test").await.unwrap();
+    assert_eq!(items, vec![path.to_string()]);
+
+    // Test delete
+    storage.delete(path).await.unwrap();
+    let deleted = storage.get(path).await.unwrap();
+    assert_eq!(deleted, None);
+    storage.delete(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_missing_key_is_none() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = FilesystemStorage::new(dir.path().join("not-created-yet"));
+
+    assert_eq!(storage.get("vectors/missing").await.unwrap(), None);
+    assert!(storage.list("vectors/").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_list_walks_prefix() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = FilesystemStorage::new(dir.path());
+    for key in [
+        "index/manifest.json",
+        "index/chunks/chunk_0000.cbor",
+        "index/chunks/chunk_0001.cbor",
+        "index/chunks/nested/deep.cbor",
+        "other/file",
+    ] {
+        storage.put(key, key.as_bytes().to_vec()).await.unwrap();
+    }
+
+    assert_eq!(
+        storage.list("index/chunks/").await.unwrap(),
+        vec![
+            "index/chunks/chunk_0000.cbor",
+            "index/chunks/chunk_0001.cbor",
+            "index/chunks/nested/deep.cbor",
+        ]
+    );
+    // Prefixes need not end on a directory boundary
+    assert_eq!(
+        storage.list("index/chunks/chunk_").await.unwrap(),
+        vec!["index/chunks/chunk_0000.cbor", "index/chunks/chunk_0001.cbor"]
+    );
+    assert_eq!(storage.list("").await.unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn test_rejects_path_traversal() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = FilesystemStorage::new(dir.path().join("root"));
+
+    assert!(storage.put("../escape", b"x".to_vec()).await.is_err());
+    assert!(storage.put("a/../../escape", b"x".to_vec()).await.is_err());
+    assert!(storage.get("../escape").await.is_err());
+    assert!(storage.delete("a/../b").await.is_err());
+    assert!(storage.list("../").await.is_err());
+    assert!(!dir.path().join("escape").exists());
+}
+
+#[tokio::test]
+async fn test_data_survives_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    FilesystemStorage::new(dir.path())
+        .put("vectors/a", b"durable".to_vec())
+        .await
+        .unwrap();
+
+    let reopened = FilesystemStorage::new(dir.path());
+    assert_eq!(reopened.get("vectors/a").await.unwrap(), Some(b"durable".to_vec()));
+}
+
+#[tokio::test]
+async fn test_put_if_absent() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = FilesystemStorage::new(dir.path());
+
+    assert!(storage.put_if_absent("vectors/a", b"first".to_vec()).await.unwrap());
+    assert!(!storage.put_if_absent("vectors/a", b"second".to_vec()).await.unwrap());
+    assert_eq!(storage.get("vectors/a").await.unwrap(), Some(b"first".to_vec()));
+    // No temporary files are left behind
+    assert_eq!(storage.list("").await.unwrap(), vec!["vectors/a"]);
+}
+
+#[tokio::test]
+async fn test_cached_storage() {
+    let dir = tempfile::tempdir().unwrap();
+    let cached = CachedS5Storage::new(FilesystemStorage::new(dir.path()), 100);
+
+    cached.put("/cached/test", b"cached data".to_vec()).await.unwrap();
+    assert_eq!(cached.get("/cached/test").await.unwrap(), Some(b"cached data".to_vec()));
+    assert_eq!(cached.stats().await.hits, 1);
+}
+
+#[tokio::test]
+async fn test_batch_operations() {
+    let dir = tempfile::tempdir().unwrap();
+    let batch = BatchS5Storage::new(FilesystemStorage::new(dir.path()), 10);
+
+    for i in 0..5 {
+        let path = format!("/batch/item_{}", i);
+        batch.put(&path, format!("data_{}", i).into_bytes()).await.unwrap();
+    }
+
+    // list flushes the buffered writes to disk
+    assert_eq!(batch.list("/batch/").await.unwrap().len(), 5);
+    assert!(dir.path().join("batch/item_4").is_file());
+}
@@ -0,0 +1,143 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError, GET_MANY_CONCURRENCY};
+use vector_db::core::types::VectorId;
+use vector_db::storage::chunk_loader::{ChunkLoadPolicy, ChunkLoader};
+
+/// Storage whose `get`s finish in reverse key order and track concurrency
+#[derive(Clone)]
+struct SlowStorage {
+    inner: MockS5Storage,
+    active: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+    gets: Arc<AtomicUsize>,
+}
+
+impl SlowStorage {
+    fn new() -> Self {
+        Self {
+            inner: MockS5Storage::new(),
+            active: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+            gets: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[async_trait]
+impl S5Storage for SlowStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.gets.fetch_add(1, Ordering::SeqCst);
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(active, Ordering::SeqCst);
+
+        let rank: u64 = path.rsplit('-').next().and_then(|n| n.parse().ok()).unwrap_or(0);
+        tokio::time::sleep(Duration::from_millis(40 - rank.min(39))).await;
+
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        self.inner.put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+#[tokio::test]
+async fn test_get_many_preserves_order_and_reports_missing() {
+    let storage = SlowStorage::new();
+    for i in [0, 2, 3] {
+        storage
+            .put(&format!("key-{}", i), vec![i as u8])
+            .await
+            .unwrap();
+    }
+
+    let keys: Vec<String> = [3, 1, 0, 2].iter().map(|i| format!("key-{}", i)).collect();
+    let results = storage.get_many(&keys).await.unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            ("key-3".to_string(), Some(vec![3])),
+            ("key-1".to_string(), None),
+            ("key-0".to_string(), Some(vec![0])),
+            ("key-2".to_string(), Some(vec![2])),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_get_many_bounds_concurrency() {
+    let storage = SlowStorage::new();
+    let keys: Vec<String> = (0..30).map(|i| format!("key-{}", i)).collect();
+
+    let results = storage.get_many(&keys).await.unwrap();
+
+    assert_eq!(results.len(), 30);
+    assert!(results.iter().all(|(_, data)| data.is_none()));
+    let peak = storage.peak.load(Ordering::SeqCst);
+    assert!(peak > 1 && peak <= GET_MANY_CONCURRENCY, "peak concurrency {}", peak);
+}
+
+#[tokio::test]
+async fn test_get_many_empty() {
+    let storage = MockS5Storage::new();
+    assert!(storage.get_many(&[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_chunk_loader_batch_load_uses_cache_and_order() {
+    let storage = SlowStorage::new();
+    let paths: Vec<String> = (0..4).map(|i| format!("chunks/chunk-{}", i)).collect();
+    for (i, path) in paths.iter().enumerate() {
+        let mut chunk = VectorChunk::new(format!("chunk-{}", i), i, i);
+        chunk.add_vector(VectorId::from_u64(i as u64), vec![i as f32; 4]);
+        storage.put(path, chunk.to_cbor().unwrap()).await.unwrap();
+    }
+    let loader = ChunkLoader::new(Arc::new(storage.clone()), Arc::new(ChunkCache::new(10)));
+    loader.load_chunk(&paths[1]).await.unwrap();
+    storage.gets.store(0, Ordering::SeqCst);
+
+    let chunks = loader.load_chunks_or_skip(&paths).await.unwrap();
+
+    let ids: Vec<String> = chunks.iter().map(|c| c.as_ref().unwrap().chunk_id.clone()).collect();
+    assert_eq!(ids, vec!["chunk-0", "chunk-1", "chunk-2", "chunk-3"]);
+    // The cached chunk is not fetched again
+    assert_eq!(storage.gets.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_chunk_loader_batch_load_missing_chunk() {
+    let storage = MockS5Storage::new();
+    let mut chunk = VectorChunk::new("chunk-0".to_string(), 0, 0);
+    chunk.add_vector(VectorId::from_u64(0), vec![0.0; 4]);
+    storage.put("chunks/chunk-0", chunk.to_cbor().unwrap()).await.unwrap();
+    let paths = vec!["chunks/chunk-0".to_string(), "chunks/gone".to_string()];
+
+    let fail_fast = ChunkLoader::new(Arc::new(storage.clone()), Arc::new(ChunkCache::new(10)));
+    let err = fail_fast.load_chunks_or_skip(&paths).await.unwrap_err();
+    assert!(err.to_string().contains("Chunk not found: chunks/gone"));
+
+    let skipping = ChunkLoader::new(Arc::new(storage), Arc::new(ChunkCache::new(10)))
+        .with_policy(ChunkLoadPolicy::SkipMissing);
+    let chunks = skipping.load_chunks_or_skip(&paths).await.unwrap();
+    assert!(chunks[0].is_some());
+    assert!(chunks[1].is_none());
+    assert_eq!(skipping.skipped_chunks().await[0].path, "chunks/gone");
+}
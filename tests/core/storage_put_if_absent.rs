@@ -0,0 +1,45 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::storage::s5_adapter::Storage;
+use vector_db::storage::EnhancedS5Storage;
+
+#[tokio::test]
+async fn test_put_if_absent_does_not_overwrite() {
+    let storage = MockS5Storage::new();
+
+    assert!(storage.put_if_absent("vectors/a", b"first".to_vec()).await.unwrap());
+    assert!(!storage.put_if_absent("vectors/a", b"second".to_vec()).await.unwrap());
+
+    assert_eq!(storage.get("vectors/a").await.unwrap(), Some(b"first".to_vec()));
+}
+
+#[tokio::test]
+async fn test_put_if_absent_race_has_one_winner() {
+    let storage = MockS5Storage::new();
+
+    let writers: Vec<_> = (0..16u8)
+        .map(|i| {
+            let storage = storage.clone();
+            tokio::spawn(async move { storage.put_if_absent("vectors/race", vec![i]).await.unwrap() })
+        })
+        .collect();
+    let mut winners = 0;
+    for writer in writers {
+        winners += writer.await.unwrap() as usize;
+    }
+
+    assert_eq!(winners, 1);
+}
+
+#[tokio::test]
+async fn test_enhanced_storage_put_if_absent() {
+    let storage = EnhancedS5Storage::with_in_process_backend(MockS5Storage::new()).unwrap();
+
+    assert!(Storage::put_if_absent(&storage, "vectors/a", &vec![1.0f32, 2.0]).await.unwrap());
+    assert!(!Storage::put_if_absent(&storage, "vectors/a", &vec![3.0f32]).await.unwrap());
+
+    let stored: Vec<f32> = Storage::get(&storage, "vectors/a").await.unwrap();
+    assert_eq!(stored, vec![1.0, 2.0]);
+}
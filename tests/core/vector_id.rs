@@ -0,0 +1,71 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for string-hashed and integer vector id representations
+
+use vector_db::core::storage::MockS5Storage;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::persistence::HybridPersister;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+#[test]
+fn test_integer_id_accessors() {
+    let id = VectorId::from_u64(42);
+    assert_eq!(id.as_u64(), Some(42));
+    assert_eq!(id.to_string(), "42");
+    assert_eq!(VectorId::from(42u64), id);
+
+    let hashed = VectorId::from_string("42");
+    assert_eq!(hashed.as_u64(), None);
+    assert_ne!(hashed, id);
+}
+
+#[test]
+fn test_both_representations_round_trip_cbor_and_json() {
+    for id in [VectorId::from_u64(7), VectorId::from_string("doc-7")] {
+        assert_eq!(VectorId::from_cbor(&id.to_cbor().unwrap()).unwrap(), id);
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(serde_json::from_str::<VectorId>(&json).unwrap(), id);
+    }
+
+    // Integer ids stay compact
+    assert!(VectorId::from_u64(7).to_cbor().unwrap().len() < 4);
+}
+
+#[test]
+fn test_legacy_byte_array_encoding_still_loads() {
+    let id = VectorId::from_string("legacy");
+    let legacy = serde_cbor::to_vec(&id.as_bytes()).unwrap();
+    assert_eq!(legacy, id.to_cbor().unwrap());
+    assert_eq!(VectorId::from_cbor(&legacy).unwrap(), id);
+}
+
+#[tokio::test]
+async fn test_mixed_ids_round_trip_through_chunked_persistence() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0, 0.5]).collect();
+    index.initialize(training).await.unwrap();
+
+    let int_ids: Vec<VectorId> = (0..10).map(VectorId::from_u64).collect();
+    let str_ids: Vec<VectorId> = (0..10).map(|i| VectorId::from_string(&format!("doc-{}", i))).collect();
+    for i in 0..10 {
+        index.insert(int_ids[i].clone(), vec![i as f32, 0.0, 0.0]).await.unwrap();
+        index.insert(str_ids[i].clone(), vec![i as f32, 10.0, 0.0]).await.unwrap();
+    }
+
+    let persister = HybridPersister::new(MockS5Storage::new());
+    persister.save_index_chunked(&index, "ids").await.unwrap();
+    let loaded = persister
+        .load_index_chunked("ids", HybridConfig::default())
+        .await
+        .unwrap();
+
+    let results = loaded.search(&[3.0, 0.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, int_ids[3]);
+    assert_eq!(results[0].vector_id.as_u64(), Some(3));
+
+    let results = loaded.search(&[5.0, 10.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, str_ids[5]);
+    assert_eq!(results[0].vector_id.as_u64(), None);
+}
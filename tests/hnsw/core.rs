@@ -29,6 +29,10 @@ mod hnsw_structure_tests {
             max_connections_layer_0: 32,
             ef_construction: 200,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         };
 
         let index = HNSWIndex::new(config.clone());
@@ -91,6 +95,10 @@ mod hnsw_insertion_tests {
             max_connections_layer_0: 8,
             ef_construction: 200,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         let vectors = vec![
@@ -203,6 +211,10 @@ mod hnsw_search_tests {
             max_connections_layer_0: 32,
             ef_construction: 200,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert 100 random vectors
@@ -263,6 +275,10 @@ mod hnsw_search_tests {
             max_connections_layer_0: 32,
             ef_construction: 200,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert many vectors
@@ -322,6 +338,10 @@ mod hnsw_edge_cases {
             max_connections_layer_0: 8,
             ef_construction: 200,
             seed: Some(42), // Fixed seed for reproducibility
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert enough nodes to likely have multiple layers
@@ -0,0 +1,72 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hnsw::core::{HNSWConfig, HNSWIndex};
+
+fn create_index(count: u64, cache_distances: bool) -> HNSWIndex {
+    let mut index = HNSWIndex::new(HNSWConfig {
+        max_connections: 8,
+        max_connections_layer_0: 16,
+        ef_construction: 50,
+        seed: Some(7),
+        cache_distances,
+        metric: DistanceMetric::Euclidean,
+        use_heuristic_selection: false,
+        keep_pruned_connections: false,
+    });
+    for i in 0..count {
+        let angle = i as f32 * 0.37;
+        index
+            .insert(
+                VectorId::from_u64(i),
+                vec![angle.cos(), angle.sin(), (i % 13) as f32 * 0.1, 1.0],
+            )
+            .unwrap();
+    }
+    index
+}
+
+#[test]
+fn test_cached_search_returns_identical_results() {
+    let cached = create_index(300, true);
+    let uncached = create_index(300, false);
+
+    for q in 0..20 {
+        let angle = q as f32 * 1.3;
+        let query = vec![angle.sin(), angle.cos(), 0.5, 1.0];
+
+        let (with_cache, _) = cached.search_with_stats(&query, 10, 50).unwrap();
+        let (without_cache, _) = uncached.search_with_stats(&query, 10, 50).unwrap();
+
+        let ids = |results: &[vector_db::core::types::SearchResult]| {
+            results
+                .iter()
+                .map(|r| (r.vector_id.clone(), r.distance))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&with_cache), ids(&without_cache));
+    }
+}
+
+#[test]
+fn test_cache_reduces_distance_computations() {
+    let cached = create_index(300, true);
+    let uncached = create_index(300, false);
+    assert!(cached.get_max_level() > 0, "graph should have upper layers");
+
+    let query = vec![0.2, 0.9, 0.3, 1.0];
+    let (_, with_cache) = cached.search_with_stats(&query, 10, 50).unwrap();
+    let (_, without_cache) = uncached.search_with_stats(&query, 10, 50).unwrap();
+
+    assert!(with_cache.distance_computations > 0);
+    assert!(with_cache.distance_computations < without_cache.distance_computations);
+}
+
+#[test]
+fn test_empty_index_reports_no_work() {
+    let index = create_index(0, true);
+    let (results, stats) = index.search_with_stats(&[0.0, 0.0, 0.0, 0.0], 5, 10).unwrap();
+    assert!(results.is_empty());
+    assert_eq!(stats.distance_computations, 0);
+}
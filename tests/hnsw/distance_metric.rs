@@ -0,0 +1,127 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hnsw::core::{HNSWConfig, HNSWError, HNSWIndex};
+
+fn create_index(metric: DistanceMetric) -> HNSWIndex {
+    HNSWIndex::new(HNSWConfig {
+        seed: Some(7),
+        metric,
+        ..HNSWConfig::default()
+    })
+}
+
+fn ids(index: &HNSWIndex, query: &[f32], k: usize) -> Vec<VectorId> {
+    index
+        .search(query, k, 50)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.vector_id)
+        .collect()
+}
+
+/// Deterministic spread of vectors with varying magnitudes
+fn scattered(count: u64) -> Vec<(VectorId, Vec<f32>)> {
+    (0..count)
+        .map(|i| {
+            let angle = i as f32 * 0.61;
+            let scale = 0.5 + (i % 7) as f32;
+            let vector = vec![angle.cos() * scale, angle.sin() * scale, (i % 5) as f32 * 0.3];
+            (VectorId::from_u64(i), vector)
+        })
+        .collect()
+}
+
+#[test]
+fn test_euclidean_is_the_default() {
+    assert_eq!(HNSWConfig::default().metric, DistanceMetric::Euclidean);
+
+    // Configs persisted before the metric existed
+    let json = r#"{"max_connections":16,"max_connections_layer_0":32,"ef_construction":200,"seed":null}"#;
+    let config: HNSWConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(config.metric, DistanceMetric::Euclidean);
+
+    assert_eq!(HNSWConfig::default_for(DistanceMetric::Cosine).metric, DistanceMetric::Cosine);
+}
+
+#[test]
+fn test_cosine_ranks_by_angle() {
+    let far_but_aligned = VectorId::from_u64(1);
+    let near_but_angled = VectorId::from_u64(2);
+    let query = [1.0, 0.0, 0.0];
+
+    for (metric, nearest) in [
+        (DistanceMetric::Euclidean, &near_but_angled),
+        (DistanceMetric::Cosine, &far_but_aligned),
+    ] {
+        let mut index = create_index(metric);
+        index.insert(far_but_aligned.clone(), vec![10.0, 1.0, 0.0]).unwrap();
+        index.insert(near_but_angled.clone(), vec![0.6, 0.6, 0.0]).unwrap();
+
+        assert_eq!(&ids(&index, &query, 1)[0], nearest, "{:?}", metric);
+    }
+
+    let index = create_index(DistanceMetric::Cosine);
+    assert!(index.distance(&[2.0, 0.0], &[5.0, 0.0]).abs() < 1e-6);
+    assert!((index.distance(&[1.0, 0.0], &[0.0, 1.0]) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_dot_product_prefers_larger_inner_product() {
+    let mut index = create_index(DistanceMetric::DotProduct);
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.0, 0.0]).unwrap();
+    index.insert(VectorId::from_u64(2), vec![10.0, 0.0, 0.0]).unwrap();
+    index.insert(VectorId::from_u64(3), vec![-5.0, 0.0, 0.0]).unwrap();
+
+    let results = index.search(&[1.0, 0.0, 0.0], 3, 50).unwrap();
+
+    let order: Vec<_> = results.iter().map(|r| r.vector_id.clone()).collect();
+    assert_eq!(
+        order,
+        vec![VectorId::from_u64(2), VectorId::from_u64(1), VectorId::from_u64(3)]
+    );
+    // Distances are negated inner products
+    assert!((results[0].distance + 10.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_graph_search_matches_brute_force_for_each_metric() {
+    let vectors = scattered(200);
+    let query = [0.3, 0.9, 0.4];
+
+    for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+        let mut index = create_index(metric);
+        for (id, vector) in &vectors {
+            index.insert(id.clone(), vector.clone()).unwrap();
+        }
+
+        let mut expected: Vec<_> = vectors
+            .iter()
+            .map(|(id, v)| (id.clone(), metric.distance(&query, v)))
+            .collect();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let expected: Vec<_> = expected.into_iter().take(5).map(|(id, _)| id).collect();
+
+        let found = index.search(&query, 5, 200).unwrap();
+        let found: Vec<_> = found.into_iter().map(|r| r.vector_id).collect();
+        assert_eq!(found, expected, "{:?}", metric);
+    }
+}
+
+#[test]
+fn test_cosine_rejects_zero_vectors() {
+    let mut index = create_index(DistanceMetric::Cosine);
+
+    let insert = index.insert(VectorId::from_u64(1), vec![0.0, 0.0, 0.0]);
+    assert!(matches!(insert, Err(HNSWError::ZeroVector)));
+
+    index.insert(VectorId::from_u64(2), vec![1.0, 0.0, 0.0]).unwrap();
+    let search = index.search(&[0.0, 0.0, 0.0], 1, 50);
+    assert!(matches!(search, Err(HNSWError::ZeroVector)));
+
+    // Other metrics accept them
+    let mut euclidean = create_index(DistanceMetric::Euclidean);
+    euclidean.insert(VectorId::from_u64(1), vec![0.0, 0.0, 0.0]).unwrap();
+    assert!(euclidean.search(&[0.0, 0.0, 0.0], 1, 50).is_ok());
+}
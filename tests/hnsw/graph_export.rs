@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hnsw::core::{HNSWConfig, HNSWIndex};
+use vector_db::hnsw::operations::GraphExportOptions;
+
+fn create_index(count: u64) -> HNSWIndex {
+    let mut index = HNSWIndex::new(HNSWConfig {
+        max_connections: 4,
+        max_connections_layer_0: 8,
+        ef_construction: 20,
+        seed: Some(3),
+        cache_distances: true,
+        metric: DistanceMetric::Euclidean,
+        use_heuristic_selection: false,
+        keep_pruned_connections: false,
+    });
+    for i in 0..count {
+        let angle = i as f32 * 0.7;
+        index
+            .insert(VectorId::from_u64(i), vec![angle.cos(), angle.sin(), i as f32 * 0.01])
+            .unwrap();
+    }
+    index
+}
+
+#[test]
+fn test_export_edge_counts_match_adjacency_sets() {
+    let index = create_index(40);
+    let export = index.export_graph();
+
+    assert_eq!(export.total_nodes, 40);
+    assert!(!export.sampled);
+    assert_eq!(export.layers.len(), index.get_max_level() + 1);
+    assert_eq!(export.entry_point, index.entry_point().map(|id| id.hash_hex()));
+
+    for layer in &export.layers {
+        let expected_nodes: Vec<_> = index
+            .get_all_nodes()
+            .into_iter()
+            .filter(|n| n.level() >= layer.layer)
+            .collect();
+        assert_eq!(layer.nodes.len(), expected_nodes.len());
+
+        let expected_edges: usize = expected_nodes.iter().map(|n| n.neighbors(layer.layer).len()).sum();
+        assert_eq!(layer.edge_count, expected_edges);
+        assert_eq!(layer.adjacency.values().map(|n| n.len()).sum::<usize>(), expected_edges);
+
+        for node in &expected_nodes {
+            assert_eq!(
+                layer.adjacency[&node.id().hash_hex()].len(),
+                node.neighbors(layer.layer).len()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_export_respects_layer_filter_and_node_budget() {
+    let index = create_index(40);
+
+    let export = index.export_graph_with(&GraphExportOptions {
+        layers: Some(vec![0]),
+        max_nodes: Some(10),
+    });
+
+    assert!(export.sampled);
+    assert_eq!(export.layers.len(), 1);
+    let layer = &export.layers[0];
+    assert_eq!(layer.layer, 0);
+    assert_eq!(layer.nodes.len(), 10);
+
+    // Edges only point at sampled nodes
+    for neighbors in layer.adjacency.values() {
+        assert!(neighbors.iter().all(|n| layer.adjacency.contains_key(n)));
+    }
+}
+
+#[test]
+fn test_export_empty_graph() {
+    let index = HNSWIndex::new(HNSWConfig::default());
+    let export = index.export_graph();
+    assert_eq!(export.total_nodes, 0);
+    assert!(export.layers.is_empty());
+}
+
+#[test]
+fn test_export_keeps_ids_sharing_display_form_apart() {
+    // Both ids display as "vec_0b08289b"
+    let first = VectorId::from_string("doc-68691");
+    let second = VectorId::from_string("doc-78960");
+    assert_eq!(first.to_string(), second.to_string());
+
+    let mut index = HNSWIndex::new(HNSWConfig::default());
+    index.insert(first.clone(), vec![1.0, 0.0]).unwrap();
+    index.insert(second.clone(), vec![0.0, 1.0]).unwrap();
+
+    let layer = &index.export_graph().layers[0];
+    assert_eq!(layer.nodes.len(), 2);
+    assert_eq!(layer.adjacency.len(), 2);
+    assert_eq!(layer.adjacency[&first.hash_hex()], vec![second.hash_hex()]);
+    assert_eq!(layer.adjacency[&second.hash_hex()], vec![first.hash_hex()]);
+}
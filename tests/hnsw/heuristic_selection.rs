@@ -0,0 +1,112 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hnsw::core::{HNSWConfig, HNSWIndex};
+
+const DIM: usize = 16;
+const CLUSTERS: usize = 20;
+const PER_CLUSTER: usize = 40;
+const K: usize = 10;
+
+/// Tight, well separated clusters: the case where the closest-m rule links
+/// every node only within its own cluster
+fn clustered_dataset(rng: &mut StdRng) -> Vec<Vec<f32>> {
+    let centers: Vec<Vec<f32>> = (0..CLUSTERS)
+        .map(|_| (0..DIM).map(|_| rng.gen_range(-10.0..10.0)).collect())
+        .collect();
+    centers
+        .iter()
+        .flat_map(|center| {
+            (0..PER_CLUSTER)
+                .map(|_| center.iter().map(|c| c + rng.gen_range(-0.3..0.3)).collect())
+                .collect::<Vec<Vec<f32>>>()
+        })
+        .collect()
+}
+
+fn build(vectors: &[Vec<f32>], heuristic: bool, keep_pruned: bool) -> HNSWIndex {
+    let mut index = HNSWIndex::new(HNSWConfig {
+        max_connections: 8,
+        max_connections_layer_0: 16,
+        ef_construction: 64,
+        seed: Some(3),
+        use_heuristic_selection: heuristic,
+        keep_pruned_connections: keep_pruned,
+        ..HNSWConfig::default()
+    });
+    for (i, vector) in vectors.iter().enumerate() {
+        index.insert(VectorId::from_u64(i as u64), vector.clone()).unwrap();
+    }
+    index
+}
+
+fn recall(index: &HNSWIndex, vectors: &[Vec<f32>], queries: &[Vec<f32>]) -> f32 {
+    let mut hits = 0;
+    for query in queries {
+        let mut exact: Vec<(usize, f32)> = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, DistanceMetric::Euclidean.distance(query, v)))
+            .collect();
+        exact.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let expected: Vec<VectorId> =
+            exact.iter().take(K).map(|(i, _)| VectorId::from_u64(*i as u64)).collect();
+
+        let found = index.search(query, K, 64).unwrap();
+        hits += found.iter().filter(|r| expected.contains(&r.vector_id)).count();
+    }
+    hits as f32 / (queries.len() * K) as f32
+}
+
+#[test]
+fn test_heuristic_selection_is_off_by_default() {
+    let config = HNSWConfig::default();
+    assert!(!config.use_heuristic_selection);
+    assert!(!config.keep_pruned_connections);
+
+    // Configs persisted before the options existed
+    let json = r#"{"max_connections":16,"max_connections_layer_0":32,"ef_construction":200,"seed":null}"#;
+    let config: HNSWConfig = serde_json::from_str(json).unwrap();
+    assert!(!config.use_heuristic_selection);
+}
+
+#[test]
+fn test_heuristic_recall_on_clustered_data() {
+    let mut rng = StdRng::seed_from_u64(17);
+    let vectors = clustered_dataset(&mut rng);
+    // Queries between clusters as well as inside them
+    let queries: Vec<Vec<f32>> = (0..100)
+        .map(|i| {
+            let a = &vectors[(i * 37) % vectors.len()];
+            let b = &vectors[(i * 101 + 13) % vectors.len()];
+            let t = if i % 2 == 0 { 0.0 } else { 0.5 };
+            a.iter().zip(b).map(|(x, y)| x + (y - x) * t).collect()
+        })
+        .collect();
+
+    let naive = recall(&build(&vectors, false, false), &vectors, &queries);
+    let heuristic = recall(&build(&vectors, true, false), &vectors, &queries);
+    let keep_pruned = recall(&build(&vectors, true, true), &vectors, &queries);
+    println!("recall@{K}: naive {naive:.3}, heuristic {heuristic:.3}, keep pruned {keep_pruned:.3}");
+
+    // The naive graph strands most clusters; the heuristic reaches them
+    assert!(heuristic > naive + 0.5, "heuristic {} vs naive {}", heuristic, naive);
+    assert!(keep_pruned > naive + 0.5, "keep pruned {} vs naive {}", keep_pruned, naive);
+}
+
+#[test]
+fn test_heuristic_respects_connection_limits() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let vectors = clustered_dataset(&mut rng);
+    let index = build(&vectors, true, true);
+
+    for node in index.get_all_nodes() {
+        assert!(node.neighbors(0).len() <= 16);
+        for layer in 1..=node.level() {
+            assert!(node.neighbors(layer).len() <= 8);
+        }
+    }
+}
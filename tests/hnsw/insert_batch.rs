@@ -0,0 +1,111 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hnsw::core::{HNSWConfig, HNSWError, HNSWIndex};
+
+fn create_index() -> HNSWIndex {
+    HNSWIndex::new(HNSWConfig {
+        seed: Some(5),
+        ..HNSWConfig::default()
+    })
+}
+
+fn vectors(range: std::ops::Range<u64>) -> Vec<(VectorId, Vec<f32>)> {
+    range
+        .map(|i| {
+            let angle = i as f32 * 0.41;
+            (VectorId::from_u64(i), vec![angle.cos() * i as f32, angle.sin() * i as f32, 1.0])
+        })
+        .collect()
+}
+
+#[test]
+fn test_insert_batch_matches_sequential_search() {
+    let items = vectors(0..300);
+    let mut batched = create_index();
+    let result = batched.insert_batch(items.clone()).unwrap();
+    assert_eq!(result.successful, 300);
+    assert_eq!(result.failed, 0);
+    assert_eq!(batched.node_count(), 300);
+    assert_eq!(batched.dimension(), Some(3));
+
+    // Every vector is reachable as its own nearest neighbor
+    for (id, vector) in items.iter().step_by(7) {
+        let results = batched.search(vector, 1, 50).unwrap();
+        assert_eq!(&results[0].vector_id, id);
+    }
+}
+
+#[test]
+fn test_insert_batch_extends_existing_graph() {
+    let mut index = create_index();
+    for (id, vector) in vectors(0..50) {
+        index.insert(id, vector).unwrap();
+    }
+
+    let result = index.insert_batch(vectors(50..120)).unwrap();
+    assert_eq!(result.successful, 70);
+    assert_eq!(index.node_count(), 120);
+
+    let (id, vector) = &vectors(90..91)[0];
+    assert_eq!(&index.search(vector, 1, 50).unwrap()[0].vector_id, id);
+}
+
+#[test]
+fn test_insert_batch_reports_duplicates() {
+    let mut index = create_index();
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.0, 1.0]).unwrap();
+
+    let batch = vec![
+        (VectorId::from_u64(1), vec![2.0, 0.0, 1.0]),
+        (VectorId::from_u64(2), vec![3.0, 0.0, 1.0]),
+        (VectorId::from_u64(2), vec![4.0, 0.0, 1.0]),
+        (VectorId::from_u64(3), vec![5.0, 0.0, 1.0]),
+    ];
+    let result = index.insert_batch(batch).unwrap();
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 2);
+    let failed: Vec<&VectorId> = result.errors.iter().map(|(id, _)| id).collect();
+    assert_eq!(failed, vec![&VectorId::from_u64(1), &VectorId::from_u64(2)]);
+    assert!(result
+        .errors
+        .iter()
+        .all(|(_, e)| matches!(e, HNSWError::DuplicateVector(_))));
+    // The first copy of a repeated id wins
+    assert_eq!(index.get_vector_by_id(&VectorId::from_u64(2)), Some(vec![3.0, 0.0, 1.0]));
+}
+
+#[test]
+fn test_insert_batch_rejects_dimension_mismatch_up_front() {
+    let mut index = create_index();
+    let mut batch = vectors(0..10);
+    batch.push((VectorId::from_u64(10), vec![1.0, 2.0]));
+
+    let result = index.insert_batch(batch);
+
+    assert!(matches!(
+        result,
+        Err(HNSWError::DimensionMismatch { expected: 3, actual: 2 })
+    ));
+    assert_eq!(index.node_count(), 0);
+    assert_eq!(index.dimension(), None);
+}
+
+#[test]
+fn test_insert_batch_reports_zero_vectors_for_cosine() {
+    let mut index = HNSWIndex::new(HNSWConfig {
+        metric: DistanceMetric::Cosine,
+        ..HNSWConfig::default()
+    });
+    let batch = vec![
+        (VectorId::from_u64(0), vec![1.0, 0.0, 0.0]),
+        (VectorId::from_u64(1), vec![0.0, 0.0, 0.0]),
+    ];
+
+    let result = index.insert_batch(batch).unwrap();
+
+    assert_eq!(result.successful, 1);
+    assert!(matches!(result.errors[..], [(_, HNSWError::ZeroVector)]));
+}
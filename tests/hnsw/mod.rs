@@ -2,5 +2,11 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 mod core;
+mod distance_cache;
+mod distance_metric;
+mod graph_export;
+mod heuristic_selection;
+mod insert_batch;
+mod node_removal;
 mod operations;
 mod persistence;
@@ -0,0 +1,110 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::VectorId;
+use vector_db::hnsw::core::{HNSWConfig, HNSWError, HNSWIndex};
+
+/// Sparse graph over a spiral, so removals leave real holes to repair
+fn create_index() -> (HNSWIndex, Vec<(VectorId, Vec<f32>)>) {
+    let mut index = HNSWIndex::new(HNSWConfig {
+        max_connections: 4,
+        max_connections_layer_0: 8,
+        seed: Some(11),
+        ..HNSWConfig::default()
+    });
+    let vectors: Vec<_> = (0..200u64)
+        .map(|i| {
+            let angle = i as f32 * 0.37;
+            (VectorId::from_u64(i), vec![angle.cos() * i as f32, angle.sin() * i as f32])
+        })
+        .collect();
+    for (id, vector) in &vectors {
+        index.insert(id.clone(), vector.clone()).unwrap();
+    }
+    (index, vectors)
+}
+
+#[test]
+fn test_remove_strips_node_and_edges() {
+    let (mut index, vectors) = create_index();
+    let id = vectors[50].0.clone();
+
+    index.remove(&id).unwrap();
+
+    assert_eq!(index.node_count(), 199);
+    assert!(index.get_node(&id).is_none());
+    for node in index.get_all_nodes() {
+        for layer in 0..=node.level() {
+            assert!(!node.neighbors(layer).contains(&id));
+        }
+    }
+    let results = index.search(&vectors[50].1, 5, 100).unwrap();
+    assert!(results.iter().all(|r| r.vector_id != id));
+}
+
+#[test]
+fn test_remove_entry_point_promotes_another() {
+    let (mut index, _) = create_index();
+    let entry = index.entry_point().unwrap();
+
+    index.remove(&entry).unwrap();
+
+    let new_entry = index.entry_point().unwrap();
+    assert_ne!(new_entry, entry);
+    assert_eq!(index.get_node(&new_entry).unwrap().level(), index.get_max_level());
+}
+
+#[test]
+fn test_survivors_stay_connected_and_reachable() {
+    let (mut index, vectors) = create_index();
+
+    // Remove the entry point and three quarters of the rest
+    let entry = index.entry_point().unwrap();
+    index.remove(&entry).unwrap();
+    let removed: Vec<_> = vectors
+        .iter()
+        .enumerate()
+        .filter(|(i, (id, _))| i % 4 != 0 && id != &entry)
+        .map(|(_, (id, _))| id.clone())
+        .collect();
+    for id in &removed {
+        index.remove(id).unwrap();
+    }
+
+    assert_eq!(index.node_count(), 200 - 1 - removed.len());
+    for node in index.get_all_nodes() {
+        assert!(!node.neighbors(0).is_empty(), "{:?} lost all neighbors", node.id());
+        for layer in 0..=node.level() {
+            for neighbor in node.neighbors(layer) {
+                assert!(index.get_node(neighbor).is_some());
+            }
+        }
+    }
+    for (id, vector) in &vectors {
+        if index.get_node(id).is_some() {
+            let results = index.search(vector, 1, 100).unwrap();
+            assert_eq!(&results[0].vector_id, id);
+        }
+    }
+}
+
+#[test]
+fn test_remove_last_node_clears_entry_point() {
+    let mut index = HNSWIndex::new(HNSWConfig::default());
+    let id = VectorId::from_u64(1);
+    index.insert(id.clone(), vec![1.0, 2.0]).unwrap();
+
+    index.remove(&id).unwrap();
+
+    assert_eq!(index.node_count(), 0);
+    assert!(index.entry_point().is_none());
+}
+
+#[test]
+fn test_remove_unknown_vector() {
+    let mut index = HNSWIndex::new(HNSWConfig::default());
+
+    let result = index.remove(&VectorId::from_u64(1));
+
+    assert!(matches!(result, Err(HNSWError::VectorNotFound(_))));
+}
@@ -233,6 +233,10 @@ mod maintenance_tests {
             max_connections_layer_0: 8,
             ef_construction: 50,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert nodes
@@ -336,6 +340,10 @@ mod compaction_tests {
             max_connections_layer_0: 8,
             ef_construction: 50,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert nodes to create multiple layers
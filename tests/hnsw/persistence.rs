@@ -43,6 +43,10 @@ mod hnsw_serialization_tests {
             max_connections_layer_0: 32,
             ef_construction: 200,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         };
 
         let entry_point = Some(VectorId::from_string("entry"));
@@ -139,6 +143,10 @@ mod hnsw_storage_tests {
             max_connections_layer_0: 8,
             ef_construction: 50,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert some nodes
@@ -181,6 +189,10 @@ mod hnsw_storage_tests {
             max_connections_layer_0: 16,
             ef_construction: 50,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert 50 nodes (reduced for faster testing)
@@ -318,6 +330,10 @@ mod recovery_tests {
             max_connections_layer_0: 8,
             ef_construction: 50,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Insert nodes
@@ -369,6 +385,10 @@ mod recovery_tests {
             max_connections_layer_0: 8,
             ef_construction: 50,
             seed: Some(42),
+            cache_distances: true,
+            metric: DistanceMetric::Euclidean,
+            use_heuristic_selection: false,
+            keep_pruned_connections: false,
         });
 
         // Create index
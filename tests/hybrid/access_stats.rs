@@ -0,0 +1,84 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for per-vector search access statistics
+
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+async fn create_index(track_access: bool) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        track_access,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 0.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..20u64 {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32, 0.0])
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_repeated_search_increments_access_count() {
+    let index = create_index(true).await;
+    let id = VectorId::from_u64(7);
+
+    assert!(index.access_stats(&id).await.is_none());
+
+    let mut previous = None;
+    for expected in 1..=3 {
+        index.search(&[7.0, 0.0], 1).await.unwrap();
+        let stats = index.access_stats(&id).await.unwrap();
+        assert_eq!(stats.count, expected);
+        if let Some(previous) = previous {
+            assert!(stats.last_access >= previous);
+        }
+        previous = Some(stats.last_access);
+    }
+
+    assert!(index.access_stats(&VectorId::from_u64(15)).await.is_none());
+}
+
+#[tokio::test]
+async fn test_top_accessed_orders_by_count() {
+    let index = create_index(true).await;
+
+    for _ in 0..3 {
+        index.search(&[2.0, 0.0], 1).await.unwrap();
+    }
+    index.search(&[9.0, 0.0], 2).await.unwrap();
+
+    let top = index.top_accessed(2).await;
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].0, VectorId::from_u64(2));
+    assert_eq!(top[0].1.count, 3);
+    assert_eq!(top[1].1.count, 1);
+}
+
+#[tokio::test]
+async fn test_access_not_tracked_by_default() {
+    let index = create_index(false).await;
+
+    index.search(&[7.0, 0.0], 3).await.unwrap();
+
+    assert!(index.access_stats(&VectorId::from_u64(7)).await.is_none());
+    assert!(index.top_accessed(10).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_clears_access_stats() {
+    let index = create_index(true).await;
+    let id = VectorId::from_u64(7);
+
+    index.search(&[7.0, 0.0], 1).await.unwrap();
+    index.delete(id.clone()).await.unwrap();
+
+    assert!(index.access_stats(&id).await.is_none());
+}
@@ -0,0 +1,118 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for adaptive HNSW ef
+
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{AdaptiveEfConfig, HybridConfig, HybridIndex, HybridSearchConfig};
+
+/// Points spiralling out from the origin at distinct radii 0.1, 0.2, ...
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..200u64 {
+        let radius = 0.1 * (i + 1) as f32;
+        let angle = i as f32 * 2.399_963;
+        index
+            .insert(VectorId::from_u64(i), vec![radius * angle.cos(), radius * angle.sin()])
+            .await
+            .unwrap();
+    }
+    index
+}
+
+fn adaptive_config(min_ef: usize, max_ef: usize) -> HybridSearchConfig {
+    HybridSearchConfig {
+        k: 5,
+        adaptive_ef: Some(AdaptiveEfConfig {
+            min_ef,
+            max_ef,
+            min_relative_gap: 0.01,
+        }),
+        ..HybridSearchConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_easy_query_keeps_min_ef() {
+    let index = create_index().await;
+
+    // Inside the data the 5th and 6th neighbours are clearly apart
+    let results = index
+        .search_with_config(&[0.0, 0.0], adaptive_config(8, 64))
+        .await
+        .unwrap();
+
+    let ids: Vec<u64> = results.iter().map(|r| r.vector_id.as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+    assert_eq!(index.adaptive_ef_escalations(), 0);
+}
+
+#[tokio::test]
+async fn test_hard_query_escalates_to_max_ef() {
+    let index = create_index().await;
+
+    // Far outside the data every candidate is at nearly the same distance
+    let results = index
+        .search_with_config(&[1000.0, 1000.0], adaptive_config(8, 64))
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 5);
+    // 8 -> 16 -> 32 -> 64
+    assert_eq!(index.adaptive_ef_escalations(), 3);
+}
+
+#[tokio::test]
+async fn test_mixed_queries_only_escalate_hard_ones() {
+    let index = create_index().await;
+    let config = adaptive_config(8, 32);
+
+    for query in [[0.0, 0.0], [1000.0, 1000.0], [0.0, 0.0], [-900.0, 500.0]] {
+        index.search_with_config(&query, config.clone()).await.unwrap();
+    }
+
+    // Two hard queries, each 8 -> 16 -> 32
+    assert_eq!(index.adaptive_ef_escalations(), 4);
+}
+
+#[tokio::test]
+async fn test_adaptive_ef_off_by_default() {
+    let index = create_index().await;
+
+    index.search(&[1000.0, 1000.0], 5).await.unwrap();
+
+    assert_eq!(index.adaptive_ef_escalations(), 0);
+}
+
+#[tokio::test]
+async fn test_deleted_nodes_do_not_count_as_reachable() {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    for i in 0..6u64 {
+        index.insert(VectorId::from_u64(i), vec![i as f32, 0.0]).await.unwrap();
+    }
+    for i in 3..6u64 {
+        index.delete(VectorId::from_u64(i)).await.unwrap();
+    }
+
+    // Only three vectors are left, so three results are a complete answer
+    let results = index
+        .search_with_config(&[0.0, 0.0], adaptive_config(8, 64))
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(index.adaptive_ef_escalations(), 0);
+}
@@ -0,0 +1,195 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for appending vectors to a persisted index without loading it
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister, PersistenceError};
+
+/// Mock storage that can be told to reject manifest writes
+#[derive(Clone)]
+struct ManifestFailingStorage {
+    inner: MockS5Storage,
+    fail_manifest: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl S5Storage for ManifestFailingStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        if path.ends_with("manifest.json") && self.fail_manifest.load(Ordering::SeqCst) {
+            return Err(StorageError::NetworkError("manifest write failed".to_string()));
+        }
+        self.inner.put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+/// Index holding only historical (IVF) vectors
+async fn create_historical_index(count: u64) -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let base = Utc::now() - Duration::days(30);
+    for i in 0..count {
+        index
+            .insert_with_timestamp(
+                VectorId::from_u64(i),
+                vec![i as f32, 1.0, 0.0],
+                base + Duration::seconds(i as i64),
+            )
+            .await
+            .unwrap();
+    }
+
+    index
+}
+
+#[tokio::test]
+async fn test_appended_vectors_searchable_after_reload() {
+    let index = create_historical_index(20).await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage).with_chunk_size(10);
+
+    let saved = persister.save_index_chunked(&index, "append").await.unwrap();
+    assert_eq!(saved.chunks.len(), 2);
+
+    let old = Utc::now() - Duration::days(20);
+    let new_vectors: Vec<(VectorId, Vec<f32>, _)> = (100..105u64)
+        .map(|i| (VectorId::from_u64(i), vec![i as f32, -3.0, 2.0], old))
+        .collect();
+
+    let manifest = persister
+        .append_chunk("append", new_vectors.clone())
+        .await
+        .unwrap();
+    assert_eq!(manifest.total_vectors, 25);
+    assert_eq!(manifest.chunks.len(), 3);
+    assert_eq!(manifest.generation, saved.generation + 1);
+    assert!(manifest
+        .ivf_structure
+        .as_ref()
+        .unwrap()
+        .cluster_assignments
+        .values()
+        .any(|chunks| chunks.contains(&"chunk-2".to_string())));
+
+    let reloaded = persister
+        .load_index_chunked("append", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(reloaded.get_stats().historical_vectors, 25);
+
+    for (id, vector, _) in &new_vectors {
+        let results = reloaded.search(vector, 1).await.unwrap();
+        assert_eq!(&results[0].vector_id, id);
+    }
+
+    // Vectors saved before the append are still there
+    let results = reloaded.search(&[3.0, 1.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(3));
+}
+
+#[tokio::test]
+async fn test_append_twice_uses_fresh_chunks() {
+    let index = create_historical_index(10).await;
+    let persister = HybridPersister::new(MockS5Storage::new()).with_chunk_size(10);
+    persister.save_index_chunked(&index, "twice").await.unwrap();
+
+    let old = Utc::now() - Duration::days(20);
+    persister
+        .append_chunk("twice", vec![(VectorId::from_u64(50), vec![50.0, 0.0, 0.0], old)])
+        .await
+        .unwrap();
+    let manifest = persister
+        .append_chunk("twice", vec![(VectorId::from_u64(60), vec![60.0, 0.0, 0.0], old)])
+        .await
+        .unwrap();
+
+    assert_eq!(manifest.get_chunk_ids(), vec!["chunk-0", "chunk-1", "chunk-2"]);
+    assert_eq!(manifest.timestamp_chunks.as_ref().unwrap().len(), 3);
+
+    let reloaded = persister
+        .load_index_chunked("twice", HybridConfig::default())
+        .await
+        .unwrap();
+    let results = reloaded.search(&[60.0, 0.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(60));
+}
+
+#[tokio::test]
+async fn test_append_rejects_dimension_mismatch() {
+    let index = create_historical_index(10).await;
+    let persister = HybridPersister::new(MockS5Storage::new());
+    let saved = persister.save_index_chunked(&index, "dims").await.unwrap();
+
+    let result = persister
+        .append_chunk("dims", vec![(VectorId::from_u64(99), vec![1.0, 2.0], Utc::now())])
+        .await;
+    assert!(matches!(result, Err(PersistenceError::InvalidData(_))));
+
+    // Nothing was written
+    let reloaded = persister
+        .load_index_chunked("dims", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(reloaded.get_stats().total_vectors, saved.total_vectors);
+}
+
+#[tokio::test]
+async fn test_append_requires_ivf_structure() {
+    let index = HybridIndex::new(HybridConfig::default());
+    let persister = HybridPersister::new(MockS5Storage::new());
+    persister.save_index_chunked(&index, "empty").await.unwrap();
+
+    let result = persister
+        .append_chunk("empty", vec![(VectorId::from_u64(1), vec![1.0, 0.0, 0.0], Utc::now())])
+        .await;
+    assert!(matches!(result, Err(PersistenceError::InvalidData(_))));
+
+    let missing = persister
+        .append_chunk("missing", vec![(VectorId::from_u64(1), vec![1.0, 0.0, 0.0], Utc::now())])
+        .await;
+    assert!(matches!(missing, Err(PersistenceError::MissingComponent(_))));
+}
+
+#[tokio::test]
+async fn test_failed_manifest_write_leaves_metadata_untouched() {
+    let index = create_historical_index(20).await;
+    let storage = ManifestFailingStorage {
+        inner: MockS5Storage::new(),
+        fail_manifest: Arc::new(AtomicBool::new(false)),
+    };
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(10);
+    persister.save_index_chunked(&index, "append").await.unwrap();
+
+    storage.fail_manifest.store(true, Ordering::SeqCst);
+    let old = Utc::now() - Duration::days(20);
+    let new_vectors = (100..105u64)
+        .map(|i| (VectorId::from_u64(i), vec![i as f32, -3.0, 2.0], old))
+        .collect();
+    assert!(persister.append_chunk("append", new_vectors).await.is_err());
+
+    // The stored manifest never saw the append, so neither may the metadata
+    let reloaded = persister
+        .load_index_chunked("append", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(reloaded.get_stats().historical_vectors, 20);
+}
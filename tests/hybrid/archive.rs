@@ -0,0 +1,124 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for exporting an index to a single archive and importing it back
+
+use chrono::{Duration, Utc};
+use vector_db::core::storage::MockS5Storage;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{
+    ArchiveOptions, HybridConfig, HybridIndex, HybridPersister, PersistenceError,
+};
+
+/// Index with recent and historical vectors, payloads and a deletion
+async fn create_index() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..30u64 {
+        let vector = vec![i as f32, (i % 7) as f32, 1.0];
+        if i % 2 == 0 {
+            index
+                .insert_with_timestamp(
+                    VectorId::from_u64(i),
+                    vector,
+                    old + Duration::seconds(i as i64),
+                )
+                .await
+                .unwrap();
+        } else {
+            index
+                .insert_with_payload(VectorId::from_u64(i), vector, vec![i as u8, 0xff])
+                .await
+                .unwrap();
+        }
+    }
+    index.delete(VectorId::from_u64(4)).await.unwrap();
+
+    index
+}
+
+async fn assert_search_parity(original: &HybridIndex, imported: &HybridIndex) {
+    for query in [[0.0, 0.0, 1.0], [12.5, 3.0, 1.0], [29.0, 6.0, 0.0]] {
+        let expected = original.search(&query, 5).await.unwrap();
+        let actual = imported.search(&query, 5).await.unwrap();
+        let expected: Vec<_> = expected.iter().map(|r| (r.vector_id.clone(), r.distance)).collect();
+        let actual: Vec<_> = actual.iter().map(|r| (r.vector_id.clone(), r.distance)).collect();
+        assert_eq!(actual, expected);
+    }
+}
+
+async fn round_trip(options: ArchiveOptions) -> Vec<u8> {
+    let index = create_index().await;
+    let exporter = HybridPersister::new(MockS5Storage::new()).with_chunk_size(8);
+    let archive = exporter
+        .export_archive(&index, Vec::new(), options)
+        .await
+        .unwrap();
+
+    let importer = HybridPersister::new(MockS5Storage::new());
+    let imported = importer
+        .import_archive(archive.as_slice(), "restored", HybridConfig::default())
+        .await
+        .unwrap();
+
+    assert_search_parity(&index, &imported).await;
+    assert_eq!(
+        imported.get_payload(&VectorId::from_u64(3)).await,
+        Some(vec![3, 0xff])
+    );
+    let original_stats = index.get_stats();
+    let imported_stats = imported.get_stats();
+    assert_eq!(imported_stats.recent_vectors, original_stats.recent_vectors);
+    assert_eq!(imported_stats.historical_vectors, original_stats.historical_vectors);
+    let results = imported.search(&[4.0, 4.0, 1.0], 30).await.unwrap();
+    assert!(results.iter().all(|r| r.vector_id != VectorId::from_u64(4)));
+
+    archive
+}
+
+#[tokio::test]
+async fn test_uncompressed_archive_round_trip() {
+    let options = ArchiveOptions {
+        compress: false,
+        ..ArchiveOptions::default()
+    };
+    let archive = round_trip(options).await;
+
+    // Plain ustar: first entry header carries the magic at offset 257
+    assert_eq!(&archive[257..262], b"ustar");
+    assert_eq!(archive.len() % 512, 0);
+}
+
+#[tokio::test]
+async fn test_compressed_archive_round_trip() {
+    let archive = round_trip(ArchiveOptions::default()).await;
+    assert_eq!(&archive[..4], &[0x28, 0xb5, 0x2f, 0xfd]);
+}
+
+#[tokio::test]
+async fn test_import_rejects_corrupt_archive() {
+    let index = create_index().await;
+    let persister = HybridPersister::new(MockS5Storage::new());
+    let options = ArchiveOptions {
+        compress: false,
+        ..ArchiveOptions::default()
+    };
+    let mut archive = persister
+        .export_archive(&index, Vec::new(), options)
+        .await
+        .unwrap();
+    archive[0] ^= 0x01;
+
+    let result = persister
+        .import_archive(archive.as_slice(), "corrupt", HybridConfig::default())
+        .await;
+    assert!(matches!(result, Err(PersistenceError::InvalidData(_))));
+
+    let result = persister
+        .import_archive(&[][..], "empty", HybridConfig::default())
+        .await;
+    assert!(matches!(result, Err(PersistenceError::MissingComponent(_))));
+}
@@ -0,0 +1,113 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+use vector_db::ivf::core::IVFConfig;
+use vector_db::ivf::pq::PQConfig;
+
+fn auto_config() -> HybridConfig {
+    HybridConfig {
+        auto_initialize: true,
+        ..HybridConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_first_insert_initializes_index() {
+    let index = HybridIndex::new(auto_config());
+    assert!(!index.is_initialized());
+    assert_eq!(index.dimension().await, None);
+
+    index
+        .insert(VectorId::from_u64(1), vec![1.0, 2.0, 3.0, 4.0])
+        .await
+        .unwrap();
+
+    assert!(index.is_initialized());
+    assert!(!index.ivf_trained());
+    assert_eq!(index.dimension().await, Some(4));
+
+    let results = index.search(&[1.0, 2.0, 3.0, 4.0], 1).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].vector_id, VectorId::from_u64(1));
+}
+
+#[tokio::test]
+async fn test_first_vector_fixes_dimension() {
+    let index = HybridIndex::new(auto_config());
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.0]).await.unwrap();
+
+    let result = index.insert(VectorId::from_u64(2), vec![1.0, 0.0, 0.0]).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: 2, actual: 3 })
+    ));
+    assert_eq!(index.dimension().await, Some(2));
+}
+
+#[tokio::test]
+async fn test_ivf_trains_once_enough_vectors_arrive() {
+    let config = auto_config();
+    let min = config.min_ivf_training_size;
+    let index = HybridIndex::new(config);
+
+    for i in 0..min - 1 {
+        index
+            .insert(VectorId::from_u64(i as u64), vec![i as f32, (i % 3) as f32])
+            .await
+            .unwrap();
+    }
+    assert!(!index.ivf_trained());
+
+    index
+        .insert(VectorId::from_u64(min as u64), vec![min as f32, 1.0])
+        .await
+        .unwrap();
+    assert!(index.ivf_trained());
+
+    // Everything inserted before training is still searchable
+    let results = index.search(&[0.0, 0.0], 3).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(0));
+
+    assert_eq!(index.total_vectors(), min);
+}
+
+#[tokio::test]
+async fn test_failed_training_does_not_fail_insert() {
+    // Three PQ slices can't split 2-dimensional vectors, so training fails
+    let config = HybridConfig {
+        ivf_config: IVFConfig {
+            n_clusters: 4,
+            n_probe: 2,
+            pq: Some(PQConfig {
+                n_subvectors: 3,
+                n_centroids_per_subvector: 4,
+            }),
+            ..IVFConfig::default()
+        },
+        ..auto_config()
+    };
+    let min = config.min_ivf_training_size;
+    let index = HybridIndex::new(config);
+
+    for i in 0..min + 1 {
+        index
+            .insert(VectorId::from_u64(i as u64), vec![i as f32, (i % 3) as f32])
+            .await
+            .unwrap();
+    }
+
+    assert!(!index.ivf_trained());
+    assert_eq!(index.total_vectors(), min + 1);
+    let results = index.search(&[0.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(0));
+}
+
+#[tokio::test]
+async fn test_insert_without_auto_initialize_still_requires_initialize() {
+    let index = HybridIndex::new(HybridConfig::default());
+    let result = index.insert(VectorId::from_u64(1), vec![1.0, 2.0]).await;
+    assert!(matches!(result, Err(HybridError::NotInitialized)));
+    assert!(!index.is_initialized());
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the background auto-migration task
+
+use chrono::Utc;
+use std::time::Duration;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex, IndexTarget};
+
+const DIM: usize = 4;
+const INTERVAL: Duration = Duration::from_secs(60);
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        recent_threshold: Duration::from_secs(3600),
+        auto_migrate: false,
+        migration_interval: INTERVAL,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_vectors_migrate_on_each_interval() {
+    let index = create_index().await;
+    index.start_auto_migration().await.unwrap();
+    assert!(index.is_auto_migrating().await);
+    // Let the first, immediate pass run while nothing is old yet
+    tokio::time::sleep(Duration::from_millis(1)).await;
+
+    let old = Utc::now() - chrono::Duration::days(1);
+    for i in 0..3 {
+        index
+            .insert_into(VectorId::from_u64(i), vector(i as f32), old, IndexTarget::Recent)
+            .await
+            .unwrap();
+    }
+
+    tokio::time::sleep(INTERVAL / 2).await;
+    assert_eq!(index.recent_count(), 3);
+
+    tokio::time::sleep(INTERVAL / 2 + Duration::from_secs(1)).await;
+    assert_eq!(index.recent_count(), 0);
+    assert_eq!(index.historical_count(), 3);
+
+    index.stop_auto_migration().await.unwrap();
+    assert!(!index.is_auto_migrating().await);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stopped_task_no_longer_migrates() {
+    let index = create_index().await;
+    index.start_auto_migration().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    index.stop_auto_migration().await.unwrap();
+
+    let old = Utc::now() - chrono::Duration::days(1);
+    index
+        .insert_into(VectorId::from_u64(1), vector(1.0), old, IndexTarget::Recent)
+        .await
+        .unwrap();
+    tokio::time::sleep(INTERVAL * 3).await;
+
+    assert_eq!(index.recent_count(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_double_start_is_rejected() {
+    let index = create_index().await;
+    index.start_auto_migration().await.unwrap();
+
+    let again = index.start_auto_migration().await;
+    assert!(matches!(again, Err(HybridError::InvalidConfig(_))));
+
+    index.stop_auto_migration().await.unwrap();
+    // Stopping twice is harmless, and the task can be started again
+    index.stop_auto_migration().await.unwrap();
+    index.start_auto_migration().await.unwrap();
+    index.stop_auto_migration().await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_task_exits_once_index_is_dropped() {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let index = create_index().await;
+    index.start_auto_migration().await.unwrap();
+    assert_eq!(metrics.num_alive_tasks(), 1);
+
+    drop(index);
+    tokio::time::sleep(INTERVAL + Duration::from_secs(1)).await;
+    assert_eq!(metrics.num_alive_tasks(), 0);
+}
@@ -0,0 +1,145 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{AutoRetrainConfig, HybridConfig, HybridIndex};
+
+async fn create_index(auto_retrain: Option<AutoRetrainConfig>) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        auto_retrain,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    // Trained on a small sample near the origin
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32 * 0.1, 0.0]).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+async fn centroids(index: &HybridIndex) -> Vec<Vec<f32>> {
+    let historical = index.get_historical_index().await;
+    historical
+        .get_centroids()
+        .iter()
+        .map(|c| c.vector().clone())
+        .collect()
+}
+
+async fn insert_historical(index: &HybridIndex, ids: std::ops::Range<u64>) {
+    let old = Utc::now() - Duration::days(30);
+    for i in ids {
+        // Data drifts far away from the training sample
+        let vector = vec![100.0 + (i % 7) as f32 * 10.0, (i % 5) as f32 * 10.0];
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vector, old)
+            .await
+            .unwrap();
+
+        // Searches keep working while a retrain may be running
+        assert!(!index.search(&[100.0, 0.0], 1).await.unwrap().is_empty());
+    }
+}
+
+async fn wait_for_retrains(index: &HybridIndex, count: usize) {
+    for _ in 0..500 {
+        if index.auto_retrain_count() >= count {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("auto retrain did not complete");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_retrains_after_insert_threshold() {
+    let index = create_index(Some(AutoRetrainConfig {
+        inserts_since_training: Some(50),
+        growth_factor: None,
+    }))
+    .await;
+    let before = centroids(&index).await;
+
+    insert_historical(&index, 0..49).await;
+    assert_eq!(index.auto_retrain_count(), 0);
+
+    insert_historical(&index, 49..60).await;
+    wait_for_retrains(&index, 1).await;
+
+    let after = centroids(&index).await;
+    assert_ne!(before, after);
+    // Centroids moved towards the inserted data
+    assert!(after.iter().any(|c| c[0] > 50.0));
+
+    let results = index.search(&[100.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].distance, 0.0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_retrains_on_growth_factor() {
+    let index = create_index(Some(AutoRetrainConfig {
+        inserts_since_training: None,
+        growth_factor: Some(3.0),
+    }))
+    .await;
+
+    // Trained on 10 vectors, so the 30th historical vector triggers it
+    insert_historical(&index, 0..29).await;
+    assert_eq!(index.auto_retrain_count(), 0);
+    insert_historical(&index, 29..30).await;
+    wait_for_retrains(&index, 1).await;
+}
+
+async fn insert_recent(index: &HybridIndex, ids: std::ops::Range<u64>) {
+    for i in ids {
+        let vector = vec![100.0 + (i % 7) as f32 * 10.0, (i % 5) as f32 * 10.0];
+        index.insert(VectorId::from_u64(i), vector).await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_migration_counts_towards_insert_threshold() {
+    let index = create_index(Some(AutoRetrainConfig {
+        inserts_since_training: Some(50),
+        growth_factor: None,
+    }))
+    .await;
+
+    insert_recent(&index, 0..60).await;
+    assert_eq!(index.auto_retrain_count(), 0);
+
+    let migrated = index
+        .migrate_with_threshold(std::time::Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(migrated, 60);
+    wait_for_retrains(&index, 1).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_migration_counts_towards_growth_factor() {
+    let index = create_index(Some(AutoRetrainConfig {
+        inserts_since_training: None,
+        growth_factor: Some(3.0),
+    }))
+    .await;
+
+    insert_recent(&index, 0..30).await;
+    let ids: Vec<VectorId> = (0..30).map(VectorId::from_u64).collect();
+    let result = index.migrate_specific_vectors(&ids).await.unwrap();
+    assert_eq!(result.vectors_migrated, 30);
+    wait_for_retrains(&index, 1).await;
+}
+
+#[tokio::test]
+async fn test_no_retrain_by_default() {
+    let index = create_index(None).await;
+    let before = centroids(&index).await;
+
+    insert_historical(&index, 0..100).await;
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    assert_eq!(index.auto_retrain_count(), 0);
+    assert_eq!(before, centroids(&index).await);
+}
@@ -0,0 +1,296 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::core::{HybridConfig, HybridIndex};
+use vector_db::hybrid::maintenance::{BackupConfig, BackupManager, BackupRetention};
+use vector_db::ivf::core::IVFConfig;
+use vector_db::storage::chunk_loader::ChunkLoader;
+
+/// Mock storage that can be told to reject writes of backup index files
+#[derive(Clone)]
+struct FailingDataStorage {
+    inner: MockS5Storage,
+    fail_data: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl S5Storage for FailingDataStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        if path.contains(".data-") && self.fail_data.load(Ordering::SeqCst) {
+            return Err(StorageError::NetworkError("index write failed".to_string()));
+        }
+        self.inner.put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+async fn create_index(n: u64) -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index
+        .initialize(vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![-1.0, -1.0]])
+        .await
+        .unwrap();
+    for i in 0..n {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32 * 0.1, 1.0 - i as f32 * 0.1])
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_backup_verify_and_restore() {
+    let storage = MockS5Storage::new();
+    let manager = BackupManager::new(storage.clone());
+    let index = create_index(20).await;
+
+    let result = manager
+        .create_backup(&index, "/backups/full", BackupConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(result.vectors_backed_up, 20);
+    assert!(result.backup_size > 0);
+    assert!(result.compression_ratio > 1.0, "ratio {}", result.compression_ratio);
+
+    let verification = manager.verify_backup("/backups/full").await.unwrap();
+    assert!(verification.is_valid);
+    assert_eq!(verification.vector_count, 20);
+    assert!(!verification.checksum.is_empty());
+
+    let restored = manager.restore_backup("/backups/full").await.unwrap();
+    assert_eq!(restored.total_vectors(), index.total_vectors());
+    assert_eq!(
+        restored.get_statistics().await.total_vectors,
+        index.get_statistics().await.total_vectors
+    );
+    let results = restored.search(&[0.5, 0.5], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(5));
+
+    // The listed size covers the stored index files
+    let info = manager.get_backup_info("/backups/full").await.unwrap();
+    assert!(info.total_size > result.backup_size);
+}
+
+#[tokio::test]
+async fn test_uncompressed_backup_round_trips() {
+    let manager = BackupManager::new(MockS5Storage::new());
+    let index = create_index(8).await;
+    let config = BackupConfig {
+        compress: false,
+        ..BackupConfig::default()
+    };
+
+    let result = manager.create_backup(&index, "/backups/raw", config).await.unwrap();
+    assert_eq!(result.compression_ratio, 1.0);
+    assert!(manager.verify_backup("/backups/raw").await.unwrap().is_valid);
+    let restored = manager.restore_backup("/backups/raw").await.unwrap();
+    assert_eq!(restored.total_vectors(), 8);
+}
+
+#[tokio::test]
+async fn test_corrupted_backup_fails_verification() {
+    let storage = MockS5Storage::new();
+    let manager = BackupManager::new(storage.clone());
+    let index = create_index(10).await;
+    manager
+        .create_backup(&index, "/backups/full", BackupConfig::default())
+        .await
+        .unwrap();
+
+    let files = storage.list("/backups/full.data-").await.unwrap();
+    assert!(!files.is_empty());
+    storage.put(&files[0], b"corrupted".to_vec()).await.unwrap();
+
+    let verification = manager.verify_backup("/backups/full").await.unwrap();
+    assert!(!verification.is_valid);
+}
+
+#[tokio::test]
+async fn test_failed_backup_keeps_existing_backup() {
+    let storage = FailingDataStorage {
+        inner: MockS5Storage::new(),
+        fail_data: Arc::new(AtomicBool::new(false)),
+    };
+    let manager = BackupManager::new(storage.clone());
+    manager
+        .create_backup(&create_index(10).await, "/backups/full", BackupConfig::default())
+        .await
+        .unwrap();
+
+    storage.fail_data.store(true, Ordering::SeqCst);
+    assert!(manager
+        .create_backup(&create_index(20).await, "/backups/full", BackupConfig::default())
+        .await
+        .is_err());
+    storage.fail_data.store(false, Ordering::SeqCst);
+
+    assert!(manager.verify_backup("/backups/full").await.unwrap().is_valid);
+    let restored = manager.restore_backup("/backups/full").await.unwrap();
+    assert_eq!(restored.total_vectors(), 10);
+
+    // A later backup replaces it and removes its files
+    let old_files = storage.list("/backups/full.data-").await.unwrap();
+    assert!(!old_files.is_empty());
+    manager
+        .create_backup(&create_index(20).await, "/backups/full", BackupConfig::default())
+        .await
+        .unwrap();
+    for file in old_files {
+        assert_eq!(storage.get(&file).await.unwrap(), None);
+    }
+    assert_eq!(manager.restore_backup("/backups/full").await.unwrap().total_vectors(), 20);
+}
+
+#[tokio::test]
+async fn test_pruning_deletes_index_files() {
+    let storage = MockS5Storage::new();
+    let manager = BackupManager::new(storage.clone());
+    let index = create_index(4).await;
+    let config = BackupConfig {
+        retention: BackupRetention::KeepLast(1),
+        ..BackupConfig::default()
+    };
+
+    manager.create_backup(&index, "/backups/b0", config.clone()).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    let result = manager.create_backup(&index, "/backups/b1", config).await.unwrap();
+
+    assert_eq!(result.pruned_backups, vec!["/backups/b0"]);
+    assert!(storage.list("/backups/b0.data-").await.unwrap().is_empty());
+    assert!(!storage.list("/backups/b1.data-").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_incremental_backup_restores_new_vectors() {
+    let manager = BackupManager::new(MockS5Storage::new());
+    let index = create_index(10).await;
+    manager
+        .create_backup(&index, "/backups/base", BackupConfig::default())
+        .await
+        .unwrap();
+
+    let mut checkpoints = vec![Utc::now()];
+    for batch in 0..2u64 {
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        for i in 0..5 {
+            let id = 100 + batch * 5 + i;
+            index
+                .insert_with_payload(VectorId::from_u64(id), vec![id as f32, 0.5], vec![id as u8])
+                .await
+                .unwrap();
+        }
+        checkpoints.push(Utc::now());
+    }
+
+    let first = manager
+        .create_incremental_backup(&index, "/backups/base", "/backups/incr1", checkpoints[0])
+        .await
+        .unwrap();
+    assert_eq!(first.vectors_backed_up, 10);
+    assert!(first.backup_size > 0);
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    let second = manager
+        .create_incremental_backup(&index, "/backups/incr1", "/backups/incr2", checkpoints[1])
+        .await
+        .unwrap();
+    assert_eq!(second.vectors_backed_up, 5);
+    assert!(manager.verify_backup("/backups/incr2").await.unwrap().is_valid);
+    assert!(manager.get_backup_info("/backups/incr1").await.unwrap().is_incremental);
+
+    // Applied in creation order whatever order they are passed in
+    let restored = manager
+        .restore_incremental("/backups/base", &["/backups/incr2", "/backups/incr1"])
+        .await
+        .unwrap();
+    assert_eq!(restored.total_vectors(), 20);
+    assert_eq!(restored.total_vectors(), index.total_vectors());
+    for id in (0..10).chain(100..110) {
+        assert!(restored.get_timestamps().await.contains_key(&VectorId::from_u64(id)));
+    }
+    assert_eq!(restored.get_payload(&VectorId::from_u64(107)).await, Some(vec![107]));
+    let results = restored.search(&[104.0, 0.5], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(104));
+
+    // An incremental backup is not a full one
+    assert!(manager.restore_backup("/backups/incr1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_incremental_backup_loads_evicted_historical_vectors() {
+    let storage = Arc::new(MockS5Storage::new());
+    let loader = Arc::new(ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(10))));
+    let vector = |i: u64| vec![i as f32, 1.0 - i as f32 * 0.1];
+
+    let mut chunk = VectorChunk::new("chunk-0".to_string(), 10, 19);
+    for i in 10..20 {
+        chunk.add_vector(VectorId::from_u64(i), vector(i));
+    }
+    storage.put("lazy/chunks/chunk-0.cbor", chunk.to_cbor().unwrap()).await.unwrap();
+
+    // Room for two cached vectors, so most historical ones are evicted
+    let config = HybridConfig {
+        recent_threshold: std::time::Duration::from_secs(3600),
+        ivf_config: IVFConfig {
+            n_clusters: 2,
+            n_probe: 2,
+            train_size: 2,
+            vector_cache_capacity: 2,
+            ..IVFConfig::default()
+        },
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::with_chunk_loader(config, Some(loader));
+    index.initialize((10..20).map(vector).collect()).await.unwrap();
+    let since = Utc::now() - Duration::hours(3);
+    for i in 10..20 {
+        index
+            .insert_with_chunk(
+                VectorId::from_u64(i),
+                vector(i),
+                Utc::now() - Duration::hours(2),
+                Some("lazy/chunks/chunk-0.cbor".to_string()),
+            )
+            .await
+            .unwrap();
+    }
+    assert!(index.get_historical_index().await.get_vector_by_id(&VectorId::from_u64(10)).is_none());
+
+    let manager = BackupManager::new(MockS5Storage::new());
+    manager
+        .create_backup(&create_index(1).await, "/backups/base", BackupConfig::default())
+        .await
+        .unwrap();
+    let result = manager
+        .create_incremental_backup(&index, "/backups/base", "/backups/incr", since)
+        .await
+        .unwrap();
+    assert_eq!(result.vectors_backed_up, 10);
+
+    let restored = manager
+        .restore_incremental("/backups/base", &["/backups/incr"])
+        .await
+        .unwrap();
+    for i in 10..20 {
+        assert_eq!(restored.get_vector(&VectorId::from_u64(i)).await, Some(vector(i)));
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, TimeZone, Utc};
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::core::{HybridConfig, HybridIndex};
+use vector_db::hybrid::maintenance::{BackupConfig, BackupInfo, BackupManager, BackupRetention};
+
+async fn create_index() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index
+        .initialize(vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![-1.0, -1.0]])
+        .await
+        .unwrap();
+    index
+}
+
+fn backup_info(path: &str, created_at: chrono::DateTime<Utc>) -> BackupInfo {
+    BackupInfo {
+        path: path.to_string(),
+        created_at,
+        total_size: 0,
+        vector_count: 0,
+        is_incremental: false,
+        base: None,
+    }
+}
+
+#[tokio::test]
+async fn test_keep_last_prunes_oldest_backups() {
+    let storage = MockS5Storage::new();
+    let manager = BackupManager::new(storage.clone());
+    let index = create_index().await;
+    let config = BackupConfig {
+        retention: BackupRetention::KeepLast(2),
+        ..BackupConfig::default()
+    };
+
+    let mut pruned = Vec::new();
+    for i in 0..5 {
+        index.insert(VectorId::from_u64(i), vec![i as f32, 0.0]).await.unwrap();
+        let result = manager
+            .create_backup(&index, &format!("/backups/b{}", i), config.clone())
+            .await
+            .unwrap();
+        pruned.extend(result.pruned_backups);
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+
+    assert_eq!(pruned, vec!["/backups/b0", "/backups/b1", "/backups/b2"]);
+
+    let remaining = manager.list_backups("/backups").await.unwrap();
+    let paths: Vec<_> = remaining.iter().map(|b| b.path.as_str()).collect();
+    assert_eq!(paths, vec!["/backups/b3", "/backups/b4"]);
+    assert_eq!(remaining[1].vector_count, 5);
+    assert!(storage.get("/backups/b0").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_keep_all_and_other_directories_untouched() {
+    let manager = BackupManager::new(MockS5Storage::new());
+    let index = create_index().await;
+
+    for i in 0..3 {
+        manager
+            .create_backup(&index, &format!("/keep/b{}", i), BackupConfig::default())
+            .await
+            .unwrap();
+    }
+    let result = manager
+        .create_backup(
+            &index,
+            "/other/b0",
+            BackupConfig {
+                retention: BackupRetention::KeepLast(1),
+                ..BackupConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert!(result.pruned_backups.is_empty());
+    assert_eq!(manager.list_backups("/keep").await.unwrap().len(), 3);
+}
+
+#[test]
+fn test_keep_daily_keeps_newest_per_day() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+    let backups = vec![
+        backup_info("today-late", now - Duration::hours(1)),
+        backup_info("today-early", now - Duration::hours(10)),
+        backup_info("yesterday", now - Duration::days(1)),
+        backup_info("six-days", now - Duration::days(6)),
+        backup_info("six-days-early", now - Duration::days(6) - Duration::hours(2)),
+        backup_info("eight-days", now - Duration::days(8)),
+    ];
+
+    let mut expired = BackupRetention::KeepDaily { days: 7 }.select_expired(&backups, now);
+    expired.sort();
+    assert_eq!(expired, vec!["eight-days", "six-days-early", "today-early"]);
+}
+
+#[test]
+fn test_newest_backup_is_always_kept() {
+    let now = Utc::now();
+    let backups = vec![
+        backup_info("old", now - Duration::days(30)),
+        backup_info("older", now - Duration::days(31)),
+    ];
+
+    assert_eq!(
+        BackupRetention::KeepDaily { days: 7 }.select_expired(&backups, now),
+        vec!["older"]
+    );
+    assert_eq!(BackupRetention::KeepLast(0).select_expired(&backups, now), vec!["older"]);
+    assert!(BackupRetention::KeepAll.select_expired(&backups, now).is_empty());
+}
+
+#[test]
+fn test_bases_of_kept_incrementals_are_kept() {
+    let now = Utc::now();
+    let backups = vec![
+        BackupInfo {
+            is_incremental: true,
+            base: Some("full-old".to_string()),
+            ..backup_info("incr", now)
+        },
+        backup_info("full-new", now - Duration::days(1)),
+        backup_info("full-mid", now - Duration::days(2)),
+        backup_info("full-old", now - Duration::days(3)),
+    ];
+
+    assert_eq!(BackupRetention::KeepLast(2).select_expired(&backups, now), vec!["full-mid"]);
+    assert_eq!(
+        BackupRetention::KeepDaily { days: 2 }.select_expired(&backups, now),
+        vec!["full-mid"]
+    );
+}
+
+#[tokio::test]
+async fn test_untimestamped_backups_rank_as_oldest() {
+    let storage = MockS5Storage::new();
+    let manager = BackupManager::new(storage.clone());
+    let index = create_index().await;
+    storage
+        .put("/backups/legacy", b"backup_metadata_3\n".to_vec())
+        .await
+        .unwrap();
+
+    let result = manager
+        .create_backup(
+            &index,
+            "/backups/new",
+            BackupConfig {
+                retention: BackupRetention::KeepLast(1),
+                ..BackupConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.pruned_backups, vec!["/backups/legacy"]);
+    let remaining = manager.list_backups("/backups").await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].path, "/backups/new");
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for checksums recorded for saved chunks and verified on load
+
+use std::sync::Arc;
+use vector_db::core::chunk::chunk_checksum;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister};
+use vector_db::storage::chunk_loader::ChunkLoader;
+
+async fn create_index(count: u64) -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    for i in 0..count {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32, 1.0, 0.0])
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_saved_chunks_record_checksums() {
+    let index = create_index(100).await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let manifest = persister.save_index_chunked(&index, "sum").await.unwrap();
+
+    for chunk in &manifest.chunks {
+        let data = storage
+            .get(&format!("sum/chunks/{}.cbor", chunk.chunk_id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chunk.checksum, Some(chunk_checksum(&data)));
+    }
+}
+
+#[tokio::test]
+async fn test_load_rejects_corrupted_chunk() {
+    let index = create_index(100).await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+    persister.save_index_chunked(&index, "sum").await.unwrap();
+
+    let path = "sum/chunks/chunk-1.cbor";
+    let mut data = storage.get(path).await.unwrap().unwrap();
+    data.truncate(data.len() - 8);
+    storage.put(path, data).await.unwrap();
+
+    let err = persister
+        .load_index_chunked("sum", HybridConfig::default())
+        .await
+        .err()
+        .expect("corrupted chunk should fail to load");
+    assert!(err.to_string().contains("Checksum mismatch for chunk chunk-1"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_chunk_loader_verifies_manifest_checksums() {
+    let index = create_index(100).await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+    let manifest = persister.save_index_chunked(&index, "sum").await.unwrap();
+
+    let loader = ChunkLoader::new(Arc::new(storage.clone()), Arc::new(ChunkCache::new(10)))
+        .with_checksums(manifest.chunk_checksums("sum"));
+    assert_eq!(loader.load_chunk("sum/chunks/chunk-0.cbor").await.unwrap().len(), 50);
+
+    storage
+        .put("sum/chunks/chunk-1.cbor", b"not the chunk".to_vec())
+        .await
+        .unwrap();
+    let err = loader.load_chunk("sum/chunks/chunk-1.cbor").await.unwrap_err();
+    assert!(err.to_string().starts_with("Checksum mismatch"), "{}", err);
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests that chunked manifests point every vector at the chunk holding it
+
+use chrono::Utc;
+use std::collections::HashMap;
+use vector_db::core::chunk::{Manifest, VectorChunk};
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister, IndexTarget};
+
+const RECENT: u64 = 60;
+const HISTORICAL: u64 = 90;
+
+fn vector(i: u64) -> Vec<f32> {
+    vec![i as f32, (i % 7) as f32, 1.0]
+}
+
+/// Vectors in both sub-indices, with ids that hash all over the id order
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..40).map(|i| vector(i * 4)).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..RECENT {
+        index.insert(VectorId::from_u64(i), vector(i)).await.unwrap();
+    }
+    for i in RECENT..RECENT + HISTORICAL {
+        index
+            .insert_into(VectorId::from_u64(i), vector(i), Utc::now(), IndexTarget::Historical)
+            .await
+            .unwrap();
+    }
+    index
+}
+
+/// Chunk id -> the vectors actually stored in that chunk
+async fn stored_chunks(storage: &MockS5Storage, manifest: &Manifest) -> HashMap<String, VectorChunk> {
+    let mut chunks = HashMap::new();
+    for meta in &manifest.chunks {
+        let data = storage
+            .get(&format!("idx/chunks/{}.cbor", meta.chunk_id))
+            .await
+            .unwrap()
+            .unwrap();
+        chunks.insert(meta.chunk_id.clone(), VectorChunk::from_cbor(&data).unwrap());
+    }
+    chunks
+}
+
+#[tokio::test]
+async fn test_hnsw_nodes_map_to_their_chunk() {
+    let index = create_index().await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(25);
+
+    let manifest = persister.save_index_chunked(&index, "idx").await.unwrap();
+
+    let chunks = stored_chunks(&storage, &manifest).await;
+    assert_eq!(chunks.len(), 6);
+    let hnsw = manifest.hnsw_structure.as_ref().unwrap();
+    assert_eq!(hnsw.node_chunk_map.len(), RECENT as usize);
+    for i in 0..RECENT {
+        let id = VectorId::from_u64(i);
+        let chunk_id = hnsw.get_chunk_for_node(&id).unwrap();
+        assert_eq!(chunks[chunk_id].vectors.get(&id), Some(&vector(i)), "{}", i);
+    }
+}
+
+#[tokio::test]
+async fn test_ivf_clusters_map_to_chunks_holding_their_vectors() {
+    let index = create_index().await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(25);
+
+    let manifest = persister.save_index_chunked(&index, "idx").await.unwrap();
+
+    let chunks = stored_chunks(&storage, &manifest).await;
+    let ivf = manifest.ivf_structure.as_ref().unwrap();
+    let historical = index.get_historical_index().await;
+    for (cluster, list) in historical.get_all_inverted_lists() {
+        let assigned = ivf.cluster_assignments.get(&cluster.0).cloned().unwrap_or_default();
+        for id in list.vectors.keys() {
+            let holder: Vec<_> = chunks
+                .iter()
+                .filter(|(_, chunk)| chunk.vectors.contains_key(id))
+                .map(|(chunk_id, _)| chunk_id.clone())
+                .collect();
+            assert_eq!(holder.len(), 1);
+            assert!(assigned.contains(&holder[0]), "cluster {} misses {}", cluster.0, holder[0]);
+        }
+        // No chunk listed that holds none of the cluster's vectors
+        for chunk_id in &assigned {
+            assert!(list.vectors.keys().any(|id| chunks[chunk_id].vectors.contains_key(id)));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_chunked_round_trip_loads_every_vector() {
+    let index = create_index().await;
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(25);
+    persister.save_index_chunked(&index, "idx").await.unwrap();
+
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let loaded = persister.load_index_chunked("idx", config).await.unwrap();
+
+    let recent = loaded.get_recent_index().await;
+    let historical = loaded.get_historical_index().await;
+    for i in 0..RECENT + HISTORICAL {
+        let id = VectorId::from_u64(i);
+        let found = recent.get_vector_by_id(&id).or_else(|| historical.get_vector_by_id(&id));
+        assert_eq!(found, Some(vector(i)), "{}", i);
+    }
+    drop(recent);
+    drop(historical);
+
+    for i in [0, RECENT - 1, RECENT, RECENT + HISTORICAL - 1] {
+        let results = loaded.search(&vector(i), 1).await.unwrap();
+        assert_eq!(results[0].vector_id, VectorId::from_u64(i));
+    }
+}
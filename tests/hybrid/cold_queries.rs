@@ -0,0 +1,117 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for capturing cold queries and feeding them into retraining
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{ColdQueryConfig, HybridConfig, HybridIndex};
+
+const COLD_REGION: [f32; 2] = [100.0, 100.0];
+
+fn vector_for(i: u64) -> Vec<f32> {
+    vec![(i % 2) as f32 * 10.0, i as f32 * 0.01]
+}
+
+/// Historical vectors in two tight groups around (0, 0) and (10, 0)
+async fn create_index(cold_queries: Option<ColdQueryConfig>) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        cold_queries,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(vector_for).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..60u64 {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vector_for(i), old)
+            .await
+            .unwrap();
+    }
+    index
+}
+
+fn cold_query(i: usize) -> Vec<f32> {
+    vec![COLD_REGION[0] + i as f32 * 0.1, COLD_REGION[1]]
+}
+
+fn policy(feed_retraining: bool) -> ColdQueryConfig {
+    ColdQueryConfig {
+        distance_threshold: 20.0,
+        max_samples: 30,
+        feed_retraining,
+    }
+}
+
+async fn closest_centroid_to_cold_region(index: &HybridIndex) -> f32 {
+    let historical = index.get_historical_index().await;
+    historical
+        .get_centroids()
+        .iter()
+        .map(|c| {
+            let v = c.vector();
+            ((v[0] - COLD_REGION[0]).powi(2) + (v[1] - COLD_REGION[1]).powi(2)).sqrt()
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+async fn retrain(index: &HybridIndex) {
+    let config = index.get_historical_index().await.config().clone();
+    index.retrain_historical(config).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cold_queries_captured() {
+    let index = create_index(Some(policy(false))).await;
+
+    index.search(&[0.5, 0.0], 3).await.unwrap();
+    assert!(index.cold_queries().await.is_empty());
+
+    for i in 0..40 {
+        index.search(&cold_query(i), 3).await.unwrap();
+    }
+
+    // Bounded by max_samples, oldest dropped first
+    let captured = index.cold_queries().await;
+    assert_eq!(captured.len(), 30);
+    assert_eq!(captured[0], cold_query(10));
+    assert_eq!(captured[29], cold_query(39));
+
+    index.clear_cold_queries().await;
+    assert!(index.cold_queries().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_cold_queries_off_by_default() {
+    let index = create_index(None).await;
+    for i in 0..10 {
+        index.search(&cold_query(i), 3).await.unwrap();
+    }
+    assert!(index.cold_queries().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_cold_queries_pull_centroids_on_retrain() {
+    let fed = create_index(Some(policy(true))).await;
+    let unfed = create_index(Some(policy(false))).await;
+    for i in 0..30 {
+        fed.search(&cold_query(i), 3).await.unwrap();
+        unfed.search(&cold_query(i), 3).await.unwrap();
+    }
+
+    retrain(&fed).await;
+    retrain(&unfed).await;
+
+    assert!(closest_centroid_to_cold_region(&fed).await < 20.0);
+    assert!(closest_centroid_to_cold_region(&unfed).await > 100.0);
+
+    // Consumed by the retrain; unused samples are kept
+    assert!(fed.cold_queries().await.is_empty());
+    assert_eq!(unfed.cold_queries().await.len(), 30);
+
+    // The stored vectors are unchanged
+    assert_eq!(fed.get_historical_index().await.total_vectors(), 60);
+}
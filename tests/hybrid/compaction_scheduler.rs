@@ -0,0 +1,107 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::time::Duration;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::core::{HybridConfig, HybridIndex};
+use vector_db::hybrid::maintenance::{CompactionPolicy, CompactionScheduler};
+
+async fn create_index_with_deletions() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index
+        .initialize(vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![-1.0, -1.0], vec![5.0, 5.0]])
+        .await
+        .unwrap();
+
+    for i in 0..20 {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32 * 0.1, 1.0])
+            .await
+            .unwrap();
+    }
+    for i in 0..5 {
+        index.delete(VectorId::from_u64(i)).await.unwrap();
+    }
+    assert_eq!(index.deletion_stats().await.2, 5);
+
+    index
+}
+
+#[tokio::test]
+async fn test_compaction_runs_on_schedule() {
+    let index = create_index_with_deletions().await;
+    let scheduler = CompactionScheduler::new(index.clone());
+    scheduler
+        .set_policy(CompactionPolicy {
+            check_interval: Duration::from_millis(50),
+            quiet_hours: vec![],
+        })
+        .await;
+
+    let handle = scheduler.start_continuous().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    scheduler.stop_continuous(handle).await.unwrap();
+
+    let stats = scheduler.get_statistics().await;
+    assert!(stats.total_runs >= 2);
+    assert_eq!(stats.skipped_runs, 0);
+    assert_eq!(stats.total_vectors_removed, 5);
+    assert!(stats.last_run.is_some());
+
+    // Tombstones are gone and live vectors remain searchable
+    assert_eq!(index.deletion_stats().await.2, 0);
+    let results = index.search(&[1.0, 1.0], 20).await.unwrap();
+    assert_eq!(results.len(), 15);
+}
+
+#[tokio::test]
+async fn test_compaction_skipped_during_quiet_hours() {
+    let index = create_index_with_deletions().await;
+    let scheduler = CompactionScheduler::new(index.clone());
+    scheduler
+        .set_policy(CompactionPolicy {
+            check_interval: Duration::from_millis(50),
+            quiet_hours: vec![(0, 24)],
+        })
+        .await;
+
+    assert!(scheduler.run_scheduled().await.unwrap().is_none());
+
+    let handle = scheduler.start_continuous().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    scheduler.stop_continuous(handle).await.unwrap();
+
+    let stats = scheduler.get_statistics().await;
+    assert_eq!(stats.total_runs, 0);
+    assert!(stats.skipped_runs >= 2);
+    assert!(stats.last_run.is_none());
+    assert_eq!(index.deletion_stats().await.2, 5);
+}
+
+#[tokio::test]
+async fn test_scheduler_rejects_second_start() {
+    let index = create_index_with_deletions().await;
+    let scheduler = CompactionScheduler::new(index);
+
+    let handle = scheduler.start_continuous().await.unwrap();
+    assert!(scheduler.start_continuous().await.is_err());
+    scheduler.stop_continuous(handle).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_stop_interrupts_default_interval() {
+    let index = create_index_with_deletions().await;
+    let scheduler = CompactionScheduler::new(index);
+
+    // The default policy sleeps an hour between runs
+    let handle = scheduler.start_continuous().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let started = std::time::Instant::now();
+    scheduler.stop_continuous(handle).await.unwrap();
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert_eq!(scheduler.get_statistics().await.total_runs, 1);
+
+    // It can be started again after stopping
+    let handle = scheduler.start_continuous().await.unwrap();
+    scheduler.stop_continuous(handle).await.unwrap();
+}
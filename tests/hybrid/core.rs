@@ -4,11 +4,13 @@
 use chrono::Utc;
 use std::time::{Duration, SystemTime};
 use tokio;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::storage::*;
 use vector_db::core::types::*;
 use vector_db::hnsw::core::{HNSWConfig, HNSWIndex};
 use vector_db::hybrid::core::*;
 use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 #[cfg(test)]
 mod hybrid_structure_tests {
@@ -23,6 +25,10 @@ mod hybrid_structure_tests {
                 max_connections_layer_0: 32,
                 ef_construction: 200,
                 seed: Some(42),
+                cache_distances: true,
+                metric: DistanceMetric::Euclidean,
+                use_heuristic_selection: false,
+                keep_pruned_connections: false,
             },
             ivf_config: IVFConfig {
                 n_clusters: 100,
@@ -30,9 +36,13 @@ mod hybrid_structure_tests {
                 train_size: 1000,
                 max_iterations: 25,
                 seed: Some(42),
+                metric: DistanceMetric::Euclidean,
+                pq: None,
+                vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
             },
             migration_batch_size: 100,
             auto_migrate: true,
+            migration_interval: Duration::from_secs(60),
         };
 
         assert_eq!(config.recent_threshold, Duration::from_secs(7 * 24 * 3600));
@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests that zero vectors are rejected under the cosine metric
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1 + 0.1).collect()
+}
+
+async fn create_index(metric: DistanceMetric) -> HybridIndex {
+    let mut config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::for_dataset(metric, 100)
+    };
+    config.ivf_config.n_clusters = 2;
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    index.insert(VectorId::from_u64(1), vector(1.0)).await.unwrap();
+    index
+}
+
+#[tokio::test]
+async fn test_zero_query_rejected_under_cosine() {
+    let index = create_index(DistanceMetric::Cosine).await;
+
+    let result = index.search(&[0.0; DIM], 5).await;
+
+    match result {
+        Err(HybridError::InvalidQuery(message)) => assert!(message.contains("zero")),
+        other => panic!("expected InvalidQuery, got {:?}", other.map(|r| r.len())),
+    }
+}
+
+#[tokio::test]
+async fn test_zero_vector_insert_rejected_under_cosine() {
+    let index = create_index(DistanceMetric::Cosine).await;
+
+    let result = index.insert(VectorId::from_u64(2), vec![0.0; DIM]).await;
+
+    let error = result.unwrap_err();
+    assert!(error.to_string().contains("Zero vector"), "{}", error);
+    assert_eq!(index.active_count().await, 1);
+}
+
+#[tokio::test]
+async fn test_zero_vectors_allowed_under_euclidean() {
+    let index = create_index(DistanceMetric::Euclidean).await;
+
+    index.insert(VectorId::from_u64(2), vec![0.0; DIM]).await.unwrap();
+    let results = index.search(&[0.0; DIM], 1).await.unwrap();
+
+    assert_eq!(results[0].vector_id, VectorId::from_u64(2));
+}
+
+#[tokio::test]
+async fn test_zero_vectors_rejected_under_cosine_ivf_only() {
+    // Recent index is Euclidean, but vectors would fail once migrated
+    let mut config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::for_dataset(DistanceMetric::Cosine, 100)
+    };
+    config.hnsw_config.metric = DistanceMetric::Euclidean;
+    config.ivf_config.n_clusters = 2;
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+
+    let insert = index.insert(VectorId::from_u64(1), vec![0.0; DIM]).await;
+    assert!(insert.unwrap_err().to_string().contains("Zero vector"));
+
+    let search = index.search(&[0.0; DIM], 5).await;
+    assert!(matches!(search, Err(HybridError::InvalidQuery(_))));
+}
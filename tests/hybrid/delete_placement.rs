@@ -0,0 +1,120 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests that deletes reach a vector wherever it was placed, not where its
+//! age says it should be
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridSearchConfig, IndexTarget};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+async fn search_ids(index: &HybridIndex, query: &[f32]) -> Vec<VectorId> {
+    let config = HybridSearchConfig {
+        k: 10,
+        ..HybridSearchConfig::default()
+    };
+    index
+        .search_with_config(query, config)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.vector_id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_delete_after_specific_migration() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(1);
+    index.insert(id.clone(), vector(1.0)).await.unwrap();
+
+    let result = index.migrate_specific_vectors(&[id.clone()]).await.unwrap();
+    assert_eq!(result.vectors_migrated, 1);
+    assert!(search_ids(&index, &vector(1.0)).await.contains(&id));
+
+    index.delete(id.clone()).await.unwrap();
+
+    assert!(index.is_deleted(&id).await);
+    assert!(!search_ids(&index, &vector(1.0)).await.contains(&id));
+}
+
+#[tokio::test]
+async fn test_delete_after_threshold_migration() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(2);
+    index
+        .insert_with_timestamp(id.clone(), vector(2.0), Utc::now() - Duration::minutes(5))
+        .await
+        .unwrap();
+
+    let migrated = index
+        .migrate_with_threshold(std::time::Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert_eq!(migrated, 1);
+
+    index.delete(id.clone()).await.unwrap();
+
+    assert!(index.is_deleted(&id).await);
+    assert!(!search_ids(&index, &vector(2.0)).await.contains(&id));
+}
+
+#[tokio::test]
+async fn test_delete_recent_vector_placed_in_historical() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(3);
+    index
+        .insert_into(id.clone(), vector(3.0), Utc::now(), IndexTarget::Historical)
+        .await
+        .unwrap();
+
+    index.delete(id.clone()).await.unwrap();
+
+    assert!(index.is_deleted(&id).await);
+    assert!(!search_ids(&index, &vector(3.0)).await.contains(&id));
+}
+
+#[tokio::test]
+async fn test_delete_old_vector_placed_in_recent() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(4);
+    index
+        .insert_into(
+            id.clone(),
+            vector(4.0),
+            Utc::now() - Duration::days(365),
+            IndexTarget::Recent,
+        )
+        .await
+        .unwrap();
+
+    index.delete(id.clone()).await.unwrap();
+
+    assert!(index.is_deleted(&id).await);
+    assert!(!search_ids(&index, &vector(4.0)).await.contains(&id));
+}
+
+#[tokio::test]
+async fn test_delete_unknown_vector_fails() {
+    let index = create_index().await;
+
+    assert!(index.delete(VectorId::from_u64(99)).await.is_err());
+    assert!(!index.is_deleted(&VectorId::from_u64(99)).await);
+}
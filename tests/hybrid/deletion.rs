@@ -3,8 +3,10 @@
 
 use chrono::Utc;
 use std::sync::Arc;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::types::VectorId;
 use vector_db::hybrid::core::{HybridConfig, HybridIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 /// Helper function to create a simple trained hybrid index for testing
 async fn create_test_hybrid_index() -> HybridIndex {
@@ -17,9 +19,13 @@ async fn create_test_hybrid_index() -> HybridIndex {
             train_size: 100,
             max_iterations: 10,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         },
         migration_batch_size: 100,
         auto_migrate: false, // Disable auto-migration for tests
+        migration_interval: std::time::Duration::from_secs(60),
     };
 
     let mut index = HybridIndex::new(config);
@@ -3,8 +3,9 @@
 
 //! Integration tests for deletion persistence in hybrid index
 
+use chrono::{Duration, Utc};
 use tokio;
-use vector_db::core::chunk::{Manifest, MANIFEST_VERSION};
+use vector_db::core::chunk::{DeletedVector, Manifest, MANIFEST_VERSION};
 use vector_db::core::storage::MockS5Storage;
 use vector_db::core::types::VectorId;
 use vector_db::hybrid::core::HybridIndex;
@@ -64,7 +65,7 @@ async fn test_save_index_with_deleted_vectors() {
         .unwrap();
 
     // Verify manifest includes deleted vectors
-    assert_eq!(manifest.version, 3, "Manifest version should be 3");
+    assert_eq!(manifest.version, MANIFEST_VERSION, "Manifest should be at the current version");
     assert!(
         manifest.deleted_vectors.is_some(),
         "Manifest should have deleted_vectors field"
@@ -74,12 +75,13 @@ async fn test_save_index_with_deleted_vectors() {
     assert_eq!(deleted_vec.len(), 3, "Should have 3 deleted vectors");
 
     // Convert to set for easier checking
-    let deleted_set: std::collections::HashSet<_> = deleted_vec.into_iter().collect();
+    let deleted_set: std::collections::HashSet<_> =
+        deleted_vec.into_iter().map(|d| d.id).collect();
 
-    // Check using VectorId string representations (which are hashes)
-    assert!(deleted_set.contains(&vec_5.to_string()));
-    assert!(deleted_set.contains(&vec_10.to_string()));
-    assert!(deleted_set.contains(&vec_15.to_string()));
+    // Ids are stored in full, not in their truncated display form
+    assert!(deleted_set.contains(&vec_5.hash_hex()));
+    assert!(deleted_set.contains(&vec_10.hash_hex()));
+    assert!(deleted_set.contains(&vec_15.hash_hex()));
 }
 
 #[tokio::test]
@@ -182,25 +184,54 @@ async fn test_deleted_vectors_excluded_from_search() {
 async fn test_manifest_v3_format() {
     // Create manifest with deleted vectors
     let mut manifest = Manifest::new(10000, 20);
-    manifest.deleted_vectors = Some(vec!["vec-1".to_string(), "vec-5".to_string()]);
+    let deleted_at = Utc::now();
+    manifest.deleted_vectors = Some(vec![
+        DeletedVector::new(&VectorId::from_string("vec-1"), Some(deleted_at)),
+        DeletedVector::new(&VectorId::from_string("vec-5"), None),
+    ]);
 
     // Serialize to JSON
     let json = manifest.to_json().unwrap();
 
     // Verify JSON contains deleted_vectors field
     assert!(json.contains("deleted_vectors"));
-    assert!(json.contains("vec-1"));
-    assert!(json.contains("vec-5"));
+    assert!(json.contains(&VectorId::from_string("vec-1").hash_hex()));
+    assert!(json.contains(&VectorId::from_string("vec-5").hash_hex()));
 
     // Deserialize back
     let loaded_manifest = Manifest::from_json(&json).unwrap();
-    assert_eq!(loaded_manifest.version, 3);
+    assert_eq!(loaded_manifest.version, MANIFEST_VERSION);
     assert!(loaded_manifest.deleted_vectors.is_some());
 
     let deleted = loaded_manifest.deleted_vectors.unwrap();
     assert_eq!(deleted.len(), 2);
-    assert!(deleted.contains(&"vec-1".to_string()));
-    assert!(deleted.contains(&"vec-5".to_string()));
+    assert_eq!(deleted[0].id, VectorId::from_string("vec-1").hash_hex());
+    assert_eq!(deleted[0].deleted_at, Some(deleted_at));
+    assert_eq!(deleted[1].id, VectorId::from_string("vec-5").hash_hex());
+    assert_eq!(deleted[1].deleted_at, None);
+}
+
+#[tokio::test]
+async fn test_v3_manifest_deleted_ids_migrate_without_times() {
+    // v3 and earlier listed deleted vectors as bare id strings
+    let v3_json = r#"{
+        "version": 3,
+        "chunk_size": 10000,
+        "total_vectors": 10,
+        "chunks": [],
+        "hnsw_structure": null,
+        "ivf_structure": null,
+        "deleted_vectors": ["vec_0b08289b", "7"]
+    }"#;
+
+    let mut manifest = Manifest::from_json(v3_json).unwrap();
+    manifest.migrate(3).unwrap();
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+
+    let deleted = manifest.deleted_vectors.unwrap();
+    let ids: Vec<&str> = deleted.iter().map(|d| d.id.as_str()).collect();
+    assert_eq!(ids, vec!["vec_0b08289b", "7"]);
+    assert!(deleted.iter().all(|d| d.deleted_at.is_none()));
 }
 
 #[tokio::test]
@@ -324,3 +355,109 @@ async fn test_active_count_after_load() {
     let active_count = loaded_index.active_count().await;
     assert_eq!(active_count, 17, "Should have 17 active vectors (20 - 3)");
 }
+
+#[tokio::test]
+async fn test_save_index_preserves_deleted_vectors() {
+    // Create index with 20 vectors, delete 2
+    let mut index = create_test_index().await;
+    add_test_vectors(&mut index, 20).await;
+
+    let vec_6 = VectorId::from_string("vec-6");
+    let vec_12 = VectorId::from_string("vec-12");
+    index.delete(vec_6.clone()).await.unwrap();
+    index.delete(vec_12.clone()).await.unwrap();
+
+    // Save and load with the non-chunked format
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone());
+    persister.save_index(&index, "test-plain-deleted").await.unwrap();
+    let loaded_index = persister.load_index("test-plain-deleted").await.unwrap();
+
+    assert!(loaded_index.is_deleted(&vec_6).await);
+    assert!(loaded_index.is_deleted(&vec_12).await);
+    assert!(!loaded_index.is_deleted(&VectorId::from_string("vec-0")).await);
+    assert_eq!(loaded_index.active_count().await, 18);
+
+    // Deleted vectors stay out of search results
+    let query: Vec<f32> = (0..128).map(|j| ((6 + j) as f32).sin() * 0.5).collect();
+    let results = loaded_index.search(&query, 20).await.unwrap();
+    assert_eq!(results.len(), 18);
+    assert!(results
+        .iter()
+        .all(|r| r.vector_id != vec_6 && r.vector_id != vec_12));
+}
+
+#[tokio::test]
+async fn test_deleted_ids_sharing_display_form_reload_exactly() {
+    // Both ids display as "vec_0b08289b"
+    let deleted = VectorId::from_string("doc-68691");
+    let kept = VectorId::from_string("doc-78960");
+    assert_eq!(deleted.to_string(), kept.to_string());
+
+    let mut index = create_test_index().await;
+    for (i, id) in [&deleted, &kept].into_iter().enumerate() {
+        let vector: Vec<f32> = (0..128).map(|j| ((i + j) as f32).cos()).collect();
+        index.insert(id.clone(), vector).await.unwrap();
+    }
+    index.delete(deleted.clone()).await.unwrap();
+
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone());
+    persister.save_index_chunked(&index, "test-collide").await.unwrap();
+    persister.save_index(&index, "test-collide-plain").await.unwrap();
+
+    let chunked = persister
+        .load_index_chunked("test-collide", HybridConfig::default())
+        .await
+        .unwrap();
+    let plain = persister.load_index("test-collide-plain").await.unwrap();
+    for loaded in [chunked, plain] {
+        assert!(loaded.is_deleted(&deleted).await);
+        assert!(!loaded.is_deleted(&kept).await);
+        assert_eq!(loaded.active_count().await, 1);
+    }
+}
+
+#[tokio::test]
+async fn test_deletion_times_survive_reload() {
+    let mut index = create_test_index().await;
+    let inserted_at = Utc::now() - Duration::minutes(5);
+    for i in 0..20 {
+        let id = VectorId::from_string(&format!("vec-{}", i));
+        let vector: Vec<f32> = (0..128).map(|j| ((i + j) as f32).sin() * 0.5).collect();
+        index.insert_with_timestamp(id, vector, inserted_at).await.unwrap();
+    }
+
+    let vec_4 = VectorId::from_string("vec-4");
+    let before_delete = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    index.delete(vec_4.clone()).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage.clone());
+    let manifest = persister
+        .save_index_chunked(&index, "test-deleted-at")
+        .await
+        .unwrap();
+    persister.save_index(&index, "test-deleted-at-plain").await.unwrap();
+
+    let saved = &manifest.deleted_vectors.unwrap()[0];
+    assert!(saved.deleted_at.is_some_and(|at| at > before_delete));
+
+    let chunked = persister
+        .load_index_chunked("test-deleted-at", HybridConfig::default())
+        .await
+        .unwrap();
+    let plain = persister.load_index("test-deleted-at-plain").await.unwrap();
+    let query: Vec<f32> = (0..128).map(|j| ((4 + j) as f32).sin() * 0.5).collect();
+    for loaded in [chunked, plain] {
+        assert!(loaded.is_deleted(&vec_4).await);
+
+        // The vector was live before its original deletion, not the reload
+        let past = loaded.search_as_of(&query, 1, before_delete).await.unwrap();
+        assert_eq!(past[0].vector_id, vec_4);
+        let now = loaded.search_as_of(&query, 1, Utc::now()).await.unwrap();
+        assert_ne!(now[0].vector_id, vec_4);
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for padding and truncating inserts to the index dimension
+
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{DimensionAdapter, HybridConfig, HybridError, HybridIndex};
+
+const DIM: usize = 4;
+
+async fn create_index(dimension_adapter: DimensionAdapter) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        dimension_adapter,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20)
+        .map(|i| (0..DIM).map(|d| i as f32 + d as f32 * 0.1).collect())
+        .collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+#[tokio::test]
+async fn test_shorter_vector_is_zero_padded() {
+    let index = create_index(DimensionAdapter::PadOrTruncate).await;
+    let id = VectorId::from_u64(1);
+
+    index.insert(id.clone(), vec![1.0, 2.0, 3.0]).await.unwrap();
+
+    let results = index.search(&[1.0, 2.0, 3.0, 0.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, id);
+    assert!(results[0].distance.abs() < 1e-6);
+    assert_eq!(index.dimension().await, Some(DIM));
+}
+
+#[tokio::test]
+async fn test_longer_vector_is_truncated() {
+    let index = create_index(DimensionAdapter::PadOrTruncate).await;
+    let id = VectorId::from_u64(2);
+
+    index
+        .insert(id.clone(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .await
+        .unwrap();
+
+    let results = index.search(&[1.0, 2.0, 3.0, 4.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, id);
+    assert!(results[0].distance.abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_matching_vector_is_unchanged() {
+    let index = create_index(DimensionAdapter::PadOrTruncate).await;
+    let id = VectorId::from_u64(3);
+
+    index.insert(id.clone(), vec![5.0, 6.0, 7.0, 8.0]).await.unwrap();
+
+    let results = index.search(&[5.0, 6.0, 7.0, 8.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, id);
+    assert!(results[0].distance.abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_mismatched_vector_rejected_by_default() {
+    assert_eq!(HybridConfig::default().dimension_adapter, DimensionAdapter::Reject);
+    let index = create_index(DimensionAdapter::default()).await;
+
+    let short = index.insert(VectorId::from_u64(4), vec![1.0, 2.0, 3.0]).await;
+    assert!(matches!(
+        short,
+        Err(HybridError::DimensionMismatch { expected: 4, actual: 3 })
+    ));
+
+    let long = index
+        .insert(VectorId::from_u64(5), vec![1.0, 2.0, 3.0, 4.0, 5.0])
+        .await;
+    assert!(matches!(
+        long,
+        Err(HybridError::DimensionMismatch { expected: 4, actual: 5 })
+    ));
+}
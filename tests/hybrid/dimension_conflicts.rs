@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+/// Trained index that lets mismatched vectors reach the sub-indices
+async fn create_trained_index(dimension: usize) -> HybridIndex {
+    let config = HybridConfig {
+        strict_dimensions: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20)
+        .map(|i| (0..dimension).map(|d| (i * dimension + d) as f32 * 0.1).collect())
+        .collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+#[tokio::test]
+async fn test_mismatched_historical_insert_names_both_dimensions() {
+    let index = create_trained_index(4).await;
+    let old = Utc::now() - Duration::days(30);
+
+    let err = index
+        .insert_with_timestamp(VectorId::from_u64(1), vec![0.5; 8], old)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        HybridError::TrainedDimensionMismatch { trained: 4, actual: 8 }
+    ));
+    let message = err.to_string();
+    assert!(message.contains("8 dimensions"));
+    assert!(message.contains("trained on 4"));
+    assert!(message.contains("Retrain"));
+}
+
+#[tokio::test]
+async fn test_report_flags_vectors_disagreeing_with_trained_dimension() {
+    let index = create_trained_index(4).await;
+    let old = Utc::now() - Duration::days(30);
+
+    for i in 0..3 {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32; 4], old)
+            .await
+            .unwrap();
+    }
+    // Recent vectors from a new 8-dimensional model land in HNSW
+    for i in 10..12 {
+        index.insert(VectorId::from_u64(i), vec![i as f32; 8]).await.unwrap();
+    }
+
+    let report = index.dimension_conflict_report().await;
+    assert!(report.has_conflicts());
+    assert_eq!(report.trained_dimension, Some(4));
+    assert_eq!(report.dimension_counts.get(&4), Some(&3));
+    assert_eq!(report.dimension_counts.get(&8), Some(&2));
+    assert_eq!(report.conflicting_vectors, 2);
+    assert_eq!(
+        report.sample_conflicts,
+        vec![(VectorId::from_u64(10), 8), (VectorId::from_u64(11), 8)]
+    );
+    assert!(report.recommendation().unwrap().contains("retrain"));
+}
+
+#[tokio::test]
+async fn test_report_is_clean_for_consistent_index() {
+    let index = create_trained_index(4).await;
+    index.insert(VectorId::from_u64(1), vec![1.0; 4]).await.unwrap();
+
+    let report = index.dimension_conflict_report().await;
+    assert!(!report.has_conflicts());
+    assert!(report.recommendation().is_none());
+}
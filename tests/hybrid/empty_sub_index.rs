@@ -0,0 +1,70 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for skipping empty sub-indices during hybrid search
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, SearchConfig, SubIndexSearches};
+
+async fn create_index() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+#[tokio::test]
+async fn test_recent_only_index_skips_historical_search() {
+    let index = create_index().await;
+    for i in 0..5u64 {
+        index.insert(VectorId::from_u64(i), vec![i as f32, 0.0]).await.unwrap();
+    }
+    assert_eq!(index.historical_count(), 0);
+
+    let results = index.search(&[2.0, 0.0], 3).await.unwrap();
+
+    assert_eq!(results[0].vector_id, VectorId::from_u64(2));
+    assert_eq!(
+        index.sub_index_searches(),
+        SubIndexSearches {
+            recent: 1,
+            historical: 0
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_historical_only_index_skips_recent_search() {
+    let index = create_index().await;
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..5u64 {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32, 0.0], old)
+            .await
+            .unwrap();
+    }
+    assert_eq!(index.recent_count(), 0);
+
+    let results = index.search(&[3.0, 0.0], 1).await.unwrap();
+
+    assert_eq!(results[0].vector_id, VectorId::from_u64(3));
+    assert_eq!(index.sub_index_searches().recent, 0);
+    assert_eq!(index.sub_index_searches().historical, 1);
+}
+
+#[tokio::test]
+async fn test_empty_sub_indices_searched_when_skipping_disabled() {
+    let index = create_index().await;
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.0]).await.unwrap();
+
+    let config = SearchConfig {
+        k: 1,
+        skip_empty_indices: false,
+        ..SearchConfig::default()
+    };
+    let results = index.search_with_config(&[1.0, 0.0], config).await.unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(index.sub_index_searches().historical, 1);
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for queries that need historical vectors evicted from the IVF
+//! vector cache
+
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::{DistanceMetric, SearchResult, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+use vector_db::ivf::core::IVFConfig;
+use vector_db::storage::chunk_loader::ChunkLoader;
+
+fn vector(i: u64) -> Vec<f32> {
+    vec![i as f32, 1.0 - i as f32 * 0.1]
+}
+
+/// Ten historical vectors backed by one chunk, with room for only two of
+/// them in the IVF vector cache
+async fn create_index() -> HybridIndex {
+    let storage = Arc::new(MockS5Storage::new());
+    let loader = Arc::new(ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(10))));
+    let mut chunk = VectorChunk::new("chunk-0".to_string(), 0, 9);
+    for i in 0..10 {
+        chunk.add_vector(VectorId::from_u64(i), vector(i));
+    }
+    storage.put("evicted/chunks/chunk-0.cbor", chunk.to_cbor().unwrap()).await.unwrap();
+
+    let config = HybridConfig {
+        auto_migrate: false,
+        ivf_config: IVFConfig {
+            n_clusters: 2,
+            n_probe: 2,
+            train_size: 2,
+            vector_cache_capacity: 2,
+            ..IVFConfig::default()
+        },
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::with_chunk_loader(config, Some(loader));
+    index.initialize((0..10).map(vector).collect()).await.unwrap();
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..10 {
+        index
+            .insert_with_chunk(
+                VectorId::from_u64(i),
+                vector(i),
+                old,
+                Some("evicted/chunks/chunk-0.cbor".to_string()),
+            )
+            .await
+            .unwrap();
+    }
+    assert!(index.get_historical_index().await.get_vector_by_id(&VectorId::from_u64(0)).is_none());
+    index
+}
+
+#[tokio::test]
+async fn test_explain_distance_loads_evicted_vector() {
+    let index = create_index().await;
+    let query = [0.5, 0.5];
+
+    let components = index.explain_distance(&query, &VectorId::from_u64(0)).await.unwrap();
+    assert_eq!(components, DistanceMetric::Euclidean.components(&query, &vector(0)));
+}
+
+#[tokio::test]
+async fn test_rerank_loads_evicted_vector() {
+    let index = create_index().await;
+    let query = [0.5, 0.5];
+
+    let reranked = index
+        .rerank_exact(&query, vec![SearchResult::new(VectorId::from_u64(0), 99.0, None)])
+        .await
+        .unwrap();
+    assert_eq!(reranked[0].distance, DistanceMetric::Euclidean.distance(&query, &vector(0)));
+}
+
+#[tokio::test]
+async fn test_negatives_penalize_evicted_vectors() {
+    let index = create_index().await;
+    let query = [0.5, 0.5];
+    let negative = vector(1);
+
+    let plain = index.search(&query, 3).await.unwrap();
+    let penalized = index
+        .search_with_negatives(&query, std::slice::from_ref(&negative), 3, 1.0)
+        .await
+        .unwrap();
+    assert!(!penalized.is_empty());
+    for result in &penalized {
+        let i = result.vector_id.as_u64().unwrap();
+        let base = DistanceMetric::Euclidean.distance(&query, &vector(i));
+        let penalty = DistanceMetric::Euclidean.similarity(&vector(i), &negative);
+        assert!((result.distance - (base + penalty)).abs() < 1e-4, "{} for {}", result.distance, i);
+    }
+    assert_ne!(plain, penalized);
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for exact (brute-force) hybrid search
+
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex, SearchConfig};
+
+const DIMENSION: usize = 8;
+
+fn random_vector(rng: &mut StdRng) -> Vec<f32> {
+    (0..DIMENSION).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt()
+}
+
+/// Index split across both sub-indices, with the live vectors it holds
+async fn create_index(config: HybridConfig) -> (HybridIndex, Vec<(VectorId, Vec<f32>)>) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|_| random_vector(&mut rng)).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    let mut live = Vec::new();
+    for i in 0..300u64 {
+        let id = VectorId::from_u64(i);
+        let vector = random_vector(&mut rng);
+        if i % 3 == 0 {
+            index
+                .insert_with_timestamp(id.clone(), vector.clone(), old)
+                .await
+                .unwrap();
+        } else {
+            index.insert(id.clone(), vector.clone()).await.unwrap();
+        }
+        if i % 10 == 0 {
+            index.delete(id).await.unwrap();
+        } else {
+            live.push((id, vector));
+        }
+    }
+
+    (index, live)
+}
+
+#[tokio::test]
+async fn test_exact_search_has_full_recall() {
+    let (index, live) = create_index(HybridConfig::default()).await;
+    let mut rng = StdRng::seed_from_u64(7);
+    let k = 10;
+
+    for _ in 0..20 {
+        let query = random_vector(&mut rng);
+        let mut truth: Vec<(VectorId, f32)> = live
+            .iter()
+            .map(|(id, vector)| (id.clone(), distance(&query, vector)))
+            .collect();
+        truth.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let truth: HashSet<VectorId> = truth.into_iter().take(k).map(|(id, _)| id).collect();
+
+        let results = index.exact_search(&query, k).await.unwrap();
+        let found: HashSet<VectorId> = results.iter().map(|r| r.vector_id.clone()).collect();
+
+        assert_eq!(found, truth);
+        assert!(results.windows(2).all(|w| w[0].distance <= w[1].distance));
+    }
+}
+
+#[tokio::test]
+async fn test_exact_flag_in_search_config() {
+    let (index, _) = create_index(HybridConfig::default()).await;
+    let query = vec![0.1; DIMENSION];
+
+    let config = SearchConfig {
+        k: 5,
+        exact: true,
+        ..SearchConfig::default()
+    };
+    let from_config = index.search_with_config(&query, config).await.unwrap();
+    let exact = index.exact_search(&query, 5).await.unwrap();
+
+    assert_eq!(from_config, exact);
+}
+
+#[tokio::test]
+async fn test_exact_search_rejects_large_index() {
+    let config = HybridConfig {
+        max_exact_search_vectors: 100,
+        ..HybridConfig::default()
+    };
+    let (index, _) = create_index(config).await;
+
+    let result = index.exact_search(&[0.0; DIMENSION], 5).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::ExactSearchTooLarge { vectors: 300, limit: 100 })
+    ));
+
+    // Approximate search is unaffected by the limit
+    assert_eq!(index.search(&[0.0; DIMENSION], 5).await.unwrap().len(), 5);
+}
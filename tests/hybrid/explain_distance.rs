@@ -0,0 +1,119 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for per-dimension distance breakdowns
+
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+const DIM: usize = 8;
+
+fn random_vector(rng: &mut StdRng) -> Vec<f32> {
+    (0..DIM).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+async fn create_index(rng: &mut StdRng) -> HybridIndex {
+    create_index_with(rng, DistanceMetric::Euclidean).await
+}
+
+async fn create_index_with(rng: &mut StdRng, metric: DistanceMetric) -> HybridIndex {
+    let mut config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::for_dataset(metric, 1_000)
+    };
+    config.ivf_config.n_clusters = 4;
+    config.ivf_config.n_probe = 4;
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|_| random_vector(rng)).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..40u64 {
+        let vector = random_vector(rng);
+        if i % 2 == 0 {
+            index.insert(VectorId::from_u64(i), vector).await.unwrap();
+        } else {
+            index
+                .insert_with_timestamp(VectorId::from_u64(i), vector, old)
+                .await
+                .unwrap();
+        }
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_components_sum_to_euclidean_distance() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let index = create_index(&mut rng).await;
+    let query = random_vector(&mut rng);
+
+    // Both a recent and a historical vector
+    for result in index.exact_search(&query, 40).await.unwrap().iter().take(10) {
+        let components = index.explain_distance(&query, &result.vector_id).await.unwrap();
+        assert_eq!(components.len(), DIM);
+        assert!(components.iter().all(|c| *c >= 0.0));
+
+        let total: f32 = components.iter().sum();
+        assert!((total.sqrt() - result.distance).abs() < 1e-5);
+    }
+}
+
+#[tokio::test]
+async fn test_components_per_dimension() {
+    let index = HybridIndex::new(HybridConfig {
+        auto_initialize: true,
+        ..HybridConfig::default()
+    });
+    index
+        .insert(VectorId::from_u64(1), vec![1.0, 2.0, 3.0])
+        .await
+        .unwrap();
+
+    let components = index
+        .explain_distance(&[1.0, 0.0, 6.0], &VectorId::from_u64(1))
+        .await
+        .unwrap();
+
+    assert_eq!(components, vec![0.0, 4.0, 9.0]);
+}
+
+#[tokio::test]
+async fn test_explain_distance_errors() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let index = create_index(&mut rng).await;
+
+    let missing = index
+        .explain_distance(&random_vector(&mut rng), &VectorId::from_u64(999))
+        .await;
+    assert!(matches!(missing, Err(HybridError::VectorNotFound(id)) if id == VectorId::from_u64(999)));
+
+    let wrong_dim = index.explain_distance(&[1.0, 2.0], &VectorId::from_u64(0)).await;
+    assert!(matches!(wrong_dim, Err(HybridError::DimensionMismatch { .. })));
+}
+
+#[tokio::test]
+async fn test_components_sum_to_distance_under_metric() {
+    for metric in [DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+        let mut rng = StdRng::seed_from_u64(11);
+        let index = create_index_with(&mut rng, metric).await;
+        let query = random_vector(&mut rng);
+
+        for result in index.exact_search(&query, 40).await.unwrap().iter().take(10) {
+            let components = index.explain_distance(&query, &result.vector_id).await.unwrap();
+            assert_eq!(components.len(), DIM);
+
+            let total: f32 = components.iter().sum();
+            assert!(
+                (total - result.distance).abs() < 1e-4,
+                "{:?}: {} vs {}",
+                metric,
+                total,
+                result.distance
+            );
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::json;
+use std::collections::HashMap;
+use vector_db::core::metadata_filter::MetadataFilter;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+async fn create_index(
+    config: HybridConfig,
+) -> (HybridIndex, HashMap<String, serde_json::Value>) {
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let mut metadata = HashMap::new();
+    for i in 0..20u64 {
+        let id = VectorId::from_u64(i);
+        index.insert(id.clone(), vec![i as f32 * 0.1, 0.0]).await.unwrap();
+        let genre = if i % 2 == 0 { "ai" } else { "music" };
+        metadata.insert(id.to_string(), json!({ "genre": genre }));
+    }
+    (index, metadata)
+}
+
+fn genre_filter(genre: &str) -> MetadataFilter {
+    MetadataFilter::from_json(&json!({ "genre": genre })).unwrap()
+}
+
+fn ids(results: &[vector_db::core::types::SearchResult]) -> Vec<VectorId> {
+    results.iter().map(|r| r.vector_id.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_filter_set_reused_across_searches() {
+    let (index, metadata) = create_index(HybridConfig::default()).await;
+    let filter = genre_filter("ai");
+
+    let first = index
+        .search_with_filter_cached(&[0.0, 0.0], 3, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+    let second = index
+        .search_with_filter_cached(&[1.42, 0.0], 3, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+
+    let stats = index.filter_cache_stats().await;
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.entries, 1);
+
+    assert_eq!(
+        ids(&first),
+        vec![VectorId::from_u64(0), VectorId::from_u64(2), VectorId::from_u64(4)]
+    );
+    assert_eq!(second[0].vector_id, VectorId::from_u64(14));
+
+    // Same results as evaluating the filter per candidate
+    let (uncached, metadata) = create_index(HybridConfig {
+        filter_cache_capacity: 0,
+        ..HybridConfig::default()
+    })
+    .await;
+    let expected = uncached
+        .search_with_filter_cached(&[0.0, 0.0], 3, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+    assert_eq!(ids(&first), ids(&expected));
+    assert_eq!(uncached.filter_cache_stats().await.entries, 0);
+
+    // Plain `search_with_filter` never touches the cache
+    index
+        .search_with_filter(&[0.0, 0.0], 3, Some(&filter), &metadata)
+        .await
+        .unwrap();
+    assert_eq!(index.filter_cache_stats().await.hits, 1);
+}
+
+#[tokio::test]
+async fn test_insert_invalidates_cached_filter_set() {
+    let (index, mut metadata) = create_index(HybridConfig::default()).await;
+    let filter = genre_filter("ai");
+    index
+        .search_with_filter_cached(&[0.0, 0.0], 3, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+
+    let new_id = VectorId::from_u64(100);
+    metadata.insert(new_id.to_string(), json!({ "genre": "ai" }));
+    index.insert(new_id.clone(), vec![-0.05, 0.0]).await.unwrap();
+
+    let results = index
+        .search_with_filter_cached(&[-0.05, 0.0], 3, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+    assert_eq!(results[0].vector_id, new_id);
+
+    let stats = index.filter_cache_stats().await;
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.invalidations, 1);
+}
+
+#[tokio::test]
+async fn test_metadata_update_under_new_generation() {
+    let (index, mut metadata) = create_index(HybridConfig::default()).await;
+    let filter = genre_filter("music");
+    let before = index
+        .search_with_filter_cached(&[0.0, 0.0], 1, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+    assert_eq!(before[0].vector_id, VectorId::from_u64(1));
+
+    // Vector 0 changes genre outside the index
+    metadata.insert(VectorId::from_u64(0).to_string(), json!({ "genre": "music" }));
+
+    let after = index
+        .search_with_filter_cached(&[0.0, 0.0], 1, Some(&filter), &metadata, 1)
+        .await
+        .unwrap();
+    assert_eq!(after[0].vector_id, VectorId::from_u64(0));
+    assert_eq!(index.filter_cache_stats().await.invalidations, 1);
+
+    // A search still holding the old generation doesn't replace the new set
+    let stale = index
+        .search_with_filter_cached(&[0.0, 0.0], 1, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+    assert_eq!(stale[0].vector_id, VectorId::from_u64(0));
+    let again = index
+        .search_with_filter_cached(&[0.0, 0.0], 1, Some(&filter), &metadata, 1)
+        .await
+        .unwrap();
+    assert_eq!(again[0].vector_id, VectorId::from_u64(0));
+
+    let stats = index.filter_cache_stats().await;
+    assert_eq!((stats.hits, stats.misses), (1, 3));
+}
+
+#[tokio::test]
+async fn test_cached_filter_set_is_per_generation() {
+    let (index, metadata) = create_index(HybridConfig::default()).await;
+    let filter = genre_filter("ai");
+    index
+        .search_with_filter_cached(&[0.0, 0.0], 1, Some(&filter), &metadata, 0)
+        .await
+        .unwrap();
+
+    // A map with the genres swapped, under its own generation, must not
+    // reuse the first map's set
+    let swapped: HashMap<String, serde_json::Value> = metadata
+        .iter()
+        .map(|(id, value)| {
+            let genre = if value["genre"] == "ai" { "music" } else { "ai" };
+            (id.clone(), json!({ "genre": genre }))
+        })
+        .collect();
+    let results = index
+        .search_with_filter_cached(&[0.0, 0.0], 1, Some(&filter), &swapped, 1)
+        .await
+        .unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(1));
+    assert_eq!(index.filter_cache_stats().await.misses, 2);
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::json;
+use std::collections::HashMap;
+use vector_db::core::metadata_filter::MetadataFilter;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+/// 400 vectors on a line, every 20th (5%) tagged `rare`
+async fn create_index(
+    config: HybridConfig,
+) -> (HybridIndex, HashMap<String, serde_json::Value>) {
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let mut metadata = HashMap::new();
+    for i in 0..400u64 {
+        let id = VectorId::from_u64(i);
+        index.insert(id.clone(), vec![i as f32 * 0.1, 0.0]).await.unwrap();
+        let kind = if i % 20 == 0 { "rare" } else { "common" };
+        metadata.insert(id.to_string(), json!({ "kind": kind }));
+    }
+    (index, metadata)
+}
+
+fn rare() -> MetadataFilter {
+    MetadataFilter::from_json(&json!({ "kind": "rare" })).unwrap()
+}
+
+#[tokio::test]
+async fn test_selective_filter_still_returns_k_results() {
+    let (index, metadata) = create_index(HybridConfig::default()).await;
+
+    let results = index
+        .search_with_filter(&[0.0, 0.0], 10, Some(&rare()), &metadata)
+        .await
+        .unwrap();
+
+    let ids: Vec<VectorId> = results.iter().map(|r| r.vector_id.clone()).collect();
+    let expected: Vec<VectorId> = (0..10).map(|i| VectorId::from_u64(i * 20)).collect();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn test_oversample_cap_limits_retries() {
+    let config = HybridConfig {
+        filter_oversample: 3,
+        max_filter_oversample: 3,
+        ..HybridConfig::default()
+    };
+    let (index, metadata) = create_index(config).await;
+
+    // 30 candidates hold only the two rare vectors 0 and 20
+    let results = index
+        .search_with_filter(&[0.0, 0.0], 10, Some(&rare()), &metadata)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+}
+
+#[tokio::test]
+async fn test_filter_with_fewer_matches_than_k() {
+    let (index, metadata) = create_index(HybridConfig::default()).await;
+    let filter = MetadataFilter::from_json(&json!({ "kind": "missing" })).unwrap();
+
+    let results = index
+        .search_with_filter(&[0.0, 0.0], 10, Some(&filter), &metadata)
+        .await
+        .unwrap();
+    assert!(results.is_empty());
+
+    // Wide enough to need the whole index
+    let results = index
+        .search_with_filter(&[0.0, 0.0], 25, Some(&rare()), &metadata)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 20);
+}
+
+#[tokio::test]
+async fn test_filter_config_options_apply_to_candidates() {
+    let config = HybridConfig {
+        max_exact_search_vectors: 100,
+        ..HybridConfig::default()
+    };
+    let (index, metadata) = create_index(config).await;
+
+    // `exact` is honoured, here hitting the exact-search limit
+    let mut exact = index.default_search_config().await;
+    exact.k = 5;
+    exact.exact = true;
+    let result = index
+        .search_with_filter_config(&[0.0, 0.0], exact, Some(&rare()), &metadata, None)
+        .await;
+    assert!(matches!(result, Err(HybridError::ExactSearchTooLarge { .. })));
+
+    // Every vector is recent, so a historical-only search finds nothing
+    let mut historical = index.default_search_config().await;
+    historical.k = 5;
+    historical.search_recent = false;
+    let results = index
+        .search_with_filter_config(&[0.0, 0.0], historical, Some(&rare()), &metadata, None)
+        .await
+        .unwrap();
+    assert!(results.is_empty());
+
+    // The offset skips filtered results
+    let mut paged = index.default_search_config().await;
+    paged.k = 2;
+    paged.offset = 3;
+    let results = index
+        .search_with_filter_config(&[0.0, 0.0], paged, Some(&rare()), &metadata, None)
+        .await
+        .unwrap();
+    let ids: Vec<VectorId> = results.iter().map(|r| r.vector_id.clone()).collect();
+    assert_eq!(ids, vec![VectorId::from_u64(60), VectorId::from_u64(80)]);
+}
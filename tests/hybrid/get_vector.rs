@@ -0,0 +1,78 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for looking vectors up by id in either sub-index
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, IndexTarget};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+#[tokio::test]
+async fn test_get_recent_and_historical_vectors() {
+    let index = create_index().await;
+    let recent = VectorId::from_string("recent");
+    let historical = VectorId::from_string("historical");
+    index.insert(recent.clone(), vector(1.0)).await.unwrap();
+    index
+        .insert_with_timestamp(historical.clone(), vector(2.0), Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+
+    assert!(index.is_in_recent(&recent));
+    assert!(index.is_in_historical(&historical));
+    assert_eq!(index.get_vector(&recent).await, Some(vector(1.0)));
+    assert_eq!(index.get_vector(&historical).await, Some(vector(2.0)));
+    assert_eq!(index.get_vector(&VectorId::from_string("missing")).await, None);
+}
+
+#[tokio::test]
+async fn test_get_vector_placed_against_its_age() {
+    let index = create_index().await;
+    let migrated = VectorId::from_string("migrated");
+    let old_in_recent = VectorId::from_string("old_in_recent");
+    index.insert(migrated.clone(), vector(3.0)).await.unwrap();
+    index.migrate_specific_vectors(&[migrated.clone()]).await.unwrap();
+    index
+        .insert_into(
+            old_in_recent.clone(),
+            vector(4.0),
+            Utc::now() - Duration::days(30),
+            IndexTarget::Recent,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(index.get_vector(&migrated).await, Some(vector(3.0)));
+    assert_eq!(index.get_vector(&old_in_recent).await, Some(vector(4.0)));
+}
+
+#[tokio::test]
+async fn test_get_deleted_vector_is_none() {
+    let index = create_index().await;
+    let id = VectorId::from_string("deleted");
+    index
+        .insert_with_timestamp(id.clone(), vector(5.0), Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+
+    index.delete(id.clone()).await.unwrap();
+
+    assert_eq!(index.get_vector(&id).await, None);
+}
@@ -0,0 +1,366 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for incremental chunked saves that only rewrite changed chunks
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister};
+
+/// Mock storage that records every path written
+#[derive(Clone)]
+struct WriteTrackingStorage {
+    inner: MockS5Storage,
+    writes: Arc<Mutex<Vec<String>>>,
+    /// Reject manifest writes while set
+    fail_manifest: Arc<AtomicBool>,
+    /// Reject writes to paths containing this while set
+    fail_path: Arc<Mutex<Option<&'static str>>>,
+}
+
+impl WriteTrackingStorage {
+    fn new() -> Self {
+        Self {
+            inner: MockS5Storage::new(),
+            writes: Arc::new(Mutex::new(Vec::new())),
+            fail_manifest: Arc::new(AtomicBool::new(false)),
+            fail_path: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Vector chunk files written since the last call
+    fn take_chunk_writes(&self) -> Vec<String> {
+        std::mem::take(&mut *self.writes.lock().unwrap())
+            .into_iter()
+            .filter(|p| p.contains("/chunks/"))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl S5Storage for WriteTrackingStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        if path.ends_with("manifest.json") && self.fail_manifest.load(Ordering::SeqCst) {
+            return Err(StorageError::NetworkError("manifest write failed".to_string()));
+        }
+        if matches!(*self.fail_path.lock().unwrap(), Some(fail) if path.contains(fail)) {
+            return Err(StorageError::NetworkError(format!("write to {} failed", path)));
+        }
+        self.writes.lock().unwrap().push(path.to_string());
+        self.inner.put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+async fn create_index(count: u64) -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let base = Utc::now() - Duration::hours(1);
+    for i in 0..count {
+        index
+            .insert_with_timestamp(
+                VectorId::from_u64(i),
+                vec![i as f32, 1.0, 0.0],
+                base + Duration::seconds(i as i64),
+            )
+            .await
+            .unwrap();
+    }
+
+    index
+}
+
+#[tokio::test]
+async fn test_incremental_save_uploads_only_changed_chunk() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "inc").await.unwrap();
+    assert_eq!(storage.take_chunk_writes().len(), 4);
+
+    // Change one vector in the third chunk (ids are saved in order)
+    let changed = VectorId::from_u64(120);
+    index.set_payload(changed.clone(), b"edited".to_vec()).await.unwrap();
+
+    let second = persister
+        .save_index_incremental(&index, "inc", &first)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        storage.take_chunk_writes(),
+        vec!["inc/chunks/chunk-2.g1.cbor".to_string()]
+    );
+    assert_eq!(second.generation, first.generation + 1);
+    assert_eq!(second.chunks.len(), 4);
+    for (old, new) in first.chunks.iter().zip(&second.chunks) {
+        if new.chunk_id == "chunk-2" {
+            assert_ne!(old.content_hash, new.content_hash);
+        } else {
+            assert_eq!(old.content_hash, new.content_hash);
+        }
+    }
+
+    let loaded = persister
+        .load_index_chunked("inc", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_payload(&changed).await, Some(b"edited".to_vec()));
+    assert_eq!(loaded.get_stats().total_vectors, 200);
+}
+
+#[tokio::test]
+async fn test_incremental_save_of_unchanged_index_uploads_no_chunks() {
+    let index = create_index(120).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "same").await.unwrap();
+    storage.take_chunk_writes();
+
+    let second = persister
+        .save_index_incremental(&index, "same", &first)
+        .await
+        .unwrap();
+
+    assert!(storage.take_chunk_writes().is_empty());
+    assert_eq!(
+        persister.diff_manifests(&first, &second).changed_chunks,
+        Vec::<String>::new()
+    );
+}
+
+#[tokio::test]
+async fn test_incremental_save_removes_stale_chunks() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "shrink").await.unwrap();
+    storage.take_chunk_writes();
+
+    // Dropping the last 50 ids leaves the first three chunks as they were
+    for i in 150..200 {
+        index.delete(VectorId::from_u64(i)).await.unwrap();
+    }
+    index.vacuum().await.unwrap();
+
+    let second = persister
+        .save_index_incremental(&index, "shrink", &first)
+        .await
+        .unwrap();
+
+    assert!(storage.take_chunk_writes().is_empty());
+    assert_eq!(second.chunks.len(), 3);
+    assert_eq!(storage.get("shrink/chunks/chunk-3.cbor").await.unwrap(), None);
+    assert!(storage.get("shrink/chunks/chunk-2.cbor").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_failed_manifest_write_keeps_stale_chunks() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "keep").await.unwrap();
+    for i in 150..200 {
+        index.delete(VectorId::from_u64(i)).await.unwrap();
+    }
+    index.vacuum().await.unwrap();
+
+    storage.fail_manifest.store(true, Ordering::SeqCst);
+    assert!(persister
+        .save_index_incremental(&index, "keep", &first)
+        .await
+        .is_err());
+
+    // The previous manifest still references chunk-3, so it must survive
+    assert!(storage.get("keep/chunks/chunk-3.cbor").await.unwrap().is_some());
+    storage.fail_manifest.store(false, Ordering::SeqCst);
+    let loaded = persister
+        .load_index_chunked("keep", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_stats().total_vectors, 200);
+}
+
+#[tokio::test]
+async fn test_failed_manifest_write_keeps_previous_chunk_contents() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "prev").await.unwrap();
+    let changed = VectorId::from_u64(120);
+    index.set_payload(changed.clone(), b"edited".to_vec()).await.unwrap();
+
+    storage.fail_manifest.store(true, Ordering::SeqCst);
+    assert!(persister
+        .save_index_incremental(&index, "prev", &first)
+        .await
+        .is_err());
+    storage.fail_manifest.store(false, Ordering::SeqCst);
+
+    // The changed chunk went to a new key, so the stored manifest still
+    // loads the chunk it was saved with
+    let loaded = persister
+        .load_index_chunked("prev", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_payload(&changed).await, None);
+
+    // The next successful save drops the file the old manifest referenced
+    let second = persister
+        .save_index_incremental(&index, "prev", &first)
+        .await
+        .unwrap();
+    assert_eq!(storage.get("prev/chunks/chunk-2.cbor").await.unwrap(), None);
+    let loaded = persister
+        .load_index_chunked("prev", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_payload(&changed).await, Some(b"edited".to_vec()));
+    assert_eq!(second.chunks[2].generation, Some(1));
+}
+
+#[tokio::test]
+async fn test_failed_graph_or_metadata_write_keeps_previous_save() {
+    for fail in ["hnsw_nodes", "metadata"] {
+        let index = create_index(200).await;
+        let storage = WriteTrackingStorage::new();
+        let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+        let path = format!("state-{}", fail);
+        let first = persister.save_index_chunked(&index, &path).await.unwrap();
+        for i in 150..200 {
+            index.delete(VectorId::from_u64(i)).await.unwrap();
+        }
+        index.vacuum().await.unwrap();
+
+        *storage.fail_path.lock().unwrap() = Some(fail);
+        assert!(persister
+            .save_index_incremental(&index, &path, &first)
+            .await
+            .is_err());
+        *storage.fail_path.lock().unwrap() = None;
+
+        // Neither the manifest nor the files it references were replaced
+        assert!(storage.get(&first.hnsw_nodes_path(&path)).await.unwrap().is_some());
+        assert!(storage.get(&first.metadata_path(&path)).await.unwrap().is_some());
+        let loaded = persister
+            .load_index_chunked(&path, HybridConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(loaded.get_stats().total_vectors, 200);
+        assert!(loaded.get_vector(&VectorId::from_u64(199)).await.is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_superseded_graph_and_metadata_deleted_after_save() {
+    let index = create_index(100).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "state").await.unwrap();
+    let second = persister
+        .save_index_incremental(&index, "state", &first)
+        .await
+        .unwrap();
+
+    assert_eq!(second.hnsw_nodes_path("state"), "state/hnsw_nodes.g1.cbor");
+    assert_eq!(second.metadata_path("state"), "state/metadata.g1.cbor");
+    assert_eq!(storage.get("state/hnsw_nodes.cbor").await.unwrap(), None);
+    assert_eq!(storage.get("state/metadata.cbor").await.unwrap(), None);
+    let loaded = persister
+        .load_index_chunked("state", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_stats().total_vectors, 100);
+}
+
+#[tokio::test]
+async fn test_insert_rewrites_only_the_covering_chunk() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    // Leave room in every chunk, so the insert below doesn't split one
+    let first = persister.save_index_chunked(&index, "grow").await.unwrap();
+    for i in (0..200).step_by(2) {
+        index.delete(VectorId::from_u64(i)).await.unwrap();
+    }
+    index.vacuum().await.unwrap();
+    let second = persister
+        .save_index_incremental(&index, "grow", &first)
+        .await
+        .unwrap();
+    assert_eq!(second.get_chunk_ids(), first.get_chunk_ids());
+    storage.take_chunk_writes();
+
+    index
+        .insert(VectorId::from_u64(1000), vec![1000.0, 1.0, 0.0])
+        .await
+        .unwrap();
+    let third = persister
+        .save_index_incremental(&index, "grow", &second)
+        .await
+        .unwrap();
+
+    // Chunks after the new vector's keep their contents
+    assert_eq!(storage.take_chunk_writes().len(), 1);
+    let diff = persister.diff_manifests(&second, &third);
+    assert_eq!(diff.changed_chunks.len(), 1);
+    assert!(diff.added_chunks.is_empty());
+    assert!(diff.removed_chunks.is_empty());
+}
+
+#[tokio::test]
+async fn test_overfull_chunk_is_split_into_new_chunks() {
+    let index = create_index(200).await;
+    let persister = HybridPersister::new(MockS5Storage::new()).with_chunk_size(50);
+
+    let first = persister.save_index_chunked(&index, "split").await.unwrap();
+    for i in 1000..1120 {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32, 1.0, 0.0])
+            .await
+            .unwrap();
+    }
+    let second = persister
+        .save_index_incremental(&index, "split", &first)
+        .await
+        .unwrap();
+
+    assert!(second.chunks.iter().all(|chunk| chunk.vector_count <= 50));
+    for chunk in &first.chunks {
+        assert!(second.get_chunk(&chunk.chunk_id).is_some());
+    }
+    assert_eq!(
+        second.chunks.iter().map(|chunk| chunk.vector_count).sum::<usize>(),
+        320
+    );
+    let loaded = persister
+        .load_index_chunked("split", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_stats().total_vectors, 320);
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the dimension `initialize` establishes
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+fn training(count: usize, dimension: usize) -> Vec<Vec<f32>> {
+    (0..count)
+        .map(|i| (0..dimension).map(|d| (i * dimension + d) as f32 * 0.1).collect())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_wrong_dimension_insert_after_initialize() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index.initialize(training(20, 4)).await.unwrap();
+    assert_eq!(index.dimension().await, Some(4));
+
+    // Recent inserts are checked too, not just those routed to IVF
+    let recent = index.insert(VectorId::from_u64(1), vec![0.5; 8]).await;
+    assert!(matches!(
+        recent,
+        Err(HybridError::DimensionMismatch { expected: 4, actual: 8 })
+    ));
+    let old = Utc::now() - Duration::days(30);
+    let historical = index
+        .insert_with_timestamp(VectorId::from_u64(2), vec![0.5; 3], old)
+        .await;
+    assert!(matches!(
+        historical,
+        Err(HybridError::DimensionMismatch { expected: 4, actual: 3 })
+    ));
+    assert_eq!(index.total_vectors(), 0);
+
+    index.insert(VectorId::from_u64(3), vec![0.5; 4]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_untrained_initialize_records_dimension() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index.initialize(training(3, 4)).await.unwrap();
+    assert_eq!(index.dimension().await, Some(4));
+
+    let result = index.insert(VectorId::from_u64(1), vec![0.5; 2]).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: 4, actual: 2 })
+    ));
+}
+
+#[tokio::test]
+async fn test_inconsistent_training_data_rejected() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let mut data = training(20, 4);
+    data[7] = vec![1.0; 5];
+
+    let result = index.initialize(data).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: 4, actual: 5 })
+    ));
+    assert!(!index.is_initialized());
+}
+
+#[tokio::test]
+async fn test_training_must_match_stored_vectors() {
+    let config = HybridConfig {
+        auto_initialize: true,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    index.insert(VectorId::from_u64(1), vec![0.5; 3]).await.unwrap();
+
+    let result = index.initialize(training(20, 4)).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: 3, actual: 4 })
+    ));
+}
+
+#[tokio::test]
+async fn test_query_checked_against_training_dimension() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index.initialize(training(20, 768)).await.unwrap();
+
+    // Nothing has been inserted, so only the training data fixes the dimension
+    let result = index.search(&[0.5; 128], 5).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: 768, actual: 128 })
+    ));
+    assert!(index.search(&[0.5; 768], 5).await.unwrap().is_empty());
+}
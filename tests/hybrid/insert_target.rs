@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for choosing the sub-index a vector is inserted into
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridSearchConfig, IndexTarget};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+async fn search_only(index: &HybridIndex, query: &[f32], recent: bool) -> Vec<VectorId> {
+    let config = HybridSearchConfig {
+        search_recent: recent,
+        search_historical: !recent,
+        k: 5,
+        ..HybridSearchConfig::default()
+    };
+    index
+        .search_with_config(query, config)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.vector_id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_recent_vector_inserted_into_historical() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(1);
+
+    index
+        .insert_into(id.clone(), vector(1.0), Utc::now(), IndexTarget::Historical)
+        .await
+        .unwrap();
+
+    assert_eq!(search_only(&index, &vector(1.0), false).await, vec![id.clone()]);
+    assert!(search_only(&index, &vector(1.0), true).await.is_empty());
+
+    let stats = index.get_stats();
+    assert_eq!(stats.historical_vectors, 1);
+    assert_eq!(stats.recent_vectors, 0);
+}
+
+#[tokio::test]
+async fn test_old_vector_inserted_into_recent() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(2);
+
+    index
+        .insert_into(id.clone(), vector(2.0), Utc::now() - Duration::days(365), IndexTarget::Recent)
+        .await
+        .unwrap();
+
+    assert_eq!(search_only(&index, &vector(2.0), true).await, vec![id]);
+}
+
+#[tokio::test]
+async fn test_auto_routes_by_age() {
+    let index = create_index().await;
+
+    index
+        .insert_into(VectorId::from_u64(3), vector(3.0), Utc::now(), IndexTarget::Auto)
+        .await
+        .unwrap();
+    index
+        .insert_into(VectorId::from_u64(4), vector(4.0), Utc::now() - Duration::days(365), IndexTarget::Auto)
+        .await
+        .unwrap();
+
+    assert_eq!(search_only(&index, &vector(3.0), true).await, vec![VectorId::from_u64(3)]);
+    assert_eq!(search_only(&index, &vector(4.0), false).await, vec![VectorId::from_u64(4)]);
+}
+
+#[tokio::test]
+async fn test_historical_requires_trained_index() {
+    let config = HybridConfig {
+        auto_initialize: true,
+        ..HybridConfig::default()
+    };
+    let index = HybridIndex::new(config);
+
+    let result = index
+        .insert_into(VectorId::from_u64(1), vector(1.0), Utc::now(), IndexTarget::Historical)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(index.total_vectors(), 0);
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for write-lock contention metrics
+
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+const DIM: usize = 8;
+
+fn vector(seed: u64) -> Vec<f32> {
+    (0..DIM).map(|d| (seed as f32 * 0.37 + d as f32).sin()).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(vector).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+#[cfg(feature = "lock-metrics")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_contention_recorded_under_concurrent_writers() {
+    const WRITERS: u64 = 8;
+    const PER_WRITER: u64 = 50;
+
+    let index = create_index().await;
+    let before = index.lock_contention().unwrap();
+    assert_eq!(before.recent_index.acquisitions, 0);
+
+    let handles: Vec<_> = (0..WRITERS)
+        .map(|w| {
+            let index = index.clone();
+            tokio::spawn(async move {
+                for i in 0..PER_WRITER {
+                    let seed = 1000 + w * PER_WRITER + i;
+                    index.insert(VectorId::from_u64(seed), vector(seed)).await.unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let after = index.lock_contention().unwrap();
+    let total = WRITERS * PER_WRITER;
+    assert_eq!(after.recent_index.acquisitions, total);
+    assert_eq!(after.timestamps.acquisitions, total);
+    // Writers queue behind each other's HNSW inserts
+    assert!(after.recent_index.total_wait_us > before.recent_index.total_wait_us);
+    assert!(after.recent_index.max_us >= after.recent_index.p99_us);
+    assert!(after.recent_index.p99_us >= after.recent_index.p50_us);
+
+    // Deletes are timed too
+    index.delete(VectorId::from_u64(1000)).await.unwrap();
+    let deleted = index.lock_contention().unwrap();
+    assert_eq!(deleted.recent_index.acquisitions, total + 1);
+    assert_eq!(deleted.historical_index.acquisitions, 1);
+}
+
+#[cfg(not(feature = "lock-metrics"))]
+#[tokio::test]
+async fn test_contention_not_recorded_without_feature() {
+    let index = create_index().await;
+    index.insert(VectorId::from_u64(1), vector(1)).await.unwrap();
+
+    assert!(index.lock_contention().is_none());
+}
@@ -336,6 +336,7 @@ mod backup_restore_tests {
                     include_historical: true,
                     compress: true,
                     encryption_key: None,
+                    retention: BackupRetention::KeepAll,
                 },
             )
             .await
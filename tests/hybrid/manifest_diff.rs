@@ -0,0 +1,111 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for diffing persisted manifests for replication
+
+use chrono::{Duration, Utc};
+use vector_db::core::chunk::{ChunkMetadata, Manifest};
+use vector_db::core::storage::MockS5Storage;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister, ManifestDiff};
+
+fn chunk(id: &str, hash: Option<&str>, cid: Option<&str>) -> ChunkMetadata {
+    let mut meta = ChunkMetadata::new(
+        id.to_string(),
+        10,
+        100,
+        VectorId::from_u64(0),
+        VectorId::from_u64(9),
+    );
+    meta.content_hash = hash.map(str::to_string);
+    meta.cid = cid.map(str::to_string);
+    meta
+}
+
+fn manifest(chunks: Vec<ChunkMetadata>) -> Manifest {
+    let mut manifest = Manifest::new(10, chunks.len() * 10);
+    for c in chunks {
+        manifest.add_chunk(c);
+    }
+    manifest
+}
+
+async fn insert_range(index: &HybridIndex, ids: std::ops::Range<u64>) {
+    let base = Utc::now() - Duration::hours(1);
+    for i in ids {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32, 1.0, 0.0], base)
+            .await
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_diff_identifies_added_removed_and_changed_chunks() {
+    let persister = HybridPersister::new(MockS5Storage::new());
+    let old = manifest(vec![
+        chunk("chunk-0", Some("a"), None),
+        chunk("chunk-1", Some("b"), None),
+        chunk("chunk-2", Some("c"), None),
+    ]);
+    let new = manifest(vec![
+        chunk("chunk-0", Some("a"), None),
+        chunk("chunk-1", Some("b2"), None),
+        chunk("chunk-3", Some("d"), None),
+    ]);
+
+    let diff = persister.diff_manifests(&old, &new);
+    assert_eq!(
+        diff,
+        ManifestDiff {
+            added_chunks: vec!["chunk-3".to_string()],
+            removed_chunks: vec!["chunk-2".to_string()],
+            changed_chunks: vec!["chunk-1".to_string()],
+            changed_timestamp_chunks: vec![],
+        }
+    );
+
+    assert!(persister.diff_manifests(&new, &new).is_empty());
+}
+
+#[test]
+fn test_chunks_without_hashes_fall_back_to_cid() {
+    let persister = HybridPersister::new(MockS5Storage::new());
+    let old = manifest(vec![
+        chunk("chunk-0", None, Some("cid-0")),
+        chunk("chunk-1", None, None),
+    ]);
+    let new = manifest(vec![
+        chunk("chunk-0", None, Some("cid-0")),
+        chunk("chunk-1", None, None),
+    ]);
+
+    // Unknown contents are assumed changed
+    let diff = persister.diff_manifests(&old, &new);
+    assert_eq!(diff.changed_chunks, vec!["chunk-1".to_string()]);
+}
+
+#[tokio::test]
+async fn test_diff_between_saved_snapshots() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    insert_range(&index, 0..200).await;
+
+    let persister = HybridPersister::new(MockS5Storage::new()).with_chunk_size(50);
+    let first = persister.save_index_chunked(&index, "primary").await.unwrap();
+    assert!(first.chunks.iter().all(|c| c.content_hash.is_some()));
+
+    // Saving unchanged contents again produces identical hashes
+    let again = persister.save_index_chunked(&index, "primary").await.unwrap();
+    assert!(persister.diff_manifests(&first, &again).is_empty());
+
+    insert_range(&index, 200..210).await;
+    let second = persister.save_index_chunked(&index, "primary").await.unwrap();
+
+    let diff = persister.diff_manifests(&first, &second);
+    assert_eq!(diff.added_chunks, vec!["chunk-4".to_string()]);
+    assert!(diff.removed_chunks.is_empty());
+    assert!(diff.changed_chunks.is_empty());
+    assert_eq!(diff.changed_timestamp_chunks, vec!["chunk-4".to_string()]);
+}
@@ -0,0 +1,251 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for upgrading manifests written by older versions
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use vector_db::core::chunk::{ChunkError, Manifest, MANIFEST_VERSION};
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError};
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::{
+    HybridConfig, HybridIndex, HybridPersister, PersistenceError, SerializableTimestamps,
+};
+
+/// A v1 manifest as written before integer ids, chunk hashes and checksums
+/// or the IVF metric existed, and without the v3 fields: ids are 32 byte
+/// hashes of "vec-0", "vec-1" and "vec-2"
+const V1_MANIFEST: &str = r#"{
+    "version": 1,
+    "chunk_size": 2,
+    "total_vectors": 3,
+    "chunks": [
+        {
+            "chunk_id": "chunk-0",
+            "cid": null,
+            "vector_count": 2,
+            "byte_size": 195,
+            "vector_id_range": [
+                [118, 245, 54, 79, 244, 252, 235, 231, 99, 199, 47, 80, 182, 60, 171, 191, 242, 39, 36, 66, 54, 56, 152, 46, 67, 215, 127, 98, 15, 140, 168, 6],
+                [128, 90, 49, 40, 62, 146, 137, 10, 104, 188, 111, 35, 115, 37, 133, 123, 143, 59, 229, 81, 5, 211, 27, 103, 63, 93, 49, 101, 62, 233, 205, 253]
+            ]
+        },
+        {
+            "chunk_id": "chunk-1",
+            "cid": "bafy-chunk-1",
+            "vector_count": 1,
+            "byte_size": 118,
+            "vector_id_range": [
+                [182, 117, 210, 232, 56, 45, 49, 140, 190, 12, 166, 234, 21, 198, 221, 195, 14, 57, 240, 10, 242, 200, 111, 129, 17, 154, 72, 47, 125, 88, 174, 164],
+                [182, 117, 210, 232, 56, 45, 49, 140, 190, 12, 166, 234, 21, 198, 221, 195, 14, 57, 240, 10, 242, 200, 111, 129, 17, 154, 72, 47, 125, 88, 174, 164]
+            ]
+        }
+    ],
+    "hnsw_structure": {
+        "entry_point": [128, 90, 49, 40, 62, 146, 137, 10, 104, 188, 111, 35, 115, 37, 133, 123, 143, 59, 229, 81, 5, 211, 27, 103, 63, 93, 49, 101, 62, 233, 205, 253],
+        "layers": [
+            { "layer_id": 0, "node_count": 2 },
+            { "layer_id": 1, "node_count": 1 }
+        ],
+        "node_chunk_map": {
+            "vec_805a3128": "chunk-0",
+            "vec_b675d2e8": "chunk-1"
+        }
+    },
+    "ivf_structure": {
+        "centroids": [[8.0, 0.5, 1.0], [1.5, 0.5, 1.0], [5.0, 0.5, 1.0]],
+        "cluster_assignments": { "0": [], "1": ["chunk-0"], "2": [] }
+    }
+}"#;
+
+#[test]
+fn test_v1_manifest_upgrades_cleanly() {
+    let mut manifest = Manifest::from_json(V1_MANIFEST).unwrap();
+    assert_eq!(manifest.version, 1);
+
+    manifest.migrate(1).unwrap();
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+    assert_eq!(manifest.total_vectors, 3);
+    assert_eq!(manifest.get_chunk_ids(), vec!["chunk-0", "chunk-1"]);
+    let id = VectorId::from_string("vec-1");
+    assert_eq!(manifest.chunks[1].vector_id_range, (id.clone(), id));
+    assert_eq!(manifest.chunks[1].cid.as_deref(), Some("bafy-chunk-1"));
+    assert!(manifest.chunks.iter().all(|c| c.content_hash.is_none() && c.checksum.is_none()));
+    let hnsw = manifest.hnsw_structure.as_ref().unwrap();
+    assert_eq!(hnsw.entry_point, VectorId::from_string("vec-2"));
+    assert_eq!(
+        hnsw.get_chunk_for_node(&VectorId::from_string("vec-1")),
+        Some(&"chunk-1".to_string())
+    );
+    let ivf = manifest.ivf_structure.as_ref().unwrap();
+    assert_eq!(ivf.metric, DistanceMetric::Euclidean);
+    assert_eq!(ivf.get_chunks_for_cluster(1), Some(&vec!["chunk-0".to_string()]));
+    assert!(manifest.deleted_vectors.is_none());
+    assert!(manifest.schema.is_none());
+    assert!(manifest.timestamp_chunks.is_none());
+    assert_eq!(manifest.generation, 0);
+
+    // The upgraded manifest round-trips as the current version
+    let reparsed = Manifest::from_json(&manifest.to_json().unwrap()).unwrap();
+    assert_eq!(reparsed.version, MANIFEST_VERSION);
+}
+
+#[test]
+fn test_unsupported_versions_are_not_migrated() {
+    let mut manifest = Manifest::from_json(V1_MANIFEST).unwrap();
+    for version in [0, MANIFEST_VERSION + 1] {
+        assert!(matches!(
+            manifest.migrate(version),
+            Err(ChunkError::InvalidVersion { found, .. }) if found == version
+        ));
+    }
+    assert_eq!(manifest.version, 1);
+
+    let future = V1_MANIFEST.replacen("\"version\": 1", &format!("\"version\": {}", MANIFEST_VERSION + 1), 1);
+    assert!(matches!(
+        Manifest::from_json(&future),
+        Err(ChunkError::InvalidVersion { .. })
+    ));
+}
+
+/// Save an index, then rewrite the save as a v1 one would look: the
+/// manifest stripped back to v1 fields and timestamps in a single file.
+/// Returns the manifest as originally saved
+async fn save_as_v1(storage: &MockS5Storage, index: &HybridIndex, path: &str) -> Manifest {
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(20);
+    let saved = persister.save_index_chunked(index, path).await.unwrap();
+
+    let manifest_path = format!("{}/manifest.json", path);
+    let data = storage.get(&manifest_path).await.unwrap().unwrap();
+    let mut manifest: serde_json::Value = serde_json::from_slice(&data).unwrap();
+    let fields = manifest.as_object_mut().unwrap();
+    fields.insert("version".to_string(), 1.into());
+    for field in ["deleted_vectors", "schema", "timestamp_chunks", "generation"] {
+        fields.remove(field);
+    }
+    for chunk in fields["chunks"].as_array_mut().unwrap() {
+        let chunk = chunk.as_object_mut().unwrap();
+        chunk.remove("content_hash");
+        chunk.remove("checksum");
+    }
+    fields["ivf_structure"].as_object_mut().unwrap().remove("metric");
+    storage
+        .put(&manifest_path, serde_json::to_vec(&manifest).unwrap())
+        .await
+        .unwrap();
+
+    let timestamps = SerializableTimestamps::new(index.get_timestamps().await);
+    storage
+        .put(&format!("{}/timestamps.cbor", path), timestamps.to_cbor().unwrap())
+        .await
+        .unwrap();
+    saved
+}
+
+#[tokio::test]
+async fn test_v1_save_loads() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..60u64 {
+        let id = VectorId::from_string(&format!("vec-{}", i));
+        let vector = vec![i as f32, 0.5, 1.0];
+        if i < 20 {
+            index.insert_with_timestamp(id, vector, old).await.unwrap();
+        } else {
+            index.insert(id, vector).await.unwrap();
+        }
+    }
+
+    let storage = MockS5Storage::new();
+    let saved = save_as_v1(&storage, &index, "legacy").await;
+
+    let persister = HybridPersister::new(storage.clone());
+    let loaded = persister
+        .load_index_chunked("legacy", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.total_vectors(), 60);
+    let results = loaded.search(&[42.0, 0.5, 1.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_string("vec-42"));
+
+    // The upgraded manifest is stored back with its chunk hashes recomputed
+    let data = storage.get("legacy/manifest.json").await.unwrap().unwrap();
+    let upgraded = Manifest::from_json(std::str::from_utf8(&data).unwrap()).unwrap();
+    assert_eq!(upgraded.version, MANIFEST_VERSION);
+    assert_eq!(upgraded.chunks.len(), saved.chunks.len());
+    for (chunk, original) in upgraded.chunks.iter().zip(&saved.chunks) {
+        assert!(chunk.content_hash.is_some() && chunk.checksum.is_some());
+        assert_eq!(chunk.content_hash, original.content_hash);
+        assert_eq!(chunk.checksum, original.checksum);
+    }
+
+    // A save from a newer version is still refused
+    let data = storage.get("legacy/manifest.json").await.unwrap().unwrap();
+    let mut manifest: serde_json::Value = serde_json::from_slice(&data).unwrap();
+    manifest["version"] = (MANIFEST_VERSION + 1).into();
+    storage
+        .put("legacy/manifest.json", serde_json::to_vec(&manifest).unwrap())
+        .await
+        .unwrap();
+    assert!(matches!(
+        persister.load_index_chunked("legacy", HybridConfig::default()).await,
+        Err(PersistenceError::Deserialization(e)) if e.contains("Invalid version")
+    ));
+}
+
+/// Storage that serves reads but refuses every write
+#[derive(Clone)]
+struct ReadOnlyStorage {
+    inner: MockS5Storage,
+}
+
+#[async_trait]
+impl S5Storage for ReadOnlyStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, _data: Vec<u8>) -> Result<(), StorageError> {
+        Err(StorageError::NetworkError(format!("{} is read-only", path)))
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        Err(StorageError::NetworkError(format!("{} is read-only", path)))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+#[tokio::test]
+async fn test_v1_save_loads_from_read_only_storage() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    for i in 0..30u64 {
+        let id = VectorId::from_string(&format!("vec-{}", i));
+        index.insert(id, vec![i as f32, 0.5, 1.0]).await.unwrap();
+    }
+
+    let storage = MockS5Storage::new();
+    save_as_v1(&storage, &index, "legacy").await;
+
+    // The upgraded manifest can't be stored back, but the load succeeds
+    let persister = HybridPersister::new(ReadOnlyStorage {
+        inner: storage.clone(),
+    });
+    let loaded = persister
+        .load_index_chunked("legacy", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.total_vectors(), 30);
+    let results = loaded.search(&[12.0, 0.5, 1.0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_string("vec-12"));
+
+    let data = storage.get("legacy/manifest.json").await.unwrap().unwrap();
+    let stored: serde_json::Value = serde_json::from_slice(&data).unwrap();
+    assert_eq!(stored["version"], 1);
+}
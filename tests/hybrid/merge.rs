@@ -0,0 +1,122 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for merging independently built indices
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+/// Shard holding ids `ids`, the first half recent and the rest a year old
+async fn build_shard(ids: std::ops::Range<u64>) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32 * 10.0)).collect();
+    index.initialize(training).await.unwrap();
+
+    let midpoint = ids.start + (ids.end - ids.start) / 2;
+    for i in ids {
+        let timestamp = if i < midpoint {
+            Utc::now()
+        } else {
+            Utc::now() - Duration::days(365)
+        };
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vector(i as f32), timestamp)
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_merged_search_spans_both_shards() {
+    let first = build_shard(0..20).await;
+    let second = build_shard(100..130).await;
+    second.delete(VectorId::from_u64(129)).await.unwrap();
+    second.set_payload(VectorId::from_u64(100), b"shard-b".to_vec()).await.unwrap();
+
+    let merged = HybridIndex::merge(vec![first, second]).await.unwrap();
+
+    // Recent halves of both shards stay recent; the deleted id stays deleted
+    assert_eq!(merged.active_count().await, 49);
+    assert_eq!(merged.get_stats().recent_vectors, 25);
+
+    for id in [3u64, 15, 105, 120] {
+        let results = merged.search(&vector(id as f32), 1).await.unwrap();
+        assert_eq!(results[0].vector_id, VectorId::from_u64(id));
+        assert_eq!(results[0].distance, 0.0);
+    }
+    let results = merged.search(&vector(129.0), 1).await.unwrap();
+    assert_ne!(results[0].vector_id, VectorId::from_u64(129));
+
+    assert_eq!(merged.get_payload(&VectorId::from_u64(100)).await, Some(b"shard-b".to_vec()));
+    assert!(merged.timestamps.read().await[&VectorId::from_u64(125)] < Utc::now() - Duration::days(300));
+}
+
+#[tokio::test]
+async fn test_merge_rejects_shared_ids() {
+    let first = build_shard(0..10).await;
+    let second = build_shard(5..15).await;
+
+    let result = HybridIndex::merge(vec![first, second]).await;
+    assert!(matches!(result, Err(HybridError::DuplicateVector(_))));
+}
+
+#[tokio::test]
+async fn test_merge_checks_every_shard_before_inserting() {
+    let base = build_shard(0..30).await;
+    let clean = build_shard(100..110).await;
+    let clashing = build_shard(300..305).await;
+    clashing.insert(VectorId::from_u64(100), vector(1.0)).await.unwrap();
+    let handle = base.clone();
+
+    // The clashing shard is merged first; none of its vectors may land in
+    // the base once the clean shard's copy of id 100 is found
+    let result = HybridIndex::merge(vec![base, clean, clashing]).await;
+    assert!(matches!(result, Err(HybridError::DuplicateVector(_))));
+    assert_eq!(handle.timestamps.read().await.len(), 30);
+}
+
+#[tokio::test]
+async fn test_merge_rejects_mismatched_dimensions() {
+    let base = build_shard(0..30).await;
+    let mut other = HybridIndex::new(HybridConfig::default());
+    other.initialize((0..20).map(|i| vec![i as f32; DIM + 2]).collect()).await.unwrap();
+    other.insert(VectorId::from_u64(100), vec![1.0; DIM + 2]).await.unwrap();
+    let handle = base.clone();
+
+    let result = HybridIndex::merge(vec![base, other]).await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: DIM, actual }) if actual == DIM + 2
+    ));
+    assert_eq!(handle.timestamps.read().await.len(), 30);
+}
+
+#[tokio::test]
+async fn test_merge_rejects_mismatched_metrics() {
+    let base = build_shard(0..30).await;
+    let mut config = HybridConfig::default();
+    config.ivf_config.metric = DistanceMetric::Cosine;
+    let mut other = HybridIndex::new(config);
+    other.initialize((1..21).map(|i| vector(i as f32)).collect()).await.unwrap();
+    other.insert(VectorId::from_u64(100), vector(1.0)).await.unwrap();
+
+    let result = HybridIndex::merge(vec![base, other]).await;
+    assert!(matches!(result, Err(HybridError::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn test_merge_requires_an_index() {
+    assert!(HybridIndex::merge(Vec::new()).await.is_err());
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for the per-metric recommended index parameters
+
+use vector_db::core::types::DistanceMetric;
+use vector_db::hnsw::core::HNSWConfig;
+use vector_db::hybrid::HybridConfig;
+use vector_db::ivf::core::IVFConfig;
+
+#[test]
+fn test_n_clusters_scales_with_sqrt_of_dataset_size() {
+    for (n_vectors, expected) in [(100, 40), (10_000, 400), (1_000_000, 4_000), (2_500, 200)] {
+        let config = IVFConfig::default_for(DistanceMetric::Euclidean, n_vectors);
+        assert_eq!(config.n_clusters, expected, "n_vectors = {}", n_vectors);
+        assert!(config.is_valid());
+    }
+
+    // Quadrupling the data doubles the clusters
+    let small = IVFConfig::default_for(DistanceMetric::Cosine, 40_000).n_clusters;
+    let large = IVFConfig::default_for(DistanceMetric::Cosine, 160_000).n_clusters;
+    assert_eq!(large, small * 2);
+}
+
+#[test]
+fn test_tiny_datasets_stay_valid() {
+    for n_vectors in [0, 1, 5, 16] {
+        let config = IVFConfig::default_for(DistanceMetric::Euclidean, n_vectors);
+        assert!(config.n_clusters >= 1);
+        assert!(config.n_clusters <= n_vectors.max(1));
+        assert!(config.is_valid(), "n_vectors = {}", n_vectors);
+    }
+}
+
+#[test]
+fn test_n_probe_by_metric() {
+    let euclidean = IVFConfig::default_for(DistanceMetric::Euclidean, 1_000_000);
+    assert_eq!(euclidean.n_probe, 250);
+    let dot = IVFConfig::default_for(DistanceMetric::DotProduct, 1_000_000);
+    assert_eq!(dot.n_probe, 500);
+    assert_eq!(euclidean.train_size, 160_000);
+}
+
+#[test]
+fn test_hnsw_defaults_by_metric() {
+    assert_eq!(HNSWConfig::default_for(DistanceMetric::Euclidean), HNSWConfig::default());
+
+    let cosine = HNSWConfig::default_for(DistanceMetric::Cosine);
+    assert_eq!(cosine.max_connections, 24);
+    assert_eq!(cosine.max_connections_layer_0, 48);
+    assert_eq!(cosine.ef_construction, 256);
+
+    let dot = HNSWConfig::default_for(DistanceMetric::DotProduct);
+    assert!(dot.max_connections > cosine.max_connections);
+}
+
+#[test]
+fn test_hybrid_config_for_dataset() {
+    let config = HybridConfig::for_dataset(DistanceMetric::Cosine, 10_000);
+    assert_eq!(config.hnsw_config, HNSWConfig::default_for(DistanceMetric::Cosine));
+    assert_eq!(config.ivf_config.n_clusters, 400);
+    assert_eq!(config.recent_threshold, HybridConfig::default().recent_threshold);
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for migration progress reporting
+
+use std::time::Duration;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+async fn create_index(vectors: u64, batch_size: usize) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        migration_batch_size: batch_size,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32, 1.0, 0.5]).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..vectors {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32, 0.0, 1.0])
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_progress_reported_per_batch() {
+    let index = create_index(25, 10).await;
+
+    let mut calls = Vec::new();
+    let migrated = index
+        .migrate_with_progress(Duration::ZERO, |migrated, total| calls.push((migrated, total)))
+        .await
+        .unwrap();
+
+    assert_eq!(migrated, 25);
+    assert_eq!(calls, vec![(10, 25), (20, 25), (25, 25)]);
+    assert_eq!(index.historical_count(), 25);
+}
+
+#[tokio::test]
+async fn test_progress_not_reported_without_candidates() {
+    let index = create_index(5, 10).await;
+
+    let mut calls = 0;
+    let migrated = index
+        .migrate_with_progress(Duration::from_secs(3600), |_, _| calls += 1)
+        .await
+        .unwrap();
+
+    assert_eq!(migrated, 0);
+    assert_eq!(calls, 0);
+}
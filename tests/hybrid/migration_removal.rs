@@ -0,0 +1,127 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests that migration moves vectors out of the recent index instead of
+//! copying them
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridSearchConfig};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    for i in 0..10 {
+        index.insert(VectorId::from_u64(i), vector(i as f32)).await.unwrap();
+    }
+    index
+}
+
+async fn search_ids(index: &HybridIndex, query: &[f32]) -> Vec<VectorId> {
+    let config = HybridSearchConfig {
+        k: 20,
+        ..HybridSearchConfig::default()
+    };
+    index
+        .search_with_config(query, config)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.vector_id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_specific_migration_removes_from_recent() {
+    let index = create_index().await;
+    let ids: Vec<_> = (0..4).map(VectorId::from_u64).collect();
+
+    let result = index.migrate_specific_vectors(&ids).await.unwrap();
+
+    assert_eq!(result.vectors_migrated, 4);
+    let recent = index.get_recent_index().await;
+    assert_eq!(recent.node_count(), 6);
+    assert!(ids.iter().all(|id| recent.get_node(id).is_none()));
+    drop(recent);
+
+    let stats = index.get_statistics().await;
+    assert_eq!(stats.recent_vectors, 6);
+    assert_eq!(stats.historical_vectors, 4);
+    assert_eq!(stats.total_vectors, 10);
+    assert_eq!(index.recent_count(), 6);
+    assert_eq!(index.historical_count(), 4);
+}
+
+#[tokio::test]
+async fn test_threshold_migration_removes_from_recent() {
+    let index = create_index().await;
+    let old = VectorId::from_u64(100);
+    index
+        .insert_with_timestamp(old.clone(), vector(3.5), Utc::now() - Duration::minutes(5))
+        .await
+        .unwrap();
+
+    let migrated = index
+        .migrate_with_threshold(std::time::Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    assert_eq!(migrated, 1);
+    let stats = index.get_statistics().await;
+    assert_eq!(stats.recent_vectors, 10);
+    assert_eq!(stats.historical_vectors, 1);
+
+    // Found once, from the historical index
+    let found = search_ids(&index, &vector(3.5)).await;
+    assert_eq!(found.iter().filter(|id| **id == old).count(), 1);
+    assert!(index.get_recent_index().await.get_node(&old).is_none());
+}
+
+#[tokio::test]
+async fn test_migration_twice_moves_nothing_more() {
+    let index = create_index().await;
+    let ids: Vec<_> = (0..3).map(VectorId::from_u64).collect();
+
+    index.migrate_specific_vectors(&ids).await.unwrap();
+    let again = index.migrate_specific_vectors(&ids).await.unwrap();
+
+    assert_eq!(again.vectors_migrated, 0);
+    assert_eq!(index.historical_count(), 3);
+    assert_eq!(index.recent_count(), 7);
+}
+
+#[tokio::test]
+async fn test_migrated_deleted_vector_stays_deleted() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(5);
+    index.delete(id.clone()).await.unwrap();
+
+    index.migrate_specific_vectors(&[id.clone()]).await.unwrap();
+
+    assert!(index.is_deleted(&id).await);
+    assert!(!search_ids(&index, &vector(5.0)).await.contains(&id));
+}
+
+#[tokio::test]
+async fn test_remaining_recent_vectors_still_searchable() {
+    let index = create_index().await;
+    let ids: Vec<_> = (0..10).step_by(2).map(VectorId::from_u64).collect();
+
+    index.migrate_specific_vectors(&ids).await.unwrap();
+
+    for i in 0..10 {
+        let found = search_ids(&index, &vector(i as f32)).await;
+        assert_eq!(found[0], VectorId::from_u64(i));
+    }
+}
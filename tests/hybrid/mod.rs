@@ -1,8 +1,57 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+mod access_stats;
+mod adaptive_ef;
+mod append_chunk;
+mod archive;
+mod auto_initialize;
+mod auto_migration;
+mod auto_retrain;
+mod chunk_checksum;
+mod chunk_mapping;
+mod cold_queries;
+mod backup_restore;
+mod backup_retention;
+mod compaction_scheduler;
 mod core;
+mod cosine_zero_vectors;
+mod delete_placement;
 mod deletion;
+mod dimension_adapter;
+mod dimension_conflicts;
+mod empty_sub_index;
+mod evicted_vectors;
+mod exact_search;
+mod explain_distance;
+mod filter_cache;
+mod filter_oversample;
+mod get_vector;
+mod incremental_save;
+mod initialize_dimension;
+mod lock_contention;
+mod insert_target;
 mod deletion_persistence;
 mod maintenance;
+mod manifest_diff;
+mod manifest_migration;
+mod merge;
+mod metric_defaults;
+mod migration_progress;
+mod migration_removal;
+mod nan_distances;
+mod nearest;
+mod negative_examples;
+mod on_duplicate;
+mod online_retrain;
+mod payloads;
+mod point_in_time;
+mod predicate_search;
+mod query_time;
+mod query_validation;
+mod rebalance;
+mod rerank;
 mod search_integration;
+mod search_offset;
+mod timestamp_chunks;
+mod update;
@@ -0,0 +1,91 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::{SearchResult, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridSearchConfig, NanDistancePolicy};
+
+async fn create_index_with_nan_vector() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..6u64 {
+        // Half recent (HNSW), half historical (IVF)
+        let timestamp = if i % 2 == 0 { Utc::now() } else { old };
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32, 0.0], timestamp)
+            .await
+            .unwrap();
+    }
+
+    // Insert does no value validation, so a poisoned vector gets stored
+    index
+        .insert_with_timestamp(VectorId::from_u64(99), vec![f32::NAN, 0.0], old)
+        .await
+        .unwrap();
+    index
+}
+
+#[tokio::test]
+async fn test_nan_distance_ranked_last() {
+    let index = create_index_with_nan_vector().await;
+
+    let results = index
+        .search_with_config(
+            &[0.0, 0.0],
+            HybridSearchConfig {
+                k: 10,
+                ivf_n_probe: 3,
+                ..HybridSearchConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let last = results.last().unwrap();
+    assert_eq!(last.vector_id, VectorId::from_u64(99));
+    assert!(last.distance.is_nan());
+    let ranked: Vec<u64> = results[..results.len() - 1]
+        .iter()
+        .map(|r| r.vector_id.as_u64().unwrap())
+        .collect();
+    assert_eq!(ranked, vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn test_nan_distance_dropped() {
+    let index = create_index_with_nan_vector().await;
+
+    let results = index
+        .search_with_config(
+            &[0.0, 0.0],
+            HybridSearchConfig {
+                k: 10,
+                ivf_n_probe: 3,
+                nan_distances: NanDistancePolicy::Drop,
+                ..HybridSearchConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 6);
+    assert!(results.iter().all(|r| !r.distance.is_nan()));
+}
+
+#[test]
+fn test_cmp_distance_orders_nan_last() {
+    let mut results = [
+        SearchResult::new(VectorId::from_u64(1), f32::NAN, None),
+        SearchResult::new(VectorId::from_u64(2), 2.0, None),
+        SearchResult::new(VectorId::from_u64(3), -f32::NAN, None),
+        SearchResult::new(VectorId::from_u64(4), 0.5, None),
+    ];
+    results.sort_by(SearchResult::cmp_distance);
+
+    assert_eq!(results[0].distance, 0.5);
+    assert_eq!(results[1].distance, 2.0);
+    assert!(results[2].distance.is_nan() && results[3].distance.is_nan());
+}
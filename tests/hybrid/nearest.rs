@@ -0,0 +1,94 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+const DIM: usize = 8;
+
+fn random_vector(rng: &mut StdRng) -> Vec<f32> {
+    (0..DIM).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+#[tokio::test]
+async fn test_nearest_matches_search_top_result() {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let training: Vec<Vec<f32>> = (0..50).map(|_| random_vector(&mut rng)).collect();
+    index.initialize(training).await.unwrap();
+
+    // Half recent (HNSW), half historical (IVF)
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..400u64 {
+        let vector = random_vector(&mut rng);
+        if i % 2 == 0 {
+            index.insert(VectorId::from_u64(i), vector).await.unwrap();
+        } else {
+            index
+                .insert_with_timestamp(VectorId::from_u64(i), vector, old)
+                .await
+                .unwrap();
+        }
+    }
+    index.delete(VectorId::from_u64(10)).await.unwrap();
+    index.delete(VectorId::from_u64(11)).await.unwrap();
+
+    for _ in 0..200 {
+        let query = random_vector(&mut rng);
+        let nearest = index.nearest(&query).await.unwrap().unwrap();
+        let top = index.search(&query, 1).await.unwrap().remove(0);
+
+        assert_eq!(nearest.vector_id, top.vector_id);
+        assert_eq!(nearest.distance, top.distance);
+    }
+}
+
+#[tokio::test]
+async fn test_nearest_on_empty_index() {
+    let index = HybridIndex::new(HybridConfig::default());
+    assert!(index.nearest(&[1.0, 2.0]).await.unwrap().is_none());
+
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index.initialize(vec![]).await.unwrap();
+    assert!(index.nearest(&[1.0, 2.0]).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_nearest_in_hnsw_only_mode() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index.initialize(vec![]).await.unwrap();
+    for i in 0..5u64 {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32, 0.0])
+            .await
+            .unwrap();
+    }
+
+    let nearest = index.nearest(&[3.2, 0.0]).await.unwrap().unwrap();
+    assert_eq!(nearest.vector_id, VectorId::from_u64(3));
+}
+
+#[tokio::test]
+async fn test_nearest_validates_query() {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    index.initialize(vec![]).await.unwrap();
+    index
+        .insert(VectorId::from_u64(1), vec![1.0, 0.0])
+        .await
+        .unwrap();
+
+    let wrong_dim = index.nearest(&[1.0, 0.0, 0.0]).await;
+    assert!(matches!(
+        wrong_dim,
+        Err(HybridError::DimensionMismatch { expected: 2, actual: 3 })
+    ));
+    assert!(index.nearest(&[]).await.is_err());
+}
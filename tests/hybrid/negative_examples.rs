@@ -0,0 +1,90 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+async fn create_index(vectors: &[(u64, [f32; 2])]) -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32 - 5.0, 0.5]).collect();
+    index.initialize(training).await.unwrap();
+
+    for (id, vector) in vectors {
+        index
+            .insert(VectorId::from_u64(*id), vector.to_vec())
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_candidate_near_negative_is_demoted() {
+    // 1 is slightly closer to the query, but sits next to the negative example
+    let index = create_index(&[(1, [1.0, 0.0]), (2, [-1.05, 0.0]), (3, [0.0, 5.0])]).await;
+    let positive = [0.0, 0.0];
+    let negatives = vec![vec![2.0, 0.0]];
+
+    let plain = index.search(&positive, 2).await.unwrap();
+    assert_eq!(plain[0].vector_id, VectorId::from_u64(1));
+
+    let refined = index
+        .search_with_negatives(&positive, &negatives, 2, 1.0)
+        .await
+        .unwrap();
+    assert_eq!(refined.len(), 2);
+    assert_eq!(refined[0].vector_id, VectorId::from_u64(2));
+    assert_eq!(refined[1].vector_id, VectorId::from_u64(1));
+    assert!(refined[0].distance <= refined[1].distance);
+}
+
+#[tokio::test]
+async fn test_zero_weight_matches_plain_search() {
+    let index = create_index(&[(1, [1.0, 0.0]), (2, [-1.05, 0.0]), (3, [0.0, 5.0])]).await;
+    let positive = [0.0, 0.0];
+
+    let plain = index.search(&positive, 3).await.unwrap();
+    let refined = index
+        .search_with_negatives(&positive, &[vec![2.0, 0.0]], 3, 0.0)
+        .await
+        .unwrap();
+
+    let ids = |results: &[vector_db::core::types::SearchResult]| {
+        results.iter().map(|r| r.vector_id.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&plain), ids(&refined));
+}
+
+#[tokio::test]
+async fn test_negative_dimension_mismatch() {
+    let index = create_index(&[(1, [1.0, 0.0])]).await;
+    let result = index
+        .search_with_negatives(&[0.0, 0.0], &[vec![1.0, 2.0, 3.0]], 1, 1.0)
+        .await;
+    assert!(matches!(
+        result,
+        Err(HybridError::DimensionMismatch { expected: 2, actual: 3 })
+    ));
+}
+
+#[tokio::test]
+async fn test_negative_similarity_uses_index_metric() {
+    let mut config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::for_dataset(DistanceMetric::Cosine, 100)
+    };
+    config.ivf_config.n_clusters = 2;
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (1..11).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.3]).await.unwrap();
+    index.insert(VectorId::from_u64(2), vec![1.0, -0.32]).await.unwrap();
+
+    // The negative is far from both under L2, but points the same way as 1
+    let refined = index
+        .search_with_negatives(&[1.0, 0.0], &[vec![10.0, 10.0]], 2, 1.0)
+        .await
+        .unwrap();
+    assert_eq!(refined[0].vector_id, VectorId::from_u64(2));
+    assert_eq!(refined[1].vector_id, VectorId::from_u64(1));
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for duplicate-id handling on insert
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex, InsertOutcome, OnDuplicate};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..10u64 {
+        index.insert(VectorId::from_u64(i), vector(i as f32)).await.unwrap();
+    }
+    index
+}
+
+/// Distance from `query` to the stored vector `id`, via exact search
+async fn distance_to(index: &HybridIndex, id: u64, query: &[f32]) -> f32 {
+    index
+        .exact_search(query, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.vector_id == VectorId::from_u64(id))
+        .map(|r| r.distance)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_skip_keeps_stored_vector() {
+    let index = create_index().await;
+
+    let outcome = index
+        .insert_with_policy(VectorId::from_u64(3), vector(50.0), Utc::now(), OnDuplicate::Skip)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, InsertOutcome::Skipped);
+    assert_eq!(distance_to(&index, 3, &vector(3.0)).await, 0.0);
+    assert_eq!(index.total_vectors(), 10);
+}
+
+#[tokio::test]
+async fn test_error_rejects_duplicate() {
+    let index = create_index().await;
+
+    let result = index
+        .insert_with_policy(VectorId::from_u64(3), vector(50.0), Utc::now(), OnDuplicate::Error)
+        .await;
+    assert!(matches!(result, Err(HybridError::DuplicateVector(_))));
+
+    // Same as the plain insert path
+    let result = index.insert(VectorId::from_u64(3), vector(50.0)).await;
+    assert!(matches!(result, Err(HybridError::DuplicateVector(_))));
+    assert_eq!(distance_to(&index, 3, &vector(3.0)).await, 0.0);
+}
+
+#[tokio::test]
+async fn test_update_replaces_stored_vector() {
+    let index = create_index().await;
+
+    let outcome = index
+        .insert_with_policy(VectorId::from_u64(3), vector(50.0), Utc::now(), OnDuplicate::Update)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, InsertOutcome::Updated);
+    assert_eq!(distance_to(&index, 3, &vector(50.0)).await, 0.0);
+    assert_eq!(index.total_vectors(), 10);
+    let top = index.search(&vector(3.0), 1).await.unwrap();
+    assert_ne!(top[0].vector_id, VectorId::from_u64(3));
+}
+
+#[tokio::test]
+async fn test_update_moves_vector_between_sub_indices() {
+    let index = create_index().await;
+    let old = Utc::now() - Duration::days(30);
+
+    index
+        .insert_with_policy(VectorId::from_u64(3), vector(50.0), old, OnDuplicate::Update)
+        .await
+        .unwrap();
+
+    assert_eq!(index.recent_count(), 9);
+    assert_eq!(index.historical_count(), 1);
+    assert_eq!(distance_to(&index, 3, &vector(50.0)).await, 0.0);
+}
+
+#[tokio::test]
+async fn test_update_revives_deleted_vector() {
+    let index = create_index().await;
+    index.delete(VectorId::from_u64(3)).await.unwrap();
+
+    index
+        .insert_with_policy(VectorId::from_u64(3), vector(50.0), Utc::now(), OnDuplicate::Update)
+        .await
+        .unwrap();
+
+    assert!(!index.is_deleted(&VectorId::from_u64(3)).await);
+    assert_eq!(distance_to(&index, 3, &vector(50.0)).await, 0.0);
+}
+
+#[tokio::test]
+async fn test_update_with_wrong_dimension_keeps_stored_vector() {
+    let index = create_index().await;
+
+    let result = index
+        .insert_with_policy(VectorId::from_u64(3), vec![1.0; DIM + 1], Utc::now(), OnDuplicate::Update)
+        .await;
+
+    assert!(matches!(result, Err(HybridError::DimensionMismatch { .. })));
+    assert_eq!(distance_to(&index, 3, &vector(3.0)).await, 0.0);
+}
@@ -0,0 +1,277 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+use vector_db::ivf::core::IVFConfig;
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+const DIM: usize = 16;
+
+fn vector_for(i: u64) -> Vec<f32> {
+    (0..DIM).map(|j| ((i as usize * 7 + j) % 50) as f32).collect()
+}
+
+async fn create_index(count: u64) -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(vector_for).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..count {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vector_for(i), old)
+            .await
+            .unwrap();
+    }
+    index
+}
+
+fn new_config() -> IVFConfig {
+    IVFConfig {
+        n_clusters: 8,
+        n_probe: 8,
+        train_size: 100,
+        max_iterations: 25,
+        seed: Some(7),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_searches_succeed_during_retrain() {
+    let index = Arc::new(create_index(2000).await);
+    let done = Arc::new(AtomicBool::new(false));
+    let searches = Arc::new(AtomicUsize::new(0));
+
+    let searcher = {
+        let index = index.clone();
+        let done = done.clone();
+        let searches = searches.clone();
+        tokio::spawn(async move {
+            while !done.load(Ordering::SeqCst) {
+                let results = index.search(&vector_for(5), 1).await.unwrap();
+                assert_eq!(results.len(), 1);
+                searches.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+
+    let result = index.retrain_historical(new_config()).await.unwrap();
+    done.store(true, Ordering::SeqCst);
+    searcher.await.unwrap();
+
+    assert_eq!(result.old_clusters, 3);
+    assert_eq!(result.new_clusters, 8);
+    assert_eq!(result.vectors_reassigned, 2000);
+    assert!(searches.load(Ordering::SeqCst) > 0);
+
+    let historical = index.get_historical_index().await;
+    assert_eq!(historical.config().n_clusters, 8);
+}
+
+#[tokio::test]
+async fn test_new_data_visible_after_retrain() {
+    let index = create_index(50).await;
+    index.retrain_historical(new_config()).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    let id = VectorId::from_u64(999);
+    let vector = vec![1000.0; DIM];
+    index
+        .insert_with_timestamp(id.clone(), vector.clone(), old)
+        .await
+        .unwrap();
+
+    let results = index.search(&vector, 1).await.unwrap();
+    assert_eq!(results[0].vector_id, id);
+}
+
+#[tokio::test]
+async fn test_retrain_keeps_deletions() {
+    let index = create_index(50).await;
+    index.delete(VectorId::from_u64(5)).await.unwrap();
+
+    index.retrain_historical(new_config()).await.unwrap();
+
+    assert!(index.is_deleted(&VectorId::from_u64(5)).await);
+    let results = index.search(&vector_for(5), 1).await.unwrap();
+    assert_ne!(results[0].vector_id, VectorId::from_u64(5));
+}
+
+#[tokio::test]
+async fn test_sync_with_catches_up_on_concurrent_changes() {
+    use vector_db::ivf::core::IVFIndex;
+
+    let mut current = IVFIndex::new(IVFConfig {
+        n_clusters: 3,
+        n_probe: 3,
+        train_size: 20,
+        max_iterations: 10,
+        seed: Some(1),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    });
+    let training: Vec<Vec<f32>> = (0..20).map(vector_for).collect();
+    current.train(&training).unwrap();
+    for i in 0..20 {
+        current.insert(VectorId::from_u64(i), vector_for(i)).unwrap();
+    }
+
+    let (mut retrained, _) =
+        IVFIndex::build_from_vectors(new_config(), None, current.stored_vectors().await.unwrap())
+            .unwrap();
+
+    // Changes that land while the retrain is running
+    current.insert(VectorId::from_u64(100), vector_for(100)).unwrap();
+    current.mark_deleted(&VectorId::from_u64(3)).unwrap();
+
+    let changed = retrained.sync_with(&current).await.unwrap();
+    assert_eq!(changed, 1);
+    assert_eq!(retrained.total_vectors(), 21);
+    assert!(retrained.get_vector_by_id(&VectorId::from_u64(100)).is_some());
+    assert!(retrained.is_deleted(&VectorId::from_u64(3)));
+}
+
+#[tokio::test]
+async fn test_sync_with_copies_vectors_updated_in_place() {
+    use vector_db::ivf::core::IVFIndex;
+
+    let mut current = IVFIndex::new(new_config());
+    let training: Vec<Vec<f32>> = (0..100).map(vector_for).collect();
+    current.train(&training).unwrap();
+    for i in 0..100 {
+        current.insert(VectorId::from_u64(i), vector_for(i)).unwrap();
+    }
+
+    let (mut retrained, _) =
+        IVFIndex::build_from_vectors(new_config(), None, current.stored_vectors().await.unwrap())
+            .unwrap();
+
+    // An in-place update keeps the id but replaces the vector
+    let id = VectorId::from_u64(5);
+    current.remove(&id).unwrap();
+    current.insert(id.clone(), vector_for(500)).unwrap();
+
+    let changed = retrained.sync_with(&current).await.unwrap();
+    assert_eq!(changed, 1);
+    assert_eq!(retrained.total_vectors(), 100);
+    assert_eq!(retrained.get_vector_by_id(&id), Some(vector_for(500)));
+}
+
+#[tokio::test]
+async fn test_sync_with_removes_product_quantized_vectors() {
+    use vector_db::ivf::core::IVFIndex;
+    use vector_db::ivf::pq::PQConfig;
+
+    let config = IVFConfig {
+        pq: Some(PQConfig {
+            n_subvectors: 4,
+            n_centroids_per_subvector: 16,
+        }),
+        ..new_config()
+    };
+    let mut current = IVFIndex::new(config.clone());
+    let training: Vec<Vec<f32>> = (0..200).map(vector_for).collect();
+    current.train(&training).unwrap();
+    for i in 0..200 {
+        current.insert(VectorId::from_u64(i), vector_for(i)).unwrap();
+    }
+
+    let (mut retrained, _) =
+        IVFIndex::build_from_vectors(config, None, current.stored_vectors().await.unwrap())
+            .unwrap();
+
+    current.remove(&VectorId::from_u64(7)).unwrap();
+
+    let changed = retrained.sync_with(&current).await.unwrap();
+    assert_eq!(changed, 1);
+    assert_eq!(retrained.total_vectors(), 199);
+    let stored: usize = retrained
+        .get_all_inverted_lists()
+        .values()
+        .map(|list| list.codes.len())
+        .sum();
+    assert_eq!(stored, 199);
+    assert!(retrained.get_vector_by_id(&VectorId::from_u64(7)).is_none());
+}
+
+#[tokio::test]
+async fn test_retrain_keeps_chunk_loaded_vectors() {
+    use vector_db::core::chunk::VectorChunk;
+    use vector_db::core::chunk_cache::ChunkCache;
+    use vector_db::core::storage::{MockS5Storage, S5Storage};
+    use vector_db::ivf::core::IVFIndex;
+    use vector_db::storage::chunk_loader::ChunkLoader;
+
+    // `vector_for` repeats every 50 ids; keep each vector distinct
+    let vector = |i: u64| -> Vec<f32> { vector_for(i).iter().map(|x| x + i as f32).collect() };
+    let storage = Arc::new(MockS5Storage::new());
+    let loader = Arc::new(ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(100))));
+
+    let mut chunk_paths = Vec::new();
+    for chunk_idx in 0..2u64 {
+        let mut chunk = VectorChunk::new(
+            format!("chunk-{}", chunk_idx),
+            chunk_idx as usize * 50,
+            chunk_idx as usize * 50 + 49,
+        );
+        for i in chunk_idx * 50..chunk_idx * 50 + 50 {
+            chunk.add_vector(VectorId::from_u64(i), vector(i));
+        }
+        let path = format!("retrain/chunks/chunk-{}.cbor", chunk_idx);
+        storage.put(&path, chunk.to_cbor().unwrap()).await.unwrap();
+        chunk_paths.push(path);
+    }
+
+    let mut warm = IVFIndex::with_chunk_loader(new_config(), Some(loader.clone()));
+    let training: Vec<Vec<f32>> = (0..100).map(vector).collect();
+    warm.train(&training).unwrap();
+    for i in 0..100 {
+        warm.insert_with_chunk(
+            VectorId::from_u64(i),
+            vector(i),
+            Some(chunk_paths[i as usize / 50].clone()),
+        )
+        .unwrap();
+    }
+    // Without the warm vector cache, every vector must come from its chunk
+    let mut index = IVFIndex::with_chunk_loader(new_config(), Some(loader));
+    index.set_trained(warm.get_centroids().to_vec(), DIM);
+    index.set_inverted_lists(warm.get_all_inverted_lists().clone());
+
+    let result = index
+        .retrain_with_config(IVFConfig {
+            n_clusters: 4,
+            n_probe: 4,
+            ..new_config()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result.vectors_reassigned, 100);
+    assert_eq!(index.total_vectors(), 100);
+    let chunk_refs: usize = index
+        .get_all_inverted_lists()
+        .values()
+        .map(|list| list.chunk_refs.len())
+        .sum();
+    assert_eq!(chunk_refs, 100);
+    for i in [0, 42, 77] {
+        let results = index.search(&vector(i), 1).await.unwrap();
+        assert_eq!(results[0].vector_id, VectorId::from_u64(i));
+        assert!(results[0].distance < 1e-4);
+    }
+}
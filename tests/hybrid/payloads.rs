@@ -0,0 +1,101 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::storage::MockS5Storage;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex, HybridPersister, SearchConfig};
+
+async fn create_index() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+fn binary_payload() -> Vec<u8> {
+    vec![0x00, 0xff, 0x10, 0x80, 0x00, 0x7f]
+}
+
+#[tokio::test]
+async fn test_payload_returned_with_search_result() {
+    let index = create_index().await;
+    index
+        .insert_with_payload(VectorId::from_u64(1), vec![1.0, 0.0], binary_payload())
+        .await
+        .unwrap();
+    index.insert(VectorId::from_u64(2), vec![5.0, 5.0]).await.unwrap();
+
+    let config = SearchConfig {
+        k: 2,
+        ..SearchConfig::default()
+    };
+    let results = index.search_with_payloads(&[1.0, 0.0], config).await.unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].result.vector_id, VectorId::from_u64(1));
+    assert_eq!(results[0].payload, Some(binary_payload()));
+    assert_eq!(results[1].payload, None);
+}
+
+#[tokio::test]
+async fn test_set_payload_and_delete() {
+    let index = create_index().await;
+    let id = VectorId::from_u64(1);
+    index.insert(id.clone(), vec![1.0, 0.0]).await.unwrap();
+
+    index.set_payload(id.clone(), b"thumb.png".to_vec()).await.unwrap();
+    assert_eq!(index.get_payload(&id).await, Some(b"thumb.png".to_vec()));
+    assert!(matches!(
+        index.set_payload(VectorId::from_u64(99), vec![1]).await,
+        Err(HybridError::VectorNotFound(id)) if id == VectorId::from_u64(99)
+    ));
+
+    index.delete(id.clone()).await.unwrap();
+    assert_eq!(index.get_payload(&id).await, None);
+}
+
+#[tokio::test]
+async fn test_payloads_survive_chunked_save_and_load() {
+    let index = create_index().await;
+    for i in 0..25u64 {
+        let vector = vec![i as f32 * 0.3, 1.0];
+        if i % 2 == 0 {
+            index
+                .insert_with_payload(VectorId::from_u64(i), vector, vec![i as u8; 4])
+                .await
+                .unwrap();
+        } else {
+            index.insert(VectorId::from_u64(i), vector).await.unwrap();
+        }
+    }
+
+    let storage = MockS5Storage::new();
+    let persister = HybridPersister::new(storage).with_chunk_size(10);
+    persister.save_index_chunked(&index, "payloads").await.unwrap();
+
+    let loaded = persister
+        .load_index_chunked("payloads", HybridConfig::default())
+        .await
+        .unwrap();
+    for i in 0..25u64 {
+        let expected = (i % 2 == 0).then(|| vec![i as u8; 4]);
+        assert_eq!(loaded.get_payload(&VectorId::from_u64(i)).await, expected);
+    }
+}
+
+#[test]
+fn test_chunk_without_payloads_keeps_hash() {
+    let mut chunk = VectorChunk::new("chunk-0".to_string(), 0, 0);
+    chunk.add_vector(VectorId::from_u64(1), vec![1.0, 2.0]);
+    let hash = chunk.content_hash();
+
+    let decoded = VectorChunk::from_cbor(&chunk.to_cbor().unwrap()).unwrap();
+    assert!(decoded.payloads.is_empty());
+    assert_eq!(decoded.content_hash(), hash);
+
+    chunk.set_payload(VectorId::from_u64(1), binary_payload());
+    assert_ne!(chunk.content_hash(), hash);
+    let decoded = VectorChunk::from_cbor(&chunk.to_cbor().unwrap()).unwrap();
+    assert_eq!(decoded.get_payload(&VectorId::from_u64(1)), Some(&binary_payload()));
+}
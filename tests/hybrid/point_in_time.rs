@@ -0,0 +1,113 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.0]).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+fn ids(results: &[vector_db::core::types::SearchResult]) -> Vec<u64> {
+    results.iter().map(|r| r.vector_id.as_u64().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn test_as_of_before_deletion_returns_deleted_vector() {
+    let index = create_index().await;
+    let inserted_at = Utc::now() - Duration::minutes(5);
+    for i in 0..5u64 {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32, 0.0], inserted_at)
+            .await
+            .unwrap();
+    }
+
+    let before_delete = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    index.delete(VectorId::from_u64(2)).await.unwrap();
+
+    let current = index.search(&[2.0, 0.0], 1).await.unwrap();
+    assert_ne!(ids(&current), vec![2]);
+
+    let past = index.search_as_of(&[2.0, 0.0], 1, before_delete).await.unwrap();
+    assert_eq!(ids(&past), vec![2]);
+
+    // After the deletion the vector is gone again
+    let after = index.search_as_of(&[2.0, 0.0], 1, Utc::now()).await.unwrap();
+    assert_ne!(ids(&after), vec![2]);
+}
+
+#[tokio::test]
+async fn test_as_of_sees_historical_deletions() {
+    let index = create_index().await;
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..5u64 {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32, 0.0], old)
+            .await
+            .unwrap();
+    }
+
+    let before_delete = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    index.delete(VectorId::from_u64(3)).await.unwrap();
+
+    let past = index.search_as_of(&[3.0, 0.0], 1, before_delete).await.unwrap();
+    assert_eq!(ids(&past), vec![3]);
+
+    let now = index.search_as_of(&[3.0, 0.0], 1, Utc::now()).await.unwrap();
+    assert_ne!(ids(&now), vec![3]);
+}
+
+#[tokio::test]
+async fn test_as_of_excludes_vectors_inserted_later() {
+    let index = create_index().await;
+    let earlier = Utc::now() - Duration::hours(2);
+    index
+        .insert_with_timestamp(VectorId::from_u64(1), vec![1.0, 0.0], earlier)
+        .await
+        .unwrap();
+    index.insert(VectorId::from_u64(2), vec![2.0, 0.0]).await.unwrap();
+
+    let as_of = Utc::now() - Duration::hours(1);
+    let results = index.search_as_of(&[2.0, 0.0], 5, as_of).await.unwrap();
+    assert_eq!(ids(&results), vec![1]);
+}
+
+#[tokio::test]
+async fn test_as_of_keeps_deletion_time_across_migration() {
+    let index = create_index().await;
+    let inserted_at = Utc::now() - Duration::minutes(5);
+    for i in 0..5u64 {
+        index
+            .insert_with_timestamp(VectorId::from_u64(i), vec![i as f32, 0.0], inserted_at)
+            .await
+            .unwrap();
+    }
+
+    let before_delete = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    index.delete(VectorId::from_u64(2)).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let between = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+    let ids_to_move: Vec<VectorId> = (0..5u64).map(VectorId::from_u64).collect();
+    index.migrate_specific_vectors(&ids_to_move).await.unwrap();
+    assert_eq!(index.historical_count(), 5);
+
+    // Still visible before the deletion, and gone between deletion and migration
+    let past = index.search_as_of(&[2.0, 0.0], 1, before_delete).await.unwrap();
+    assert_eq!(ids(&past), vec![2]);
+    let after = index.search_as_of(&[2.0, 0.0], 1, between).await.unwrap();
+    assert_ne!(ids(&after), vec![2]);
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::collections::HashSet;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::core::{HybridConfig, HybridIndex};
+
+async fn create_index() -> (HybridIndex, Vec<VectorId>) {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, (i % 3) as f32]).collect();
+    index.initialize(training).await.unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..20 {
+        let id = VectorId::from_string(&format!("item_{}", i));
+        index.insert(id.clone(), vec![i as f32 * 0.1, 0.0]).await.unwrap();
+        ids.push(id);
+    }
+
+    (index, ids)
+}
+
+#[tokio::test]
+async fn test_predicate_excludes_odd_ids() {
+    let (index, ids) = create_index().await;
+    let even: HashSet<VectorId> = ids.iter().step_by(2).cloned().collect();
+
+    let results = index
+        .search_with_predicate(&[0.0, 0.0], 5, |id| even.contains(id))
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 5);
+    assert!(results.iter().all(|r| even.contains(&r.vector_id)));
+    // Nearest even ids in distance order
+    let expected: Vec<VectorId> = ids.iter().step_by(2).take(5).cloned().collect();
+    let returned: Vec<VectorId> = results.iter().map(|r| r.vector_id.clone()).collect();
+    assert_eq!(returned, expected);
+}
+
+#[tokio::test]
+async fn test_predicate_rejecting_everything_returns_empty() {
+    let (index, _) = create_index().await;
+
+    let results = index
+        .search_with_predicate(&[0.0, 0.0], 5, |_| false)
+        .await
+        .unwrap();
+
+    assert!(results.is_empty());
+}
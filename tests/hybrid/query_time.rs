@@ -0,0 +1,44 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::time::Instant;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+
+async fn create_index() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0, 0.5]).collect();
+    index.initialize(training).await.unwrap();
+    for i in 0..50u64 {
+        index
+            .insert(VectorId::from_u64(i), vec![i as f32, (i % 7) as f32, 0.5])
+            .await
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_avg_query_time_starts_at_zero() {
+    let index = create_index().await;
+
+    assert_eq!(index.get_statistics().await.avg_query_time_ms, 0.0);
+    assert_eq!(index.get_stats().avg_query_time_ms, 0.0);
+}
+
+#[tokio::test]
+async fn test_avg_query_time_reflects_searches() {
+    let index = create_index().await;
+
+    let started = Instant::now();
+    for i in 0..20 {
+        index.search(&[i as f32, 2.0, 0.5], 5).await.unwrap();
+    }
+    let wall_ms = started.elapsed().as_secs_f32() * 1000.0;
+
+    let avg = index.get_statistics().await.avg_query_time_ms;
+    assert!(avg > 0.0);
+    // The average of 20 searches can't exceed their combined wall time
+    assert!(avg <= wall_ms / 20.0 + f32::EPSILON, "avg {} ms, wall {} ms", avg, wall_ms);
+    assert_eq!(index.get_stats().avg_query_time_ms, avg);
+}
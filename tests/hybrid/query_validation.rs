@@ -0,0 +1,114 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for empty and wrong-length query vectors through the core API
+
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+use vector_db::core::metadata_filter::MetadataFilter;
+use vector_db::core::types::VectorId;
+use vector_db::core::types::SearchResult;
+use vector_db::hybrid::{EmptyQueryPolicy, HybridConfig, HybridError, HybridIndex, SearchConfig};
+
+async fn create_index(config: HybridConfig) -> HybridIndex {
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..20u64 {
+        let vector = vec![i as f32, 1.0, 0.0];
+        if i % 2 == 0 {
+            index.insert(VectorId::from_u64(i), vector).await.unwrap();
+        } else {
+            index
+                .insert_with_timestamp(VectorId::from_u64(i), vector, old)
+                .await
+                .unwrap();
+        }
+    }
+    index
+}
+
+fn assert_mismatch(result: Result<Vec<SearchResult>, HybridError>, actual: usize) {
+    match result {
+        Err(HybridError::DimensionMismatch { expected: 3, actual: got }) => assert_eq!(got, actual),
+        other => panic!("expected DimensionMismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_empty_query_rejected_by_default() {
+    let index = create_index(HybridConfig::default()).await;
+
+    assert_mismatch(index.search(&[], 5).await, 0);
+    assert_mismatch(index.search_with_config(&[], SearchConfig::default()).await, 0);
+    assert_mismatch(index.search_with_predicate(&[], 5, |_| true).await, 0);
+    assert_mismatch(index.search_as_of(&[], 5, Utc::now()).await, 0);
+}
+
+#[tokio::test]
+async fn test_empty_query_returns_empty_when_configured() {
+    let config = HybridConfig {
+        empty_query_policy: EmptyQueryPolicy::ReturnEmpty,
+        ..HybridConfig::default()
+    };
+    let index = create_index(config).await;
+
+    assert!(index.search(&[], 5).await.unwrap().is_empty());
+    assert!(index.search_as_of(&[], 5, Utc::now()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_wrong_length_query_rejected_regardless_of_policy() {
+    for policy in [EmptyQueryPolicy::Reject, EmptyQueryPolicy::ReturnEmpty] {
+        let config = HybridConfig {
+            empty_query_policy: policy,
+            ..HybridConfig::default()
+        };
+        let index = create_index(config).await;
+
+        assert_mismatch(index.search(&[1.0, 2.0], 5).await, 2);
+        assert_mismatch(index.search(&[1.0, 2.0, 3.0, 4.0], 5).await, 4);
+        assert_mismatch(index.search_as_of(&[1.0], 5, Utc::now()).await, 1);
+
+        // Correct length still works
+        assert_eq!(index.search(&[4.0, 1.0, 0.0], 1).await.unwrap().len(), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_empty_query_on_fresh_index() {
+    let index = HybridIndex::new(HybridConfig::default());
+    match index.search(&[], 5).await {
+        Err(HybridError::DimensionMismatch { expected: 0, actual: 0 }) => {}
+        other => panic!("expected DimensionMismatch, got {:?}", other),
+    }
+
+    // Any non-empty query is fine before a dimension is known
+    assert!(index.search(&[1.0, 2.0], 5).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_filtered_and_negative_searches_check_query_up_front() {
+    let index = create_index(HybridConfig::default()).await;
+    let filter = MetadataFilter::from_json(&json!({ "genre": "ai" })).unwrap();
+    let metadata = HashMap::new();
+
+    assert_mismatch(
+        index.search_with_filter(&[1.0; 128], 5, Some(&filter), &metadata).await,
+        128,
+    );
+    // Rejected before the filter's matches were built and cached
+    assert_eq!(index.filter_cache_stats().await.entries, 0);
+
+    assert_mismatch(
+        index.search_with_negatives(&[1.0; 128], &[vec![1.0; 3]], 5, 0.5).await,
+        128,
+    );
+    assert_mismatch(
+        index.search_with_negatives(&[1.0, 1.0], &[vec![1.0; 2]], 5, 0.5).await,
+        2,
+    );
+}
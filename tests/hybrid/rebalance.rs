@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::maintenance::{IndexRebalancer, RebalanceConfig};
+use vector_db::hybrid::{HybridConfig, HybridIndex};
+use vector_db::ivf::core::IVFConfig;
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+/// IVF trained on a wide grid, then filled with vectors packed into one
+/// corner of it
+async fn imbalanced_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ivf_config: IVFConfig {
+            n_clusters: 8,
+            n_probe: 2,
+            train_size: 64,
+            max_iterations: 25,
+            seed: Some(3),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+        },
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let grid: Vec<Vec<f32>> = (0..64)
+        .map(|i| vec![(i % 8) as f32 * 10.0, (i / 8) as f32 * 10.0])
+        .collect();
+    index.initialize(grid).await.unwrap();
+
+    let old = Utc::now() - Duration::days(30);
+    for i in 0..300u64 {
+        let vector = vec![(i % 15) as f32 * 0.6, (i / 15) as f32 * 0.6];
+        index.insert_with_timestamp(VectorId::from_u64(i), vector, old).await.unwrap();
+    }
+    // A few vectors elsewhere so the other clusters aren't all empty
+    for i in 300..316u64 {
+        let vector = vec![70.0 - (i % 4) as f32, 70.0 - (i / 4 % 4) as f32 * 10.0];
+        index.insert_with_timestamp(VectorId::from_u64(i), vector, old).await.unwrap();
+    }
+    index
+}
+
+fn config() -> RebalanceConfig {
+    RebalanceConfig {
+        target_cluster_size_variance: 0.2,
+        max_iterations: 20,
+        converge_threshold: 0.01,
+    }
+}
+
+#[tokio::test]
+async fn test_rebalance_reduces_cluster_size_variance() {
+    let index = imbalanced_index().await;
+    let rebalancer = IndexRebalancer::new(index.clone());
+
+    let analysis = rebalancer.analyze_balance().await.unwrap();
+    assert!(analysis.ivf_needs_rebalancing);
+    assert!(analysis.cluster_imbalance > 1.0, "imbalance {}", analysis.cluster_imbalance);
+
+    let result = rebalancer.rebalance_ivf(config()).await.unwrap();
+    assert!(result.iterations > 0);
+    assert!(result.clusters_modified > 0);
+    assert!(result.vectors_moved > 0);
+    assert!(
+        result.final_variance < analysis.cluster_imbalance / 2.0,
+        "variance {} -> {}",
+        analysis.cluster_imbalance,
+        result.final_variance
+    );
+
+    // The reported variance is the index's real one, and nothing was lost
+    let after = rebalancer.analyze_balance().await.unwrap();
+    assert_eq!(after.cluster_imbalance, result.final_variance);
+    let historical = index.get_historical_index().await;
+    assert_eq!(historical.total_vectors(), 316);
+    assert_eq!(historical.get_cluster_sizes().values().sum::<usize>(), 316);
+    let found = historical.search(&[8.4, 11.4], 1).await.unwrap();
+    assert_eq!(found[0].vector_id, VectorId::from_u64(299));
+    drop(historical);
+
+    let stats = rebalancer.get_statistics().await;
+    assert_eq!(stats.total_rebalances, 1);
+    assert_eq!(stats.total_vectors_moved, result.vectors_moved);
+    assert!(stats.avg_improvement > 0.0);
+}
+
+#[tokio::test]
+async fn test_balanced_index_is_left_alone() {
+    let index = imbalanced_index().await;
+    let rebalancer = IndexRebalancer::new(index.clone());
+    let loose = RebalanceConfig {
+        target_cluster_size_variance: 100.0,
+        ..config()
+    };
+
+    let result = rebalancer.rebalance_ivf(loose).await.unwrap();
+    assert_eq!(result.iterations, 0);
+    assert_eq!(result.vectors_moved, 0);
+    assert_eq!(result.clusters_modified, 0);
+}
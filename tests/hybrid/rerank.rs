@@ -0,0 +1,64 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{SearchResult, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridSearchConfig};
+
+async fn create_index() -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..10u64 {
+        index.insert(VectorId::from_u64(i), vec![i as f32, 0.0]).await.unwrap();
+    }
+
+    index
+}
+
+#[tokio::test]
+async fn test_rerank_corrects_approximate_ordering() {
+    let index = create_index().await;
+    let query = [2.1, 0.0];
+
+    // Candidates with slightly wrong approximate distances: 3 ranked ahead of 2
+    let candidates = vec![
+        SearchResult::new(VectorId::from_u64(3), 0.85, None),
+        SearchResult::new(VectorId::from_u64(2), 0.9, None),
+        SearchResult::new(VectorId::from_u64(1), 1.1, None),
+    ];
+
+    let reranked = index.rerank_exact(&query, candidates).await.unwrap();
+    let ids: Vec<u64> = reranked.iter().map(|r| r.vector_id.as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![2, 3, 1]);
+    assert!((reranked[0].distance - 0.1).abs() < 1e-5);
+    assert!((reranked[1].distance - 0.9).abs() < 1e-5);
+    assert!((reranked[2].distance - 1.1).abs() < 1e-5);
+}
+
+#[tokio::test]
+async fn test_rerank_keeps_distance_of_unknown_ids() {
+    let index = create_index().await;
+    let unknown = VectorId::from_string("missing");
+
+    let reranked = index
+        .rerank_exact(&[0.0, 0.0], vec![SearchResult::new(unknown.clone(), 0.5, None)])
+        .await
+        .unwrap();
+    assert_eq!(reranked[0].vector_id, unknown);
+    assert_eq!(reranked[0].distance, 0.5);
+}
+
+#[tokio::test]
+async fn test_search_with_rerank_returns_exact_top_k() {
+    let index = create_index().await;
+    let config = HybridSearchConfig {
+        k: 3,
+        rerank_exact: true,
+        ..HybridSearchConfig::default()
+    };
+
+    let results = index.search_with_config(&[6.2, 0.0], config).await.unwrap();
+    let ids: Vec<u64> = results.iter().map(|r| r.vector_id.as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![6, 7, 5]);
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for paging search results with an offset
+
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, SearchConfig};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+/// Vectors at increasing distances from the origin, alternating between
+/// the recent and historical sub-indices
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+
+    for i in 0..30 {
+        let id = VectorId::from_string(&format!("v{}", i));
+        if i % 2 == 0 {
+            index.insert(id, vector(i as f32)).await.unwrap();
+        } else {
+            index
+                .insert_with_timestamp(id, vector(i as f32), Utc::now() - Duration::days(30))
+                .await
+                .unwrap();
+        }
+    }
+    index
+}
+
+async fn page(index: &HybridIndex, offset: usize, k: usize) -> Vec<(VectorId, f32)> {
+    let config = SearchConfig {
+        k,
+        offset,
+        ..index.default_search_config().await
+    };
+    index
+        .search_with_config(&vector(0.0), config)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.vector_id, r.distance))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_second_page_follows_first() {
+    let index = create_index().await;
+
+    let first = page(&index, 0, 10).await;
+    let second = page(&index, 10, 10).await;
+    assert_eq!(first.len(), 10);
+    assert_eq!(second.len(), 10);
+
+    let first_ids: HashSet<_> = first.iter().map(|(id, _)| id.clone()).collect();
+    assert!(second.iter().all(|(id, _)| !first_ids.contains(id)));
+    assert!(first.last().unwrap().1 <= second[0].1);
+    assert!(second.windows(2).all(|w| w[0].1 <= w[1].1));
+
+    let expected: Vec<VectorId> = (10..20).map(|i| VectorId::from_string(&format!("v{}", i))).collect();
+    let second_ids: Vec<VectorId> = second.into_iter().map(|(id, _)| id).collect();
+    assert_eq!(second_ids, expected);
+}
+
+#[tokio::test]
+async fn test_offset_past_last_result_is_empty() {
+    let index = create_index().await;
+
+    assert_eq!(page(&index, 25, 10).await.len(), 5);
+    assert!(page(&index, 30, 10).await.is_empty());
+    assert!(page(&index, usize::MAX, 10).await.is_empty());
+}
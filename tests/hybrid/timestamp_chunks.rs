@@ -0,0 +1,131 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for timestamps persisted in chunks next to the vector chunks
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::{Arc, Mutex};
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister};
+
+/// Mock storage that records every path written
+#[derive(Clone)]
+struct WriteTrackingStorage {
+    inner: MockS5Storage,
+    writes: Arc<Mutex<Vec<String>>>,
+}
+
+impl WriteTrackingStorage {
+    fn new() -> Self {
+        Self {
+            inner: MockS5Storage::new(),
+            writes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn take_writes(&self) -> Vec<String> {
+        std::mem::take(&mut *self.writes.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl S5Storage for WriteTrackingStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        self.writes.lock().unwrap().push(path.to_string());
+        self.inner.put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+async fn create_index(count: u64) -> HybridIndex {
+    let mut index = HybridIndex::new(HybridConfig::default());
+    let training: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 0.5, 1.0]).collect();
+    index.initialize(training).await.unwrap();
+
+    let base = Utc::now() - Duration::hours(1);
+    for i in 0..count {
+        index
+            .insert_with_timestamp(
+                VectorId::from_u64(i),
+                vec![i as f32, 1.0, 0.0],
+                base + Duration::seconds(i as i64),
+            )
+            .await
+            .unwrap();
+    }
+
+    index
+}
+
+#[tokio::test]
+async fn test_chunked_timestamps_round_trip() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    let manifest = persister.save_index_chunked(&index, "ts").await.unwrap();
+
+    let timestamp_chunks = manifest.timestamp_chunks.unwrap();
+    assert_eq!(timestamp_chunks.len(), 4);
+    assert!(timestamp_chunks.iter().all(|c| c.entry_count == 50));
+    assert!(storage.get("ts/timestamps.cbor").await.unwrap().is_none());
+
+    let loaded = persister
+        .load_index_chunked("ts", HybridConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(loaded.get_timestamps().await, index.get_timestamps().await);
+
+    // A single chunk can be read on its own
+    let first = persister
+        .load_timestamp_chunk("ts", &timestamp_chunks[0])
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 50);
+}
+
+#[tokio::test]
+async fn test_incremental_save_rewrites_only_changed_timestamp_chunk() {
+    let index = create_index(200).await;
+    let storage = WriteTrackingStorage::new();
+    let persister = HybridPersister::new(storage.clone()).with_chunk_size(50);
+
+    persister.save_index_chunked(&index, "ts").await.unwrap();
+    let first_writes: Vec<String> = storage
+        .take_writes()
+        .into_iter()
+        .filter(|p| p.starts_with("ts/timestamps/"))
+        .collect();
+    assert_eq!(first_writes.len(), 4);
+
+    // Touch one vector that lives in the third chunk (ids are saved in order)
+    let changed = VectorId::from_u64(120);
+    index.timestamps.write().await.insert(changed.clone(), Utc::now());
+
+    let second = persister.save_index_chunked(&index, "ts").await.unwrap();
+    let second_writes: Vec<String> = storage
+        .take_writes()
+        .into_iter()
+        .filter(|p| p.starts_with("ts/timestamps/"))
+        .collect();
+    assert_eq!(second_writes, vec!["ts/timestamps/chunk-2.g1.cbor".to_string()]);
+
+    let chunk = persister
+        .load_timestamp_chunk("ts", &second.timestamp_chunks.unwrap()[2])
+        .await
+        .unwrap();
+    assert_eq!(chunk.get(&changed), index.get_timestamps().await.get(&changed));
+}
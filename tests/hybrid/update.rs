@@ -0,0 +1,96 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for updating stored vectors in place
+
+use chrono::{Duration, Utc};
+use vector_db::core::types::VectorId;
+use vector_db::hybrid::{HybridConfig, HybridError, HybridIndex};
+
+const DIM: usize = 4;
+
+fn vector(seed: f32) -> Vec<f32> {
+    (0..DIM).map(|d| seed + d as f32 * 0.1).collect()
+}
+
+async fn create_index() -> HybridIndex {
+    let config = HybridConfig {
+        auto_migrate: false,
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config);
+    let training: Vec<Vec<f32>> = (0..20).map(|i| vector(i as f32)).collect();
+    index.initialize(training).await.unwrap();
+    index
+}
+
+async fn nearest(index: &HybridIndex, query: &[f32]) -> VectorId {
+    index.search(query, 1).await.unwrap()[0].vector_id.clone()
+}
+
+#[tokio::test]
+async fn test_update_recent_vector_moves_it() {
+    let index = create_index().await;
+    for i in 0..10 {
+        index
+            .insert(VectorId::from_u64(i), vector(i as f32 * 10.0))
+            .await
+            .unwrap();
+    }
+    let id = VectorId::from_u64(0);
+    let timestamp = index.get_timestamps().await[&id];
+    assert_eq!(nearest(&index, &vector(0.0)).await, id);
+
+    index.update(id.clone(), vector(95.0)).await.unwrap();
+
+    assert_eq!(nearest(&index, &vector(95.0)).await, id);
+    assert_ne!(nearest(&index, &vector(0.0)).await, id);
+    assert_eq!(index.get_vector(&id).await, Some(vector(95.0)));
+    assert_eq!(index.get_timestamps().await[&id], timestamp);
+    assert!(index.is_in_recent(&id));
+    assert_eq!(index.get_stats().recent_vectors, 10);
+}
+
+#[tokio::test]
+async fn test_update_historical_vector_stays_historical() {
+    let index = create_index().await;
+    let id = VectorId::from_string("old");
+    let timestamp = Utc::now() - Duration::days(30);
+    index
+        .insert_with_timestamp(id.clone(), vector(1.0), timestamp)
+        .await
+        .unwrap();
+    index
+        .set_payload(id.clone(), b"payload".to_vec())
+        .await
+        .unwrap();
+
+    index.update(id.clone(), vector(15.0)).await.unwrap();
+
+    assert_eq!(index.get_vector(&id).await, Some(vector(15.0)));
+    assert_eq!(nearest(&index, &vector(15.0)).await, id);
+    assert!(index.get_historical_index().await.get_vector_by_id(&id).is_some());
+    assert!(index.get_recent_index().await.get_vector_by_id(&id).is_none());
+    assert_eq!(index.get_timestamps().await[&id], timestamp);
+    assert_eq!(index.get_payload(&id).await, Some(b"payload".to_vec()));
+}
+
+#[tokio::test]
+async fn test_update_rejects_missing_and_mismatched_vectors() {
+    let index = create_index().await;
+    let id = VectorId::from_string("present");
+    index.insert(id.clone(), vector(1.0)).await.unwrap();
+
+    let missing = index.update(VectorId::from_string("missing"), vector(2.0)).await;
+    assert!(matches!(missing, Err(HybridError::VectorNotFound(_))));
+
+    let mismatched = index.update(id.clone(), vec![1.0; DIM + 1]).await;
+    assert!(matches!(
+        mismatched,
+        Err(HybridError::DimensionMismatch { expected: DIM, actual: 5 })
+    ));
+
+    index.delete(id.clone()).await.unwrap();
+    let deleted = index.update(id.clone(), vector(2.0)).await;
+    assert!(matches!(deleted, Err(HybridError::VectorNotFound(_))));
+}
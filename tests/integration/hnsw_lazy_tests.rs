@@ -7,7 +7,7 @@ use tokio::time::Duration;
 use vector_db::core::storage::{S5Storage, MockS5Storage};
 use vector_db::core::chunk_cache::ChunkCache;
 use vector_db::core::chunk::VectorChunk;
-use vector_db::core::types::VectorId;
+use vector_db::core::types::{DistanceMetric, VectorId};
 use vector_db::storage::chunk_loader::ChunkLoader;
 use vector_db::hnsw::core::{HNSWIndex, HNSWConfig};
 
@@ -76,6 +76,10 @@ async fn test_hnsw_search_with_lazy_loading() {
         max_connections_layer_0: 32,
         ef_construction: 200,
         seed: Some(42),
+        cache_distances: true,
+        metric: DistanceMetric::Euclidean,
+        use_heuristic_selection: false,
+        keep_pruned_connections: false,
     };
 
     let mut index = HNSWIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -156,6 +160,10 @@ async fn test_cache_hit_rate_during_repeated_searches() {
         max_connections_layer_0: 16,
         ef_construction: 100,
         seed: Some(42),
+        cache_distances: true,
+        metric: DistanceMetric::Euclidean,
+        use_heuristic_selection: false,
+        keep_pruned_connections: false,
     };
     let mut index = HNSWIndex::with_chunk_loader(config, Some(chunk_loader));
 
@@ -242,6 +250,10 @@ async fn test_performance_cold_vs_warm_cache() {
         max_connections_layer_0: 32,
         ef_construction: 200,
         seed: Some(42),
+        cache_distances: true,
+        metric: DistanceMetric::Euclidean,
+        use_heuristic_selection: false,
+        keep_pruned_connections: false,
     };
     let mut index = HNSWIndex::with_chunk_loader(config, Some(chunk_loader));
 
@@ -328,6 +340,10 @@ async fn test_concurrent_searches_thread_safety() {
         max_connections_layer_0: 32,
         ef_construction: 200,
         seed: Some(42),
+        cache_distances: true,
+        metric: DistanceMetric::Euclidean,
+        use_heuristic_selection: false,
+        keep_pruned_connections: false,
     };
     let mut index = HNSWIndex::with_chunk_loader(config, Some(chunk_loader));
 
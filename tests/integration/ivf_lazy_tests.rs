@@ -4,12 +4,14 @@
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::time::Duration;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::storage::{S5Storage, MockS5Storage};
 use vector_db::core::chunk_cache::ChunkCache;
 use vector_db::core::chunk::VectorChunk;
 use vector_db::core::types::VectorId;
 use vector_db::storage::chunk_loader::ChunkLoader;
 use vector_db::ivf::core::{IVFIndex, IVFConfig, ClusterId};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 /// Helper to create test vectors with known clustering
 /// Vectors are created in groups to naturally cluster together
@@ -97,6 +99,9 @@ async fn test_ivf_search_with_lazy_cluster_loading() {
         train_size: 100,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -156,6 +161,9 @@ async fn test_multi_probe_search_across_chunks() {
         train_size: 300,
         max_iterations: 15,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -208,6 +216,9 @@ async fn test_cache_hit_rate_for_hot_clusters() {
         train_size: 100,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -272,6 +283,9 @@ async fn test_ivf_insert_to_lazy_loaded_cluster() {
         train_size: 100,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -322,6 +336,9 @@ async fn test_performance_cold_vs_warm_cache() {
         train_size: 600,
         max_iterations: 20,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -375,6 +392,9 @@ async fn test_error_handling_missing_chunk() {
         train_size: 50,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
@@ -429,6 +449,9 @@ async fn test_cluster_rebalancing_with_lazy_loading() {
         train_size: 100,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::with_chunk_loader(config, Some(chunk_loader));
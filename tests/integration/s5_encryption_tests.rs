@@ -20,6 +20,8 @@ async fn test_encryption_enabled_by_default() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: None, // Not specified - should default to true
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -40,6 +42,8 @@ async fn test_explicit_encryption_enable() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(true),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -64,6 +68,8 @@ async fn test_explicit_encryption_disable() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(false),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -90,6 +96,8 @@ async fn test_encryption_header_included_when_enabled() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(true),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -110,6 +118,8 @@ async fn test_encryption_header_not_included_when_disabled() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(false),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -137,6 +147,8 @@ async fn test_encryption_with_mock_mode() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(true),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -156,6 +168,8 @@ async fn test_encryption_with_real_mode() {
         connection_timeout: Some(30000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(true),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
@@ -182,6 +196,8 @@ async fn test_get_decrypts_transparently() {
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
         encrypt_at_rest: Some(true),
+        encryption_key: None,
+        compress: None,
     };
 
     let storage = EnhancedS5Storage::new(config).expect("Failed to create storage");
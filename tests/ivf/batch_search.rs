@@ -0,0 +1,115 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::types::VectorId;
+use vector_db::ivf::core::{BatchSearchOptions, IVFConfig, IVFError, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+fn create_index() -> IVFIndex {
+    let config = IVFConfig {
+        n_clusters: 4,
+        n_probe: 2,
+        train_size: 80,
+        max_iterations: 20,
+        seed: Some(7),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    };
+    let mut index = IVFIndex::new(config);
+
+    // Four well separated groups of 20 vectors
+    let vectors: Vec<Vec<f32>> = (0..80)
+        .map(|i| {
+            let group = (i / 20) as f32 * 100.0;
+            vec![group + (i % 20) as f32 * 0.1, group]
+        })
+        .collect();
+    index.train(&vectors).unwrap();
+    for (i, vector) in vectors.into_iter().enumerate() {
+        index.insert(VectorId::from_u64(i as u64), vector).unwrap();
+    }
+
+    index
+}
+
+/// Similar queries around one group, with a repeat
+fn similar_queries() -> Vec<Vec<f32>> {
+    vec![
+        vec![0.5, 0.0],
+        vec![0.7, 0.1],
+        vec![1.1, 0.2],
+        vec![0.5, 0.0],
+        vec![201.0, 200.0],
+    ]
+}
+
+fn options(share_clusters: bool) -> BatchSearchOptions {
+    BatchSearchOptions {
+        n_probe: 2,
+        share_clusters,
+    }
+}
+
+#[tokio::test]
+async fn test_batch_matches_individual_searches() {
+    let index = create_index();
+    let queries = similar_queries();
+
+    let batch = index.search_batch(&queries, 5).await.unwrap();
+
+    assert_eq!(batch.len(), queries.len());
+    for (query, results) in queries.iter().zip(&batch) {
+        let single = index.search(query, 5).await.unwrap();
+        let expected: Vec<_> = single.iter().map(|r| r.vector_id.clone()).collect();
+        let actual: Vec<_> = results.iter().map(|r| r.vector_id.clone()).collect();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_shared_probing_does_less_work() {
+    let index = create_index();
+    let queries = similar_queries();
+
+    let (shared, shared_stats) = index
+        .search_batch_with(&queries, 5, options(true))
+        .await
+        .unwrap();
+    let (independent, independent_stats) = index
+        .search_batch_with(&queries, 5, options(false))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        shared.iter().map(|r| r.len()).collect::<Vec<_>>(),
+        independent.iter().map(|r| r.len()).collect::<Vec<_>>()
+    );
+    assert_eq!(independent_stats.cluster_fetches, queries.len() * 2);
+    assert!(shared_stats.cluster_fetches < independent_stats.cluster_fetches);
+    assert!(shared_stats.distance_computations < independent_stats.distance_computations);
+}
+
+#[tokio::test]
+async fn test_batch_skips_deleted_vectors() {
+    let mut index = create_index();
+    let deleted = VectorId::from_u64(5);
+    index.mark_deleted(&deleted).unwrap();
+
+    let batch = index.search_batch(&[vec![0.5, 0.0]], 20).await.unwrap();
+
+    assert!(batch[0].iter().all(|r| r.vector_id != deleted));
+}
+
+#[tokio::test]
+async fn test_batch_rejects_wrong_dimension() {
+    let index = create_index();
+
+    let result = index.search_batch(&[vec![0.5, 0.0], vec![1.0]], 5).await;
+
+    assert!(matches!(
+        result,
+        Err(IVFError::DimensionMismatch { expected: 2, actual: 1 })
+    ));
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::sync::Arc;
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::chunk::{ChunkMetadata, Manifest, VectorChunk};
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::VectorId;
+use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::ivf::operations::ChunkCompactionConfig;
+use vector_db::storage::chunk_loader::ChunkLoader;
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+const DIM: usize = 8;
+
+fn make_vector(i: usize) -> Vec<f32> {
+    (0..DIM).map(|d| (i % 4) as f32 * 10.0 + i as f32 * 0.01 + d as f32 * 0.1).collect()
+}
+
+/// Build a lazily loaded index over two chunks of 20 vectors each
+async fn setup() -> (Arc<MockS5Storage>, IVFIndex, Vec<String>, Vec<(VectorId, Vec<f32>)>) {
+    let storage = Arc::new(MockS5Storage::new());
+    let cache = Arc::new(ChunkCache::new(100));
+    let loader = Arc::new(ChunkLoader::new(storage.clone(), cache));
+
+    let vectors: Vec<(VectorId, Vec<f32>)> = (0..40)
+        .map(|i| (VectorId::from_string(&format!("vec_{}", i)), make_vector(i)))
+        .collect();
+
+    let mut chunk_paths = Vec::new();
+    for (chunk_idx, chunk_vectors) in vectors.chunks(20).enumerate() {
+        let mut chunk = VectorChunk::new(format!("chunk-{}", chunk_idx), chunk_idx * 20, chunk_idx * 20 + 19);
+        for (id, vector) in chunk_vectors {
+            chunk.add_vector(id.clone(), vector.clone());
+        }
+        let path = format!("test/compaction/chunks/chunk-{}.cbor", chunk_idx);
+        storage.put(&path, chunk.to_cbor().unwrap()).await.unwrap();
+        chunk_paths.push(path);
+    }
+
+    let config = IVFConfig {
+        n_clusters: 4,
+        n_probe: 4,
+        train_size: 40,
+        max_iterations: 10,
+        seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    };
+    let mut index = IVFIndex::with_chunk_loader(config, Some(loader));
+    let training: Vec<Vec<f32>> = vectors.iter().map(|(_, v)| v.clone()).collect();
+    index.train(&training).unwrap();
+
+    for (i, (id, vector)) in vectors.iter().enumerate() {
+        index
+            .insert_with_chunk(id.clone(), vector.clone(), Some(chunk_paths[i / 20].clone()))
+            .unwrap();
+    }
+
+    (storage, index, chunk_paths, vectors)
+}
+
+#[tokio::test]
+async fn test_compaction_rewrites_chunk_over_threshold() {
+    let (storage, mut index, chunk_paths, vectors) = setup().await;
+    let size_before = storage.get(&chunk_paths[0]).await.unwrap().unwrap().len();
+
+    // Delete 15 of the 20 vectors in the first chunk
+    for (id, _) in &vectors[..15] {
+        index.mark_deleted(id).unwrap();
+    }
+
+    let result = index
+        .compact_chunks(&ChunkCompactionConfig { deleted_ratio_threshold: 0.5 })
+        .await
+        .unwrap();
+
+    assert_eq!(result.chunks_compacted.len(), 1);
+    assert_eq!(result.chunks_compacted[0].chunk_path, chunk_paths[0]);
+    assert_eq!(result.chunks_compacted[0].vectors_before, 20);
+    assert_eq!(result.chunks_compacted[0].vectors_after, 5);
+    assert_eq!(result.removed_ids.len(), 15);
+    assert!(result.bytes_reclaimed > 0);
+    assert_eq!(
+        result.chunks_compacted[0].compacted_path.as_deref(),
+        Some("test/compaction/chunks/chunk-0.g1.cbor")
+    );
+
+    // The old chunk is untouched; the compacted one has its own key
+    assert_eq!(storage.get(&chunk_paths[0]).await.unwrap().unwrap().len(), size_before);
+    let rewritten = storage
+        .get("test/compaction/chunks/chunk-0.g1.cbor")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(rewritten.len() < size_before);
+    assert_eq!(rewritten.len(), result.chunks_compacted[0].bytes_after);
+    assert_eq!(VectorChunk::from_cbor(&rewritten).unwrap().len(), 5);
+
+    assert_eq!(index.total_vectors(), 25);
+    assert!(index.get_deleted_ids().is_empty());
+
+    // Search still returns only live vectors
+    let results = index.search(&make_vector(0), 40).await.unwrap();
+    assert_eq!(results.len(), 25);
+    for (id, _) in &vectors[..15] {
+        assert!(results.iter().all(|r| &r.vector_id != id));
+    }
+    for (id, _) in &vectors[15..] {
+        assert!(results.iter().any(|r| &r.vector_id == id));
+    }
+}
+
+#[tokio::test]
+async fn test_compaction_skips_chunks_below_threshold() {
+    let (storage, mut index, chunk_paths, vectors) = setup().await;
+    let size_before = storage.get(&chunk_paths[1]).await.unwrap().unwrap().len();
+
+    // 5 of 20 deleted -> 25%, below the 50% trigger
+    for (id, _) in &vectors[20..25] {
+        index.mark_deleted(id).unwrap();
+    }
+
+    let result = index.compact_chunks(&ChunkCompactionConfig::default()).await.unwrap();
+
+    assert!(result.chunks_compacted.is_empty());
+    assert_eq!(result.bytes_reclaimed, 0);
+    assert_eq!(storage.get(&chunk_paths[1]).await.unwrap().unwrap().len(), size_before);
+    assert_eq!(index.get_deleted_ids().len(), 5);
+}
+
+#[tokio::test]
+async fn test_compaction_removes_fully_deleted_chunk() {
+    let (storage, mut index, chunk_paths, vectors) = setup().await;
+
+    for (id, _) in &vectors[..20] {
+        index.mark_deleted(id).unwrap();
+    }
+
+    let result = index.compact_chunks(&ChunkCompactionConfig::default()).await.unwrap();
+
+    assert_eq!(result.chunks_removed, vec![chunk_paths[0].clone()]);
+    assert_eq!(result.chunks_compacted[0].compacted_path, None);
+    assert!(storage.get(&chunk_paths[0]).await.unwrap().is_some());
+    assert_eq!(index.total_vectors(), 20);
+
+    index.remove_superseded_chunks(&result).await.unwrap();
+    assert!(storage.get(&chunk_paths[0]).await.unwrap().is_none());
+
+    let results = index.search(&make_vector(0), 40).await.unwrap();
+    assert_eq!(results.len(), 20);
+}
+
+#[tokio::test]
+async fn test_compaction_ratio_counts_unreferenced_chunk_vectors() {
+    let (storage, mut index, chunk_paths, vectors) = setup().await;
+
+    // Chunk 0 also stores 10 vectors the IVF lists do not reference (as
+    // recent vectors would be)
+    let mut chunk = VectorChunk::from_cbor(&storage.get(&chunk_paths[0]).await.unwrap().unwrap()).unwrap();
+    for i in 0..10 {
+        chunk.add_vector(VectorId::from_string(&format!("recent_{}", i)), make_vector(i));
+    }
+    storage.put(&chunk_paths[0], chunk.to_cbor().unwrap()).await.unwrap();
+
+    // 11 deleted of 30 stored is under the 50% trigger even though it is
+    // 55% of the referenced vectors
+    for (id, _) in &vectors[..11] {
+        index.mark_deleted(id).unwrap();
+    }
+
+    let result = index.compact_chunks(&ChunkCompactionConfig::default()).await.unwrap();
+
+    assert!(result.chunks_compacted.is_empty());
+    assert_eq!(index.get_deleted_ids().len(), 11);
+    let chunk = VectorChunk::from_cbor(&storage.get(&chunk_paths[0]).await.unwrap().unwrap()).unwrap();
+    assert_eq!(chunk.len(), 30);
+}
+
+#[tokio::test]
+async fn test_compaction_drops_payloads_of_deleted_vectors() {
+    let (storage, mut index, chunk_paths, vectors) = setup().await;
+
+    let mut chunk = VectorChunk::from_cbor(&storage.get(&chunk_paths[0]).await.unwrap().unwrap()).unwrap();
+    for (id, _) in &vectors[..20] {
+        chunk.payloads.insert(id.clone(), b"payload".to_vec());
+    }
+    storage.put(&chunk_paths[0], chunk.to_cbor().unwrap()).await.unwrap();
+
+    for (id, _) in &vectors[..15] {
+        index.mark_deleted(id).unwrap();
+    }
+
+    let result = index.compact_chunks(&ChunkCompactionConfig::default()).await.unwrap();
+
+    let compacted_path = result.chunks_compacted[0].compacted_path.clone().unwrap();
+    let compacted = VectorChunk::from_cbor(&storage.get(&compacted_path).await.unwrap().unwrap()).unwrap();
+    assert_eq!(compacted.payloads.len(), 5);
+    for (id, _) in &vectors[..15] {
+        assert!(!compacted.payloads.contains_key(id));
+    }
+}
+
+#[tokio::test]
+async fn test_compaction_keeps_old_chunk_until_manifest_is_updated() {
+    let (storage, mut index, chunk_paths, vectors) = setup().await;
+
+    let mut manifest = Manifest::new(20, 40);
+    manifest.generation = 3;
+    for chunk_idx in 0..2 {
+        manifest.add_chunk(ChunkMetadata::new(
+            format!("chunk-{}", chunk_idx),
+            20,
+            0,
+            vectors[chunk_idx * 20].0.clone(),
+            vectors[chunk_idx * 20 + 19].0.clone(),
+        ));
+    }
+
+    for (id, _) in &vectors[..15] {
+        index.mark_deleted(id).unwrap();
+    }
+    let result = index.compact_chunks(&ChunkCompactionConfig::default()).await.unwrap();
+
+    // Live vectors now load from the compacted chunk
+    let compacted_path = result.chunks_compacted[0].compacted_path.clone().unwrap();
+    let results = index.search(&make_vector(15), 40).await.unwrap();
+    assert_eq!(results.len(), 25);
+    assert!(storage.get(&chunk_paths[0]).await.unwrap().is_some());
+
+    result.apply_to_manifest(&mut manifest);
+    let meta = &manifest.chunks[0];
+    assert_eq!(meta.chunk_id, "chunk-0");
+    assert_eq!(meta.generation, Some(1));
+    assert_eq!(meta.vector_count, 5);
+    assert_eq!(meta.storage_path("test/compaction"), compacted_path);
+    assert_eq!(manifest.generation, 4);
+
+    index.remove_superseded_chunks(&result).await.unwrap();
+    assert!(storage.get(&chunk_paths[0]).await.unwrap().is_none());
+    assert!(storage.get(&compacted_path).await.unwrap().is_some());
+    assert!(storage.get(&chunk_paths[1]).await.unwrap().is_some());
+}
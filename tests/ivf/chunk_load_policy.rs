@@ -0,0 +1,97 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::sync::Arc;
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::VectorId;
+use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::storage::chunk_loader::{ChunkLoadPolicy, ChunkLoader};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+const DIM: usize = 8;
+
+fn make_vector(i: usize) -> Vec<f32> {
+    (0..DIM).map(|d| (i % 4) as f32 * 10.0 + i as f32 * 0.01 + d as f32 * 0.1).collect()
+}
+
+/// Cold lazily loaded index over two chunks of 20 vectors, as after a reload;
+/// the second chunk is deleted from storage
+async fn setup(policy: ChunkLoadPolicy) -> (IVFIndex, Vec<String>) {
+    let storage = Arc::new(MockS5Storage::new());
+    let cache = Arc::new(ChunkCache::new(100));
+    let loader = Arc::new(ChunkLoader::new(storage.clone(), cache).with_policy(policy));
+
+    let vectors: Vec<(VectorId, Vec<f32>)> = (0..40)
+        .map(|i| (VectorId::from_string(&format!("vec_{}", i)), make_vector(i)))
+        .collect();
+
+    let mut chunk_paths = Vec::new();
+    for (chunk_idx, chunk_vectors) in vectors.chunks(20).enumerate() {
+        let mut chunk = VectorChunk::new(format!("chunk-{}", chunk_idx), chunk_idx * 20, chunk_idx * 20 + 19);
+        for (id, vector) in chunk_vectors {
+            chunk.add_vector(id.clone(), vector.clone());
+        }
+        let path = format!("test/policy/chunks/chunk-{}.cbor", chunk_idx);
+        storage.put(&path, chunk.to_cbor().unwrap()).await.unwrap();
+        chunk_paths.push(path);
+    }
+
+    let config = IVFConfig {
+        n_clusters: 4,
+        n_probe: 4,
+        train_size: 40,
+        max_iterations: 10,
+        seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    };
+    let mut warm = IVFIndex::with_chunk_loader(config.clone(), Some(loader.clone()));
+    let training: Vec<Vec<f32>> = vectors.iter().map(|(_, v)| v.clone()).collect();
+    warm.train(&training).unwrap();
+
+    for (i, (id, vector)) in vectors.iter().enumerate() {
+        warm
+            .insert_with_chunk(id.clone(), vector.clone(), Some(chunk_paths[i / 20].clone()))
+            .unwrap();
+    }
+
+    // Rebuild without the warm vector cache so searches must load chunks
+    let mut index = IVFIndex::with_chunk_loader(config, Some(loader));
+    index.set_trained(warm.get_centroids().to_vec(), DIM);
+    index.set_inverted_lists(warm.get_all_inverted_lists().clone());
+
+    storage.delete(&chunk_paths[1]).await.unwrap();
+    (index, chunk_paths)
+}
+
+#[tokio::test]
+async fn test_fail_fast_errors_on_unreadable_chunk() {
+    let (index, _) = setup(ChunkLoadPolicy::FailFast).await;
+
+    let result = index.search(&make_vector(0), 5).await;
+    assert!(result.is_err());
+    assert!(index.chunk_integrity_report().await.is_complete());
+}
+
+#[tokio::test]
+async fn test_skip_missing_searches_available_chunks() {
+    let (index, chunk_paths) = setup(ChunkLoadPolicy::SkipMissing).await;
+
+    let results = index.search(&make_vector(0), 40).await.unwrap();
+    assert_eq!(results.len(), 20);
+    let available: Vec<VectorId> = (0..20)
+        .map(|i| VectorId::from_string(&format!("vec_{}", i)))
+        .collect();
+    assert!(results.iter().all(|r| available.contains(&r.vector_id)));
+
+    let report = index.chunk_integrity_report().await;
+    assert!(!report.is_complete());
+    assert_eq!(report.skipped_chunks.len(), 1);
+    assert_eq!(report.skipped_chunks[0].path, chunk_paths[1]);
+    assert!(report.skipped_chunks[0].error.contains("not found"));
+    assert_eq!(report.unavailable_vectors, 20);
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::collections::HashSet;
+use vector_db::core::types::DistanceMetric;
+use vector_db::core::types::VectorId;
+use vector_db::ivf::core::{ClusterId, IVFConfig, IVFError, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+fn create_index() -> IVFIndex {
+    let config = IVFConfig {
+        n_clusters: 4,
+        n_probe: 1,
+        train_size: 80,
+        max_iterations: 20,
+        seed: Some(7),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    };
+    let mut index = IVFIndex::new(config);
+
+    // Four well separated groups of 20 vectors
+    let vectors: Vec<Vec<f32>> = (0..80)
+        .map(|i| {
+            let group = (i / 20) as f32 * 100.0;
+            vec![group + (i % 20) as f32 * 0.1, group]
+        })
+        .collect();
+    index.train(&vectors).unwrap();
+    for (i, vector) in vectors.into_iter().enumerate() {
+        index.insert(VectorId::from_u64(i as u64), vector).unwrap();
+    }
+
+    index
+}
+
+fn cluster_members(index: &IVFIndex, clusters: &[ClusterId]) -> HashSet<VectorId> {
+    clusters
+        .iter()
+        .flat_map(|c| index.get_inverted_list(*c).unwrap().vectors.keys().cloned())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_results_only_come_from_requested_clusters() {
+    let index = create_index();
+    let clusters = [ClusterId(1), ClusterId(3)];
+    let members = cluster_members(&index, &clusters);
+    assert!(!members.is_empty());
+
+    // Query near another group so nearest-centroid probing would miss these clusters
+    let query = [0.0, 0.0];
+    let results = index.search_in_clusters(&query, 10, &clusters).await.unwrap();
+
+    assert_eq!(results.len(), members.len().min(10));
+    assert!(results.iter().all(|r| members.contains(&r.vector_id)));
+}
+
+#[tokio::test]
+async fn test_matches_full_search_restricted_to_clusters() {
+    let index = create_index();
+    let clusters = [ClusterId(0), ClusterId(2)];
+    let members = cluster_members(&index, &clusters);
+
+    let query = [150.0, 100.0];
+    let restricted = index.search_in_clusters(&query, 5, &clusters).await.unwrap();
+
+    // Exhaustive search over every cluster, filtered to the requested ones
+    let full = index.search_with_config(&query, 80, 4).await.unwrap();
+    let expected: Vec<VectorId> = full
+        .into_iter()
+        .filter(|r| members.contains(&r.vector_id))
+        .take(5)
+        .map(|r| r.vector_id)
+        .collect();
+
+    let returned: Vec<VectorId> = restricted.into_iter().map(|r| r.vector_id).collect();
+    assert_eq!(returned, expected);
+}
+
+#[tokio::test]
+async fn test_unknown_cluster_is_rejected() {
+    let index = create_index();
+
+    let err = index
+        .search_in_clusters(&[0.0, 0.0], 5, &[ClusterId(0), ClusterId(9)])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, IVFError::ClusterNotFound(ClusterId(9))));
+}
+
+#[tokio::test]
+async fn test_duplicate_cluster_ids_do_not_duplicate_results() {
+    let index = create_index();
+    let members = cluster_members(&index, &[ClusterId(0)]);
+
+    let results = index
+        .search_in_clusters(&[0.0, 0.0], 100, &[ClusterId(0), ClusterId(0)])
+        .await
+        .unwrap();
+    assert_eq!(results.len(), members.len());
+}
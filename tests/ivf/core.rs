@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 use std::collections::HashSet;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::types::*;
 use vector_db::core::vector_ops::*;
 use vector_db::ivf::core::*;
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 #[cfg(test)]
 mod ivf_structure_tests {
@@ -18,6 +20,9 @@ mod ivf_structure_tests {
             train_size: 10000,
             max_iterations: 25,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         assert_eq!(config.n_clusters, 100);
@@ -33,6 +38,9 @@ mod ivf_structure_tests {
             train_size: 100,
             max_iterations: 25,
             seed: None,
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         assert!(!config.is_valid());
@@ -73,6 +81,9 @@ mod ivf_training_tests {
             train_size: 9,
             max_iterations: 10,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         let mut index = IVFIndex::new(config);
@@ -129,6 +140,9 @@ mod ivf_training_tests {
             train_size: 100,
             max_iterations: 50,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         let mut index = IVFIndex::new(config);
@@ -174,6 +188,9 @@ mod ivf_training_tests {
             train_size: 100,
             max_iterations: 25,
             seed: None,
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         let mut index = IVFIndex::new(config);
@@ -199,6 +216,9 @@ mod ivf_training_tests {
             train_size: 10,
             max_iterations: 10,
             seed: None,
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
         let mut index = IVFIndex::new(config);
 
@@ -350,6 +370,9 @@ mod ivf_search_tests {
             train_size: 9,
             max_iterations: 10,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         let mut index = IVFIndex::new(config);
@@ -445,6 +468,9 @@ fn create_trained_index() -> IVFIndex {
         train_size: 9,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::new(config);
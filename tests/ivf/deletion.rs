@@ -0,0 +1,73 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+fn create_index() -> IVFIndex {
+    let mut index = IVFIndex::new(IVFConfig {
+        n_clusters: 4,
+        n_probe: 4,
+        train_size: 40,
+        max_iterations: 20,
+        seed: Some(7),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    });
+    let training: Vec<Vec<f32>> = (0..40)
+        .map(|i| vec![(i % 4) as f32 * 10.0, (i / 4) as f32 * 0.1])
+        .collect();
+    index.train(&training).unwrap();
+    for i in 0..20u64 {
+        index
+            .insert(VectorId::from_u64(i), vec![(i % 4) as f32 * 10.0, i as f32 * 0.1])
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_search_excludes_deleted_vectors() {
+    let mut index = create_index();
+    let query = vec![0.0, 0.0];
+    let nearest = index.search(&query, 1).await.unwrap()[0].vector_id.clone();
+    assert_eq!(nearest, VectorId::from_u64(0));
+
+    index.mark_deleted(&nearest).unwrap();
+
+    let results = index.search(&query, 20).await.unwrap();
+    assert_eq!(results.len(), 19);
+    assert!(results.iter().all(|r| r.vector_id != nearest));
+    assert!(index.is_deleted(&nearest));
+    assert_eq!(index.active_count(), 19);
+    assert_eq!(index.total_vectors(), 20);
+    assert_eq!(index.get_deleted_ids(), vec![&nearest]);
+}
+
+#[tokio::test]
+async fn test_vacuum_reclaims_deleted_vectors() {
+    let mut index = create_index();
+    for i in [0u64, 5, 10] {
+        index.mark_deleted(&VectorId::from_u64(i)).unwrap();
+    }
+
+    let removed = index.vacuum().unwrap();
+
+    assert_eq!(removed, 3);
+    assert_eq!(index.total_vectors(), 17);
+    assert_eq!(index.active_count(), 17);
+    assert!(index.get_deleted_ids().is_empty());
+    let stored: usize = index
+        .get_all_inverted_lists()
+        .values()
+        .map(|list| list.vectors.len())
+        .sum();
+    assert_eq!(stored, 17);
+    assert_eq!(index.get_vector_by_id(&VectorId::from_u64(5)), None);
+    assert_eq!(index.search(&[0.0, 0.0], 20).await.unwrap().len(), 17);
+
+    // Nothing left to reclaim
+    assert_eq!(index.vacuum().unwrap(), 0);
+}
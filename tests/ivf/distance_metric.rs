@@ -0,0 +1,122 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::chunk::IVFManifest;
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::ivf::core::{IVFConfig, IVFError, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+fn create_index(metric: DistanceMetric) -> IVFIndex {
+    IVFIndex::new(IVFConfig {
+        n_clusters: 4,
+        n_probe: 4,
+        train_size: 40,
+        max_iterations: 20,
+        seed: Some(7),
+        metric,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    })
+}
+
+/// Vectors pointing in a few directions with varying magnitudes
+fn training_data() -> Vec<Vec<f32>> {
+    (0..40)
+        .map(|i| {
+            let angle = (i % 4) as f32 * 1.5 + (i / 4) as f32 * 0.02;
+            let scale = 1.0 + (i % 9) as f32;
+            vec![angle.cos() * scale, angle.sin() * scale, 0.1 * scale]
+        })
+        .collect()
+}
+
+fn trained(metric: DistanceMetric) -> IVFIndex {
+    let mut index = create_index(metric);
+    index.train(&training_data()).unwrap();
+    index
+}
+
+#[test]
+fn test_euclidean_is_the_default() {
+    assert_eq!(IVFConfig::default().metric, DistanceMetric::Euclidean);
+
+    // Configs persisted before the metric existed
+    let json = r#"{"n_clusters":8,"n_probe":2,"train_size":100,"max_iterations":25,"seed":null}"#;
+    let config: IVFConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(config.metric, DistanceMetric::Euclidean);
+
+    assert_eq!(IVFConfig::default_for(DistanceMetric::Cosine, 1000).metric, DistanceMetric::Cosine);
+
+    // Manifests written before the metric was recorded
+    let json = r#"{"centroids":[[1.0,0.0]],"cluster_assignments":{}}"#;
+    let manifest: IVFManifest = serde_json::from_str(json).unwrap();
+    assert_eq!(manifest.metric, DistanceMetric::Euclidean);
+}
+
+#[test]
+fn test_cosine_centroids_are_unit_length() {
+    let index = trained(DistanceMetric::Cosine);
+
+    for centroid in index.get_centroids() {
+        let norm: f32 = centroid.vector().iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-4, "centroid norm {}", norm);
+    }
+}
+
+#[tokio::test]
+async fn test_cosine_ranks_by_angle() {
+    let far_but_aligned = VectorId::from_u64(1);
+    let near_but_angled = VectorId::from_u64(2);
+    let query = [1.0, 0.0, 0.1];
+
+    for (metric, nearest) in [
+        (DistanceMetric::Euclidean, &near_but_angled),
+        (DistanceMetric::Cosine, &far_but_aligned),
+    ] {
+        let mut index = trained(metric);
+        index.insert(far_but_aligned.clone(), vec![10.0, 1.0, 1.0]).unwrap();
+        index.insert(near_but_angled.clone(), vec![0.6, 0.6, 0.1]).unwrap();
+
+        let results = index.search(&query, 1).await.unwrap();
+        assert_eq!(&results[0].vector_id, nearest, "{:?}", metric);
+    }
+}
+
+#[tokio::test]
+async fn test_dot_product_prefers_larger_inner_product() {
+    let mut index = trained(DistanceMetric::DotProduct);
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.0, 0.0]).unwrap();
+    index.insert(VectorId::from_u64(2), vec![10.0, 0.0, 0.0]).unwrap();
+    index.insert(VectorId::from_u64(3), vec![-5.0, 0.0, 0.0]).unwrap();
+
+    let results = index.search(&[1.0, 0.0, 0.0], 3).await.unwrap();
+
+    let order: Vec<_> = results.iter().map(|r| r.vector_id.clone()).collect();
+    assert_eq!(
+        order,
+        vec![VectorId::from_u64(2), VectorId::from_u64(1), VectorId::from_u64(3)]
+    );
+    // Distances are negated inner products
+    assert!((results[0].distance + 10.0).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_cosine_rejects_zero_vectors() {
+    let mut training = training_data();
+    training[3] = vec![0.0, 0.0, 0.0];
+    let mut index = create_index(DistanceMetric::Cosine);
+    assert!(matches!(index.train(&training), Err(IVFError::ZeroVector)));
+
+    let mut index = trained(DistanceMetric::Cosine);
+    let insert = index.insert(VectorId::from_u64(1), vec![0.0, 0.0, 0.0]);
+    assert!(matches!(insert, Err(IVFError::ZeroVector)));
+
+    index.insert(VectorId::from_u64(2), vec![1.0, 0.0, 0.0]).unwrap();
+    let search = index.search(&[0.0, 0.0, 0.0], 1).await;
+    assert!(matches!(search, Err(IVFError::ZeroVector)));
+
+    // Other metrics accept them
+    let mut euclidean = trained(DistanceMetric::Euclidean);
+    euclidean.insert(VectorId::from_u64(1), vec![0.0, 0.0, 0.0]).unwrap();
+    assert!(euclidean.search(&[0.0, 0.0, 0.0], 1).await.is_ok());
+}
@@ -0,0 +1,94 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::VectorId;
+use vector_db::ivf::core::{IVFConfig, IVFError, IVFIndex};
+
+fn trained() -> IVFIndex {
+    let mut index = IVFIndex::new(IVFConfig {
+        n_clusters: 4,
+        n_probe: 4,
+        train_size: 40,
+        seed: Some(3),
+        ..IVFConfig::default()
+    });
+    let training: Vec<Vec<f32>> = (0..40)
+        .map(|i| vec![(i % 4) as f32 * 10.0, (i / 4) as f32 * 0.1])
+        .collect();
+    index.train(&training).unwrap();
+    index
+}
+
+fn vectors(range: std::ops::Range<u64>) -> Vec<(VectorId, Vec<f32>)> {
+    range
+        .map(|i| (VectorId::from_u64(i), vec![(i % 4) as f32 * 10.0, i as f32 * 0.01]))
+        .collect()
+}
+
+#[test]
+fn test_insert_batch_assigns_like_insert() {
+    let mut batched = trained();
+    let mut sequential = trained();
+
+    let result = batched.insert_batch(vectors(0..100)).unwrap();
+    for (id, vector) in vectors(0..100) {
+        sequential.insert(id, vector).unwrap();
+    }
+
+    assert_eq!(result.successful, 100);
+    assert_eq!(result.failed, 0);
+    assert_eq!(batched.total_vectors(), 100);
+    for (id, vector) in vectors(0..100) {
+        let cluster = batched.find_cluster(&vector).unwrap();
+        let list = batched.get_inverted_list(cluster).unwrap();
+        assert!(list.vectors.contains_key(&id));
+        assert!(sequential.get_inverted_list(cluster).unwrap().vectors.contains_key(&id));
+    }
+}
+
+#[test]
+fn test_insert_batch_reports_duplicates() {
+    let mut index = trained();
+    index.insert(VectorId::from_u64(0), vec![0.0, 0.0]).unwrap();
+
+    let batch = vec![
+        // Stored already, though it would land in another cluster now
+        (VectorId::from_u64(0), vec![30.0, 0.0]),
+        (VectorId::from_u64(1), vec![10.0, 0.0]),
+        (VectorId::from_u64(1), vec![20.0, 0.0]),
+    ];
+    let result = index.insert_batch(batch).unwrap();
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 2);
+    assert!(result
+        .errors
+        .iter()
+        .all(|(_, e)| matches!(e, IVFError::DuplicateVector(_))));
+    assert_eq!(index.total_vectors(), 2);
+}
+
+#[test]
+fn test_insert_batch_rejects_dimension_mismatch_up_front() {
+    let mut index = trained();
+    let mut batch = vectors(0..10);
+    batch.push((VectorId::from_u64(10), vec![1.0, 2.0, 3.0]));
+
+    let result = index.insert_batch(batch);
+
+    assert!(matches!(
+        result,
+        Err(IVFError::DimensionMismatch { expected: 2, actual: 3 })
+    ));
+    assert_eq!(index.total_vectors(), 0);
+}
+
+#[test]
+fn test_insert_batch_requires_training() {
+    let mut index = IVFIndex::new(IVFConfig::default());
+
+    assert!(matches!(
+        index.insert_batch(vectors(0..3)),
+        Err(IVFError::NotTrained)
+    ));
+}
@@ -1,6 +1,17 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+mod batch_search;
+mod chunk_compaction;
+mod chunk_load_policy;
+mod cluster_search;
 mod core;
+mod deletion;
+mod distance_metric;
+mod insert_batch;
 mod operations;
 mod persistence;
+mod probe_concurrency;
+mod product_quantization;
+mod retrain;
+mod vector_cache;
@@ -3,9 +3,11 @@
 
 use std::sync::Arc;
 use tokio;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::types::*;
 use vector_db::ivf::core::*;
 use vector_db::ivf::operations::*;
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 #[cfg(test)]
 mod batch_operations_tests {
@@ -122,8 +124,8 @@ mod batch_operations_tests {
 mod retraining_tests {
     use super::*;
 
-    #[test]
-    fn test_retrain_with_new_config() {
+    #[tokio::test]
+    async fn test_retrain_with_new_config() {
         let mut index = create_trained_index();
 
         // Insert initial vectors
@@ -145,9 +147,12 @@ mod retraining_tests {
             train_size: 50,
             max_iterations: 20,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
-        let result = index.retrain(new_config).unwrap();
+        let result = index.retrain_with_config(new_config).await.unwrap();
 
         assert_eq!(result.old_clusters, 3);
         assert_eq!(result.new_clusters, 10);
@@ -159,12 +164,12 @@ mod retraining_tests {
         assert_eq!(index.total_vectors(), 50);
 
         // Search should still work
-        let results = index.search(&vec![0.0, 0.0], 5).unwrap();
+        let results = index.search(&vec![0.0, 0.0], 5).await.unwrap();
         assert!(!results.is_empty());
     }
 
-    #[test]
-    fn test_add_clusters() {
+    #[tokio::test]
+    async fn test_add_clusters() {
         let mut index = create_trained_index();
 
         // Insert vectors that don't fit well into existing clusters
@@ -177,7 +182,7 @@ mod retraining_tests {
         let initial_clusters = index.config().n_clusters;
 
         // Add more clusters to better fit the data
-        let result = index.add_clusters(2).unwrap();
+        let result = index.add_clusters(2).await.unwrap();
 
         assert_eq!(result.clusters_added, 2);
         assert_eq!(result.vectors_reassigned, 20);
@@ -408,6 +413,9 @@ fn create_trained_index() -> IVFIndex {
         train_size: 9,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::new(config);
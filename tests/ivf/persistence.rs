@@ -3,6 +3,7 @@
 
 use std::collections::HashMap;
 use tokio;
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::storage::*;
 use vector_db::core::types::*;
 use vector_db::ivf::core::*;
@@ -34,6 +35,9 @@ mod ivf_serialization_tests {
             train_size: 10000,
             max_iterations: 25,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         let metadata = IVFMetadata {
@@ -59,6 +63,7 @@ mod ivf_serialization_tests {
         let mut list = SerializableInvertedList {
             cluster_id: ClusterId(5),
             vectors: HashMap::new(),
+            codes: HashMap::new(),
         };
 
         // Add some vectors
@@ -88,6 +93,7 @@ mod ivf_serialization_tests {
         let mut list = SerializableInvertedList {
             cluster_id: ClusterId(1),
             vectors: HashMap::new(),
+            codes: HashMap::new(),
         };
 
         // Add vectors with repetitive patterns (good for compression)
@@ -397,6 +403,9 @@ mod ivf_migration_tests {
             train_size: 9,
             max_iterations: 10,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         });
 
         train_simple_index(&mut index);
@@ -419,6 +428,9 @@ mod ivf_migration_tests {
             train_size: 30,
             max_iterations: 20,
             seed: Some(42),
+            metric: DistanceMetric::Euclidean,
+            pq: None,
+            vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
         };
 
         // Migrate data
@@ -446,6 +458,9 @@ fn create_trained_index() -> IVFIndex {
         train_size: 9,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::new(config);
@@ -460,6 +475,9 @@ fn create_large_trained_index(n_clusters: usize) -> IVFIndex {
         train_size: n_clusters * 10,
         max_iterations: 25,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::new(config);
@@ -493,3 +511,4 @@ fn train_simple_index(index: &mut IVFIndex) {
 }
 
 use vector_db::ivf::persistence::{calculate_total_size, serialize_centroids};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
@@ -0,0 +1,140 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tests for loading probed clusters concurrently during search
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage, StorageError};
+use vector_db::core::types::{DistanceMetric, SearchResult, VectorId};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::storage::chunk_loader::ChunkLoader;
+
+const DIM: usize = 8;
+const GROUPS: usize = 4;
+
+/// Storage whose `get`s are slow and track how many overlap
+#[derive(Clone)]
+struct SlowStorage {
+    inner: MockS5Storage,
+    active: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl S5Storage for SlowStorage {
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(active, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        self.inner.put(path, data).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+fn make_vector(i: usize) -> Vec<f32> {
+    (0..DIM).map(|d| (i % GROUPS) as f32 * 10.0 + i as f32 * 0.01 + d as f32 * 0.1).collect()
+}
+
+/// Cold lazily loaded index of four well separated groups, each stored in
+/// its own chunk, so every probed cluster costs one storage `get`
+async fn setup() -> (IVFIndex, SlowStorage) {
+    let storage = SlowStorage {
+        inner: MockS5Storage::new(),
+        active: Arc::new(AtomicUsize::new(0)),
+        peak: Arc::new(AtomicUsize::new(0)),
+    };
+    let cache = Arc::new(ChunkCache::new(100));
+    let loader = Arc::new(ChunkLoader::new(Arc::new(storage.clone()), cache));
+
+    let vectors: Vec<(VectorId, Vec<f32>)> = (0..40)
+        .map(|i| (VectorId::from_string(&format!("vec_{}", i)), make_vector(i)))
+        .collect();
+    let mut chunks: Vec<VectorChunk> = (0..GROUPS)
+        .map(|g| VectorChunk::new(format!("chunk-{}", g), 0, 0))
+        .collect();
+    for (i, (id, vector)) in vectors.iter().enumerate() {
+        chunks[i % GROUPS].add_vector(id.clone(), vector.clone());
+    }
+    let paths: Vec<String> = (0..GROUPS).map(|g| format!("probe/chunks/chunk-{}.cbor", g)).collect();
+    for (chunk, path) in chunks.iter().zip(&paths) {
+        storage.inner.put(path, chunk.to_cbor().unwrap()).await.unwrap();
+    }
+
+    let config = IVFConfig {
+        n_clusters: GROUPS,
+        n_probe: GROUPS,
+        train_size: 40,
+        max_iterations: 10,
+        seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    };
+    let mut warm = IVFIndex::with_chunk_loader(config.clone(), Some(loader.clone()));
+    let training: Vec<Vec<f32>> = vectors.iter().map(|(_, v)| v.clone()).collect();
+    warm.train(&training).unwrap();
+    for (i, (id, vector)) in vectors.iter().enumerate() {
+        warm
+            .insert_with_chunk(id.clone(), vector.clone(), Some(paths[i % GROUPS].clone()))
+            .unwrap();
+    }
+
+    // Rebuild without the warm vector cache so searches must load chunks
+    let mut index = IVFIndex::with_chunk_loader(config, Some(loader));
+    index.set_trained(warm.get_centroids().to_vec(), DIM);
+    index.set_inverted_lists(warm.get_all_inverted_lists().clone());
+    (index, storage)
+}
+
+#[tokio::test]
+async fn test_probed_clusters_load_concurrently() {
+    let (index, storage) = setup().await;
+
+    let results = index.search(&make_vector(0), 10).await.unwrap();
+    assert_eq!(results.len(), 10);
+    assert!(
+        storage.peak.load(Ordering::SeqCst) > 1,
+        "cluster loads never overlapped"
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_probing_matches_one_cluster_at_a_time() {
+    let (index, _) = setup().await;
+    let query = make_vector(5);
+
+    let concurrent = index.search(&query, 15).await.unwrap();
+
+    // Probe each cluster on its own and merge, as a sequential search would
+    let mut sequential: Vec<SearchResult> = Vec::new();
+    for centroid in index.get_centroids() {
+        sequential.extend(index.search_in_clusters(&query, 40, &[centroid.id()]).await.unwrap());
+    }
+    sequential.sort_by(SearchResult::cmp_distance);
+    sequential.truncate(15);
+
+    let ids = |results: &[SearchResult]| results.iter().map(|r| r.vector_id.clone()).collect::<Vec<_>>();
+    assert_eq!(ids(&concurrent), ids(&sequential));
+    assert!(concurrent
+        .iter()
+        .zip(&sequential)
+        .all(|(a, b)| a.distance == b.distance));
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vector_db::core::storage::MockS5Storage;
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::hybrid::{HybridConfig, HybridIndex, HybridPersister};
+use vector_db::ivf::core::{IVFConfig, IVFError, IVFIndex};
+use vector_db::ivf::persistence::IVFPersister;
+use vector_db::ivf::pq::PQConfig;
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+const DIM: usize = 32;
+
+fn config(metric: DistanceMetric, pq: Option<PQConfig>) -> IVFConfig {
+    IVFConfig {
+        n_clusters: 16,
+        n_probe: 4,
+        train_size: 2000,
+        max_iterations: 20,
+        seed: Some(42),
+        metric,
+        pq,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    }
+}
+
+fn pq() -> Option<PQConfig> {
+    Some(PQConfig {
+        n_subvectors: 8,
+        n_centroids_per_subvector: 64,
+    })
+}
+
+/// Points near a random 6-dimensional subspace, like real embeddings whose
+/// intrinsic dimension is far below their width
+fn dataset(n: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut basis_rng = StdRng::seed_from_u64(0);
+    let basis: Vec<Vec<f32>> = (0..6)
+        .map(|_| (0..DIM).map(|_| basis_rng.gen_range(-1.0..1.0)).collect())
+        .collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| {
+            let mut vector: Vec<f32> = (0..DIM).map(|_| rng.gen_range(-0.05..0.05)).collect();
+            for direction in &basis {
+                let weight: f32 = rng.gen_range(-1.0..1.0);
+                vector.iter_mut().zip(direction).for_each(|(x, d)| *x += weight * d);
+            }
+            vector
+        })
+        .collect()
+}
+
+fn build(metric: DistanceMetric, pq: Option<PQConfig>, data: &[Vec<f32>]) -> IVFIndex {
+    let mut index = IVFIndex::new(config(metric, pq));
+    index.train(data).unwrap();
+    for (i, vector) in data.iter().enumerate() {
+        index.insert(VectorId::from_string(&format!("v{}", i)), vector.clone()).unwrap();
+    }
+    index
+}
+
+/// Exact top-k ids by brute force
+fn exact_top_k(metric: DistanceMetric, data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<VectorId> {
+    let mut scored: Vec<(usize, f32)> = data
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (i, metric.distance(query, v)))
+        .collect();
+    scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+    scored
+        .into_iter()
+        .take(k)
+        .map(|(i, _)| VectorId::from_string(&format!("v{}", i)))
+        .collect()
+}
+
+/// Fraction of queries whose exact nearest neighbour is in the top 10
+async fn recall_at_10(index: &IVFIndex, metric: DistanceMetric, data: &[Vec<f32>], queries: &[Vec<f32>]) -> f32 {
+    let mut hits = 0;
+    for query in queries {
+        let nearest = exact_top_k(metric, data, query, 1).remove(0);
+        let results = index.search(query, 10).await.unwrap();
+        if results.iter().any(|r| r.vector_id == nearest) {
+            hits += 1;
+        }
+    }
+    hits as f32 / queries.len() as f32
+}
+
+#[tokio::test]
+async fn test_pq_recall_and_memory_against_full_precision() {
+    let data = dataset(2000, 1);
+    let queries = dataset(50, 2);
+
+    for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine] {
+        let full = build(metric, None, &data);
+        let quantized = build(metric, pq(), &data);
+
+        let full_recall = recall_at_10(&full, metric, &data, &queries).await;
+        let pq_recall = recall_at_10(&quantized, metric, &data, &queries).await;
+        assert!(
+            pq_recall >= 0.7 && pq_recall > full_recall - 0.25,
+            "{:?}: PQ recall {} vs full precision {}",
+            metric,
+            pq_recall,
+            full_recall
+        );
+
+        // 8 one-byte codes instead of 32 four-byte floats per vector
+        let full_bytes = full.estimate_memory_usage().vectors_bytes;
+        let pq_bytes = quantized.estimate_memory_usage().vectors_bytes;
+        assert_eq!(full_bytes, data.len() * DIM * 4);
+        assert_eq!(pq_bytes, data.len() * 8);
+        assert_eq!(quantized.total_vectors(), data.len());
+    }
+}
+
+#[tokio::test]
+async fn test_insert_requires_trained_quantizer() {
+    let data = dataset(500, 3);
+    let mut index = IVFIndex::new(config(DistanceMetric::Euclidean, pq()));
+    index.set_trained(full_centroids(&data), DIM);
+
+    let id = VectorId::from_string("early");
+    assert!(matches!(index.insert(id.clone(), data[0].clone()), Err(IVFError::PQNotTrained)));
+    assert!(matches!(
+        index.insert_batch(vec![(id.clone(), data[0].clone())]),
+        Err(IVFError::PQNotTrained)
+    ));
+
+    index.train_pq(&data).unwrap();
+    index.insert(id.clone(), data[0].clone()).unwrap();
+    assert!(matches!(index.insert(id.clone(), data[0].clone()), Err(IVFError::DuplicateVector(_))));
+    assert_eq!(index.get_vector_by_id(&id).unwrap().len(), DIM);
+
+    let results = index.search(&data[0], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, id);
+
+    // PQ must be enabled in the config
+    let mut plain = IVFIndex::new(config(DistanceMetric::Euclidean, None));
+    assert!(matches!(plain.train_pq(&data), Err(IVFError::InvalidConfig(_))));
+}
+
+fn full_centroids(data: &[Vec<f32>]) -> Vec<vector_db::ivf::core::Centroid> {
+    let mut index = IVFIndex::new(config(DistanceMetric::Euclidean, None));
+    index.train(data).unwrap();
+    index.get_centroids().to_vec()
+}
+
+#[tokio::test]
+async fn test_pq_index_persistence_round_trip() {
+    let data = dataset(1000, 4);
+    let index = build(DistanceMetric::Euclidean, pq(), &data);
+    let persister = IVFPersister::new(MockS5Storage::new());
+    persister.save_index(&index, "pq_index").await.unwrap();
+
+    let loaded = persister.load_index("pq_index").await.unwrap();
+    assert!(loaded.product_quantizer().is_some());
+    assert_eq!(loaded.total_vectors(), data.len());
+
+    let query = &data[17];
+    let before = index.search(query, 10).await.unwrap();
+    let after = loaded.search(query, 10).await.unwrap();
+    let ids = |results: &[vector_db::core::types::SearchResult]| {
+        results.iter().map(|r| r.vector_id.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&before), ids(&after));
+}
+
+#[tokio::test]
+async fn test_pq_hybrid_index_chunked_round_trip() {
+    let data = dataset(1000, 5);
+    let config = HybridConfig {
+        auto_migrate: false,
+        ivf_config: config(DistanceMetric::Euclidean, pq()),
+        ..HybridConfig::default()
+    };
+    let mut index = HybridIndex::new(config.clone());
+    index.initialize(data.clone()).await.unwrap();
+    let old = Utc::now() - Duration::days(30);
+    for (i, vector) in data.iter().enumerate() {
+        index
+            .insert_with_timestamp(VectorId::from_string(&format!("v{}", i)), vector.clone(), old)
+            .await
+            .unwrap();
+    }
+
+    let persister = HybridPersister::new(MockS5Storage::new()).with_chunk_size(100);
+    persister.save_index_chunked(&index, "pq_chunked").await.unwrap();
+    let loaded = persister.load_index_chunked("pq_chunked", config).await.unwrap();
+
+    {
+        let historical = loaded.get_historical_index().await;
+        assert!(historical.product_quantizer().is_some());
+        assert_eq!(historical.total_vectors(), data.len());
+        let codes: usize = historical.get_all_inverted_lists().values().map(|list| list.codes.len()).sum();
+        assert_eq!(codes, data.len());
+    }
+    let id = VectorId::from_string("v17");
+    assert_eq!(loaded.get_vector(&id).await, index.get_vector(&id).await);
+
+    let before = index.search(&data[17], 10).await.unwrap();
+    let after = loaded.search(&data[17], 10).await.unwrap();
+    let ids = |results: &[vector_db::core::types::SearchResult]| {
+        results.iter().map(|r| r.vector_id.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&before), ids(&after));
+}
@@ -0,0 +1,95 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use vector_db::core::types::{DistanceMetric, VectorId};
+use vector_db::ivf::core::{IVFConfig, IVFError, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
+
+fn create_index() -> IVFIndex {
+    IVFIndex::new(IVFConfig {
+        n_clusters: 8,
+        n_probe: 2,
+        train_size: 64,
+        max_iterations: 25,
+        seed: Some(11),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
+    })
+}
+
+/// Trained on a wide grid, then filled with vectors packed into one corner
+fn skewed_index() -> IVFIndex {
+    let mut index = create_index();
+    let grid: Vec<Vec<f32>> = (0..64)
+        .map(|i| vec![(i % 8) as f32 * 10.0, (i / 8) as f32 * 10.0])
+        .collect();
+    index.train(&grid).unwrap();
+    for i in 0..400u64 {
+        let x = (i % 20) as f32 * 0.5;
+        let y = (i / 20) as f32 * 0.5;
+        index.insert(VectorId::from_u64(i), vec![x, y]).unwrap();
+    }
+    index
+}
+
+fn largest_cluster(index: &IVFIndex) -> usize {
+    index.get_cluster_sizes().values().copied().max().unwrap()
+}
+
+#[tokio::test]
+async fn test_retrain_rebalances_skewed_clusters() {
+    let mut index = skewed_index();
+    let before = largest_cluster(&index);
+    assert!(before > 200, "largest cluster holds {} of 400", before);
+
+    let result = index.retrain().await.unwrap();
+
+    assert!(result.iterations > 0);
+    assert!(result.vectors_moved > 0);
+    assert_eq!(result.vectors_reassigned, 400);
+    assert_eq!(result.old_clusters, result.new_clusters);
+    let after = largest_cluster(&index);
+    assert!(after * 2 < before, "largest cluster {} -> {}", before, after);
+
+    // Every vector is kept and findable in its new cluster
+    assert_eq!(index.total_vectors(), 400);
+    assert_eq!(index.get_cluster_sizes().values().sum::<usize>(), 400);
+    let results = index.search(&[9.5, 9.5], 1).await.unwrap();
+    assert_eq!(results[0].vector_id, VectorId::from_u64(399));
+}
+
+#[tokio::test]
+async fn test_retrain_preserves_deletions() {
+    let mut index = skewed_index();
+    let deleted = VectorId::from_u64(0);
+    index.mark_deleted(&deleted).unwrap();
+    let deleted_at = index.deleted_at(&deleted);
+
+    index.retrain().await.unwrap();
+
+    assert!(index.is_deleted(&deleted));
+    assert_eq!(index.deleted_at(&deleted), deleted_at);
+    assert_eq!(index.total_vectors(), 400);
+    let results = index.search(&[0.0, 0.0], 5).await.unwrap();
+    assert!(results.iter().all(|r| r.vector_id != deleted));
+
+    assert_eq!(index.vacuum().unwrap(), 1);
+    assert_eq!(index.total_vectors(), 399);
+}
+
+#[tokio::test]
+async fn test_retrain_requires_enough_vectors() {
+    let mut untrained = create_index();
+    assert!(matches!(untrained.retrain().await, Err(IVFError::NotTrained)));
+
+    let mut index = create_index();
+    let grid: Vec<Vec<f32>> = (0..64).map(|i| vec![i as f32, 0.0]).collect();
+    index.train(&grid).unwrap();
+    index.insert(VectorId::from_u64(1), vec![1.0, 0.0]).unwrap();
+    assert!(matches!(
+        index.retrain().await,
+        Err(IVFError::InsufficientTrainingData { got: 1, need: 8 })
+    ));
+    assert_eq!(index.total_vectors(), 1);
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::sync::Arc;
+use vector_db::core::chunk::VectorChunk;
+use vector_db::core::chunk_cache::ChunkCache;
+use vector_db::core::storage::{MockS5Storage, S5Storage};
+use vector_db::core::types::VectorId;
+use vector_db::core::vector_cache::VectorCache;
+use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::storage::chunk_loader::ChunkLoader;
+
+const DIM: usize = 8;
+const CACHE_CAPACITY: usize = 10;
+
+fn make_vector(i: usize) -> Vec<f32> {
+    (0..DIM).map(|d| (i % 4) as f32 * 10.0 + i as f32 * 0.01 + d as f32 * 0.1).collect()
+}
+
+fn vector_id(i: usize) -> VectorId {
+    VectorId::from_string(&format!("vec_{}", i))
+}
+
+/// Index over 40 vectors stored in two chunks, with room for only
+/// `CACHE_CAPACITY` of them in its vector cache
+async fn setup() -> IVFIndex {
+    setup_with_capacity(CACHE_CAPACITY).await
+}
+
+async fn setup_with_capacity(capacity: usize) -> IVFIndex {
+    let storage = Arc::new(MockS5Storage::new());
+    let loader = Arc::new(ChunkLoader::new(storage.clone(), Arc::new(ChunkCache::new(100))));
+
+    let mut chunk_paths = Vec::new();
+    for chunk_idx in 0..2 {
+        let mut chunk = VectorChunk::new(format!("chunk-{}", chunk_idx), chunk_idx * 20, chunk_idx * 20 + 19);
+        for i in chunk_idx * 20..(chunk_idx + 1) * 20 {
+            chunk.add_vector(vector_id(i), make_vector(i));
+        }
+        let path = format!("test/cache/chunks/chunk-{}.cbor", chunk_idx);
+        storage.put(&path, chunk.to_cbor().unwrap()).await.unwrap();
+        chunk_paths.push(path);
+    }
+
+    let config = IVFConfig {
+        n_clusters: 4,
+        n_probe: 4,
+        train_size: 40,
+        max_iterations: 10,
+        seed: Some(42),
+        vector_cache_capacity: capacity,
+        ..IVFConfig::default()
+    };
+    let mut index = IVFIndex::with_chunk_loader(config, Some(loader));
+    let training: Vec<Vec<f32>> = (0..40).map(make_vector).collect();
+    index.train(&training).unwrap();
+    for i in 0..40 {
+        index
+            .insert_with_chunk(vector_id(i), make_vector(i), Some(chunk_paths[i / 20].clone()))
+            .unwrap();
+    }
+    index
+}
+
+#[tokio::test]
+async fn test_evicted_vectors_reload_from_chunks() {
+    let index = setup().await;
+
+    // Only the most recently inserted vectors stay cached
+    assert_eq!(index.vector_cache_metrics().evictions, 30);
+    assert!(index.get_vector_by_id(&vector_id(0)).is_none());
+    assert_eq!(index.get_vector_by_id(&vector_id(39)), Some(make_vector(39)));
+
+    let mut loaded = Vec::new();
+    for centroid in index.get_centroids() {
+        loaded.extend(index.get_cluster_vectors(centroid.id()).await.unwrap());
+    }
+    assert_eq!(loaded.len(), 40);
+    for (id, vector) in &loaded {
+        let i = (0..40).find(|&i| &vector_id(i) == id).unwrap();
+        assert_eq!(vector, &make_vector(i));
+    }
+
+    let metrics = index.vector_cache_metrics();
+    assert!(metrics.misses >= 30, "{:?}", metrics);
+    assert!(metrics.evictions > 30, "{:?}", metrics);
+
+    // Searches still find evicted vectors exactly
+    for i in [0, 7, 21] {
+        let results = index.search(&make_vector(i), 1).await.unwrap();
+        assert_eq!(results[0].vector_id, vector_id(i));
+        assert!(results[0].distance < 1e-6);
+    }
+}
+
+#[test]
+fn test_vector_cache_evicts_least_recently_used() {
+    let cache = VectorCache::new(2);
+    cache.put(vector_id(0), vec![0.0]);
+    cache.put(vector_id(1), vec![1.0]);
+    assert_eq!(cache.get(&vector_id(0)), Some(vec![0.0]));
+
+    cache.put(vector_id(2), vec![2.0]);
+    assert!(!cache.contains(&vector_id(1)));
+    assert!(cache.contains(&vector_id(0)));
+
+    // Replacing a cached vector evicts nothing
+    cache.put(vector_id(2), vec![2.5]);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&vector_id(1)), None);
+
+    let metrics = cache.metrics();
+    assert_eq!((metrics.hits, metrics.misses, metrics.evictions), (1, 1, 1));
+}
+
+#[tokio::test]
+async fn test_zero_capacity_disables_cache() {
+    let cache = VectorCache::new(0);
+    cache.put(vector_id(0), vec![0.0]);
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&vector_id(0)), None);
+    assert_eq!(cache.capacity(), 0);
+
+    // Every vector is loaded from its chunk instead
+    let index = setup_with_capacity(0).await;
+    assert!(index.get_vector_by_id(&vector_id(39)).is_none());
+    for i in [0, 7, 21] {
+        let results = index.search(&make_vector(i), 1).await.unwrap();
+        assert_eq!(results[0].vector_id, vector_id(i));
+        assert!(results[0].distance < 1e-6);
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+mod api {
+    pub mod backup;
+    pub mod mock_s5_server;
+}
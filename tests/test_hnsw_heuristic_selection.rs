@@ -0,0 +1,6 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+mod hnsw {
+    mod heuristic_selection;
+}
@@ -6,6 +6,7 @@
 
 use vector_db::storage::{Storage, S5StorageAdapter, S5StorageConfig, StorageMode, EnhancedS5Storage};
 use vector_db::core::{Vector, VectorId, Embedding};
+use vector_db::core::storage::MockS5Storage;
 use vector_db::types::{VideoNFTMetadata, Attribute};
 use std::time::Instant;
 use futures::stream::{FuturesUnordered, StreamExt};
@@ -48,11 +49,20 @@ fn create_mock_storage() -> Result<EnhancedS5Storage, Box<dyn std::error::Error
         seed_phrase: None,
         connection_timeout: Some(5000),
         retry_attempts: Some(3),
+        encrypt_at_rest: None,
+        encryption_key: None,
+        compress: None,
     };
     
     EnhancedS5Storage::new(config)
 }
 
+/// Create a storage instance backed by in-process memory, so tests run
+/// without the mock server
+fn create_in_process_storage() -> EnhancedS5Storage {
+    EnhancedS5Storage::with_in_process_backend(MockS5Storage::new()).unwrap()
+}
+
 mod phase_8_2_mock_server_integration {
     use super::*;
 
@@ -61,12 +71,7 @@ mod phase_8_2_mock_server_integration {
 
         #[tokio::test]
         async fn test_vector_store_and_retrieve() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available at http://localhost:5524");
-                return;
-            }
-
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             
             // Create a test vector
             let vector_id = VectorId::from_string("mock-test-vector-001");
@@ -84,13 +89,26 @@ mod phase_8_2_mock_server_integration {
         }
 
         #[tokio::test]
-        async fn test_vector_update() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
+        async fn test_in_process_instances_share_backend() {
+            let backend = MockS5Storage::new();
+            let writer = EnhancedS5Storage::with_in_process_backend(backend.clone()).unwrap();
+            let reader = EnhancedS5Storage::with_in_process_backend(backend).unwrap();
+            assert_eq!(<EnhancedS5Storage as S5StorageAdapter>::get_mode(&writer), StorageMode::InProcess);
 
-            let storage = create_mock_storage().unwrap();
+            let vector = Vector::new(
+                VectorId::from_string("shared-vector"),
+                Embedding::new(vec![1.0, 2.0]).unwrap(),
+            );
+            <EnhancedS5Storage as Storage>::put(&writer, "shared/vector", &vector).await.unwrap();
+
+            let retrieved: Vector = <EnhancedS5Storage as Storage>::get(&reader, "shared/vector").await.unwrap();
+            assert_eq!(retrieved.id, vector.id);
+            assert!(<EnhancedS5Storage as Storage>::get::<Vector>(&reader, "shared/missing").await.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_vector_update() {
+            let storage = create_in_process_storage();
             let vector_id = VectorId::from_string("mock-test-vector-update");
             
             // Store initial vector
@@ -111,12 +129,7 @@ mod phase_8_2_mock_server_integration {
 
         #[tokio::test]
         async fn test_vector_delete() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             let vector_id = VectorId::from_string("mock-test-vector-delete");
             let embedding = Embedding::new(vec![1.0, 2.0, 3.0]).unwrap();
             let vector = Vector::new(vector_id.clone(), embedding);
@@ -132,12 +145,7 @@ mod phase_8_2_mock_server_integration {
 
         #[tokio::test]
         async fn test_vector_with_metadata() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             let vector_id = VectorId::from_string("mock-test-vector-metadata");
             let embedding = Embedding::new(vec![0.1, 0.2, 0.3]).unwrap();
             
@@ -160,13 +168,8 @@ mod phase_8_2_mock_server_integration {
         
         #[tokio::test]
         async fn test_single_vector_crud_with_timing() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-            
-            let storage = create_mock_storage().unwrap();
-            assert!(<EnhancedS5Storage as S5StorageAdapter>::is_connected(&storage).await, "Should be connected to mock server");
+            let storage = create_in_process_storage();
+            assert!(<EnhancedS5Storage as S5StorageAdapter>::is_connected(&storage).await, "In-process storage is always connected");
             
             // Create a test vector
             let vector_id = VectorId::from_string("test-vector-crud-timing");
@@ -211,12 +214,7 @@ mod phase_8_2_mock_server_integration {
         
         #[tokio::test]
         async fn test_complex_metadata_crud() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-            
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             
             // Create complex metadata
             let metadata = VideoNFTMetadata {
@@ -277,12 +275,7 @@ mod phase_8_2_mock_server_integration {
 
         #[tokio::test]
         async fn test_batch_vector_operations() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             let batch_size = 100;
             
             // Create batch of vectors
@@ -336,12 +329,7 @@ mod phase_8_2_mock_server_integration {
 
         #[tokio::test]
         async fn test_concurrent_operations() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             let concurrent_ops = 50;
             
             // Mix of operations: insert, update, read
@@ -382,12 +370,7 @@ mod phase_8_2_mock_server_integration {
         
         #[tokio::test]
         async fn test_mixed_type_batch_operations() {
-            if !is_mock_server_available().await {
-                eprintln!("Skipping test: Mock server not available");
-                return;
-            }
-            
-            let storage = create_mock_storage().unwrap();
+            let storage = create_in_process_storage();
             
             // Store different types of data concurrently
             let storage_clone1 = storage.clone();
@@ -503,7 +486,7 @@ mod phase_8_2_mock_server_integration {
                 let key = format!("vectors/hamt/{}", vector_id.to_string());
                 
                 let _vector: Vector = <EnhancedS5Storage as Storage>::get(&storage, &key).await
-                    .expect(&format!("Failed to retrieve vector at index {}", i));
+                    .unwrap_or_else(|_| panic!("Failed to retrieve vector at index {}", i));
                 
                 let retrieval_time = start.elapsed();
                 println!("Retrieved vector {} in {:?}", i, retrieval_time);
@@ -623,9 +606,9 @@ mod phase_8_2_mock_server_integration {
             });
             
             let key = "test/complex-cbor-data";
-            <EnhancedS5Storage as Storage>::put(&storage, &key, &complex_data).await.unwrap();
+            <EnhancedS5Storage as Storage>::put(&storage, key, &complex_data).await.unwrap();
             
-            let retrieved: serde_json::Value = <EnhancedS5Storage as Storage>::get(&storage, &key).await.unwrap();
+            let retrieved: serde_json::Value = <EnhancedS5Storage as Storage>::get(&storage, key).await.unwrap();
             assert_eq!(retrieved["nested"]["arrays"], complex_data["nested"]["arrays"]);
             assert_eq!(retrieved["nested"]["unicode"], complex_data["nested"]["unicode"]);
             assert_eq!(retrieved["metadata"]["tags"], complex_data["metadata"]["tags"]);
@@ -779,6 +762,9 @@ mod phase_8_2_mock_server_integration {
                 seed_phrase: None,
                 connection_timeout: Some(1000), // 1 second timeout
                 retry_attempts: Some(3),
+                encrypt_at_rest: None,
+                encryption_key: None,
+                compress: None,
             };
             
             let storage = EnhancedS5Storage::new(config).unwrap();
@@ -5,7 +5,7 @@
 use vector_db::core::chunk::{
     ChunkMetadata, HNSWManifest, IVFManifest, LayerMetadata, Manifest, VectorChunk,
 };
-use vector_db::core::types::VectorId;
+use vector_db::core::types::{DistanceMetric, VectorId};
 use std::collections::HashMap;
 
 // ============================================================================
@@ -244,6 +244,7 @@ fn test_manifest_with_ivf_structure() {
             map.insert(1, vec!["chunk-1".to_string(), "chunk-2".to_string()]);
             map
         },
+        metric: DistanceMetric::Euclidean,
     };
 
     manifest.ivf_structure = Some(ivf_manifest);
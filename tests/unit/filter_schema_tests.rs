@@ -0,0 +1,89 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Unit tests for checking metadata filters against a schema at parse time
+
+use serde_json::json;
+use std::collections::HashMap;
+use vector_db::core::metadata_filter::{FilterError, MetadataFilter};
+use vector_db::core::schema::{FieldType, MetadataSchema};
+
+fn schema() -> MetadataSchema {
+    let mut schema = MetadataSchema::new();
+    schema.add_field("title", FieldType::String, false);
+    schema.add_field("views", FieldType::Number, false);
+    schema.add_field("published", FieldType::Boolean, false);
+    schema.add_field("tags", FieldType::Array(Box::new(FieldType::String)), false);
+    schema.add_field(
+        "creator",
+        FieldType::Object(HashMap::from([("age".to_string(), FieldType::Number)])),
+        false,
+    );
+    schema
+}
+
+#[test]
+fn test_range_on_string_field_rejected() {
+    let result = MetadataFilter::from_json_with_schema(&json!({"title": {"$gte": 10}}), &schema());
+
+    match result {
+        Err(FilterError::TypeMismatch { field, actual, .. }) => {
+            assert_eq!(field, "title");
+            assert_eq!(actual, "String");
+        }
+        other => panic!("expected TypeMismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_range_on_number_field_accepted() {
+    let filter =
+        MetadataFilter::from_json_with_schema(&json!({"views": {"$gt": 100}}), &schema()).unwrap();
+    assert!(filter.matches(&json!({"views": 150})));
+}
+
+#[test]
+fn test_equality_value_must_match_field_type() {
+    let result = MetadataFilter::from_json_with_schema(&json!({"views": "100"}), &schema());
+    assert!(matches!(result, Err(FilterError::TypeMismatch { .. })));
+
+    let result = MetadataFilter::from_json_with_schema(&json!({"published": true}), &schema());
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_in_values_must_match_field_type() {
+    let result =
+        MetadataFilter::from_json_with_schema(&json!({"title": {"$in": ["a", 2]}}), &schema());
+    assert!(matches!(result, Err(FilterError::TypeMismatch { .. })));
+
+    let result =
+        MetadataFilter::from_json_with_schema(&json!({"title": {"$in": ["a", "b"]}}), &schema());
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_array_field_accepts_element_or_whole_array() {
+    assert!(MetadataFilter::from_json_with_schema(&json!({"tags": "ai"}), &schema()).is_ok());
+    assert!(MetadataFilter::from_json_with_schema(&json!({"tags": ["ai"]}), &schema()).is_ok());
+    assert!(MetadataFilter::from_json_with_schema(&json!({"tags": 3}), &schema()).is_err());
+}
+
+#[test]
+fn test_nested_and_combined_filters_checked() {
+    let result = MetadataFilter::from_json_with_schema(
+        &json!({"$or": [{"views": {"$gte": 1}}, {"creator.age": "old"}]}),
+        &schema(),
+    );
+    match result {
+        Err(FilterError::TypeMismatch { field, .. }) => assert_eq!(field, "creator.age"),
+        other => panic!("expected TypeMismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_fields_outside_schema_not_checked() {
+    let result =
+        MetadataFilter::from_json_with_schema(&json!({"unknown": {"$lt": 5}, "other": "x"}), &schema());
+    assert!(result.is_ok());
+}
@@ -1,8 +1,10 @@
 // Copyright (c) 2025 Fabstir
 // SPDX-License-Identifier: BUSL-1.1
 
+use vector_db::core::types::DistanceMetric;
 use vector_db::core::types::VectorId;
 use vector_db::ivf::core::{IVFConfig, IVFIndex};
+use vector_db::core::vector_cache::DEFAULT_VECTOR_CACHE_CAPACITY;
 
 /// Helper function to create a simple trained IVF index for testing
 async fn create_test_index() -> IVFIndex {
@@ -12,6 +14,9 @@ async fn create_test_index() -> IVFIndex {
         train_size: 100,
         max_iterations: 10,
         seed: Some(42),
+        metric: DistanceMetric::Euclidean,
+        pq: None,
+        vector_cache_capacity: DEFAULT_VECTOR_CACHE_CAPACITY,
     };
 
     let mut index = IVFIndex::new(config);
@@ -0,0 +1,80 @@
+// Copyright (c) 2025 Fabstir
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::json;
+use std::collections::HashMap;
+use vector_db::core::schema::*;
+use vector_db::types::{Attribute, VideoNFTMetadata};
+
+fn video(id: &str, genre: &[&str], supply: Option<u32>, rating: serde_json::Value) -> serde_json::Value {
+    let metadata = VideoNFTMetadata {
+        address: "0xabc".to_string(),
+        attributes: vec![Attribute {
+            key: "rating".to_string(),
+            value: rating,
+        }],
+        genre: genre.iter().map(|g| g.to_string()).collect(),
+        id: id.to_string(),
+        name: format!("Video {}", id),
+        supply,
+        r#type: "video".to_string(),
+        ..Default::default()
+    };
+    serde_json::to_value(metadata).unwrap()
+}
+
+fn samples() -> Vec<serde_json::Value> {
+    vec![
+        video("1", &["action", "drama"], Some(100), json!(4.5)),
+        video("2", &[], None, json!("PG-13")),
+        video("3", &["comedy"], Some(10), json!(null)),
+    ]
+}
+
+#[test]
+fn test_infer_video_nft_field_types() {
+    let schema = MetadataSchema::infer_from(&samples());
+
+    assert_eq!(schema.fields["genre"], FieldType::Array(Box::new(FieldType::String)));
+    assert_eq!(schema.fields["supply"], FieldType::Number);
+    assert_eq!(schema.fields["name"], FieldType::String);
+    assert_eq!(schema.fields["mint_date_time"], FieldType::String);
+
+    let mut attribute = HashMap::new();
+    attribute.insert("key".to_string(), FieldType::String);
+    attribute.insert("value".to_string(), FieldType::Any);
+    assert_eq!(
+        schema.fields["attributes"],
+        FieldType::Array(Box::new(FieldType::Object(attribute)))
+    );
+}
+
+#[test]
+fn test_infer_required_fields() {
+    let schema = MetadataSchema::infer_from(&samples());
+
+    assert!(schema.required.contains("genre"));
+    assert!(schema.required.contains("id"));
+    assert!(!schema.required.contains("supply"));
+
+    for sample in samples() {
+        assert!(schema.validate(&sample).is_ok());
+    }
+}
+
+#[test]
+fn test_infer_conflicting_types() {
+    let schema = MetadataSchema::infer_from(&[
+        json!({ "year": 2020, "tags": ["a"] }),
+        json!({ "year": "2021", "tags": [1] }),
+        json!({ "year": null, "note": null }),
+        json!("not an object"),
+    ]);
+
+    assert_eq!(schema.fields["year"], FieldType::Any);
+    assert_eq!(schema.fields["tags"], FieldType::Array(Box::new(FieldType::Any)));
+    assert_eq!(schema.fields["note"], FieldType::Any);
+    assert!(schema.required.contains("year"));
+    assert!(!schema.required.contains("tags"));
+    assert!(MetadataSchema::infer_from(&[]).fields.is_empty());
+}